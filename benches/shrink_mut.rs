@@ -0,0 +1,53 @@
+//! Times shrinking mutable slices and string slices one element at a time, as a cursor that
+//! writes to the front of a buffer does, against splitting them by hand.
+//!
+//! Run with `cargo bench --bench shrink_mut`, and again with `--features unsafe-fast` to compare
+//! the pointer-based implementation with the default one. Both should be close to the manual
+//! split, which is what they compile to when the store of the empty slice is optimized out.
+
+use shrink_slice::ShrinkExt;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ROUNDS: u32 = 200;
+const LEN: usize = 1 << 16;
+
+fn time(name: &str, mut f: impl FnMut()) -> f64 {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let per_shrink = start.elapsed().as_secs_f64() * 1e9 / f64::from(ROUNDS) / LEN as f64;
+    println!("{:<24} {:>8.2}ns per shrink", name, per_shrink);
+    per_shrink
+}
+
+fn main() {
+    let mut bytes = vec![0u8; LEN];
+    let mut text = "x".repeat(LEN);
+
+    let manual = time("split_at_mut", || {
+        let mut slice: &mut [u8] = black_box(&mut bytes[..]);
+        while !slice.is_empty() {
+            slice[0] = slice[0].wrapping_add(1);
+            slice = core::mem::take(&mut slice).split_at_mut(1).1;
+            black_box(&mut slice);
+        }
+    });
+    let shrink = time("&mut [u8] shrink_front", || {
+        let mut slice: &mut [u8] = black_box(&mut bytes[..]);
+        while !slice.is_empty() {
+            slice[0] = slice[0].wrapping_add(1);
+            slice.shrink_front(1);
+            black_box(&mut slice);
+        }
+    });
+    time("&mut str shrink_front", || {
+        let mut s: &mut str = black_box(&mut text[..]);
+        while !s.is_empty() {
+            s.shrink_front(1);
+            black_box(&mut s);
+        }
+    });
+    println!("overhead: {:.2}x", shrink / manual);
+}
@@ -0,0 +1,40 @@
+//! Compares the word-at-a-time trimming of `shrink_slice::trim` with the standard library's
+//! byte-at-a-time `trim_ascii_start`, on a megabyte of padding.
+//!
+//! Run with `cargo bench --bench trim`.
+
+use shrink_slice::trim::{TrimAscii, TrimByte};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 200;
+
+fn time(name: &str, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let per_round = start.elapsed() / ROUNDS;
+    println!("{:<24} {:>10.1?} per MiB", name, per_round);
+    per_round
+}
+
+fn main() {
+    let mut padded = vec![b' '; 1 << 20];
+    padded.push(b'x');
+
+    let scalar = time("std trim_ascii_start", || {
+        black_box(black_box(&padded[..]).trim_ascii_start());
+    });
+    let words = time("trim_ascii_start_in_place", || {
+        let mut slice = black_box(&padded[..]);
+        slice.trim_ascii_start_in_place();
+        black_box(slice);
+    });
+    time("trim_byte_start", || {
+        let mut slice = black_box(&padded[..]);
+        slice.trim_byte_start(b' ');
+        black_box(slice);
+    });
+    println!("speedup: {:.1}x", scalar.as_secs_f64() / words.as_secs_f64());
+}
@@ -0,0 +1,428 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+//! # shrink_slice_derive
+//!
+//! Derive macros for the `shrink_slice` crate. Enable its `derive` feature rather than depending
+//! on this crate directly.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+use std::fmt::Write;
+
+/// Derive `shrink_slice::write::ShrinkWrite` for a struct, writing its fields in declaration
+/// order.
+///
+/// Fields accept `#[shrink(skip)]` to leave them out, and `#[shrink(little_endian)]` or
+/// `#[shrink(big_endian)]` to choose their byte order. The same byte order attributes on the
+/// struct itself set the default for all of its fields; without one, the byte order is whatever
+/// the caller asked for.
+#[proc_macro_derive(ShrinkWrite, attributes(shrink))]
+pub fn derive_shrink_write(input: TokenStream) -> TokenStream {
+    match Struct::parse(input) {
+        Ok(s) => s.shrink_write(),
+        Err(message) => compile_error(&message),
+    }
+}
+
+/// Derive `shrink_slice::read::ShrinkRead` for a struct, reading its fields in declaration
+/// order.
+///
+/// This accepts the same attributes as `ShrinkWrite`, where skipped fields are set to their
+/// `Default`. In addition, a `Vec<T>` field can be marked with `#[shrink(len = u16)]`, or any
+/// other unsigned integer type, to read a length prefix of that type followed by that many
+/// elements.
+#[proc_macro_derive(ShrinkRead, attributes(shrink))]
+pub fn derive_shrink_read(input: TokenStream) -> TokenStream {
+    match Struct::parse(input) {
+        Ok(s) => s.shrink_read(),
+        Err(message) => compile_error(&message),
+    }
+}
+
+/// Derive `shrink_slice::Shrink` for a wrapper struct by forwarding to one of its fields.
+///
+/// A struct with a single field forwards to that field. Otherwise, mark the field to forward to
+/// with `#[shrink(inner)]`. The field's type gets a `Shrink` bound in the impl's where clause, so
+/// wrappers that are generic over it need no bound of their own.
+#[proc_macro_derive(Shrink, attributes(shrink))]
+pub fn derive_shrink(input: TokenStream) -> TokenStream {
+    match Struct::parse(input) {
+        Ok(s) => s.shrink(),
+        Err(message) => compile_error(&message),
+    }
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("compile_error!({:?});", message).parse().unwrap()
+}
+
+/// Options given in `#[shrink(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    skip: bool,
+    inner: bool,
+    endian: Option<&'static str>,
+    len: Option<String>,
+}
+
+impl Options {
+    /// Collect the options from every `#[shrink(...)]` attribute in `attrs`, which holds the
+    /// bracketed groups following each `#`.
+    fn parse(attrs: &[TokenStream]) -> Result<Options, String> {
+        let mut options = Options::default();
+        for attr in attrs {
+            let mut tokens = attr.clone().into_iter();
+            match tokens.next() {
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "shrink" => {}
+                _ => continue,
+            }
+            let args = match tokens.next() {
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => g.stream(),
+                _ => return Err("expected `#[shrink(...)]`".into()),
+            };
+            for arg in split_commas(args) {
+                let arg = arg.to_string();
+                if let Some((key, ty)) = arg.split_once('=') {
+                    if key.trim() == "len" {
+                        options.len = Some(ty.trim().to_string());
+                        continue;
+                    }
+                }
+                match arg.as_str() {
+                    "skip" => options.skip = true,
+                    "inner" => options.inner = true,
+                    "little_endian" => options.endian = Some("Little"),
+                    "big_endian" => options.endian = Some("Big"),
+                    other => return Err(format!("unknown shrink option `{}`", other)),
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+struct Field {
+    /// The expression that accesses the field on `self`, like `name` or `0`.
+    member: String,
+    ty: String,
+    options: Options,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Named,
+    Tuple,
+    Unit,
+}
+
+struct Struct {
+    name: String,
+    kind: Kind,
+    /// Generic parameters as written, minus defaults, for the `impl<...>`.
+    impl_generics: String,
+    /// Generic parameters as arguments, for the type after `for`.
+    type_generics: String,
+    where_clause: String,
+    options: Options,
+    fields: Vec<Field>,
+}
+
+impl Struct {
+    fn parse(input: TokenStream) -> Result<Struct, String> {
+        let mut tokens = input.into_iter().peekable();
+        let attrs = take_attrs(&mut tokens);
+        let options = Options::parse(&attrs)?;
+
+        // Skip the visibility and find the `struct` keyword.
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => break,
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => {
+                    return Err("enums are not supported".into())
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "union" => {
+                    return Err("unions are not supported".into())
+                }
+                Some(_) => {}
+                None => return Err("expected a struct".into()),
+            }
+        }
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => return Err("expected a struct name".into()),
+        };
+
+        // Generic parameters, up to the matching `>`.
+        let mut params = Vec::new();
+        if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<') {
+            tokens.next();
+            let mut depth = 1;
+            for tt in tokens.by_ref() {
+                if let TokenTree::Punct(p) = &tt {
+                    match p.as_char() {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        _ => {}
+                    }
+                }
+                if depth == 0 {
+                    break;
+                }
+                params.push(tt);
+            }
+        }
+        let params = split_commas(params.into_iter().collect());
+        let impl_generics = params.iter().map(strip_default).collect::<Vec<_>>().join(", ");
+        let type_generics = params.iter().map(param_name).collect::<Vec<_>>().join(", ");
+
+        // Tuple fields come right after the generics, before any where clause, while named
+        // fields come last, after it. Anything else is the where clause, which may contain
+        // parenthesized groups of its own, as in `F: Fn(u8)`.
+        let mut rest: Vec<TokenTree> = tokens.collect();
+        if matches!(rest.last(), Some(TokenTree::Punct(p)) if p.as_char() == ';') {
+            rest.pop();
+        }
+        let (kind, fields) = match (rest.first(), rest.last()) {
+            (Some(TokenTree::Group(g)), _) if g.delimiter() == Delimiter::Parenthesis => {
+                let fields = parse_fields(g.stream(), false)?;
+                rest.remove(0);
+                (Kind::Tuple, fields)
+            }
+            (_, Some(TokenTree::Group(g))) if g.delimiter() == Delimiter::Brace => {
+                let fields = parse_fields(g.stream(), true)?;
+                rest.pop();
+                (Kind::Named, fields)
+            }
+            _ => (Kind::Unit, Vec::new()),
+        };
+        let where_clause: TokenStream = rest.into_iter().collect();
+
+        Ok(Struct {
+            name,
+            kind,
+            impl_generics,
+            type_generics,
+            where_clause: where_clause.to_string(),
+            options,
+            fields,
+        })
+    }
+
+    /// The start of an impl of `trait_path` for this struct, up to and including the opening
+    /// brace.
+    fn impl_header(&self, trait_path: &str) -> String {
+        format!(
+            "impl<{}> {} for {}<{}> {} {{",
+            self.impl_generics, trait_path, self.name, self.type_generics, self.where_clause,
+        )
+    }
+
+    fn endian_expr(options: &Options, default: &str) -> String {
+        match options.endian {
+            Some(endian) => format!("::shrink_slice::endian::Endian::{}", endian),
+            None => default.to_string(),
+        }
+    }
+
+    fn shrink(&self) -> TokenStream {
+        let field = match self.fields.iter().filter(|f| f.options.inner).count() {
+            0 if self.fields.len() == 1 => &self.fields[0],
+            0 => return compile_error("mark the field to shrink with `#[shrink(inner)]`"),
+            1 => self.fields.iter().find(|f| f.options.inner).unwrap(),
+            _ => return compile_error("only one field can be marked `#[shrink(inner)]`"),
+        };
+        let bound = format!("{}: ::shrink_slice::Shrink", field.ty);
+        let where_clause = match self.where_clause.trim().trim_end_matches(',') {
+            "" => format!("where {}", bound),
+            clause => format!("{}, {}", clause, bound),
+        };
+        let mut out = format!(
+            "impl<{}> ::shrink_slice::Shrink for {}<{}> {} {{",
+            self.impl_generics, self.name, self.type_generics, where_clause,
+        );
+        let _ = write!(
+            out,
+            "type Slice = <{ty} as ::shrink_slice::Shrink>::Slice; \
+             #[inline] \
+             fn try_shrink<R>(&mut self, range: R) \
+                 -> ::core::result::Result<(), ::shrink_slice::ShrinkError> \
+             where R: ::core::slice::SliceIndex<Self::Slice, Output = Self::Slice> \
+                 + ::core::ops::RangeBounds<usize> \
+             {{ <{ty} as ::shrink_slice::Shrink>::try_shrink(&mut self.{member}, range) }} \
+             #[inline] \
+             fn len(&self) -> usize {{ <{ty} as ::shrink_slice::Shrink>::len(&self.{member}) }} }}",
+            ty = field.ty, member = field.member,
+        );
+        out.parse().unwrap()
+    }
+
+    fn shrink_write(&self) -> TokenStream {
+        let mut out = self.impl_header("::shrink_slice::write::ShrinkWrite");
+        out.push_str(
+            "#[allow(unused_variables, unused_mut)] \
+             fn write_to_endian(\
+                 &self, out: &mut &mut [u8], endian: ::shrink_slice::endian::Endian,\
+             ) -> ::core::result::Result<(), ::shrink_slice::NeedMore> {",
+        );
+        let _ = write!(out, "let endian = {};", Self::endian_expr(&self.options, "endian"));
+        out.push_str("let mut view: &mut [u8] = &mut **out;");
+        for field in self.fields.iter().filter(|f| !f.options.skip) {
+            let _ = write!(
+                out,
+                "<{} as ::shrink_slice::write::ShrinkWrite>::write_to_endian(\
+                     &self.{}, &mut view, {})?;",
+                field.ty, field.member, Self::endian_expr(&field.options, "endian"),
+            );
+        }
+        out.push_str(
+            "let remaining = view.len(); \
+             let written = out.len() - remaining; \
+             ::shrink_slice::Take::take_exact(out, written)?; \
+             ::core::result::Result::Ok(()) } }",
+        );
+        out.parse().unwrap()
+    }
+
+    fn shrink_read(&self) -> TokenStream {
+        let mut out = self.impl_header("::shrink_slice::read::ShrinkRead");
+        out.push_str(
+            "#[allow(unused_variables, unused_mut)] \
+             fn read_from_endian(\
+                 input: &mut &[u8], endian: ::shrink_slice::endian::Endian,\
+             ) -> ::core::result::Result<Self, ::shrink_slice::NeedMore> {",
+        );
+        let _ = write!(out, "let endian = {};", Self::endian_expr(&self.options, "endian"));
+        out.push_str("let mut view: &[u8] = *input;");
+        for (i, field) in self.fields.iter().enumerate() {
+            let endian = Self::endian_expr(&field.options, "endian");
+            let _ = match (&field.options.len, field.options.skip) {
+                (_, true) => write!(out, "let field{} = ::core::default::Default::default();", i),
+                (Some(len), false) => write!(
+                    out,
+                    "let field{} = ::shrink_slice::read::read_len_prefixed::<{}, _>(\
+                         &mut view, {})?;",
+                    i, len, endian,
+                ),
+                (None, false) => write!(
+                    out,
+                    "let field{} = <{} as ::shrink_slice::read::ShrinkRead>::read_from_endian(\
+                         &mut view, {})?;",
+                    i, field.ty, endian,
+                ),
+            };
+        }
+        out.push_str("*input = view; ::core::result::Result::Ok(");
+        out.push_str(&self.name);
+        let values = self.fields.iter().enumerate().map(|(i, field)| match self.kind {
+            Kind::Named => format!("{}: field{}", field.member, i),
+            _ => format!("field{}", i),
+        });
+        let values = values.collect::<Vec<_>>().join(", ");
+        let _ = match self.kind {
+            Kind::Named => write!(out, " {{ {} }}", values),
+            Kind::Tuple => write!(out, "({})", values),
+            Kind::Unit => Ok(()),
+        };
+        out.push_str(") } }");
+        out.parse().unwrap()
+    }
+}
+
+/// Take any leading `#[...]` attributes, returning the contents of their brackets.
+fn take_attrs<I>(tokens: &mut std::iter::Peekable<I>) -> Vec<TokenStream>
+where I: Iterator<Item = TokenTree>,
+{
+    let mut attrs = Vec::new();
+    while matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        tokens.next();
+        if let Some(TokenTree::Group(g)) = tokens.next() {
+            attrs.push(g.stream());
+        }
+    }
+    attrs
+}
+
+/// Split a token stream on the commas that are not nested inside angle brackets.
+fn split_commas(stream: TokenStream) -> Vec<TokenStream> {
+    let mut parts = Vec::new();
+    let mut current = TokenStream::new();
+    let mut depth = 0i32;
+    let mut arrow = false;
+    for tt in stream {
+        if let TokenTree::Punct(p) = &tt {
+            let after_dash = std::mem::replace(&mut arrow, p.as_char() == '-');
+            match p.as_char() {
+                '<' => depth += 1,
+                '>' if !after_dash => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            }
+        } else {
+            arrow = false;
+        }
+        current.extend(Some(tt));
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// A generic parameter without its default, as in `T: Clone` for `T: Clone = u8`.
+fn strip_default(param: &TokenStream) -> String {
+    let tokens: Vec<_> = param.clone().into_iter()
+        .take_while(|tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == '='))
+        .collect();
+    tokens.into_iter().collect::<TokenStream>().to_string()
+}
+
+/// The name of a generic parameter, as in `'a` for `'a: 'b` or `N` for `const N: usize`.
+fn param_name(param: &TokenStream) -> String {
+    let mut tokens = param.clone().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '\'' => {
+            format!("'{}", tokens.next().map(|t| t.to_string()).unwrap_or_default())
+        }
+        Some(TokenTree::Ident(i)) if i.to_string() == "const" => {
+            tokens.next().map(|t| t.to_string()).unwrap_or_default()
+        }
+        Some(tt) => tt.to_string(),
+        None => String::new(),
+    }
+}
+
+fn parse_fields(stream: TokenStream, named: bool) -> Result<Vec<Field>, String> {
+    let mut fields = Vec::new();
+    for (index, field) in split_commas(stream).into_iter().enumerate() {
+        let mut tokens = field.into_iter().peekable();
+        let options = Options::parse(&take_attrs(&mut tokens))?;
+
+        // Visibility: `pub`, optionally followed by a restriction like `(crate)`.
+        if matches!(tokens.peek(), Some(TokenTree::Ident(i)) if i.to_string() == "pub") {
+            tokens.next();
+            if matches!(tokens.peek(),
+                Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+            {
+                tokens.next();
+            }
+        }
+
+        let member = if named {
+            let name = match tokens.next() {
+                Some(TokenTree::Ident(ident)) => ident.to_string(),
+                _ => return Err("expected a field name".into()),
+            };
+            tokens.next(); // The colon.
+            name
+        } else {
+            index.to_string()
+        };
+        let ty = tokens.collect::<TokenStream>().to_string();
+        fields.push(Field { member, ty, options });
+    }
+    Ok(fields)
+}
@@ -0,0 +1,137 @@
+//! Consuming known prefixes and suffixes.
+
+use crate::{Shrink, ShrinkExt};
+
+/// Extension trait for shrinking past a prefix or suffix, if the slice has it.
+///
+/// These mirror `strip_prefix` and `strip_suffix`, but shrink the slice instead of returning a
+/// new one. They return whether the affix was there; if it was not, the slice is left untouched.
+///
+/// ```rust
+/// use shrink_slice::affix::ConsumeAffix;
+///
+/// let mut command = "git commit --amend";
+/// assert!(command.consume_prefix("git "));
+/// assert!(!command.consume_prefix("push"));
+/// assert!(command.consume_suffix(" --amend"));
+/// assert_eq!(command, "commit");
+///
+/// let mut packet: &[u8] = b"\x7fELF\x02\x01";
+/// assert!(packet.consume_prefix(b"\x7fELF"));
+/// assert_eq!(packet, [2, 1]);
+/// ```
+pub trait ConsumeAffix {
+    /// The type of slice the affixes are given as.
+    type Pattern: ?Sized;
+
+    /// If the slice starts with `prefix`, shrink past it and return `true`.
+    fn consume_prefix(&mut self, prefix: &Self::Pattern) -> bool;
+
+    /// If the slice ends with `suffix`, shrink it away and return `true`.
+    fn consume_suffix(&mut self, suffix: &Self::Pattern) -> bool;
+}
+
+impl<T: PartialEq> ConsumeAffix for &[T] {
+    type Pattern = [T];
+
+    fn consume_prefix(&mut self, prefix: &[T]) -> bool {
+        let matches = self.starts_with(prefix);
+        if matches {
+            self.shrink(prefix.len()..);
+        }
+        matches
+    }
+
+    fn consume_suffix(&mut self, suffix: &[T]) -> bool {
+        let matches = self.ends_with(suffix);
+        if matches {
+            self.shrink(..self.len() - suffix.len());
+        }
+        matches
+    }
+}
+
+impl<T: PartialEq> ConsumeAffix for &mut [T] {
+    type Pattern = [T];
+
+    fn consume_prefix(&mut self, prefix: &[T]) -> bool {
+        let matches = self.starts_with(prefix);
+        if matches {
+            self.shrink(prefix.len()..);
+        }
+        matches
+    }
+
+    fn consume_suffix(&mut self, suffix: &[T]) -> bool {
+        let matches = self.ends_with(suffix);
+        if matches {
+            let end = self.len() - suffix.len();
+            self.shrink(..end);
+        }
+        matches
+    }
+}
+
+impl ConsumeAffix for &str {
+    type Pattern = str;
+
+    fn consume_prefix(&mut self, prefix: &str) -> bool {
+        let matches = self.starts_with(prefix);
+        if matches {
+            self.shrink(prefix.len()..);
+        }
+        matches
+    }
+
+    fn consume_suffix(&mut self, suffix: &str) -> bool {
+        let matches = self.ends_with(suffix);
+        if matches {
+            self.shrink(..self.len() - suffix.len());
+        }
+        matches
+    }
+}
+
+impl ConsumeAffix for &mut str {
+    type Pattern = str;
+
+    fn consume_prefix(&mut self, prefix: &str) -> bool {
+        let matches = self.starts_with(prefix);
+        if matches {
+            self.shrink(prefix.len()..);
+        }
+        matches
+    }
+
+    fn consume_suffix(&mut self, suffix: &str) -> bool {
+        let matches = self.ends_with(suffix);
+        if matches {
+            let end = self.len() - suffix.len();
+            self.shrink(..end);
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mut_slices() {
+        let mut buffer = [1, 2, 3, 4];
+        let mut slice: &mut [i32] = &mut buffer;
+        assert!(!slice.consume_suffix(&[3]));
+        assert!(slice.consume_suffix(&[3, 4]));
+        assert!(slice.consume_prefix(&[]));
+        assert!(!slice.consume_prefix(&[1, 2, 3]));
+        slice[1] = 20;
+        assert_eq!(buffer, [1, 20, 3, 4]);
+
+        let mut owned = String::from("<b>text</b>");
+        let mut text: &mut str = &mut owned;
+        assert!(text.consume_prefix("<b>") && text.consume_suffix("</b>"));
+        text.make_ascii_uppercase();
+        assert_eq!(owned, "<b>TEXT</b>");
+    }
+}
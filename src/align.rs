@@ -0,0 +1,285 @@
+//! Splitting byte buffers along the alignment of a target type.
+
+use crate::{Shrink, ShrinkExt};
+use core::ops::Range;
+use core::slice::SliceIndex;
+use core::{fmt, mem};
+
+/// The lengths of the unaligned head and of the aligned body of `bytes`, for elements of `A`.
+fn aligned_parts<A>(bytes: &[u8]) -> (usize, usize) {
+    let len = bytes.len();
+    let head_len = bytes.as_ptr().align_offset(mem::align_of::<A>()).min(len);
+    let rest = len - head_len;
+    let body_len = match mem::size_of::<A>() {
+        0 => rest,
+        size => rest - rest % size,
+    };
+    (head_len, body_len)
+}
+
+/// The range of `bytes` that is aligned for `A` and holds a whole number of them.
+fn aligned_range<A>(bytes: &[u8]) -> Range<usize> {
+    let (head_len, body_len) = aligned_parts::<A>(bytes);
+    head_len..head_len + body_len
+}
+
+/// The range of `bytes` from the first address aligned to `align` on, if it is not empty.
+fn alignment_range(bytes: &[u8], align: usize) -> Result<Range<usize>, AlignError> {
+    if !align.is_power_of_two() {
+        return Err(AlignError::NotPowerOfTwo(align));
+    }
+    match bytes.as_ptr().align_offset(align) {
+        head if head < bytes.len() => Ok(head..bytes.len()),
+        _ => Err(AlignError::Empty),
+    }
+}
+
+/// The range of the blocks of `align` bytes in `bytes` that start at the first address aligned
+/// to `align`, as long as there is at least one block.
+fn blocks_range(bytes: &[u8], align: usize) -> Result<Range<usize>, AlignError> {
+    let Range { start, end } = alignment_range(bytes, align)?;
+    let rest = end - start;
+    match rest - rest % align {
+        0 => Err(AlignError::Empty),
+        body => Ok(start..start + body),
+    }
+}
+
+/// Shrink `bytes` to `range`, which is within bounds, and return the number of bytes dropped
+/// from the front and from the back.
+fn shrink_to<S>(bytes: &mut S, range: Range<usize>) -> (usize, usize)
+where S: Shrink, Range<usize>: SliceIndex<S::Slice, Output = S::Slice>,
+{
+    let dropped = (range.start, bytes.len() - range.end);
+    bytes.shrink(range);
+    dropped
+}
+
+/// Extension trait for carving an aligned region out of a mutable byte slice.
+pub trait SplitAligned {
+    /// Split the slice into an unaligned head, a body that is aligned for `A` and whose length
+    /// is a multiple of `size_of::<A>()`, and the leftover tail.
+    ///
+    /// The three parts are disjoint and cover the whole slice, so each of them can be shrunk
+    /// independently afterwards. If no aligned element fits, the body is empty and the tail
+    /// holds whatever follows the head.
+    ///
+    /// ```rust
+    /// use shrink_slice::align::SplitAligned;
+    ///
+    /// #[repr(align(4))]
+    /// struct Aligned([u8; 16]);
+    ///
+    /// let mut storage = Aligned([0; 16]);
+    /// let (head, body, tail) = storage.0[1..15].split_aligned::<u32>();
+    /// assert_eq!((head.len(), body.len(), tail.len()), (3, 8, 3));
+    /// ```
+    fn split_aligned<A>(&mut self) -> (&mut [u8], &mut [u8], &mut [u8]);
+}
+
+impl SplitAligned for [u8] {
+    fn split_aligned<A>(&mut self) -> (&mut [u8], &mut [u8], &mut [u8]) {
+        let (head_len, body_len) = aligned_parts::<A>(self);
+        let (head, rest) = self.split_at_mut(head_len);
+        let (body, tail) = rest.split_at_mut(body_len);
+        (head, body, tail)
+    }
+}
+
+/// Extension trait for shrinking a byte slice to its part that is aligned for a target type.
+pub trait ShrinkAligned {
+    /// Shrink the slice to the body that [`split_aligned`](SplitAligned::split_aligned) would
+    /// return: aligned for `A`, with a length that is a multiple of `size_of::<A>()`. Returns the
+    /// number of bytes dropped from the front and from the back.
+    ///
+    /// ```rust
+    /// use shrink_slice::align::ShrinkAligned;
+    ///
+    /// #[repr(align(4))]
+    /// struct Aligned([u8; 16]);
+    ///
+    /// let storage = Aligned([0; 16]);
+    /// let mut window = &storage.0[1..15];
+    /// assert_eq!(window.shrink_to_aligned::<u32>(), (3, 3));
+    /// assert_eq!((window.as_ptr() as usize % 4, window.len()), (0, 8));
+    /// ```
+    fn shrink_to_aligned<A>(&mut self) -> (usize, usize);
+
+    /// Drop bytes from the front until the slice starts at an address that is a multiple of
+    /// `align`, which must be a power of two. Returns the number of bytes dropped.
+    ///
+    /// If `align` is not a power of two, or the slice would be left empty, it is left untouched
+    /// and an error is returned instead.
+    ///
+    /// ```rust
+    /// use shrink_slice::align::{AlignError, ShrinkAligned};
+    ///
+    /// #[repr(align(64))]
+    /// struct Aligned([u8; 128]);
+    ///
+    /// let storage = Aligned([0; 128]);
+    /// let mut window = &storage.0[3..100];
+    /// assert_eq!(window.try_shrink_to_alignment(16), Ok(13));
+    /// assert_eq!((window.as_ptr() as usize % 16, window.len()), (0, 84));
+    /// assert_eq!(window.try_shrink_to_alignment(48), Err(AlignError::NotPowerOfTwo(48)));
+    /// assert_eq!((&storage.0[1..64]).try_shrink_to_alignment(64), Err(AlignError::Empty));
+    /// ```
+    fn try_shrink_to_alignment(&mut self, align: usize) -> Result<usize, AlignError>;
+
+    /// Drop bytes from the front like
+    /// [`try_shrink_to_alignment`](ShrinkAligned::try_shrink_to_alignment), and from the back
+    /// until the length is a multiple of `align` too. Returns the number of bytes dropped from
+    /// the front and from the back.
+    ///
+    /// If `align` is not a power of two, or not even one block of `align` bytes would be left,
+    /// the slice is left untouched and an error is returned instead.
+    fn try_shrink_to_aligned_blocks(&mut self, align: usize)
+        -> Result<(usize, usize), AlignError>;
+}
+
+/// The panicking counterpart of
+/// [`try_shrink_to_alignment`](ShrinkAligned::try_shrink_to_alignment), implemented for every
+/// [`ShrinkAligned`] slice.
+pub trait ShrinkAlignedExt: ShrinkAligned {
+    /// Drop bytes from the front like
+    /// [`try_shrink_to_alignment`](ShrinkAligned::try_shrink_to_alignment), panicking if `align`
+    /// is not a power of two or the slice would be left empty.
+    #[track_caller]
+    fn shrink_to_alignment(&mut self, align: usize) -> usize {
+        match self.try_shrink_to_alignment(align) {
+            Ok(dropped) => dropped,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<S: ShrinkAligned + ?Sized> ShrinkAlignedExt for S { }
+
+impl ShrinkAligned for &[u8] {
+    fn shrink_to_aligned<A>(&mut self) -> (usize, usize) {
+        shrink_to(self, aligned_range::<A>(self))
+    }
+
+    fn try_shrink_to_alignment(&mut self, align: usize) -> Result<usize, AlignError> {
+        let range = alignment_range(self, align)?;
+        Ok(shrink_to(self, range).0)
+    }
+
+    fn try_shrink_to_aligned_blocks(&mut self, align: usize)
+        -> Result<(usize, usize), AlignError>
+    {
+        let range = blocks_range(self, align)?;
+        Ok(shrink_to(self, range))
+    }
+}
+
+impl ShrinkAligned for &mut [u8] {
+    fn shrink_to_aligned<A>(&mut self) -> (usize, usize) {
+        shrink_to(self, aligned_range::<A>(self))
+    }
+
+    fn try_shrink_to_alignment(&mut self, align: usize) -> Result<usize, AlignError> {
+        let range = alignment_range(self, align)?;
+        Ok(shrink_to(self, range).0)
+    }
+
+    fn try_shrink_to_aligned_blocks(&mut self, align: usize)
+        -> Result<(usize, usize), AlignError>
+    {
+        let range = blocks_range(self, align)?;
+        Ok(shrink_to(self, range))
+    }
+}
+
+/// The error for shrinking a slice to an alignment given at runtime.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AlignError {
+    /// The alignment is not a power of two.
+    NotPowerOfTwo(usize),
+    /// Nothing would be left of the slice once it was aligned.
+    Empty,
+}
+
+impl fmt::Display for AlignError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlignError::NotPowerOfTwo(align) => {
+                write!(f, "alignment {} is not a power of two", align)
+            }
+            AlignError::Empty => f.write_str("nothing is left of the slice once it is aligned"),
+        }
+    }
+}
+
+impl core::error::Error for AlignError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(align(8))]
+    struct Aligned([u8; 32]);
+
+    #[test]
+    fn split_every_offset() {
+        let mut storage = Aligned([0; 32]);
+        let bytes = &mut storage.0;
+
+        for start in 0..bytes.len() {
+            let len = bytes.len() - start;
+            let (head, body, tail) = bytes[start..].split_aligned::<u64>();
+            assert_eq!(head.len() + body.len() + tail.len(), len);
+            assert_eq!(body.as_ptr() as usize % 8, 0);
+            assert_eq!(body.len() % 8, 0);
+            assert!(head.len() < 8 && tail.len() < 8);
+        }
+    }
+
+    #[test]
+    fn parts_shrink_independently() {
+        let mut storage = Aligned([0; 32]);
+        let (mut head, mut body, tail) = storage.0[2..18].split_aligned::<u32>();
+        head.shrink(1..);
+        body.shrink(4..);
+        body[0] = 1;
+        assert_eq!((head.len(), body.len(), tail.len()), (1, 8, 2));
+    }
+
+    #[test]
+    fn mut_shrinks_to_body() {
+        let mut storage = Aligned([0; 32]);
+        let mut window: &mut [u8] = &mut storage.0[3..12];
+        assert_eq!(window.shrink_to_aligned::<u64>(), (5, 4));
+        assert!(window.is_empty());
+
+        let mut window: &mut [u8] = &mut storage.0[1..];
+        assert_eq!(window.shrink_to_aligned::<u16>(), (1, 0));
+        window[0] = 1;
+        assert_eq!(storage.0[2], 1);
+    }
+
+    #[test]
+    fn runtime_alignment() {
+        // The expectations below count on the storage starting on a 16-byte boundary.
+        #[repr(align(16))]
+        struct Aligned([u8; 32]);
+
+        let mut storage = Aligned([0; 32]);
+        let mut window: &mut [u8] = &mut storage.0[5..30];
+        assert_eq!(window.try_shrink_to_aligned_blocks(16), Err(AlignError::Empty));
+        assert_eq!(window.try_shrink_to_aligned_blocks(0), Err(AlignError::NotPowerOfTwo(0)));
+        assert_eq!(window.try_shrink_to_aligned_blocks(4), Ok((3, 2)));
+        assert_eq!(window.len(), 20);
+        assert_eq!(window.shrink_to_alignment(1), 0);
+        assert_eq!(window.shrink_to_alignment(16), 8);
+        window[0] = 1;
+        assert_eq!(storage.0[16], 1);
+    }
+
+    #[test]
+    #[should_panic = "nothing is left of the slice"]
+    fn alignment_past_the_end() {
+        let storage = Aligned([0; 32]);
+        (&storage.0[1..8]).shrink_to_alignment(8);
+    }
+}
@@ -0,0 +1,294 @@
+//! Views that remember the buffer they were shrunk from.
+//!
+//! An [`Anchored`] view shrinks like a plain slice, but keeps hold of the original buffer, so
+//! positions within the view can be translated into positions within the original at any time.
+//!
+//! ```rust
+//! use shrink_slice::anchor::{Anchored, Span};
+//! use shrink_slice::ShrinkExt;
+//!
+//! let mut source = Anchored::new("let answer = 42;");
+//! source.shrink(4..);
+//! let (ident, span) = source.take_while_spanned(|c| c.is_alphanumeric());
+//! assert_eq!(ident, "answer");
+//! assert_eq!(span, Span { start: 4, end: 10 });
+//! assert_eq!(&source.original()[span.range()], "answer");
+//! ```
+
+use crate::{range, Shrink, ShrinkError, ShrinkExt};
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+use core::ops::{Range, RangeBounds};
+use core::slice::SliceIndex;
+#[cfg(feature = "std")]
+use std::io;
+
+/// A range of byte or element positions within an original buffer.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Span {
+    /// The position of the first element.
+    pub start: usize,
+    /// The position just past the last element.
+    pub end: usize,
+}
+
+impl Span {
+    /// The span as a range, for indexing into the original buffer.
+    pub fn range(self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// The number of elements covered.
+    pub fn len(self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers nothing.
+    pub fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.range()
+    }
+}
+
+/// A shrinkable view into a slice or string slice that remembers the original.
+#[derive(Debug)]
+pub struct Anchored<'a, T: ?Sized> {
+    original: &'a T,
+    view: &'a T,
+    offset: usize,
+}
+
+impl<T: ?Sized> Clone for Anchored<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Anchored<'_, T> { }
+
+impl<'a, T: ?Sized> Anchored<'a, T> {
+    /// A view of all of `original`.
+    pub fn new(original: &'a T) -> Self {
+        Anchored { original, view: original, offset: 0 }
+    }
+
+    /// The buffer this view was created from.
+    pub fn original(&self) -> &'a T {
+        self.original
+    }
+
+    /// The current view.
+    pub fn view(&self) -> &'a T {
+        self.view
+    }
+
+    /// Where the current view starts within the original.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Anchored<'a, str> {
+    /// The span of the current view within the original.
+    pub fn span(&self) -> Span {
+        Span { start: self.offset, end: self.offset + self.view.len() }
+    }
+
+    /// The span of `token` within the original, if it is a substring of it.
+    pub fn span_of(&self, token: &str) -> Option<Span> {
+        let start = range::offset_in(self.original.as_bytes(), token.as_bytes())?;
+        Some(Span { start, end: start + token.len() })
+    }
+
+    /// Consume `n` bytes from the front of the view.
+    ///
+    /// If `n` is past the end or inside a multi-byte character, nothing is consumed.
+    pub fn take(&mut self, n: usize) -> Result<&'a str, ShrinkError> {
+        self.take_spanned(n).map(|(token, _)| token)
+    }
+
+    /// Like [`take`](Self::take), but also return the span of what was consumed.
+    pub fn take_spanned(&mut self, n: usize) -> Result<(&'a str, Span), ShrinkError> {
+        let token = self.view.get(..n).ok_or_else(|| ShrinkError::for_str(..n, self.view))?;
+        Ok(self.consume(token))
+    }
+
+    /// Consume chars from the front of the view for as long as `pred` returns `true`.
+    pub fn take_while<P: FnMut(char) -> bool>(&mut self, pred: P) -> &'a str {
+        self.take_while_spanned(pred).0
+    }
+
+    /// Like [`take_while`](Self::take_while), but also return the span of what was consumed.
+    pub fn take_while_spanned<P>(&mut self, mut pred: P) -> (&'a str, Span)
+    where P: FnMut(char) -> bool,
+    {
+        let n = self.view.find(|c| !pred(c)).unwrap_or(self.view.len());
+        self.consume(&self.view[..n])
+    }
+
+    fn consume(&mut self, token: &'a str) -> (&'a str, Span) {
+        let span = Span { start: self.offset, end: self.offset + token.len() };
+        self.view.shrink(token.len()..);
+        self.offset = span.end;
+        (token, span)
+    }
+}
+
+impl<'a, T> Anchored<'a, [T]> {
+    /// The span of the current view within the original.
+    pub fn span(&self) -> Span {
+        Span { start: self.offset, end: self.offset + self.view.len() }
+    }
+}
+
+/// Reading consumes bytes from the front of the view.
+#[cfg(feature = "std")]
+impl io::Read for Anchored<'_, [u8]> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.view.len());
+        buf[..n].copy_from_slice(&self.view[..n]);
+        self.shrink(n..);
+        Ok(n)
+    }
+}
+
+/// Seeking moves the start of the view to anywhere in the original from its beginning up to the
+/// end of the view, which stays put. [`SeekFrom::End`](io::SeekFrom::End) is relative to the end
+/// of the view, so a shrunken view reads like a stream of its own.
+///
+/// Seeking past the end of the view, or before the start of the original, is an error of kind
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) and leaves the view untouched.
+///
+/// ```rust
+/// use std::io::{Read, Seek, SeekFrom};
+/// use shrink_slice::anchor::Anchored;
+/// use shrink_slice::ShrinkExt;
+///
+/// let mut file = Anchored::new(&b"header|body|trailer"[..]);
+/// file.shrink(7..11);
+/// assert_eq!(file.seek(SeekFrom::End(-2)).unwrap(), 9);
+/// let mut tail = String::new();
+/// file.read_to_string(&mut tail).unwrap();
+/// assert_eq!(tail, "dy");
+/// file.seek(SeekFrom::Start(0)).unwrap();
+/// assert_eq!(file.view(), b"header|body");
+/// assert!(file.seek(SeekFrom::End(1)).is_err());
+/// ```
+#[cfg(feature = "std")]
+impl io::Seek for Anchored<'_, [u8]> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let end = self.offset + self.view.len();
+        let (base, delta) = match pos {
+            io::SeekFrom::Start(n) => (0, i64::try_from(n).ok()),
+            io::SeekFrom::End(n) => (end, Some(n)),
+            io::SeekFrom::Current(n) => (self.offset, Some(n)),
+        };
+        let target = delta
+            .and_then(|delta| isize::try_from(delta).ok())
+            .and_then(|delta| base.checked_add_signed(delta))
+            .filter(|&target| target <= end)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "seek outside of the view")
+            })?;
+        self.view = &self.original[target..end];
+        self.offset = target;
+        Ok(target as u64)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.offset as u64)
+    }
+}
+
+impl<T> Shrink for Anchored<'_, [T]> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let bounds = range::bounds(&range);
+        let view = self.view.get(range)
+            .ok_or_else(|| ShrinkError::out_of_bounds(bounds, self.view.len()))?;
+        self.offset += range::offset_in(self.view, view).expect("subslice is within the slice");
+        self.view = view;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.view.len()
+    }
+}
+
+impl Shrink for Anchored<'_, str> {
+    type Slice = str;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>
+    {
+        let bounds = range::bounds(&range);
+        let view = self.view.get(range).ok_or_else(|| ShrinkError::for_str(bounds, self.view))?;
+        self.offset += range::offset_in(self.view.as_bytes(), view.as_bytes())
+            .expect("substring is within the string");
+        self.view = view;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.view.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_view_offsets() {
+        let data = [0u16, 1, 2, 3, 4, 5];
+        let mut view = Anchored::new(&data[..]);
+        view.shrink(2..5);
+        view.shrink(1..);
+        assert_eq!(view.view(), [3, 4]);
+        assert_eq!(view.span(), Span { start: 3, end: 5 });
+        assert!(view.try_shrink(..3).is_err());
+        assert_eq!(view.offset(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn seek_keeps_view_end() {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let data = *b"0123456789";
+        let mut view = Anchored::new(&data[..]);
+        view.shrink(2..6);
+        let mut buf = [0; 3];
+        assert_eq!(view.read(&mut buf).unwrap(), 3);
+        assert_eq!(view.seek(SeekFrom::Current(-4)).unwrap(), 1);
+        assert_eq!(view.view(), b"12345");
+        assert!(view.seek(SeekFrom::Start(7)).is_err());
+        assert!(view.seek(SeekFrom::Current(-2)).is_err());
+        assert_eq!(view.seek(SeekFrom::End(0)).unwrap(), 6);
+        assert_eq!(view.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn lexer_spans() {
+        let mut source = Anchored::new("a \u{3bb} bc");
+        let mut tokens = Vec::new();
+        while !source.view().is_empty() {
+            source.take_while(char::is_whitespace);
+            tokens.push(source.take_while_spanned(|c| !c.is_whitespace()).1);
+        }
+        let ranges: Vec<_> = tokens.iter().map(|span| span.range()).collect();
+        assert_eq!(ranges, [0..1, 2..4, 5..7]);
+        assert!(matches!(source.take_spanned(1), Err(ShrinkError::OutOfBounds { .. })));
+        assert_eq!(source.span_of(&source.original()[2..4]), Some(Span { start: 2, end: 4 }));
+        assert_eq!(source.span_of("elsewhere"), None);
+    }
+}
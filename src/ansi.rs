@@ -0,0 +1,180 @@
+//! Consuming ANSI/VT escape sequences.
+
+use crate::ShrinkExt;
+use core::fmt;
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+/// The kind of an escape sequence, determined by the byte following `ESC`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EscapeKind {
+    /// A control sequence, `ESC [`, such as a cursor movement or SGR color change.
+    Csi,
+    /// An operating system command, `ESC ]`, such as setting the window title.
+    Osc,
+    /// A device control string, `ESC P`.
+    Dcs,
+    /// A start of string, `ESC X`.
+    Sos,
+    /// A privacy message, `ESC ^`.
+    Pm,
+    /// An application program command, `ESC _`.
+    Apc,
+    /// Any other sequence: `ESC`, optional intermediate bytes, and a final byte.
+    Escape,
+}
+
+/// Find the length of the escape sequence at the front of `bytes`.
+fn scan(bytes: &[u8]) -> Result<(EscapeKind, usize), AnsiError> {
+    if bytes.first() != Some(&ESC) {
+        return Err(AnsiError::NotEscape);
+    }
+    let kind = match bytes.get(1).ok_or(AnsiError::Incomplete)? {
+        b'[' => EscapeKind::Csi,
+        b']' => EscapeKind::Osc,
+        b'P' => EscapeKind::Dcs,
+        b'X' => EscapeKind::Sos,
+        b'^' => EscapeKind::Pm,
+        b'_' => EscapeKind::Apc,
+        _ => EscapeKind::Escape,
+    };
+
+    match kind {
+        EscapeKind::Csi => {
+            let mut i = 2;
+            while let Some(0x30..=0x3f) = bytes.get(i) {
+                i += 1;
+            }
+            while let Some(0x20..=0x2f) = bytes.get(i) {
+                i += 1;
+            }
+            match bytes.get(i).ok_or(AnsiError::Incomplete)? {
+                0x40..=0x7e => Ok((kind, i + 1)),
+                _ => Err(AnsiError::Invalid),
+            }
+        }
+        EscapeKind::Escape => {
+            let mut i = 1;
+            while let Some(0x20..=0x2f) = bytes.get(i) {
+                i += 1;
+            }
+            match bytes.get(i).ok_or(AnsiError::Incomplete)? {
+                0x30..=0x7e => Ok((kind, i + 1)),
+                _ => Err(AnsiError::Invalid),
+            }
+        }
+        // The string sequences run until a string terminator, `ESC \`. An OSC may also be ended
+        // by BEL, as xterm does.
+        _ => {
+            let mut i = 2;
+            loop {
+                match *bytes.get(i).ok_or(AnsiError::Incomplete)? {
+                    BEL if kind == EscapeKind::Osc => return Ok((kind, i + 1)),
+                    ESC => return match bytes.get(i + 1).ok_or(AnsiError::Incomplete)? {
+                        b'\\' => Ok((kind, i + 2)),
+                        _ => Err(AnsiError::Invalid),
+                    },
+                    _ => i += 1,
+                }
+            }
+        }
+    }
+}
+
+/// Extension trait for consuming escape sequences from the front of terminal output.
+pub trait TakeAnsiEscape: Sized {
+    /// If the slice starts with a complete escape sequence, shrink past it and return it along
+    /// with its kind. On error the slice is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::ansi::{EscapeKind, TakeAnsiEscape};
+    /// use shrink_slice::ShrinkExt;
+    ///
+    /// let mut output = "\x1b[1;31mred\x1b]0;title\x07";
+    /// assert_eq!(output.take_ansi_escape(), Ok((EscapeKind::Csi, "\x1b[1;31m")));
+    /// output.shrink(3..);
+    /// assert_eq!(output.take_ansi_escape(), Ok((EscapeKind::Osc, "\x1b]0;title\x07")));
+    /// assert!(output.is_empty());
+    /// ```
+    fn take_ansi_escape(&mut self) -> Result<(EscapeKind, Self), AnsiError>;
+}
+
+impl TakeAnsiEscape for &[u8] {
+    fn take_ansi_escape(&mut self) -> Result<(EscapeKind, Self), AnsiError> {
+        let (kind, len) = scan(self)?;
+        let sequence = &self[..len];
+        self.shrink(len..);
+        Ok((kind, sequence))
+    }
+}
+
+impl TakeAnsiEscape for &str {
+    fn take_ansi_escape(&mut self) -> Result<(EscapeKind, Self), AnsiError> {
+        // Sequences always end in an ASCII byte, so this is a char boundary.
+        let (kind, len) = scan(self.as_bytes())?;
+        let sequence = &self[..len];
+        self.shrink(len..);
+        Ok((kind, sequence))
+    }
+}
+
+/// This error signifies that no escape sequence could be taken from the front of the input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnsiError {
+    /// The input does not start with `ESC`.
+    NotEscape,
+    /// The input ends before the sequence does.
+    Incomplete,
+    /// The sequence contains a byte that is not allowed where it appears.
+    Invalid,
+}
+
+impl fmt::Display for AnsiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            AnsiError::NotEscape => "not an escape sequence",
+            AnsiError::Incomplete => "incomplete escape sequence",
+            AnsiError::Invalid => "invalid escape sequence",
+        })
+    }
+}
+
+impl core::error::Error for AnsiError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds() {
+        for (input, kind, len) in [
+            (&b"\x1b[?25h!"[..], EscapeKind::Csi, 6),
+            (b"\x1b]8;;http://x\x1b\\!", EscapeKind::Osc, 15),
+            (b"\x1bPq#0\x1b\\!", EscapeKind::Dcs, 7),
+            (b"\x1b_app\x1b\\!", EscapeKind::Apc, 7),
+            (b"\x1b(B!", EscapeKind::Escape, 3),
+            (b"\x1b7!", EscapeKind::Escape, 2),
+        ] {
+            let mut slice = input;
+            assert_eq!(slice.take_ansi_escape(), Ok((kind, &input[..len])));
+            assert_eq!(slice, b"!");
+        }
+    }
+
+    #[test]
+    fn errors_leave_input() {
+        for (input, err) in [
+            (&b"plain"[..], AnsiError::NotEscape),
+            (b"\x1b", AnsiError::Incomplete),
+            (b"\x1b[12", AnsiError::Incomplete),
+            (b"\x1b]title", AnsiError::Incomplete),
+            (b"\x1b[1\x01", AnsiError::Invalid),
+            (b"\x1bP\x1bx", AnsiError::Invalid),
+        ] {
+            let mut slice = input;
+            assert_eq!(slice.take_ansi_escape(), Err(err));
+            assert_eq!(slice, input);
+        }
+    }
+}
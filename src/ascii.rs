@@ -0,0 +1,111 @@
+//! Consuming runs of ASCII characters by scanning bytes.
+
+use crate::ShrinkExt;
+
+/// Count the leading bytes that are ASCII and satisfy `pred`.
+fn prefix_len(bytes: &[u8], pred: impl Fn(u8) -> bool) -> usize {
+    bytes.iter().take_while(|&&b| b.is_ascii() && pred(b)).count()
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_continue(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Extension trait for taking runs of ASCII characters from the front of a string or byte slice.
+///
+/// These scan byte by byte rather than decoding chars. Bytes outside the ASCII range never match,
+/// so a string slice is only ever cut at a char boundary. Each method returns the matched prefix,
+/// which may be empty, and shrinks past it.
+///
+/// ```rust
+/// use shrink_slice::ascii::TakeAscii;
+///
+/// let mut line = "x_1 = 42;";
+/// assert_eq!(line.take_ascii_ident(), "x_1");
+/// assert_eq!(line.take_while_ascii(|b| b == b' ' || b == b'='), " = ");
+/// assert_eq!(line.take_ascii_digits(), "42");
+/// assert_eq!(line, ";");
+/// ```
+pub trait TakeAscii: Sized {
+    /// Take the longest prefix of ASCII bytes that satisfy `pred`.
+    fn take_while_ascii(&mut self, pred: impl Fn(u8) -> bool) -> Self;
+
+    /// Take the longest prefix of ASCII digits.
+    fn take_ascii_digits(&mut self) -> Self {
+        self.take_while_ascii(|b| b.is_ascii_digit())
+    }
+
+    /// Take the longest prefix of ASCII letters and digits.
+    fn take_ascii_alphanumeric(&mut self) -> Self {
+        self.take_while_ascii(|b| b.is_ascii_alphanumeric())
+    }
+
+    /// Take an identifier: a letter or underscore followed by letters, digits and underscores.
+    /// If the slice does not start with an identifier, nothing is taken.
+    fn take_ascii_ident(&mut self) -> Self;
+}
+
+impl TakeAscii for &[u8] {
+    fn take_while_ascii(&mut self, pred: impl Fn(u8) -> bool) -> Self {
+        let len = prefix_len(self, pred);
+        let prefix = &self[..len];
+        self.shrink(len..);
+        prefix
+    }
+
+    fn take_ascii_ident(&mut self) -> Self {
+        match self.first() {
+            Some(&b) if is_ident_start(b) => self.take_while_ascii(is_ident_continue),
+            _ => &self[..0],
+        }
+    }
+}
+
+impl TakeAscii for &str {
+    fn take_while_ascii(&mut self, pred: impl Fn(u8) -> bool) -> Self {
+        let len = prefix_len(self.as_bytes(), pred);
+        let prefix = &self[..len];
+        self.shrink(len..);
+        prefix
+    }
+
+    fn take_ascii_ident(&mut self) -> Self {
+        match self.as_bytes().first() {
+            Some(&b) if is_ident_start(b) => self.take_while_ascii(is_ident_continue),
+            _ => &self[..0],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_at_non_ascii() {
+        let mut text = "abcé";
+        assert_eq!(text.take_while_ascii(|_| true), "abc");
+        assert_eq!(text, "é");
+
+        let mut bytes: &[u8] = b"12ab\xff";
+        assert_eq!(bytes.take_ascii_digits(), b"12");
+        assert_eq!(bytes.take_ascii_alphanumeric(), b"ab");
+        assert_eq!(bytes.take_while_ascii(|_| true), b"");
+        assert_eq!(bytes, b"\xff");
+    }
+
+    #[test]
+    fn ident_needs_a_start() {
+        let mut text = "9lives";
+        assert_eq!(text.take_ascii_ident(), "");
+        assert_eq!(text, "9lives");
+
+        let mut bytes: &[u8] = b"_tmp9-";
+        assert_eq!(bytes.take_ascii_ident(), b"_tmp9");
+        assert_eq!(bytes, b"-");
+    }
+}
@@ -0,0 +1,118 @@
+//! Using shrinking slices as cursors for tokio's async readers and writers.
+//!
+//! These mirror the helpers in [`io`](crate::io): a `&mut [u8]` window is the space still to
+//! fill, and a `&[u8]` is the bytes still to send. Each completed read or write shrinks the window
+//! before the next one starts, so if a future is dropped part way, say by a timeout, the window
+//! still records exactly how far it got.
+//!
+//! ```rust
+//! use shrink_slice::async_io::{drain_into, fill_from};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> std::io::Result<()> {
+//! let mut frame = [0; 4];
+//! let mut free: &mut [u8] = &mut frame;
+//! let mut socket: &[u8] = b"\x00\x02hi more";
+//! assert_eq!(fill_from(&mut socket, &mut free).await?, 4);
+//! assert_eq!((&frame, socket), (b"\x00\x02hi", &b" more"[..]));
+//!
+//! let mut sent = Vec::new();
+//! let mut pending: &[u8] = &frame[2..];
+//! drain_into(&mut sent, &mut pending).await?;
+//! assert_eq!((&*sent, pending), (&b"hi"[..], &b""[..]));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Shrink, ShrinkExt};
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ErrorKind};
+
+/// Read from `reader` into the front of `buf` until it is full or the reader is exhausted,
+/// shrinking it past each part that is read, and return the total.
+///
+/// Reads that are [`Interrupted`](ErrorKind::Interrupted) are retried. If any other error
+/// occurs, it is returned, and `buf` has still been shrunk past the bytes read before it.
+pub async fn fill_from<R>(reader: &mut R, buf: &mut &mut [u8]) -> io::Result<usize>
+where R: AsyncRead + Unpin + ?Sized,
+{
+    let mut total = 0;
+    while !buf.is_empty() {
+        match reader.read(buf).await {
+            Ok(0) => break,
+            Ok(n) => {
+                buf.shrink(n..);
+                total += n;
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
+/// Write all of `buf` to `writer`, shrinking it past each part the writer accepts.
+///
+/// Writes that are [`Interrupted`](ErrorKind::Interrupted) are retried. On any other error,
+/// `buf` is left holding exactly the bytes that were not written. A writer that accepts nothing
+/// results in an error of kind [`WriteZero`](ErrorKind::WriteZero).
+pub async fn drain_into<W>(writer: &mut W, buf: &mut &[u8]) -> io::Result<()>
+where W: AsyncWrite + Unpin + ?Sized,
+{
+    while !buf.is_empty() {
+        match writer.write(buf).await {
+            Ok(0) => {
+                let message = "failed to write the whole buffer";
+                return Err(io::Error::new(ErrorKind::WriteZero, message));
+            }
+            Ok(n) => {
+                buf.shrink(n..);
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    /// Accepts two bytes at a time, refusing everything after `limit`.
+    struct Trickle(Vec<u8>, usize);
+
+    impl AsyncWrite for Trickle {
+        fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8])
+            -> Poll<io::Result<usize>>
+        {
+            let n = buf.len().min(2).min(self.1 - self.0.len());
+            self.0.extend_from_slice(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn partial_writes_and_short_reads() {
+        let mut sink = Trickle(Vec::new(), 5);
+        let mut pending: &[u8] = b"abcdefg";
+        let err = drain_into(&mut sink, &mut pending).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WriteZero);
+        assert_eq!((&*sink.0, pending), (&b"abcde"[..], &b"fg"[..]));
+
+        let mut buffer = [0; 8];
+        let mut free: &mut [u8] = &mut buffer;
+        let mut reader: &[u8] = b"xyz";
+        assert_eq!(fill_from(&mut reader, &mut free).await.unwrap(), 3);
+        assert_eq!(free.len(), 5);
+    }
+}
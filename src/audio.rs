@@ -0,0 +1,95 @@
+//! Draining interleaved PCM byte buffers into float samples.
+//!
+//! This module is only available with the `audio` feature.
+
+use crate::{Shrink, ShrinkExt};
+
+/// Extension trait for consuming little-endian PCM samples from a byte slice, converting them to
+/// `f32` in the range `[-1.0, 1.0)`.
+///
+/// Each method converts as many whole samples as fit in `out`, shrinks the source past the bytes
+/// it consumed, and returns the number of samples written. A trailing partial sample is left in
+/// the source, so that it can be completed by the next driver buffer.
+///
+/// ```rust
+/// use shrink_slice::audio::PcmRead;
+///
+/// let mut pcm: &[u8] = &[0x00, 0x40, 0x00, 0xc0, 0x7f];
+/// let mut out = [0.0; 4];
+/// assert_eq!(pcm.take_i16_as_f32(&mut out), 2);
+/// assert_eq!(out[..2], [0.5, -0.5]);
+/// assert_eq!(pcm, [0x7f]);
+/// ```
+pub trait PcmRead {
+    /// Convert unsigned 8-bit samples.
+    fn take_u8_as_f32(&mut self, out: &mut [f32]) -> usize;
+
+    /// Convert signed 16-bit samples.
+    fn take_i16_as_f32(&mut self, out: &mut [f32]) -> usize;
+
+    /// Convert signed 32-bit samples.
+    fn take_i32_as_f32(&mut self, out: &mut [f32]) -> usize;
+
+    /// Copy 32-bit float samples.
+    fn take_f32(&mut self, out: &mut [f32]) -> usize;
+}
+
+fn convert<const N: usize>(
+    src: &mut &[u8],
+    out: &mut [f32],
+    sample: impl Fn([u8; N]) -> f32,
+) -> usize {
+    let count = out.len().min(src.len() / N);
+    for (chunk, out) in src[..count * N].chunks_exact(N).zip(out.iter_mut()) {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(chunk);
+        *out = sample(bytes);
+    }
+    src.shrink(count * N..);
+    count
+}
+
+impl PcmRead for &[u8] {
+    fn take_u8_as_f32(&mut self, out: &mut [f32]) -> usize {
+        convert(self, out, |[b]| (b as f32 - 128.0) / 128.0)
+    }
+
+    fn take_i16_as_f32(&mut self, out: &mut [f32]) -> usize {
+        convert(self, out, |b| i16::from_le_bytes(b) as f32 / 32768.0)
+    }
+
+    fn take_i32_as_f32(&mut self, out: &mut [f32]) -> usize {
+        convert(self, out, |b| i32::from_le_bytes(b) as f32 / 2_147_483_648.0)
+    }
+
+    fn take_f32(&mut self, out: &mut [f32]) -> usize {
+        convert(self, out, f32::from_le_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_runs_out() {
+        let mut pcm: &[u8] = &[0, 128, 255];
+        let mut out = [0.0; 2];
+        assert_eq!(pcm.take_u8_as_f32(&mut out), 2);
+        assert_eq!(out, [-1.0, 0.0]);
+        assert_eq!(pcm, [255]);
+    }
+
+    #[test]
+    fn wide_samples() {
+        let mut pcm: &[u8] = &[0, 0, 0, 0x80, 0, 0, 0x80, 0x3f, 1, 2];
+        let mut out = [0.0; 4];
+        assert_eq!(pcm.take_i32_as_f32(&mut out), 2);
+        assert_eq!(out[..2], [-1.0, (0x3f80_0000 as f32) / 2_147_483_648.0]);
+
+        let mut pcm: &[u8] = &[0, 0, 0x80, 0x3f, 1, 2];
+        assert_eq!(pcm.take_f32(&mut out), 1);
+        assert_eq!(out[0], 1.0);
+        assert_eq!(pcm, [1, 2]);
+    }
+}
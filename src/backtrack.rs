@@ -0,0 +1,164 @@
+//! Backtracking over a mutable slice.
+//!
+//! A shared slice can be saved and restored just by copying it, but a `&mut [T]` that has been
+//! shrunk cannot grow back. [`Backtrack`] keeps hold of the whole buffer and only shrinks a window
+//! into it, so that the window can be saved as a [`Checkpoint`] and restored after a failed
+//! attempt.
+//!
+//! ```rust
+//! use shrink_slice::backtrack::Backtrack;
+//! use shrink_slice::ShrinkExt;
+//!
+//! fn keyword(input: &mut Backtrack<u8>, word: &[u8]) -> bool {
+//!     let checkpoint = input.checkpoint();
+//!     for &expected in word {
+//!         if input.first() != Some(&expected) {
+//!             input.restore(checkpoint).unwrap();
+//!             return false;
+//!         }
+//!         input.shrink(1..);
+//!     }
+//!     true
+//! }
+//!
+//! let mut buffer = *b"letter";
+//! let mut input = Backtrack::new(&mut buffer);
+//! assert!(!keyword(&mut input, b"let "));
+//! assert!(keyword(&mut input, b"lett"));
+//! input.make_ascii_uppercase();
+//! assert_eq!(&buffer, b"lettER");
+//! ```
+
+use crate::{range, Shrink, ShrinkError};
+use core::fmt;
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A shrinkable window into a mutable slice, which can be saved and restored.
+#[derive(Debug)]
+pub struct Backtrack<'a, T> {
+    buf: &'a mut [T],
+    start: usize,
+    end: usize,
+}
+
+/// A saved window of a [`Backtrack`].
+///
+/// It records which buffer it was taken from, so that it cannot be restored into another one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Checkpoint {
+    addr: usize,
+    buf_len: usize,
+    start: usize,
+    end: usize,
+}
+
+impl<'a, T> Backtrack<'a, T> {
+    /// A window covering all of `buf`.
+    pub fn new(buf: &'a mut [T]) -> Self {
+        let end = buf.len();
+        Backtrack { buf, start: 0, end }
+    }
+
+    /// Where the current window starts within the buffer.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// Save the current window.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            addr: self.buf.as_ptr() as usize,
+            buf_len: self.buf.len(),
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    /// Go back to a saved window, which may be larger than the current one.
+    ///
+    /// Fails, leaving the window untouched, if `checkpoint` was taken from a different buffer.
+    /// Buffers of zero-sized elements cannot be told apart by address, so only their lengths are
+    /// compared.
+    pub fn restore(&mut self, checkpoint: Checkpoint) -> Result<(), ForeignCheckpoint> {
+        let own = self.checkpoint();
+        if (checkpoint.addr, checkpoint.buf_len) != (own.addr, own.buf_len) {
+            return Err(ForeignCheckpoint);
+        }
+        self.start = checkpoint.start;
+        self.end = checkpoint.end;
+        Ok(())
+    }
+
+    /// Give up the ability to backtrack, returning the current window with the buffer's lifetime.
+    pub fn into_view(self) -> &'a mut [T] {
+        &mut self.buf[self.start..self.end]
+    }
+}
+
+impl<T> Deref for Backtrack<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.buf[self.start..self.end]
+    }
+}
+
+impl<T> DerefMut for Backtrack<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.buf[self.start..self.end]
+    }
+}
+
+impl<T> Shrink for Backtrack<'_, T> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.end = self.start + range.end;
+        self.start += range.start;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// The error returned when restoring a [`Checkpoint`] that was taken from a different buffer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ForeignCheckpoint;
+
+impl fmt::Display for ForeignCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("checkpoint was taken from a different buffer")
+    }
+}
+
+impl core::error::Error for ForeignCheckpoint { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn restore_checks_origin() {
+        let mut first = [1, 2, 3, 4];
+        let mut second = [1, 2, 3, 4];
+        let mut a = Backtrack::new(&mut first);
+        let mut b = Backtrack::new(&mut second);
+        a.shrink(1..3);
+        let saved = a.checkpoint();
+        a.shrink(2..);
+        assert!(a.is_empty());
+        assert_eq!(b.restore(saved), Err(ForeignCheckpoint));
+        assert_eq!(b.len(), 4);
+        a.restore(saved).unwrap();
+        assert_eq!((&*a, a.offset()), (&[2, 3][..], 1));
+        a.into_view()[0] = 20;
+        assert_eq!(first, [1, 20, 3, 4]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Consuming bracket-balanced regions of text.
+
+use crate::ShrinkExt;
+use core::fmt;
+
+/// Extension trait for consuming a delimited region, such as `(a (b) c)`, from the front of a
+/// string slice.
+pub trait TakeBalanced<'a> {
+    /// Consume everything from the `open` delimiter at the front of the string up to and
+    /// including its matching `close`, counting nested pairs along the way. Returns the text
+    /// between the two delimiters.
+    ///
+    /// On error, the string is left untouched. Panics if `open` and `close` are the same.
+    ///
+    /// ```rust
+    /// use shrink_slice::balanced::TakeBalanced;
+    ///
+    /// let mut template = "{outer {inner}} rest";
+    /// assert_eq!(template.take_balanced('{', '}'), Ok("outer {inner}"));
+    /// assert_eq!(template, " rest");
+    /// ```
+    fn take_balanced(&mut self, open: char, close: char) -> Result<&'a str, BalanceError> {
+        self.take_balanced_quoted(open, close, &[])
+    }
+
+    /// Like [`take_balanced`](Self::take_balanced), but delimiters inside string literals are
+    /// ignored. A string literal starts with any of the characters in `quotes` and ends with the
+    /// same character. Within it, a backslash escapes the character after it.
+    ///
+    /// ```rust
+    /// use shrink_slice::balanced::TakeBalanced;
+    ///
+    /// let mut call = r#"(print ")" "\"(") done"#;
+    /// assert_eq!(call.take_balanced_quoted('(', ')', &['"']), Ok(r#"print ")" "\"(""#));
+    /// assert_eq!(call, " done");
+    /// ```
+    fn take_balanced_quoted(&mut self, open: char, close: char, quotes: &[char])
+        -> Result<&'a str, BalanceError>;
+}
+
+impl<'a> TakeBalanced<'a> for &'a str {
+    fn take_balanced_quoted(&mut self, open: char, close: char, quotes: &[char])
+        -> Result<&'a str, BalanceError>
+    {
+        assert!(open != close, "opening and closing delimiters must differ");
+        let s: &'a str = self;
+        let mut chars = s.char_indices();
+        match chars.next() {
+            Some((_, c)) if c == open => {}
+            _ => return Err(BalanceError::NotOpen),
+        }
+
+        let mut depth = 1usize;
+        while let Some((i, c)) = chars.next() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    let inner = &s[open.len_utf8()..i];
+                    self.shrink(i + close.len_utf8()..);
+                    return Ok(inner);
+                }
+            } else if quotes.contains(&c) {
+                loop {
+                    match chars.next() {
+                        Some((_, q)) if q == c => break,
+                        Some((_, '\\')) => {
+                            chars.next();
+                        }
+                        Some(_) => {}
+                        None => return Err(BalanceError::UnterminatedString),
+                    }
+                }
+            }
+        }
+        Err(BalanceError::Unclosed { depth })
+    }
+}
+
+/// The error returned when a balanced region cannot be consumed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BalanceError {
+    /// The input did not start with the opening delimiter.
+    NotOpen,
+    /// The input ended before the region was closed, with `depth` pairs still open.
+    Unclosed {
+        /// The number of delimiters still waiting to be closed.
+        depth: usize,
+    },
+    /// The input ended inside a string literal.
+    UnterminatedString,
+}
+
+impl fmt::Display for BalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BalanceError::NotOpen => f.write_str("expected an opening delimiter"),
+            BalanceError::Unclosed { depth } => write!(f, "{} unclosed delimiters", depth),
+            BalanceError::UnterminatedString => f.write_str("unterminated string literal"),
+        }
+    }
+}
+
+impl core::error::Error for BalanceError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_keep_input() {
+        let mut text = "[a [b]";
+        assert_eq!(text.take_balanced('[', ']'), Err(BalanceError::Unclosed { depth: 1 }));
+        assert_eq!(text.take_balanced('(', ')'), Err(BalanceError::NotOpen));
+        let mut quoted = "['a]";
+        let err = quoted.take_balanced_quoted('[', ']', &['\'']);
+        assert_eq!(err, Err(BalanceError::UnterminatedString));
+        assert_eq!((text, quoted), ("[a [b]", "['a]"));
+    }
+
+    #[test]
+    fn multibyte_delimiters() {
+        let mut text = "«a«b»»c";
+        assert_eq!(text.take_balanced('«', '»'), Ok("a«b»"));
+        assert_eq!(text, "c");
+        let mut empty = "«»";
+        assert_eq!(empty.take_balanced('«', '»'), Ok(""));
+        assert_eq!(empty, "");
+    }
+}
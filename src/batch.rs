@@ -0,0 +1,123 @@
+//! Applying several shrinks as a single all-or-nothing operation.
+
+use crate::{range, Shrink, ShrinkError};
+use core::ops::{Range, RangeBounds};
+
+/// Compose a sequence of relative ranges into one range over a slice of length `len`, checking
+/// every intermediate cut with `is_boundary`.
+///
+/// Errors describe the first range that failed, relative to the view it was applied to.
+fn compose_all<I>(len: usize, ops: I, is_boundary: impl Fn(usize) -> bool)
+    -> Result<Range<usize>, ShrinkError>
+where I: IntoIterator,
+      I::Item: RangeBounds<usize>,
+{
+    let mut window = 0..len;
+    for op in ops {
+        let (bounds, len) = (range::bounds(&op), window.len());
+        let relative = range::resolve(op, len)
+            .ok_or_else(|| ShrinkError::out_of_bounds(bounds, len))?;
+        let (start, end) = (relative.start, relative.end);
+        window = range::rebase(relative, window.start).expect("window is within the slice");
+        for (cut, index) in [(window.start, start), (window.end, end)] {
+            if !is_boundary(cut) {
+                return Err(ShrinkError::NotCharBoundary { start, end, len, index });
+            }
+        }
+    }
+    Ok(window)
+}
+
+/// Extension trait for shrinking a slice by a sequence of ranges, transactionally.
+pub trait ShrinkBatch {
+    /// Apply each range in turn, as if by successive calls to [`Shrink::try_shrink`], each one
+    /// relative to the view left by the one before.
+    ///
+    /// All ranges are validated before anything is changed: if any of them would fail, the slice
+    /// is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::batch::ShrinkBatch;
+    ///
+    /// let mut packet: &[u8] = b"\x02\x00\x05hello!";
+    /// assert!(packet.try_shrink_batch([3..9, 0..9]).is_err());
+    /// assert_eq!(packet.len(), 9);
+    /// packet.try_shrink_batch([3..9, 0..5]).unwrap();
+    /// assert_eq!(packet, b"hello");
+    /// ```
+    fn try_shrink_batch<I>(&mut self, ops: I) -> Result<(), ShrinkError>
+    where I: IntoIterator,
+          I::Item: RangeBounds<usize>;
+}
+
+impl<T> ShrinkBatch for &[T] {
+    fn try_shrink_batch<I>(&mut self, ops: I) -> Result<(), ShrinkError>
+    where I: IntoIterator,
+          I::Item: RangeBounds<usize>,
+    {
+        let window = compose_all(self.len(), ops, |_| true)?;
+        self.try_shrink(window)
+    }
+}
+
+impl<T> ShrinkBatch for &mut [T] {
+    fn try_shrink_batch<I>(&mut self, ops: I) -> Result<(), ShrinkError>
+    where I: IntoIterator,
+          I::Item: RangeBounds<usize>,
+    {
+        let window = compose_all(self.len(), ops, |_| true)?;
+        self.try_shrink(window)
+    }
+}
+
+impl ShrinkBatch for &str {
+    fn try_shrink_batch<I>(&mut self, ops: I) -> Result<(), ShrinkError>
+    where I: IntoIterator,
+          I::Item: RangeBounds<usize>,
+    {
+        let s: &str = self;
+        let window = compose_all(s.len(), ops, |i| s.is_char_boundary(i))?;
+        self.try_shrink(window)
+    }
+}
+
+impl ShrinkBatch for &mut str {
+    fn try_shrink_batch<I>(&mut self, ops: I) -> Result<(), ShrinkError>
+    where I: IntoIterator,
+          I::Item: RangeBounds<usize>,
+    {
+        let s: &str = self;
+        let window = compose_all(s.len(), ops, |i| s.is_char_boundary(i))?;
+        self.try_shrink(window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_range_types() {
+        use core::ops::Bound::*;
+
+        let mut buffer = *b"0123456789";
+        let mut slice: &mut [u8] = &mut buffer;
+        let ops = [(Included(1), Unbounded), (Excluded(0), Included(5)), (Unbounded, Excluded(3))];
+        slice.try_shrink_batch(ops).unwrap();
+        assert_eq!(slice, b"234");
+        slice.try_shrink_batch(core::iter::empty::<Range<usize>>()).unwrap();
+        assert_eq!(slice, b"234");
+    }
+
+    #[test]
+    fn intermediate_char_boundaries() {
+        // Cutting inside 'é' is invalid, even if a later range would drop the partial character
+        // again.
+        let mut text = "aéb";
+        let err = ShrinkError::NotCharBoundary { start: 0, end: 2, len: 4, index: 2 };
+        assert_eq!(text.try_shrink_batch([0..2, 0..1]), Err(err));
+        assert_eq!(text, "aéb");
+        text.try_shrink_batch([1..4, 0..2]).unwrap();
+        assert_eq!(text, "é");
+    }
+}
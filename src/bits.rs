@@ -0,0 +1,66 @@
+//! `Shrink` for the bit slices of the `bitvec` crate.
+
+use crate::{SliceLike, SliceLikeMut};
+use bitvec::order::BitOrder;
+use bitvec::slice::BitSlice;
+use bitvec::store::BitStore;
+use core::ops::Range;
+
+/// Bit slices cannot be indexed through the standard library's `SliceIndex`, which is sealed, so
+/// their [`SliceLike::Index`] is `[bool]`: ranges are checked as if they indexed a slice of `bool`
+/// with one element per bit. Indices count bits, in the slice's own bit order, and a shrunk bit
+/// slice may start in the middle of a storage element.
+///
+/// ```rust
+/// use bitvec::prelude::*;
+/// use shrink_slice::ShrinkExt;
+///
+/// let byte = 0b1011_0010u8;
+/// let mut bits = byte.view_bits::<Msb0>();
+/// bits.shrink(1..);
+/// let field = bits[..3].load_be::<u8>();
+/// bits.shrink(3..);
+/// assert_eq!((field, bits.len()), (0b011, 4));
+/// ```
+impl<T: BitStore, O: BitOrder> SliceLike for BitSlice<T, O> {
+    type Index = [bool];
+
+    fn len(&self) -> usize {
+        BitSlice::len(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+}
+
+impl<T: BitStore, O: BitOrder> SliceLikeMut for BitSlice<T, O> {
+    fn empty_mut<'a>() -> &'a mut Self {
+        BitSlice::empty_mut()
+    }
+
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut Self {
+        &mut self[range]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Shrink, ShrinkExt};
+    use bitvec::prelude::*;
+
+    #[test]
+    fn bit_indices() {
+        let mut data = [0u8; 2];
+        let mut bits = data.view_bits_mut::<Lsb0>();
+        assert_eq!(bits.len(), 16);
+        bits.shrink(3..13);
+        assert!(bits.try_shrink(..11).is_err());
+        bits.shrink_back(2);
+        bits.set(0, true);
+        bits.shrink_front(7);
+        bits.set(0, true);
+        assert_eq!(bits.len(), 1);
+        assert_eq!(data, [0b0000_1000, 0b0000_0100]);
+    }
+}
@@ -0,0 +1,88 @@
+//! Detecting and consuming byte order marks.
+
+use crate::ShrinkExt;
+
+/// A text encoding identified by its byte order mark.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    /// UTF-8, marked by `EF BB BF`.
+    Utf8,
+    /// Little-endian UTF-16, marked by `FF FE`.
+    Utf16Le,
+    /// Big-endian UTF-16, marked by `FE FF`.
+    Utf16Be,
+}
+
+impl Encoding {
+    /// The byte order mark that identifies this encoding.
+    pub fn bom(self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8 => b"\xEF\xBB\xBF",
+            Encoding::Utf16Le => b"\xFF\xFE",
+            Encoding::Utf16Be => b"\xFE\xFF",
+        }
+    }
+
+    fn sniff(bytes: &[u8]) -> Option<Self> {
+        [Encoding::Utf8, Encoding::Utf16Le, Encoding::Utf16Be]
+            .iter()
+            .copied()
+            .find(|encoding| bytes.starts_with(encoding.bom()))
+    }
+}
+
+/// Extension trait for consuming a byte order mark from the front of a byte slice.
+pub trait StripBom {
+    /// If the slice starts with a UTF-8, UTF-16LE or UTF-16BE byte order mark, shrink past it
+    /// and return the encoding it stands for. Otherwise, leave the slice untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::bom::{Encoding, StripBom};
+    ///
+    /// let mut file: &[u8] = b"\xEF\xBB\xBFhello";
+    /// assert_eq!(file.strip_bom(), Some(Encoding::Utf8));
+    /// assert_eq!(file.strip_bom(), None);
+    /// assert_eq!(file, b"hello");
+    /// ```
+    fn strip_bom(&mut self) -> Option<Encoding>;
+}
+
+impl StripBom for &[u8] {
+    fn strip_bom(&mut self) -> Option<Encoding> {
+        let encoding = Encoding::sniff(self)?;
+        self.shrink(encoding.bom().len()..);
+        Some(encoding)
+    }
+}
+
+impl StripBom for &mut [u8] {
+    fn strip_bom(&mut self) -> Option<Encoding> {
+        let encoding = Encoding::sniff(self)?;
+        self.shrink(encoding.bom().len()..);
+        Some(encoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf16() {
+        let mut le: &[u8] = b"\xFF\xFEa\0";
+        assert_eq!(le.strip_bom(), Some(Encoding::Utf16Le));
+        assert_eq!(le, b"a\0");
+
+        let mut buffer = *b"\xFE\xFF\0a";
+        let mut be: &mut [u8] = &mut buffer;
+        assert_eq!(be.strip_bom(), Some(Encoding::Utf16Be));
+        assert_eq!(be, b"\0a");
+    }
+
+    #[test]
+    fn partial_bom() {
+        let mut slice: &[u8] = b"\xEF\xBB";
+        assert_eq!(slice.strip_bom(), None);
+        assert_eq!(slice, b"\xEF\xBB");
+    }
+}
@@ -0,0 +1,101 @@
+//! Interop with the standard library's [`BorrowedBuf`] and [`BorrowedCursor`].
+//!
+//! These types are still unstable, so this module is only available with the `borrowed-buf`
+//! feature, which requires a nightly compiler. The feature also enables `unsafe-fast`, since
+//! handing out the filled part of a buffer needs `unsafe`.
+
+use crate::{fast, ShrinkExt};
+use std::io::{self, BorrowedCursor, Read};
+use std::mem::MaybeUninit;
+
+/// Extension trait for filling the front of an uninitialized buffer through a [`BorrowedCursor`].
+pub trait FillUninit<'a> {
+    /// Hand the buffer to `fill` as a [`BorrowedCursor`], then split off whatever it filled,
+    /// shrinking the buffer past it.
+    ///
+    /// If `fill` returns an error, the error is passed on and the buffer is left as it was.
+    /// Any bytes written are then treated as uninitialized again.
+    fn fill_uninit<F>(&mut self, fill: F) -> io::Result<&'a mut [u8]>
+    where F: FnOnce(BorrowedCursor<'_>) -> io::Result<()>;
+
+    /// Fill the front of the buffer with a single call to [`Read::read_buf`] and split off the
+    /// bytes that were read.
+    ///
+    /// ```rust
+    /// use std::mem::MaybeUninit;
+    /// use shrink_slice::borrowed::FillUninit;
+    ///
+    /// let mut storage = [MaybeUninit::uninit(); 16];
+    /// let mut free: &mut [MaybeUninit<u8>] = &mut storage;
+    /// let mut reader: &[u8] = b"hello";
+    /// let got = free.read_buf_from(&mut reader).unwrap();
+    /// assert_eq!(got, b"hello");
+    /// assert_eq!(free.len(), 11);
+    /// ```
+    fn read_buf_from<R: Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<&'a mut [u8]> {
+        self.fill_uninit(|cursor| reader.read_buf(cursor))
+    }
+}
+
+impl<'a> FillUninit<'a> for &'a mut [MaybeUninit<u8>] {
+    fn fill_uninit<F>(&mut self, fill: F) -> io::Result<&'a mut [u8]>
+    where F: FnOnce(BorrowedCursor<'_>) -> io::Result<()>,
+    {
+        fast::fill_front(self, fill)
+    }
+}
+
+/// Append as much of `src` to `cursor` as fits, shrinking `src` past it. Returns the number of
+/// bytes copied.
+///
+/// ```rust
+/// # #![feature(core_io_borrowed_buf)]
+/// use std::io::BorrowedBuf;
+/// use std::mem::MaybeUninit;
+/// use shrink_slice::borrowed::append_to_cursor;
+///
+/// let mut storage = [MaybeUninit::uninit(); 4];
+/// let mut buf = BorrowedBuf::from(&mut storage[..]);
+/// let mut src: &[u8] = b"abcdef";
+/// assert_eq!(append_to_cursor(&mut buf.unfilled(), &mut src), 4);
+/// assert_eq!(buf.filled(), b"abcd");
+/// assert_eq!(src, b"ef");
+/// ```
+pub fn append_to_cursor(cursor: &mut BorrowedCursor<'_>, src: &mut &[u8]) -> usize {
+    let n = cursor.capacity().min(src.len());
+    cursor.append(&src[..n]);
+    src.shrink(n..);
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_restores_buffer() {
+        let mut storage = [MaybeUninit::uninit(); 8];
+        let mut free: &mut [MaybeUninit<u8>] = &mut storage;
+        let err = free.fill_uninit(|mut cursor| {
+            cursor.append(b"xy");
+            Err(io::ErrorKind::Interrupted.into())
+        });
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert_eq!(free.len(), 8);
+
+        let first = free.fill_uninit(|mut cursor| {
+            cursor.append(b"abc");
+            Ok(())
+        }).unwrap();
+        assert_eq!(first, b"abc");
+        assert_eq!(free.len(), 5);
+    }
+
+    #[test]
+    fn exhausted() {
+        let mut free: &mut [MaybeUninit<u8>] = &mut [];
+        let mut reader: &[u8] = b"data";
+        assert_eq!(free.read_buf_from(&mut reader).unwrap(), b"");
+        assert_eq!(reader, b"data");
+    }
+}
@@ -0,0 +1,93 @@
+//! Shorthands for the bounds of generic code over byte slices and string slices.
+
+use crate::Shrink;
+use core::ops::Deref;
+
+/// A [`Shrink`] of byte slices that can also be read as one: shorthand for
+/// `Shrink<Slice = [u8]> + Deref<Target = [u8]>`.
+///
+/// It is implemented for every type with those bounds, like `&[u8]`, `&mut [u8]`, and the
+/// `Bytes` buffers of the `bytes` crate, so it never needs to be implemented by hand. Generic
+/// parsing code can take it instead of spelling out both bounds, and look at the bytes through
+/// `Deref` before deciding how far to shrink:
+///
+/// ```rust
+/// use shrink_slice::ShrinkBytes;
+/// use shrink_slice::ShrinkExt;
+///
+/// /// Skip a `#` comment, up to but not including the line ending.
+/// fn skip_comment<S: ShrinkBytes>(input: &mut S) {
+///     if input.first() == Some(&b'#') {
+///         let end = input.iter().position(|&b| b == b'\n').unwrap_or(input.len());
+///         input.shrink_front(end);
+///     }
+/// }
+///
+/// let mut line: &[u8] = b"# note\nkey";
+/// skip_comment(&mut line);
+/// assert_eq!(line, b"\nkey");
+///
+/// let mut buffer = *b"#\n";
+/// let mut line: &mut [u8] = &mut buffer;
+/// skip_comment(&mut line);
+/// assert_eq!(line, b"\n");
+/// ```
+pub trait ShrinkBytes: Shrink<Slice = [u8]> + Deref<Target = [u8]> { }
+
+impl<S: Shrink<Slice = [u8]> + Deref<Target = [u8]> + ?Sized> ShrinkBytes for S { }
+
+/// A [`Shrink`] of string slices that can also be read as one: shorthand for
+/// `Shrink<Slice = str> + Deref<Target = str>`.
+///
+/// Like [`ShrinkBytes`], it is implemented for every type with those bounds, like `&str` and
+/// `&mut str`. Ranges passed to its methods are byte offsets, and shrinking fails if they land
+/// within a multi-byte character, so offsets are best taken from the string's own methods:
+///
+/// ```rust
+/// use shrink_slice::ShrinkStr;
+/// use shrink_slice::ShrinkExt;
+///
+/// /// Drop the first word and the spaces after it.
+/// fn skip_word<S: ShrinkStr>(input: &mut S) {
+///     let word = input.find(' ').unwrap_or(input.len());
+///     let spaces = input[word..].len() - input[word..].trim_start_matches(' ').len();
+///     input.shrink_front(word + spaces);
+/// }
+///
+/// let mut text = "na\u{ef}ve  caf\u{e9}";
+/// skip_word(&mut text);
+/// assert_eq!(text, "caf\u{e9}");
+/// ```
+pub trait ShrinkStr: Shrink<Slice = str> + Deref<Target = str> { }
+
+impl<S: Shrink<Slice = str> + Deref<Target = str> + ?Sized> ShrinkStr for S { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    fn first_field<S: ShrinkBytes>(mut record: S) -> S {
+        let end = record.iter().position(|&b| b == b',').unwrap_or(record.len());
+        record.shrink(..end);
+        record
+    }
+
+    fn drop_first_byte<S: ShrinkStr>(text: &mut S) -> bool {
+        text.try_shrink(1..).is_ok()
+    }
+
+    #[test]
+    fn generic_over_aliases() {
+        let mut buffer = *b"ab,c";
+        let field = first_field(&mut buffer[..]);
+        field[1] = b'B';
+        assert_eq!(&buffer, b"aB,c");
+
+        let mut text = "\u{e9}";
+        assert!(!drop_first_byte(&mut text));
+        let mut text = "hey";
+        assert!(drop_first_byte(&mut text));
+        assert_eq!(text, "ey");
+    }
+}
@@ -0,0 +1,59 @@
+//! `Shrink` for the byte strings of the `bstr` crate.
+
+use crate::{SliceLike, SliceLikeMut};
+use bstr::BStr;
+use core::ops::Range;
+
+/// Byte strings cannot be indexed through the standard library's `SliceIndex`, which is sealed,
+/// so their [`SliceLike::Index`] is `[u8]`. As with any byte slice, ranges need not respect UTF-8
+/// boundaries.
+///
+/// ```rust
+/// use bstr::{BStr, ByteSlice};
+/// use shrink_slice::ShrinkExt;
+///
+/// let mut line: &BStr = b"name = \xffvalue".as_bstr();
+/// line.shrink(7..);
+/// assert_eq!(line, b"\xffvalue".as_bstr());
+/// assert!(line.to_str().is_err());
+/// ```
+impl SliceLike for BStr {
+    type Index = [u8];
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+}
+
+impl SliceLikeMut for BStr {
+    fn empty_mut<'a>() -> &'a mut Self {
+        Default::default()
+    }
+
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut Self {
+        &mut self[range]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Shrink, ShrinkExt};
+    use bstr::ByteSlice;
+
+    #[test]
+    fn mut_keeps_type() {
+        let mut buffer = *b"[\xe2\x82\xac]";
+        let mut text: &mut BStr = buffer.as_bstr_mut();
+        text.shrink(1..4);
+        assert!(text.try_shrink(4..).is_err());
+        assert_eq!(text.to_str(), Ok("\u{20ac}"));
+        text.shrink(1..);
+        text[0] = b'!';
+        assert_eq!(&buffer, b"[\xe2!\xac]");
+    }
+}
@@ -0,0 +1,172 @@
+//! Capping how much of a slice a parser may consume.
+//!
+//! Formats that carry their own length fields let an adversary claim enormous sizes. Wrapping
+//! the input in a [`Budgeted`] bounds the total work a parser does on it, no matter what the
+//! input says, and reports a distinct error once the budget runs out.
+//!
+//! ```rust
+//! use shrink_slice::budget::{Budgeted, BudgetError};
+//!
+//! let mut input = Budgeted::new(&b"\x03abc\xffxyz"[..], 6);
+//! let len = input.take_exact(1).unwrap()[0] as usize;
+//! assert_eq!(input.take_exact(len).unwrap(), b"abc");
+//! let len = input.take_exact(1).unwrap()[0] as usize;
+//! let err = input.take_exact(len).unwrap_err();
+//! assert_eq!(err, BudgetError::QuotaExceeded { requested: 255, left: 1 });
+//! assert_eq!(input.consumed(), 5);
+//! ```
+
+use crate::{range, NeedMore, Shrink, ShrinkError, ShrinkExt, Take};
+use core::fmt;
+use core::ops::{Deref, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A slice that may only have a limited number of elements consumed from it in total.
+///
+/// Every element removed counts against the budget, whether it was taken from the front or
+/// discarded from either end by shrinking.
+#[derive(Clone, Debug)]
+pub struct Budgeted<S> {
+    inner: S,
+    left: usize,
+    consumed: usize,
+}
+
+impl<S, T> Budgeted<S>
+where S: Take + Shrink<Slice = [T]> + Deref<Target = [T]>,
+{
+    /// Wrap `inner`, allowing at most `budget` of its elements to be consumed.
+    pub fn new(inner: S, budget: usize) -> Self {
+        Budgeted { inner, left: budget, consumed: 0 }
+    }
+
+    /// The slice that is left.
+    pub fn get(&self) -> &S {
+        &self.inner
+    }
+
+    /// Unwrap the slice that is left.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// The number of elements that may still be consumed.
+    pub fn budget(&self) -> usize {
+        self.left
+    }
+
+    /// The number of elements consumed so far.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    fn charge(&mut self, requested: usize) -> Result<(), BudgetError> {
+        if requested > self.left {
+            return Err(BudgetError::QuotaExceeded { requested, left: self.left });
+        }
+        self.left -= requested;
+        self.consumed += requested;
+        Ok(())
+    }
+
+    /// Shrink the slice, charging the budget for every element that falls outside `range`.
+    ///
+    /// On error, both the slice and the budget are left untouched.
+    pub fn try_shrink<R>(&mut self, range: R) -> Result<(), BudgetError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>,
+    {
+        let bounds = range::bounds(&range);
+        let view = self.inner.get(range)
+            .ok_or_else(|| ShrinkError::out_of_bounds(bounds, self.inner.len()))?;
+        let start = range::offset_in(&self.inner, view).expect("subslice is within the slice");
+        let end = start + view.len();
+        self.charge(self.inner.len() - view.len())?;
+        self.inner.shrink(start..end);
+        Ok(())
+    }
+
+    /// Split off exactly `n` elements from the front of the slice and return them, charging the
+    /// budget for each.
+    ///
+    /// On error, both the slice and the budget are left untouched. If the slice is too short
+    /// and the budget too small, the budget is what gets reported.
+    pub fn take_exact(&mut self, n: usize) -> Result<S, BudgetError> {
+        if n > self.left {
+            return Err(BudgetError::QuotaExceeded { requested: n, left: self.left });
+        }
+        let head = self.inner.take_exact(n)?;
+        self.charge(n)?;
+        Ok(head)
+    }
+}
+
+/// The error returned when a [`Budgeted`] slice cannot be consumed as requested.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BudgetError {
+    /// Consuming would exceed the budget.
+    QuotaExceeded {
+        /// The number of elements that would have been consumed.
+        requested: usize,
+        /// The number of elements the budget still allowed.
+        left: usize,
+    },
+    /// The range was out of bounds.
+    OutOfRange(ShrinkError),
+    /// The slice was too short, by the given number of elements.
+    NeedMore(usize),
+}
+
+impl From<ShrinkError> for BudgetError {
+    fn from(e: ShrinkError) -> Self {
+        BudgetError::OutOfRange(e)
+    }
+}
+
+impl From<NeedMore> for BudgetError {
+    fn from(NeedMore(n): NeedMore) -> Self {
+        BudgetError::NeedMore(n)
+    }
+}
+
+impl fmt::Display for BudgetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BudgetError::QuotaExceeded { requested, left } => {
+                write!(f, "quota exceeded: {} elements requested, {} left", requested, left)
+            }
+            BudgetError::OutOfRange(e) => e.fmt(f),
+            BudgetError::NeedMore(n) => NeedMore(*n).fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for BudgetError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinking_counts_both_ends() {
+        let mut buffer = [0u8; 10];
+        let mut slice = Budgeted::new(&mut buffer[..], 5);
+        slice.try_shrink(1..9).unwrap();
+        assert_eq!(slice.budget(), 3);
+        assert_eq!(
+            slice.try_shrink(2..4),
+            Err(BudgetError::QuotaExceeded { requested: 6, left: 3 }),
+        );
+        assert!(matches!(slice.try_shrink(..20), Err(BudgetError::OutOfRange(_))));
+        assert_eq!((slice.get().len(), slice.consumed()), (8, 2));
+        slice.into_inner()[7] = 2;
+        assert_eq!(buffer, [0, 0, 0, 0, 0, 0, 0, 0, 2, 0]);
+    }
+
+    #[test]
+    fn short_input_keeps_budget() {
+        let mut input = Budgeted::new(&b"ab"[..], 10);
+        assert_eq!(input.take_exact(3), Err(BudgetError::NeedMore(1)));
+        assert_eq!(input.budget(), 10);
+        assert_eq!(input.take_exact(2), Ok(&b"ab"[..]));
+    }
+}
@@ -0,0 +1,143 @@
+//! A bump allocator that hands out pieces of a borrowed buffer.
+//!
+//! [`Bump`] owns a `&mut [MaybeUninit<u8>]` and shrinks it from the front with every allocation.
+//! Allocations live as long as the buffer, are never freed individually, and start out
+//! uninitialized.
+//!
+//! ```rust
+//! use core::mem::MaybeUninit;
+//! use shrink_slice::bump::Bump;
+//!
+//! let mut scratch = [MaybeUninit::uninit(); 64];
+//! let mut bump = Bump::new(&mut scratch);
+//!
+//! let header = bump.alloc_bytes(6, 1).unwrap();
+//! let body = bump.alloc_bytes(32, 16).unwrap();
+//! assert_eq!(body.as_ptr() as usize % 16, 0);
+//! assert!(bump.remaining() <= 64 - 6 - 32);
+//! # let _ = header;
+//! ```
+//!
+//! Allocating typed values needs `unsafe` internally, so [`Bump::alloc_slice`] and
+//! [`Bump::alloc`] are only available with the `unsafe-fast` feature.
+
+#[cfg(feature = "unsafe-fast")]
+use crate::fast;
+use crate::Take;
+#[cfg(feature = "unsafe-fast")]
+use core::mem;
+use core::mem::MaybeUninit;
+
+/// A bump allocator over a borrowed byte buffer.
+#[derive(Debug)]
+pub struct Bump<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+}
+
+impl<'a> Bump<'a> {
+    /// Start allocating from the front of `buf`.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Bump { buf }
+    }
+
+    /// The number of bytes left, not accounting for any padding a future allocation may need.
+    pub fn remaining(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Allocate `len` bytes, starting at an address that is a multiple of `align`.
+    ///
+    /// Returns `None`, without consuming anything, if there is not enough room. Panics if `align`
+    /// is not a power of two.
+    pub fn alloc_bytes(&mut self, len: usize, align: usize) -> Option<&'a mut [MaybeUninit<u8>]> {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        let padding = self.buf.as_ptr().align_offset(align);
+        let total = padding.checked_add(len)?;
+        if total > self.buf.len() {
+            return None;
+        }
+        let mut block = self.buf.take_exact(total).ok()?;
+        block.take_exact(padding).ok()?;
+        Some(block)
+    }
+
+    /// Allocate room for `count` values of type `T`.
+    ///
+    /// Returns `None`, without consuming anything, if there is not enough room.
+    ///
+    /// ```rust
+    /// use core::mem::MaybeUninit;
+    /// use shrink_slice::bump::Bump;
+    ///
+    /// let mut scratch = [MaybeUninit::uninit(); 64];
+    /// let mut bump = Bump::new(&mut scratch);
+    /// let header = bump.alloc(0xfeed_u32).unwrap();
+    /// let samples = bump.alloc_slice::<u16>(8).unwrap();
+    /// for (i, sample) in samples.iter_mut().enumerate() {
+    ///     sample.write(i as u16);
+    /// }
+    /// *header += 1;
+    /// assert!(bump.remaining() <= 64 - 4 - 16);
+    /// ```
+    #[cfg(feature = "unsafe-fast")]
+    pub fn alloc_slice<T>(&mut self, count: usize) -> Option<&'a mut [MaybeUninit<T>]> {
+        let len = mem::size_of::<T>().checked_mul(count)?;
+        let bytes = self.alloc_bytes(len, mem::align_of::<T>())?;
+        Some(fast::cast_uninit(bytes, count))
+    }
+
+    /// Move `value` into a new allocation.
+    ///
+    /// Returns `None`, dropping `value`, if there is not enough room. Values stored this way are
+    /// never dropped.
+    #[cfg(feature = "unsafe-fast")]
+    pub fn alloc<T>(&mut self, value: T) -> Option<&'a mut T> {
+        let slot = self.alloc_slice::<T>(1)?;
+        Some(slot[0].write(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untyped_exhaustion() {
+        let mut scratch = [MaybeUninit::uninit(); 16];
+        let mut bump = Bump::new(&mut scratch);
+        bump.alloc_bytes(3, 1).unwrap();
+        let aligned = bump.alloc_bytes(4, 4).unwrap();
+        assert_eq!(aligned.as_ptr() as usize % 4, 0);
+        let before = bump.remaining();
+        assert!(bump.alloc_bytes(before + 1, 1).is_none());
+        assert_eq!(bump.remaining(), before);
+        assert_eq!(bump.alloc_bytes(before, 1).unwrap().len(), before);
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe-fast")]
+    fn alignment_and_exhaustion() {
+        let mut scratch = [MaybeUninit::uninit(); 32];
+        let mut bump = Bump::new(&mut scratch);
+
+        bump.alloc_bytes(1, 1).unwrap();
+        let word = bump.alloc(7u64).unwrap();
+        assert_eq!(word as *mut u64 as usize % mem::align_of::<u64>(), 0);
+
+        let before = bump.remaining();
+        assert!(bump.alloc_slice::<u8>(before + 1).is_none());
+        assert_eq!(bump.remaining(), before);
+        assert_eq!(bump.alloc_slice::<u8>(before).unwrap().len(), before);
+        assert_eq!(bump.remaining(), 0);
+        assert_eq!(*word, 7);
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe-fast")]
+    fn zero_sized() {
+        let mut bump = Bump::new(&mut []);
+        assert_eq!(bump.alloc_slice::<()>(usize::MAX).unwrap().len(), usize::MAX);
+        assert!(bump.alloc(()).is_some());
+        assert!(bump.alloc(1u8).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! `Shrink` and `Take` for the buffers of the `bytes` crate.
+
+use crate::{range, NeedMore, Shrink, ShrinkError, Take};
+use bytes::{Buf, Bytes, BytesMut};
+use core::ops::RangeBounds;
+use core::slice::SliceIndex;
+
+/// Shrinking only adjusts the view, sharing the underlying buffer.
+impl Shrink for Bytes {
+    type Slice = [u8];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[u8], Output = [u8]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.truncate(range.end);
+        self.advance(range.start);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        Bytes::len(self)
+    }
+}
+
+/// Taking splits the buffer in two, both sharing the underlying allocation.
+///
+/// ```rust
+/// use bytes::Bytes;
+/// use shrink_slice::Take;
+///
+/// let mut frame = Bytes::from_static(b"\x00\x03abc!");
+/// let header = frame.take_exact(2).unwrap();
+/// let body = frame.take_exact(3).unwrap();
+/// assert_eq!((&header[..], &body[..], &frame[..]), (&b"\x00\x03"[..], &b"abc"[..], &b"!"[..]));
+/// ```
+impl Take for Bytes {
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        Ok(self.split_to(n))
+    }
+
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        Ok(self.split_off(self.len() - n))
+    }
+}
+
+/// Shrinking from the front only adjusts the view, while shrinking from the back gives up the
+/// trailing bytes; neither releases any capacity.
+impl Shrink for BytesMut {
+    type Slice = [u8];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[u8], Output = [u8]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.truncate(range.end);
+        self.advance(range.start);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        BytesMut::len(self)
+    }
+}
+
+/// Taking splits the buffer in two, both sharing the underlying allocation.
+impl Take for BytesMut {
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        Ok(self.split_to(n))
+    }
+
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        Ok(self.split_off(self.len() - n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_framing() {
+        fn payload<S: Shrink<Slice = [u8]>>(frame: &mut S) -> Result<(), ShrinkError> {
+            frame.try_shrink_front(1)?;
+            frame.try_shrink_back(1)
+        }
+
+        let mut shared = Bytes::from_static(b"[ab]");
+        payload(&mut shared).unwrap();
+        assert_eq!(&shared[..], b"ab");
+        let mut owned = BytesMut::from(&b"[cd]"[..]);
+        payload(&mut owned).unwrap();
+        assert!(owned.try_shrink(3..).is_err());
+        owned[0] = b'C';
+        let tail = owned.try_take_back(1).unwrap();
+        assert_eq!((&owned[..], &tail[..]), (&b"C"[..], &b"d"[..]));
+        assert_eq!(owned.take_exact(2), Err(NeedMore(1)));
+    }
+}
@@ -0,0 +1,175 @@
+//! Consuming prefixes without regard to case.
+
+use crate::ShrinkExt;
+
+/// Extension trait for consuming case-insensitive keywords from the front of a string slice.
+pub trait StripPrefixIgnoreCase {
+    /// If the slice starts with `prefix`, ignoring ASCII case, shrink past it and return `true`.
+    ///
+    /// Non-ASCII characters must match exactly.
+    ///
+    /// ```rust
+    /// use shrink_slice::case::StripPrefixIgnoreCase;
+    ///
+    /// let mut request = "get /index.html";
+    /// assert!(request.strip_prefix_ignore_ascii_case("GET "));
+    /// assert_eq!(request, "/index.html");
+    /// ```
+    fn strip_prefix_ignore_ascii_case(&mut self, prefix: &str) -> bool;
+
+    /// If the slice starts with `prefix` when both are lowercased, shrink past the matching part
+    /// and return `true`.
+    ///
+    /// Lowercasing is done with [`char::to_lowercase`], so characters may match regardless of
+    /// Unicode case. A match has to end at a character boundary of the slice: a prefix that only
+    /// covers part of the lowercase form of a character does not match.
+    ///
+    /// This is simple lowercasing, not Unicode case folding, so characters whose case-insensitive
+    /// form is longer than their lowercase form do not match it: `"STRASSE"` does not start
+    /// with `"stra\u{df}e"`. With the `caseless` feature,
+    /// [`strip_prefix_caseless`](StripPrefixIgnoreCase::strip_prefix_caseless) folds case fully.
+    ///
+    /// ```rust
+    /// use shrink_slice::case::StripPrefixIgnoreCase;
+    ///
+    /// let mut command = "ÖFFNEN datei.txt";
+    /// assert!(command.strip_prefix_ignore_case("öffnen"));
+    /// assert_eq!(command, " datei.txt");
+    /// ```
+    fn strip_prefix_ignore_case(&mut self, prefix: &str) -> bool;
+
+    /// If the slice starts with `prefix` when both are case folded, shrink past the matching part
+    /// and return `true`.
+    ///
+    /// This uses Unicode default case folding, through the `caseless` crate, which also matches
+    /// characters whose folded form is several characters long. As with
+    /// [`strip_prefix_ignore_case`](StripPrefixIgnoreCase::strip_prefix_ignore_case), a match
+    /// has to end at a character boundary of the slice.
+    ///
+    /// ```rust
+    /// use shrink_slice::case::StripPrefixIgnoreCase;
+    ///
+    /// let mut street = "STRASSE 1";
+    /// assert!(street.strip_prefix_caseless("stra\u{df}e"));
+    /// assert_eq!(street, " 1");
+    /// ```
+    #[cfg(feature = "caseless")]
+    fn strip_prefix_caseless(&mut self, prefix: &str) -> bool;
+}
+
+impl StripPrefixIgnoreCase for &str {
+    fn strip_prefix_ignore_ascii_case(&mut self, prefix: &str) -> bool {
+        let n = prefix.len();
+        // Bytes that only differ in ASCII case are both ASCII, so a match ends at a char boundary.
+        let matches = matches!(
+            self.as_bytes().get(..n),
+            Some(head) if head.eq_ignore_ascii_case(prefix.as_bytes())
+        );
+        if matches {
+            self.shrink(n..);
+        }
+        matches
+    }
+
+    fn strip_prefix_ignore_case(&mut self, prefix: &str) -> bool {
+        let mut want = prefix.chars().flat_map(char::to_lowercase).peekable();
+        let mut chars = self.char_indices();
+        let mut end = 0;
+
+        loop {
+            if want.peek().is_none() {
+                self.shrink(end..);
+                return true;
+            }
+            let (i, c) = match chars.next() {
+                Some(next) => next,
+                None => return false,
+            };
+            for lower in c.to_lowercase() {
+                if want.next() != Some(lower) {
+                    return false;
+                }
+            }
+            end = i + c.len_utf8();
+        }
+    }
+
+    #[cfg(feature = "caseless")]
+    fn strip_prefix_caseless(&mut self, prefix: &str) -> bool {
+        use caseless::Caseless;
+
+        let mut want = prefix.chars().default_case_fold().peekable();
+        let mut chars = self.char_indices();
+        let mut end = 0;
+
+        loop {
+            if want.peek().is_none() {
+                self.shrink(end..);
+                return true;
+            }
+            let (i, c) = match chars.next() {
+                Some(next) => next,
+                None => return false,
+            };
+            for folded in core::iter::once(c).default_case_fold() {
+                if want.next() != Some(folded) {
+                    return false;
+                }
+            }
+            end = i + c.len_utf8();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_mismatch() {
+        let mut text = "Éclair";
+        assert!(!text.strip_prefix_ignore_ascii_case("éC"));
+        assert!(!text.strip_prefix_ignore_ascii_case("Éclairs"));
+        assert!(text.strip_prefix_ignore_ascii_case("ÉCL"));
+        assert_eq!(text, "air");
+    }
+
+    #[test]
+    fn unicode_partial_char() {
+        // 'İ' lowercases to "i\u{307}", so "i" alone covers only half of it.
+        let mut text = "İx";
+        assert!(!text.strip_prefix_ignore_case("i"));
+        assert!(text.strip_prefix_ignore_case("i\u{307}"));
+        assert_eq!(text, "x");
+
+        let mut text = "ab";
+        assert!(!text.strip_prefix_ignore_case("abc"));
+        assert!(text.strip_prefix_ignore_case(""));
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn lowercasing_is_not_folding() {
+        let mut text = "STRASSE";
+        assert!(!text.strip_prefix_ignore_case("stra\u{df}e"));
+        assert_eq!(text, "STRASSE");
+    }
+
+    #[cfg(feature = "caseless")]
+    #[test]
+    fn full_case_folding() {
+        let mut text = "STRASSE 1";
+        assert!(text.strip_prefix_caseless("stra\u{df}e"));
+        assert_eq!(text, " 1");
+
+        // '\u{df}' folds to "ss", so "stras" covers only half of it.
+        let mut text = "Stra\u{df}e";
+        assert!(!text.strip_prefix_caseless("stras"));
+        assert!(text.strip_prefix_caseless("STRASS"));
+        assert_eq!(text, "e");
+
+        let mut text = "\u{1e9e}X";
+        assert!(text.strip_prefix_caseless("ss"));
+        assert_eq!(text, "X");
+    }
+}
@@ -0,0 +1,161 @@
+//! Shrinking slices borrowed from a `RefCell`.
+//!
+//! A [`Ref`] to a slice can be shrunk directly, by mapping a clone of the guard. A [`RefMut`]
+//! cannot be cloned, so it has to be wrapped in a [`ShrinkRefMut`] first.
+//!
+//! ```rust
+//! use core::cell::{Ref, RefCell, RefMut};
+//! use shrink_slice::cell::ShrinkRefMut;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let buffer = RefCell::new(b"HDRpayload".to_vec());
+//! {
+//!     let mut body = ShrinkRefMut::new(RefMut::map(buffer.borrow_mut(), |vec| &mut vec[..]));
+//!     body.shrink(3..);
+//!     body.make_ascii_uppercase();
+//! }
+//! let mut header = Ref::map(buffer.borrow(), |vec| &vec[..]);
+//! header.shrink(..3);
+//! assert_eq!(&*header, b"HDR");
+//! assert_eq!(buffer.borrow()[3..], *b"PAYLOAD");
+//! ```
+
+use crate::{range, Shrink, ShrinkError};
+use core::cell::{Ref, RefMut};
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::slice::SliceIndex;
+
+impl<T> Shrink for Ref<'_, [T]> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        *self = Ref::map(Ref::clone(self), |slice| &slice[range]);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+impl Shrink for Ref<'_, str> {
+    type Slice = str;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>
+    {
+        let mut view: &str = self;
+        view.try_shrink(range)?;
+        let start = range::offset_in(self.as_bytes(), view.as_bytes())
+            .expect("substring is within the string");
+        let end = start + view.len();
+        *self = Ref::map(Ref::clone(self), |s| &s[start..end]);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+}
+
+/// A shrinkable [`RefMut`] to a slice or string slice.
+#[derive(Debug)]
+pub struct ShrinkRefMut<'b, T: ?Sized> {
+    // Only `None` while the guard is being remapped.
+    guard: Option<RefMut<'b, T>>,
+}
+
+impl<'b, T: ?Sized> ShrinkRefMut<'b, T> {
+    /// Wrap `guard` so that it can be shrunk.
+    pub fn new(guard: RefMut<'b, T>) -> Self {
+        ShrinkRefMut { guard: Some(guard) }
+    }
+
+    /// Give up the wrapper, returning the guard.
+    pub fn into_inner(self) -> RefMut<'b, T> {
+        self.guard.expect("guard is present")
+    }
+
+    fn map<F>(&mut self, f: F)
+    where F: FnOnce(&mut T) -> &mut T,
+    {
+        let guard = self.guard.take().expect("guard is present");
+        self.guard = Some(RefMut::map(guard, f));
+    }
+}
+
+impl<T: ?Sized> Deref for ShrinkRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.as_ref().expect("guard is present")
+    }
+}
+
+impl<T: ?Sized> DerefMut for ShrinkRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.guard.as_mut().expect("guard is present")
+    }
+}
+
+impl<T> Shrink for ShrinkRefMut<'_, [T]> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.map(|slice| &mut slice[range]);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+impl Shrink for ShrinkRefMut<'_, str> {
+    type Slice = str;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>
+    {
+        let mut view: &str = self;
+        view.try_shrink(range)?;
+        let start = range::offset_in(self.as_bytes(), view.as_bytes())
+            .expect("substring is within the string");
+        let end = start + view.len();
+        self.map(|s| &mut s[start..end]);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn str_guards() {
+        let cell = RefCell::new(String::from("\u{e9}t\u{e9}"));
+        {
+            let mut text = ShrinkRefMut::new(RefMut::map(cell.borrow_mut(), |s| s.as_mut_str()));
+            assert!(text.try_shrink(1..).is_err());
+            text.shrink(2..);
+            text.make_ascii_uppercase();
+            assert_eq!(&*text.into_inner(), "T\u{e9}");
+        }
+        let mut text = Ref::map(cell.borrow(), String::as_str);
+        text.shrink(..3);
+        assert_eq!(&*text, "\u{e9}T");
+        assert!(cell.try_borrow_mut().is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Consuming string slices one `char` at a time, or by ranges of character indices.
+//!
+//! Ranges of character indices are written as [`Chars`]. Unlike byte ranges, they cannot be
+//! passed to [`Shrink::try_shrink`](crate::Shrink::try_shrink), and this is deliberate: its range
+//! must be a `SliceIndex` of the slice, so that every implementation can index with it directly,
+//! and only the standard library can implement that trait. Shrink to a character range with
+//! [`ShrinkChars::try_shrink_char_range`] instead, or convert it with [`Chars::byte_range`].
+//!
+//! When a character range is out of bounds, the error is a [`ShrinkError::OutOfBounds`] whose
+//! `len` is the number of characters in the string, not its length in bytes.
+
+use crate::{range, NeedMore, ShrinkError, ShrinkExt, ShrinkStr};
+use core::ops::{Bound, Range, RangeBounds};
+
+/// Extension trait for shrinking string slices by whole characters rather than bytes.
+///
+/// It is implemented for every [`ShrinkStr`], like `&str` and `&mut str`.
+///
+/// ```rust
+/// use shrink_slice::chars::{ShrinkChars, ShrinkCharsExt};
+///
+/// let mut word = "\u{a1}ol\u{e9}!";
+/// assert_eq!(word.pop_char(), Some('\u{a1}'));
+/// assert_eq!(word.pop_char_back(), Some('!'));
+/// word.shrink_chars_back(1);
+/// assert_eq!(word, "ol");
+/// assert_eq!(word.try_shrink_chars(5), Err(shrink_slice::NeedMore(3)));
+/// assert_eq!(word, "ol");
+/// ```
+pub trait ShrinkChars: ShrinkStr {
+    /// Remove the first character and return it, or return `None` if the string is empty.
+    fn pop_char(&mut self) -> Option<char> {
+        let c = self.chars().next()?;
+        self.shrink(c.len_utf8()..);
+        Some(c)
+    }
+
+    /// Remove the last character and return it, or return `None` if the string is empty.
+    fn pop_char_back(&mut self) -> Option<char> {
+        let c = self.chars().next_back()?;
+        let end = self.len() - c.len_utf8();
+        self.shrink(..end);
+        Some(c)
+    }
+
+    /// Drop the first `n` characters.
+    ///
+    /// If fewer than `n` remain, the string is left untouched and the error reports how many
+    /// more characters would have been needed.
+    fn try_shrink_chars(&mut self, n: usize) -> Result<(), NeedMore> {
+        let start = match self.char_indices().nth(n) {
+            Some((i, _)) => i,
+            None => {
+                enough(self, n)?;
+                self.len()
+            }
+        };
+        self.shrink(start..);
+        Ok(())
+    }
+
+    /// Drop the last `n` characters, or report how many more would have been needed, like
+    /// [`try_shrink_chars`](ShrinkChars::try_shrink_chars).
+    fn try_shrink_chars_back(&mut self, n: usize) -> Result<(), NeedMore> {
+        let end = match n.checked_sub(1) {
+            None => self.len(),
+            Some(last) => match self.char_indices().nth_back(last) {
+                Some((i, _)) => i,
+                None => {
+                    enough(self, n)?;
+                    0
+                }
+            },
+        };
+        self.shrink(..end);
+        Ok(())
+    }
+
+    /// Shrink the string to a range of character indices rather than byte indices, like ones
+    /// that refer to positions shown to a user.
+    ///
+    /// The range is converted to bytes by [`Chars::byte_range`], and if it is decreasing or
+    /// extends past the last character, the error reports it in characters and the string is
+    /// left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::chars::ShrinkChars;
+    ///
+    /// let mut line = "\u{bf}Qu\u{e9} tal?";
+    /// line.try_shrink_char_range(1..4).unwrap();
+    /// assert_eq!(line, "Qu\u{e9}");
+    /// assert!(line.try_shrink_char_range(2..=3).is_err());
+    /// assert_eq!(line, "Qu\u{e9}");
+    /// ```
+    fn try_shrink_char_range<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(), ShrinkError>
+    {
+        let range = Chars(range).byte_range(self)?;
+        self.try_shrink(range)
+    }
+}
+
+/// The methods of [`ShrinkChars`] that panic instead of returning an error, implemented for
+/// every string slice.
+pub trait ShrinkCharsExt: ShrinkChars {
+    /// Shrink the string to a range of character indices, panicking if it is decreasing or
+    /// extends past the last character.
+    #[track_caller]
+    fn shrink_char_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        if let Err(e) = self.try_shrink_char_range(range) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Drop the first `n` characters, panicking if fewer than `n` remain.
+    #[track_caller]
+    fn shrink_chars(&mut self, n: usize) {
+        if let Err(NeedMore(missing)) = self.try_shrink_chars(n) {
+            panic!("cannot drop {} characters, {} are missing", n, missing);
+        }
+    }
+
+    /// Drop the last `n` characters, panicking if fewer than `n` remain.
+    #[track_caller]
+    fn shrink_chars_back(&mut self, n: usize) {
+        if let Err(NeedMore(missing)) = self.try_shrink_chars_back(n) {
+            panic!("cannot drop {} characters, {} are missing", n, missing);
+        }
+    }
+}
+
+impl<S: ShrinkStr + ?Sized> ShrinkChars for S { }
+
+impl<S: ShrinkChars + ?Sized> ShrinkCharsExt for S { }
+
+/// A range of character indices into a string slice, rather than byte indices.
+///
+/// [`Shrink::try_shrink`](crate::Shrink::try_shrink) only accepts ranges that the standard
+/// library can index string slices by, so character ranges are converted to byte ranges first,
+/// or shrunk to directly with [`ShrinkChars::try_shrink_char_range`].
+///
+/// ```rust
+/// use shrink_slice::chars::Chars;
+///
+/// let s = "na\u{ef}ve";
+/// assert_eq!(Chars(2..4).byte_range(s), Ok(2..5));
+/// assert_eq!(&s[Chars(3..).byte_range(s).unwrap()], "ve");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Chars<R>(pub R);
+
+impl<R: RangeBounds<usize>> Chars<R> {
+    /// The byte range of `s` covering the characters in this range, found in a single pass over
+    /// them.
+    ///
+    /// A range that is decreasing or extends past the last character is reported as
+    /// [`ShrinkError::OutOfBounds`], with the range as given and the length counted in
+    /// characters.
+    pub fn byte_range(&self, s: &str) -> Result<Range<usize>, ShrinkError> {
+        let (start, end) = range::endpoints(&self.0, usize::MAX);
+        let unbounded = matches!(self.0.end_bound(), Bound::Unbounded);
+        let fail = || ShrinkError::out_of_bounds(range::bounds(&self.0), s.chars().count());
+        if !unbounded && end < start {
+            return Err(fail());
+        }
+        let mut boundaries = s.char_indices().map(|(i, _)| i).chain(Some(s.len()));
+        let byte_start = boundaries.nth(start).ok_or_else(fail)?;
+        let byte_end = match end - start {
+            _ if unbounded => s.len(),
+            0 => byte_start,
+            n => boundaries.nth(n - 1).ok_or_else(fail)?,
+        };
+        Ok(byte_start..byte_end)
+    }
+}
+
+/// Check that `s` has at least `n` characters, or report how many are missing.
+fn enough(s: &str, n: usize) -> Result<(), NeedMore> {
+    match s.chars().count() {
+        count if count < n => Err(NeedMore(n - count)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mut_str_by_chars() {
+        let mut owned = String::from("\u{1f600}ab\u{e9}c");
+        let mut text: &mut str = &mut owned;
+        text.shrink_chars(1);
+        text.shrink_chars_back(2);
+        text.make_ascii_uppercase();
+        assert_eq!(text.pop_char(), Some('A'));
+        assert_eq!(text.try_shrink_chars_back(2), Err(NeedMore(1)));
+        assert_eq!(text.pop_char_back(), Some('B'));
+        assert_eq!((text.pop_char(), text.pop_char_back()), (None, None));
+        assert_eq!(owned, "\u{1f600}AB\u{e9}c");
+        let mut empty = "";
+        assert_eq!(empty.try_shrink_chars(0), Ok(()));
+    }
+
+    #[test]
+    fn char_ranges() {
+        let s = "\u{1f600}a\u{e9}";
+        assert_eq!(Chars(..).byte_range(s), Ok(0..7));
+        assert_eq!(Chars(3..).byte_range(s), Ok(7..7));
+        assert_eq!(Chars(1..=1).byte_range(s), Ok(4..5));
+        assert_eq!(Chars(2..2).byte_range(s), Ok(5..5));
+        assert_eq!(
+            Chars(1..=3).byte_range(s),
+            Err(ShrinkError::OutOfBounds { start: 1, end: 4, len: 3 }),
+        );
+        assert!(Chars(4..).byte_range(s).is_err());
+        assert!(Chars(..=usize::MAX).byte_range(s).is_err());
+        let (start, end) = (2, 1);
+        assert_eq!(
+            Chars(start..end).byte_range(s),
+            Err(ShrinkError::OutOfBounds { start: 2, end: 1, len: 3 }),
+        );
+
+        let mut owned = String::from(s);
+        let mut text: &mut str = &mut owned;
+        text.shrink_char_range(1..);
+        text.make_ascii_uppercase();
+        assert_eq!(owned, "\u{1f600}A\u{e9}");
+    }
+}
@@ -0,0 +1,221 @@
+//! Consuming a slice one chunk at a time.
+
+use crate::{Shrink, ShrinkExt, Take, TakeArray};
+
+/// Extension trait for processing the front of a slice in fixed-size chunks.
+pub trait ConsumeChunks<T> {
+    /// Pass successive chunks of `n` elements to `f`, shrinking past each chunk once `f` has
+    /// accepted it. The last chunk is shorter if the length is not a multiple of `n`.
+    ///
+    /// Processing stops at the first error. The chunk that failed, and everything after it, is
+    /// left in the slice. On success, the number of elements consumed is returned.
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```rust
+    /// use shrink_slice::chunks::ConsumeChunks;
+    ///
+    /// let mut queue: &[u8] = b"aabbccdd";
+    /// let mut sent = Vec::new();
+    /// let result = queue.try_consume_chunks(2, |chunk| {
+    ///     if chunk == b"cc" {
+    ///         return Err("connection reset");
+    ///     }
+    ///     sent.extend_from_slice(chunk);
+    ///     Ok(())
+    /// });
+    /// assert_eq!(result, Err("connection reset"));
+    /// assert_eq!(sent, b"aabb");
+    /// assert_eq!(queue, b"ccdd");
+    /// ```
+    fn try_consume_chunks<E, F>(&mut self, n: usize, f: F) -> Result<usize, E>
+    where F: FnMut(&[T]) -> Result<(), E>;
+}
+
+impl<T> ConsumeChunks<T> for &[T] {
+    fn try_consume_chunks<E, F>(&mut self, n: usize, mut f: F) -> Result<usize, E>
+    where F: FnMut(&[T]) -> Result<(), E>
+    {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut consumed = 0;
+        while !self.is_empty() {
+            let len = n.min(self.len());
+            f(&self[..len])?;
+            self.shrink(len..);
+            consumed += len;
+        }
+        Ok(consumed)
+    }
+}
+
+impl<T> ConsumeChunks<T> for &mut [T] {
+    fn try_consume_chunks<E, F>(&mut self, n: usize, f: F) -> Result<usize, E>
+    where F: FnMut(&[T]) -> Result<(), E>
+    {
+        let mut rest: &[T] = self;
+        let result = rest.try_consume_chunks(n, f);
+        let consumed = self.len() - rest.len();
+        self.shrink(consumed..);
+        result
+    }
+}
+
+/// Extension trait for splitting fixed-size chunks off the front of a slice, keeping the
+/// slice's original lifetime.
+///
+/// It is implemented for everything that implements [`Take`].
+///
+/// ```rust
+/// use shrink_slice::chunks::NextChunk;
+///
+/// let mut data = *b"0123456789";
+/// let mut input: &mut [u8] = &mut data;
+/// input.for_each_chunk(4, |block| block.reverse());
+/// assert_eq!(input, b"89");
+/// assert_eq!(input.next_chunk(4), None);
+/// assert_eq!(input.next_chunk_or_rest(4).as_deref(), Some(&b"89"[..]));
+/// assert_eq!(&data, b"3210765489");
+/// ```
+pub trait NextChunk: Take {
+    /// Split off the next `n` elements, or return `None` and leave the slice untouched if fewer
+    /// remain.
+    fn next_chunk(&mut self, n: usize) -> Option<Self> {
+        self.take_front(n)
+    }
+
+    /// Split off the next `n` elements, or everything that is left if fewer remain. Returns
+    /// `None` once the slice is empty.
+    fn next_chunk_or_rest(&mut self, n: usize) -> Option<Self> {
+        if self.is_empty() {
+            return None;
+        }
+        let n = n.min(self.len());
+        self.take_front(n)
+    }
+
+    /// Split off chunks of exactly `n` elements and pass each to `f`, until fewer than `n`
+    /// remain. The remainder is left in the slice.
+    ///
+    /// Panics if `n` is zero.
+    fn for_each_chunk<F: FnMut(Self)>(&mut self, n: usize, mut f: F) {
+        assert!(n != 0, "chunk size must be non-zero");
+        while let Some(chunk) = self.next_chunk(n) {
+            f(chunk);
+        }
+    }
+}
+
+impl<S: Take> NextChunk for S { }
+
+/// Extension trait for splitting chunks whose size is known at compile time off the front of a
+/// slice, as array references with the slice's original lifetime.
+///
+/// It is implemented for everything that implements [`TakeArray`]. Each call is a single length
+/// check followed by a split, so the loops below compile to the same code as iterating over
+/// `chunks_exact`, with the tail left in the slice afterwards.
+///
+/// ```rust
+/// use shrink_slice::chunks::NextChunkArray;
+///
+/// let mut input: &[u8] = &[1, 2, 3, 4, 5, 6, 7];
+/// let mut checksum = 0u32;
+/// while let Some(block) = input.next_chunk_array::<4>() {
+///     checksum += u32::from_le_bytes(*block);
+/// }
+/// assert_eq!(checksum, 0x0403_0201);
+/// assert_eq!(input, [5, 6, 7]);
+/// ```
+pub trait NextChunkArray: TakeArray {
+    /// Split off the next `N` elements, or return `None` and leave the slice untouched if fewer
+    /// remain. This is the same as [`take_array`](TakeArray::take_array).
+    fn next_chunk_array<const N: usize>(&mut self) -> Option<Self::Array<N>> {
+        self.take_array()
+    }
+
+    /// Split off chunks of exactly `N` elements and pass each to `f`, until fewer than `N`
+    /// remain. The remainder is left in the slice.
+    ///
+    /// Panics if `N` is zero.
+    fn for_each_chunk_array<const N: usize, F>(&mut self, mut f: F)
+    where F: FnMut(Self::Array<N>),
+    {
+        assert!(N != 0, "chunk size must be non-zero");
+        while let Some(chunk) = self.take_array() {
+            f(chunk);
+        }
+    }
+}
+
+impl<S: TakeArray> NextChunkArray for S { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_last_chunk() {
+        let mut slice: &[u8] = b"abcde";
+        let mut chunks = Vec::new();
+        let consumed = slice.try_consume_chunks(2, |chunk| {
+            chunks.push(chunk.to_vec());
+            Ok::<_, ()>(())
+        });
+        assert_eq!(consumed, Ok(5));
+        assert_eq!(chunks, [&b"ab"[..], b"cd", b"e"]);
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn mut_keeps_failing_chunk() {
+        let mut buffer = *b"abcdef";
+        let mut slice: &mut [u8] = &mut buffer;
+        let result = slice.try_consume_chunks(4, |chunk| {
+            if chunk.len() < 4 { Err(chunk.len()) } else { Ok(()) }
+        });
+        assert_eq!(result, Err(2));
+        assert_eq!(slice, b"ef");
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let units = vec![(); usize::MAX];
+        let mut slice: &[()] = &units;
+        let mut calls = 0;
+        let consumed = slice.try_consume_chunks(usize::MAX / 2, |chunk| {
+            calls += 1;
+            assert!(chunk.len() <= usize::MAX / 2);
+            Ok::<_, ()>(())
+        });
+        assert_eq!(consumed, Ok(usize::MAX));
+        assert_eq!(calls, 3);
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_chunk_size() {
+        let mut slice: &[u8] = b"abc";
+        let _ = slice.try_consume_chunks(0, |_| Ok::<_, ()>(()));
+    }
+
+    #[test]
+    fn exact_chunks_leave_tail() {
+        let mut slice: &[u32] = &[1, 2, 3, 4, 5];
+        let mut sums = Vec::new();
+        slice.for_each_chunk(2, |chunk| sums.push(chunk.iter().sum::<u32>()));
+        assert_eq!((sums, slice), (vec![3, 7], &[5][..]));
+        assert_eq!(slice.next_chunk_or_rest(2), Some(&[5][..]));
+        assert_eq!(slice.next_chunk_or_rest(2), None);
+        assert_eq!(slice.next_chunk(0), Some(&[][..]));
+    }
+
+    #[test]
+    fn mut_array_chunks() {
+        let mut buffer = [1u8, 2, 3, 4, 5];
+        let mut slice: &mut [u8] = &mut buffer;
+        slice.for_each_chunk_array(|pair: &mut [u8; 2]| pair.swap(0, 1));
+        assert_eq!(slice.next_chunk_array::<2>(), None);
+        slice[0] = 50;
+        assert_eq!(buffer, [2, 1, 4, 3, 50]);
+    }
+}
@@ -0,0 +1,198 @@
+//! Shrinking by ranges that may not fit, clamping or snapping them instead of failing.
+
+use crate::{range, Shrink, ShrinkError, ShrinkExt};
+use core::ops::RangeBounds;
+
+/// The closest char boundary of `s` at or before `index`, which is clamped to `s.len()`.
+///
+/// ```rust
+/// use shrink_slice::clamp::{ceil_char_boundary, floor_char_boundary};
+///
+/// let s = "a\u{1f980}b";
+/// assert_eq!((floor_char_boundary(s, 3), ceil_char_boundary(s, 3)), (1, 5));
+/// assert_eq!((floor_char_boundary(s, 5), ceil_char_boundary(s, 9)), (5, 6));
+/// ```
+pub fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// The closest char boundary of `s` at or after `index`, which is clamped to `s.len()`.
+pub fn ceil_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+/// Clamp `range` into `[0, len]`, with a decreasing range collapsing to empty at its start.
+fn clamp<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let (start, end) = crate::range::endpoints(&range, len);
+    let start = start.min(len);
+    (start, end.clamp(start, len))
+}
+
+/// Clamp `range` into `s`, and then move both ends inwards onto char boundaries.
+fn clamp_str<R: RangeBounds<usize>>(range: R, s: &str) -> (usize, usize) {
+    let (start, end) = clamp(range, s.len());
+    let start = ceil_char_boundary(s, start);
+    (start, floor_char_boundary(s, end).max(start))
+}
+
+/// Extension trait for shrinking to as much of a range as the slice allows.
+pub trait ShrinkClamped {
+    /// Shrink the slice to `range`, clamped to its bounds. This never fails.
+    ///
+    /// Ends past the end of the slice are moved back onto it, and a decreasing range leaves an
+    /// empty slice at its start. For string slices, an end that lands within a multi-byte
+    /// character is moved inwards to the nearest char boundary, so that no partial character is
+    /// kept.
+    ///
+    /// ```rust
+    /// use shrink_slice::clamp::ShrinkClamped;
+    ///
+    /// let mut preview = "na\u{ef}ve caf\u{e9}";
+    /// preview.shrink_clamped(..3);
+    /// assert_eq!(preview, "na");
+    ///
+    /// let mut bytes: &[u8] = b"short";
+    /// bytes.shrink_clamped(2..80);
+    /// assert_eq!(bytes, b"ort");
+    /// ```
+    fn shrink_clamped<R: RangeBounds<usize>>(&mut self, range: R);
+}
+
+impl<T> ShrinkClamped for &[T] {
+    fn shrink_clamped<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = clamp(range, self.len());
+        self.shrink(start..end);
+    }
+}
+
+impl<T> ShrinkClamped for &mut [T] {
+    fn shrink_clamped<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = clamp(range, self.len());
+        self.shrink(start..end);
+    }
+}
+
+impl ShrinkClamped for &str {
+    fn shrink_clamped<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = clamp_str(range, self);
+        self.shrink(start..end);
+    }
+}
+
+impl ShrinkClamped for &mut str {
+    fn shrink_clamped<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = clamp_str(range, self);
+        self.shrink(start..end);
+    }
+}
+
+/// Extension trait for shrinking string slices to ranges that may split characters, widening
+/// them to whole characters instead of failing.
+///
+/// ```rust
+/// use shrink_slice::clamp::{ShrinkLossy, ShrinkLossyExt};
+///
+/// // Byte offsets from a display width calculation that assumed one byte per column.
+/// let mut cell = "\u{2502} f\u{fc}r \u{2502}";
+/// cell.shrink_lossy(2..8);
+/// assert_eq!(cell, "\u{2502} f\u{fc}r");
+/// assert!(cell.try_shrink_lossy(..20).is_err());
+/// ```
+pub trait ShrinkLossy {
+    /// Shrink the string to `range`, moving a start that lands within a multi-byte character
+    /// back to that character's start, and an end that lands within one forward to its end, so
+    /// that every character the range touches is kept.
+    ///
+    /// Ranges that are decreasing or out of bounds are still reported and leave the string
+    /// untouched.
+    fn try_shrink_lossy<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError>;
+}
+
+/// The panicking counterpart of [`try_shrink_lossy`](ShrinkLossy::try_shrink_lossy), for
+/// ranges that are known to be in bounds.
+pub trait ShrinkLossyExt: ShrinkLossy {
+    /// Shrink the string to `range`, widened to whole characters like
+    /// [`try_shrink_lossy`](ShrinkLossy::try_shrink_lossy), panicking if it is decreasing or out
+    /// of bounds.
+    #[track_caller]
+    fn shrink_lossy<R: RangeBounds<usize>>(&mut self, range: R) {
+        if let Err(e) = self.try_shrink_lossy(range) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<S: ShrinkLossy + ?Sized> ShrinkLossyExt for S { }
+
+/// Resolve `range` against `s` and widen it to char boundaries.
+fn widen<R: RangeBounds<usize>>(range: R, s: &str) -> Result<(usize, usize), ShrinkError> {
+    let range = range::try_resolve(range, s.len())?;
+    Ok((floor_char_boundary(s, range.start), ceil_char_boundary(s, range.end)))
+}
+
+impl ShrinkLossy for &str {
+    fn try_shrink_lossy<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let (start, end) = widen(range, self)?;
+        self.try_shrink(start..end)
+    }
+}
+
+impl ShrinkLossy for &mut str {
+    fn try_shrink_lossy<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let (start, end) = widen(range, self)?;
+        self.try_shrink(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range() {
+        let data = [1, 2, 3, 4];
+        let mut slice: &[i32] = &data;
+        let (start, end) = (3, 1);
+        slice.shrink_clamped(start..end);
+        assert_eq!((slice.len(), slice.as_ptr()), (0, data[3..].as_ptr()));
+
+        let mut slice: &[i32] = &data;
+        slice.shrink_clamped(10..=usize::MAX);
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn snaps_inwards() {
+        let mut text = "\u{1f980}\u{1f980}";
+        text.shrink_clamped(1..7);
+        assert_eq!(text, "");
+        let mut text = "a\u{1f980}b";
+        text.shrink_clamped(2..);
+        assert_eq!(text, "b");
+        let mut buffer = String::from("\u{e9}t\u{e9}");
+        let mut text: &mut str = &mut buffer;
+        text.shrink_clamped(..4);
+        assert_eq!(text, "\u{e9}t");
+    }
+
+    #[test]
+    fn lossy_snaps_outwards() {
+        let mut buffer = String::from("\u{e9}t\u{e9}");
+        let mut text: &mut str = &mut buffer;
+        text.shrink_lossy(1..4);
+        assert_eq!(text, "\u{e9}t\u{e9}");
+        text.shrink_lossy(3..3);
+        assert_eq!(text, "");
+        let (start, end) = (2, 1);
+        assert!("ab".try_shrink_lossy(start..end).is_err());
+        assert_eq!(ceil_char_boundary("", 1), 0);
+    }
+}
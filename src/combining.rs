@@ -0,0 +1,131 @@
+//! Shrinking strings without separating base characters from their combining marks.
+
+use crate::{range, Shrink, ShrinkError};
+use core::ops::RangeBounds;
+
+/// Whether `c` is a combining mark that attaches to the character before it.
+///
+/// These are the characters of the Unicode general category Mark, taken from the
+/// `unicode-normalization` crate, which include the combining blocks, the variation selectors,
+/// and the vowel signs and other marks encoded inside script-specific blocks. The zero width
+/// joiner is treated as one too, since it binds the characters on either side of it.
+///
+/// ```rust
+/// use shrink_slice::combining::is_combining;
+///
+/// assert!(is_combining('\u{301}') && is_combining('\u{93e}') && is_combining('\u{200d}'));
+/// assert!(!is_combining('e') && !is_combining('\u{915}'));
+/// ```
+pub fn is_combining(c: char) -> bool {
+    c == '\u{200D}' || unicode_normalization::char::is_combining_mark(c)
+}
+
+/// Whether cutting `s` at byte `index` keeps every combining mark with its base character, and
+/// every zero width joiner with the characters on both sides of it.
+fn is_mark_boundary(s: &str, index: usize) -> bool {
+    s.is_char_boundary(index)
+        && !matches!(s[index..].chars().next(), Some(c) if is_combining(c))
+        && !s[..index].ends_with('\u{200D}')
+}
+
+/// Resolve `range` against `s`, checking that both ends are mark boundaries.
+fn check_marks<R: RangeBounds<usize>>(s: &str, range: R) -> Result<(usize, usize), ShrinkError> {
+    let range = range::try_resolve(range, s.len())?;
+    let (start, end) = (range.start, range.end);
+    for index in [start, end] {
+        if !is_mark_boundary(s, index) {
+            return Err(ShrinkError::NotCharBoundary { start, end, len: s.len(), index });
+        }
+    }
+    Ok((start, end))
+}
+
+/// Extension trait for shrinking string slices along combining-sequence boundaries.
+pub trait ShrinkCombining {
+    /// Shrink the string to the byte range `range`, like [`Shrink::try_shrink`], but also refuse
+    /// cuts that would separate a combining mark (as recognized by [`is_combining`]) from the
+    /// character it modifies. Such cuts are reported as [`ShrinkError::NotCharBoundary`].
+    ///
+    /// ```rust
+    /// use shrink_slice::combining::ShrinkCombining;
+    ///
+    /// let mut text = "cafe\u{301}!";
+    /// assert!(text.try_shrink_combining(..4).is_err());
+    /// assert!(text.try_shrink_combining(2..).is_ok());
+    /// assert_eq!(text, "fe\u{301}!");
+    /// ```
+    fn try_shrink_combining<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError>;
+}
+
+impl ShrinkCombining for &str {
+    fn try_shrink_combining<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let (start, end) = check_marks(self, range)?;
+        self.try_shrink(start..end)
+    }
+}
+
+impl ShrinkCombining for &mut str {
+    fn try_shrink_combining<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let (start, end) = check_marks(self, range)?;
+        self.try_shrink(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_orphaned_marks() {
+        let mut text = "a\u{308}\u{20dd}b";
+        let cut = |start, end, index| {
+            Err(ShrinkError::NotCharBoundary { start, end, len: 7, index })
+        };
+        assert_eq!(text.try_shrink_combining(1..), cut(1, 7, 1));
+        assert_eq!(text.try_shrink_combining(..3), cut(0, 3, 3));
+        assert_eq!(text.try_shrink_combining(..2), cut(0, 2, 2));
+        assert_eq!(text.try_shrink_combining(..=4), cut(0, 5, 5));
+        assert_eq!(text.try_shrink_combining(..6), Ok(()));
+        assert_eq!(text, "a\u{308}\u{20dd}");
+    }
+
+    #[test]
+    fn mut_str() {
+        let mut buffer = String::from("x\u{1f44d}\u{fe0f}y");
+        let mut text: &mut str = &mut buffer;
+        assert!(text.try_shrink_combining(..5).is_err());
+        assert!(text.try_shrink_combining(1..8).is_ok());
+        assert_eq!(text, "\u{1f44d}\u{fe0f}");
+        assert!(text.try_shrink_combining(..8).is_err());
+    }
+
+    #[test]
+    fn indic_marks() {
+        // "\u{915}\u{93e}" is KA with the vowel sign AA, a spacing mark.
+        let mut text = "\u{915}\u{93e}\u{930}";
+        assert!(text.try_shrink_combining(..3).is_err());
+        assert!(text.try_shrink_combining(3..).is_err());
+        // SA with a virama, then TA with the vowel sign E.
+        let mut word = "\u{938}\u{94d}\u{924}\u{947}";
+        assert!(word.try_shrink_combining(..3).is_err());
+        assert!(word.try_shrink_combining(..9).is_err());
+        assert!(word.try_shrink_combining(6..).is_ok());
+        assert_eq!(word, "\u{924}\u{947}");
+        assert!(text.try_shrink_combining(..6).is_ok());
+        assert_eq!(text, "\u{915}\u{93e}");
+    }
+
+    #[test]
+    fn joiner_binds_both_sides() {
+        // WOMAN, ZERO WIDTH JOINER, PERSONAL COMPUTER.
+        let mut text = "\u{1f469}\u{200d}\u{1f4bb}!";
+        assert!(text.try_shrink_combining(..4).is_err());
+        assert_eq!(
+            text.try_shrink_combining(..7),
+            Err(ShrinkError::NotCharBoundary { start: 0, end: 7, len: 12, index: 7 }),
+        );
+        assert!(text.try_shrink_combining(7..).is_err());
+        assert_eq!(text.try_shrink_combining(..11), Ok(()));
+        assert_eq!(text, "\u{1f469}\u{200d}\u{1f4bb}");
+    }
+}
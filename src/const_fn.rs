@@ -0,0 +1,168 @@
+//! Shrinking in const contexts.
+//!
+//! Trait methods cannot be called in a `const fn` on stable Rust, so these free functions mirror
+//! the most common shapes of [`Shrink`](crate::Shrink) for slices and string slices. Rather than
+//! shrinking a slice in place, they return the shrunk slice, which is usually what a constant
+//! initializer wants. The `try_` functions report errors like their trait counterparts, and the
+//! others panic, which fails compilation when evaluated at compile time.
+//!
+//! ```rust
+//! use shrink_slice::const_fn::{shrink_back_const, shrink_front_const};
+//!
+//! const RECORD: &[u8] = b"HDR\x00payload\x0d\x0a";
+//! const PAYLOAD: &[u8] = shrink_back_const(shrink_front_const(RECORD, 4), 2);
+//! assert_eq!(PAYLOAD, b"payload");
+//! ```
+
+use crate::ShrinkError;
+
+/// Drop the first `n` elements of `slice`.
+pub const fn try_shrink_front_const<T>(slice: &[T], n: usize) -> Result<&[T], ShrinkError> {
+    match slice.split_at_checked(n) {
+        Some((_, rest)) => Ok(rest),
+        None => Err(ShrinkError::OutOfBounds { start: n, end: slice.len(), len: slice.len() }),
+    }
+}
+
+/// Drop the last `n` elements of `slice`. Too large an `n` is reported as the range `0..n` being
+/// out of bounds.
+pub const fn try_shrink_back_const<T>(slice: &[T], n: usize) -> Result<&[T], ShrinkError> {
+    let len = slice.len();
+    match len.checked_sub(n) {
+        Some(end) => Ok(slice.split_at(end).0),
+        None => Err(ShrinkError::OutOfBounds { start: 0, end: n, len }),
+    }
+}
+
+/// Shrink `slice` to the range `start..end`.
+pub const fn try_shrink_range_const<T>(slice: &[T], start: usize, end: usize)
+    -> Result<&[T], ShrinkError>
+{
+    let len = slice.len();
+    if start > end || end > len {
+        return Err(ShrinkError::OutOfBounds { start, end, len });
+    }
+    Ok(slice.split_at(end).0.split_at(start).1)
+}
+
+/// Drop the first `n` elements of `slice`, panicking if fewer than `n` remain.
+#[track_caller]
+pub const fn shrink_front_const<T>(slice: &[T], n: usize) -> &[T] {
+    match try_shrink_front_const(slice, n) {
+        Ok(slice) => slice,
+        Err(_) => panic!("shrunk past the end of the slice"),
+    }
+}
+
+/// Drop the last `n` elements of `slice`, panicking if fewer than `n` remain.
+#[track_caller]
+pub const fn shrink_back_const<T>(slice: &[T], n: usize) -> &[T] {
+    match try_shrink_back_const(slice, n) {
+        Ok(slice) => slice,
+        Err(_) => panic!("shrunk past the start of the slice"),
+    }
+}
+
+/// Shrink `slice` to the range `start..end`, panicking if it is decreasing or out of bounds.
+#[track_caller]
+pub const fn shrink_range_const<T>(slice: &[T], start: usize, end: usize) -> &[T] {
+    match try_shrink_range_const(slice, start, end) {
+        Ok(slice) => slice,
+        Err(_) => panic!("range is out of bounds of the slice"),
+    }
+}
+
+/// The error for shrinking `s` to `start..end`, if that range does not index it.
+const fn str_error(s: &str, start: usize, end: usize) -> Option<ShrinkError> {
+    let len = s.len();
+    if start > end || end > len {
+        Some(ShrinkError::OutOfBounds { start, end, len })
+    } else if !s.is_char_boundary(start) {
+        Some(ShrinkError::NotCharBoundary { start, end, len, index: start })
+    } else if !s.is_char_boundary(end) {
+        Some(ShrinkError::NotCharBoundary { start, end, len, index: end })
+    } else {
+        None
+    }
+}
+
+/// Drop the first `n` bytes of `s`.
+pub const fn try_shrink_str_front_const(s: &str, n: usize) -> Result<&str, ShrinkError> {
+    try_shrink_str_range_const(s, n, s.len())
+}
+
+/// Drop the last `n` bytes of `s`. Too large an `n` is reported as the range `0..n` being out
+/// of bounds.
+pub const fn try_shrink_str_back_const(s: &str, n: usize) -> Result<&str, ShrinkError> {
+    let len = s.len();
+    match len.checked_sub(n) {
+        Some(end) => try_shrink_str_range_const(s, 0, end),
+        None => Err(ShrinkError::OutOfBounds { start: 0, end: n, len }),
+    }
+}
+
+/// Shrink `s` to the byte range `start..end`.
+pub const fn try_shrink_str_range_const(s: &str, start: usize, end: usize)
+    -> Result<&str, ShrinkError>
+{
+    if let Some(e) = str_error(s, start, end) {
+        return Err(e);
+    }
+    Ok(s.split_at(end).0.split_at(start).1)
+}
+
+/// Drop the first `n` bytes of `s`, panicking if fewer than `n` remain or if `n` lands within a
+/// multi-byte character.
+#[track_caller]
+pub const fn shrink_str_front_const(s: &str, n: usize) -> &str {
+    match try_shrink_str_front_const(s, n) {
+        Ok(s) => s,
+        Err(_) => panic!("cannot shrink the front of the string slice there"),
+    }
+}
+
+/// Drop the last `n` bytes of `s`, panicking if fewer than `n` remain or if the new end lands
+/// within a multi-byte character.
+#[track_caller]
+pub const fn shrink_str_back_const(s: &str, n: usize) -> &str {
+    match try_shrink_str_back_const(s, n) {
+        Ok(s) => s,
+        Err(_) => panic!("cannot shrink the back of the string slice there"),
+    }
+}
+
+/// Shrink `s` to the byte range `start..end`, panicking if it is decreasing, out of bounds, or
+/// if either end lands within a multi-byte character.
+#[track_caller]
+pub const fn shrink_str_range_const(s: &str, start: usize, end: usize) -> &str {
+    match try_shrink_str_range_const(s, start, end) {
+        Ok(s) => s,
+        Err(_) => panic!("cannot shrink the string slice to that range"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_match_the_trait() {
+        use crate::Shrink;
+
+        const WORD: &str = shrink_str_range_const("(caf\u{e9})", 1, 6);
+        assert_eq!(WORD, "caf\u{e9}");
+        for (start, end) in [(0, 5), (0, 4), (1, 5), (4, 6), (3, 2)] {
+            let mut s = WORD;
+            let expected = s.try_shrink(start..end).map(|()| s);
+            assert_eq!(try_shrink_str_range_const(WORD, start, end), expected);
+        }
+        let mut s = WORD;
+        assert_eq!(try_shrink_str_back_const(WORD, 1).err(), s.try_shrink_back(1).err());
+
+        let bytes: &[u8] = b"abc";
+        let mut slice = bytes;
+        assert_eq!(try_shrink_front_const(bytes, 4).err(), slice.try_shrink_front(4).err());
+        assert_eq!(try_shrink_back_const(bytes, 4).err(), slice.try_shrink_back(4).err());
+        assert_eq!(shrink_range_const(bytes, 1, 2), b"b");
+    }
+}
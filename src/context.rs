@@ -0,0 +1,159 @@
+//! Labelling errors with what was being parsed when they occurred.
+//!
+//! ```rust
+//! use shrink_slice::context::{Context, ContextError, ShrinkContext};
+//!
+//! fn payload_length(input: &mut &[u8]) -> Result<(), ContextError> {
+//!     input.try_shrink_ctx("payload length", 2..)
+//! }
+//!
+//! fn frame_header(input: &mut &[u8]) -> Result<(), ContextError> {
+//!     payload_length(input).context("frame header")
+//! }
+//!
+//! let mut input: &[u8] = b"\x01";
+//! let err = frame_header(&mut input).unwrap_err();
+//! assert_eq!(err.labels().collect::<Vec<_>>(), ["frame header", "payload length"]);
+//! assert_eq!(
+//!     err.to_string(),
+//!     "frame header \u{2192} payload length: \
+//!      range start index 2 out of range for slice of length 1",
+//! );
+//! ```
+
+use crate::{NeedMore, Shrink, ShrinkError};
+use core::fmt;
+use core::ops::RangeBounds;
+use core::slice::SliceIndex;
+
+/// The maximum number of labels a [`ContextError`] can hold. Labels added beyond this are
+/// dropped, keeping the innermost ones.
+pub const MAX_LABELS: usize = 6;
+
+/// An error together with a chain of static labels describing where it happened.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ContextError<E = ShrinkError> {
+    error: E,
+    // Innermost first.
+    labels: [&'static str; MAX_LABELS],
+    depth: usize,
+}
+
+impl<E> ContextError<E> {
+    /// Wrap `error` without any labels.
+    pub fn new(error: E) -> Self {
+        ContextError { error, labels: [""; MAX_LABELS], depth: 0 }
+    }
+
+    /// Add an outer label to the chain.
+    pub fn context(mut self, label: &'static str) -> Self {
+        if self.depth < MAX_LABELS {
+            self.labels[self.depth] = label;
+            self.depth += 1;
+        }
+        self
+    }
+
+    /// The labels, from the outermost to the innermost.
+    pub fn labels(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.labels[..self.depth].iter().rev().copied()
+    }
+
+    /// The underlying error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Discard the labels and return the underlying error.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E> From<E> for ContextError<E> {
+    fn from(error: E) -> Self {
+        ContextError::new(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ContextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, label) in self.labels().enumerate() {
+            if i > 0 {
+                f.write_str(" \u{2192} ")?;
+            }
+            f.write_str(label)?;
+        }
+        if self.depth > 0 {
+            f.write_str(": ")?;
+        }
+        write!(f, "{}", self.error)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for ContextError<E> { }
+
+/// Extension trait for attaching a label to the error of a [`Result`].
+pub trait Context<T, E> {
+    /// Label the error, if any, as having happened while parsing `label`.
+    fn context(self, label: &'static str) -> Result<T, ContextError<E>>;
+}
+
+impl<T> Context<T, ShrinkError> for Result<T, ShrinkError> {
+    fn context(self, label: &'static str) -> Result<T, ContextError<ShrinkError>> {
+        self.map_err(|e| ContextError::new(e).context(label))
+    }
+}
+
+impl<T> Context<T, NeedMore> for Result<T, NeedMore> {
+    fn context(self, label: &'static str) -> Result<T, ContextError<NeedMore>> {
+        self.map_err(|e| ContextError::new(e).context(label))
+    }
+}
+
+impl<T, E> Context<T, E> for Result<T, ContextError<E>> {
+    fn context(self, label: &'static str) -> Result<T, ContextError<E>> {
+        self.map_err(|e| e.context(label))
+    }
+}
+
+/// Extension trait for shrinking with a labelled error.
+pub trait ShrinkContext: Shrink {
+    /// Like [`Shrink::try_shrink`], but label any error with `label`.
+    // The labels are stored inline, so that labelling works without an allocator.
+    #[allow(clippy::result_large_err)]
+    fn try_shrink_ctx<R>(&mut self, label: &'static str, range: R) -> Result<(), ContextError>
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>,
+    {
+        self.try_shrink(range).context(label)
+    }
+}
+
+impl<S: Shrink + ?Sized> ShrinkContext for S { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Take;
+
+    #[test]
+    fn unlabelled_display() {
+        let inner = ShrinkError::OutOfBounds { start: 0, end: 1, len: 0 };
+        let err = ContextError::new(inner);
+        assert_eq!(err.to_string(), inner.to_string());
+        assert_eq!(err.labels().count(), 0);
+    }
+
+    #[test]
+    fn overflowing_chain_keeps_innermost() {
+        let mut input: &[u8] = b"";
+        let mut result = input.take_exact(1).context("innermost");
+        for _ in 1..MAX_LABELS + 2 {
+            result = result.context("outer");
+        }
+        let err = result.unwrap_err();
+        assert_eq!(err.labels().count(), MAX_LABELS);
+        assert_eq!(err.labels().last(), Some("innermost"));
+        assert_eq!(*err.error(), NeedMore(1));
+    }
+}
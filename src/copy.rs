@@ -0,0 +1,192 @@
+//! Copying between shrinking slices.
+
+use crate::{range, Shrink, ShrinkError, ShrinkExt};
+use core::mem;
+
+/// Extension trait for distributing the front of a source slice over several destinations.
+pub trait Scatter<T: Copy> {
+    /// Fill each destination in order with elements taken from the front of `self`, and return
+    /// the total number of elements copied.
+    ///
+    /// Both sides shrink past whatever was copied: `self` loses its front, and every destination
+    /// that was written to is shrunk to the part that is still unfilled. Copying stops when
+    /// either the source or the destinations run out.
+    ///
+    /// ```rust
+    /// use shrink_slice::copy::Scatter;
+    ///
+    /// let (mut a, mut b) = ([0; 2], [0; 3]);
+    /// let mut src: &[u8] = &[1, 2, 3, 4];
+    /// let mut dsts: [&mut [u8]; 2] = [&mut a, &mut b];
+    ///
+    /// assert_eq!(src.scatter_to(&mut dsts), 4);
+    /// assert_eq!(dsts[0].len(), 0);
+    /// assert_eq!(dsts[1].len(), 1);
+    /// assert_eq!((a, b), ([1, 2], [3, 4, 0]));
+    /// ```
+    fn scatter_to(&mut self, dsts: &mut [&mut [T]]) -> usize;
+
+    /// Copy as many elements as fit from the front of `self` into the front of `dst`, shrink
+    /// both past them, and return how many were copied.
+    ///
+    /// This is the step of a buffered copy loop, and the same as scattering to a single
+    /// destination.
+    ///
+    /// ```rust
+    /// use shrink_slice::copy::Scatter;
+    ///
+    /// let mut buffer = [0; 4];
+    /// let mut dst: &mut [u8] = &mut buffer;
+    /// let mut src: &[u8] = b"abc";
+    /// assert_eq!(src.copy_to(&mut dst), 3);
+    /// assert_eq!(b"xyz".as_slice().copy_to(&mut dst), 1);
+    /// assert!(src.is_empty() && dst.is_empty());
+    /// assert_eq!(&buffer, b"abcx");
+    /// ```
+    fn copy_to(&mut self, dst: &mut &mut [T]) -> usize {
+        self.scatter_to(core::slice::from_mut(dst))
+    }
+}
+
+impl<T: Copy> Scatter<T> for &[T] {
+    fn scatter_to(&mut self, dsts: &mut [&mut [T]]) -> usize {
+        let mut total = 0;
+        for dst in dsts {
+            if self.is_empty() {
+                break;
+            }
+            let n = dst.len().min(self.len());
+            dst[..n].copy_from_slice(&self[..n]);
+            dst.shrink(n..);
+            self.shrink(n..);
+            total += n;
+        }
+        total
+    }
+}
+
+impl<T: Copy> Scatter<T> for &mut [T] {
+    fn scatter_to(&mut self, dsts: &mut [&mut [T]]) -> usize {
+        let mut src: &[T] = self;
+        let total = src.scatter_to(dsts);
+        self.shrink(total..);
+        total
+    }
+}
+
+/// Extension trait for writing to the front of a mutable slice and shrinking past what was
+/// written, in one bounds-checked step.
+///
+/// ```rust
+/// use shrink_slice::copy::FillFront;
+///
+/// let mut buffer = [0; 6];
+/// let mut slice: &mut [u8] = &mut buffer;
+/// let mut emit = |bytes: &[u8]| slice.fill_front(bytes);
+/// emit(b"ab").unwrap();
+/// emit(b"cde").unwrap();
+/// assert!(emit(b"fg").is_err());
+/// slice.fill_front_with(1, || b'!').unwrap();
+/// assert_eq!(&buffer, b"abcde!");
+/// ```
+pub trait FillFront<T> {
+    /// Copy `src` into the front of the slice and drop that many elements from it.
+    ///
+    /// If the slice is shorter than `src`, nothing is written and the error is the one that
+    /// [`Shrink::try_shrink_front`] would have returned.
+    fn fill_front(&mut self, src: &[T]) -> Result<(), ShrinkError>
+    where T: Copy;
+
+    /// Overwrite the first `n` elements of the slice with the results of calling `f`, in order,
+    /// and drop them from it.
+    ///
+    /// If fewer than `n` elements remain, `f` is never called and the slice is left untouched.
+    fn fill_front_with<F: FnMut() -> T>(&mut self, n: usize, f: F) -> Result<(), ShrinkError>;
+}
+
+/// Split `n` elements off the front of `slice`, or fail like shrinking its front would.
+fn front<'a, T>(slice: &mut &'a mut [T], n: usize) -> Result<&'a mut [T], ShrinkError> {
+    range::try_resolve(n.., slice.len())?;
+    let (head, tail) = mem::take(slice).split_at_mut(n);
+    *slice = tail;
+    Ok(head)
+}
+
+impl<T> FillFront<T> for &mut [T] {
+    fn fill_front(&mut self, src: &[T]) -> Result<(), ShrinkError>
+    where T: Copy
+    {
+        front(self, src.len())?.copy_from_slice(src);
+        Ok(())
+    }
+
+    fn fill_front_with<F: FnMut() -> T>(&mut self, n: usize, f: F) -> Result<(), ShrinkError> {
+        front(self, n)?.fill_with(f);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_runs_out() {
+        let (mut a, mut b) = ([0; 3], [0; 3]);
+        let mut buffer = [1, 2, 3, 4];
+        let mut src: &mut [u8] = &mut buffer;
+        let mut dsts: [&mut [u8]; 2] = [&mut a, &mut b];
+
+        assert_eq!(src.scatter_to(&mut dsts), 4);
+        assert!(src.is_empty());
+        assert_eq!(dsts[1].len(), 2);
+        assert_eq!(src.scatter_to(&mut dsts), 0);
+        assert_eq!((a, b), ([1, 2, 3], [4, 0, 0]));
+    }
+
+    #[test]
+    fn destinations_run_out() {
+        let mut a = [0; 2];
+        let mut src: &[u8] = &[1, 2, 3];
+        assert_eq!(src.scatter_to(&mut [&mut a]), 2);
+        assert_eq!(src, [3]);
+        assert_eq!(src.scatter_to(&mut []), 0);
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let units = vec![(); usize::MAX];
+        let (mut a, mut b) = (vec![(); usize::MAX - 1], [(); 3]);
+        let mut src: &[()] = &units;
+        assert_eq!(src.scatter_to(&mut [&mut a, &mut b]), usize::MAX);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn copy_between_mut_slices() {
+        let (mut a, mut b) = ([1, 2, 3], [0; 2]);
+        let mut src: &mut [i32] = &mut a;
+        let mut dst: &mut [i32] = &mut b;
+        assert_eq!(src.copy_to(&mut dst), 2);
+        assert_eq!(src.copy_to(&mut dst), 0);
+        src[0] = 30;
+        assert_eq!((a, b), ([1, 2, 30], [1, 2]));
+    }
+
+    #[test]
+    fn fill_front_errors_like_shrink_front() {
+        let mut buffer = [0; 3];
+        let mut dst: &mut [i32] = &mut buffer;
+        let mut copy: &[i32] = &[0; 3];
+        let error = dst.fill_front(&[1, 2, 3, 4]).unwrap_err();
+        assert_eq!(Err(error), copy.try_shrink_front(4));
+        let mut calls = 0;
+        assert!(dst.fill_front_with(4, || { calls += 1; 0 }).is_err());
+        assert_eq!((calls, dst.len()), (0, 3));
+        dst.fill_front(&[]).unwrap();
+        let mut next = 10;
+        dst.fill_front_with(3, || { next += 1; next }).unwrap();
+        assert!(dst.is_empty());
+        assert_eq!(buffer, [11, 12, 13]);
+    }
+}
@@ -0,0 +1,51 @@
+//! `Shrink` for C strings.
+
+use crate::{range, Shrink, ShrinkError};
+use core::ffi::CStr;
+use core::ops::RangeBounds;
+use core::slice::SliceIndex;
+
+/// C strings can only be shrunk from the front, since the result must still end with the same
+/// nul terminator. Their length and indices count the bytes before the terminator, and ranges
+/// that do not reach its end are refused with [`ShrinkError::FrontOnly`].
+///
+/// ```rust
+/// use core::ffi::CStr;
+/// use shrink_slice::{Shrink, ShrinkError, ShrinkExt};
+///
+/// let mut arg = CStr::from_bytes_with_nul(b"--name=value\0").unwrap();
+/// arg.shrink_front(7);
+/// assert_eq!(arg.to_bytes_with_nul(), b"value\0");
+/// assert!(matches!(arg.try_shrink(..2), Err(ShrinkError::FrontOnly { .. })));
+/// ```
+impl Shrink for &CStr {
+    type Slice = [u8];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[u8], Output = [u8]> + RangeBounds<usize>
+    {
+        let n = range::front_only(range, self.len())?;
+        let rest = &self.to_bytes_with_nul()[n..];
+        *self = CStr::from_bytes_with_nul(rest).expect("the rest ends at the same nul");
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.to_bytes().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn down_to_the_terminator() {
+        let mut s = CStr::from_bytes_with_nul(b"ab\0").unwrap();
+        assert!(s.try_shrink(3..).is_err());
+        s.shrink(2..);
+        assert!(s.is_empty());
+        assert_eq!(s.to_bytes_with_nul(), b"\0");
+    }
+}
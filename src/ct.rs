@@ -0,0 +1,76 @@
+//! Constant-time comparison for consuming secret-dependent prefixes.
+//!
+//! The comparison itself is [`subtle`]'s, which is written to resist the optimizer turning it
+//! back into an early exit, and whether it matched is returned as a [`Choice`] rather than a
+//! `bool`, so that callers decide themselves where it is safe to branch on.
+
+use crate::ShrinkExt;
+use subtle::{Choice, ConstantTimeEq};
+
+/// Whether the front of `slice` equals `expected`, compared in constant time. Lengths are not
+/// treated as secret.
+fn front_eq(slice: &[u8], expected: &[u8]) -> Choice {
+    match slice.get(..expected.len()) {
+        Some(front) => front.ct_eq(expected),
+        None => Choice::from(0),
+    }
+}
+
+/// Extension trait for consuming an expected prefix, such as a MAC or authentication tag,
+/// without leaking through timing how much of it matched.
+pub trait EatCt {
+    /// Compare the front of the slice against `expected` in constant time, and shrink past it if
+    /// it matches.
+    ///
+    /// The time taken by the comparison depends only on the length of `expected`, never on the
+    /// contents of either slice. Lengths are not treated as secret: if the slice is shorter
+    /// than `expected`, no match is returned immediately. Only whether it matched as a whole is
+    /// revealed, by shrinking the slice or not, which is what every caller goes on to branch on.
+    ///
+    /// ```rust
+    /// use shrink_slice::ct::EatCt;
+    ///
+    /// let mut message: &[u8] = b"\x8f\x1c\x03payload";
+    /// assert!(!bool::from(message.eat_ct(b"\x8f\x1c\x04")));
+    /// assert!(bool::from(message.eat_ct(b"\x8f\x1c\x03")));
+    /// assert_eq!(message, b"payload");
+    /// ```
+    fn eat_ct(&mut self, expected: &[u8]) -> Choice;
+}
+
+impl EatCt for &[u8] {
+    fn eat_ct(&mut self, expected: &[u8]) -> Choice {
+        let matched = front_eq(self, expected);
+        if bool::from(matched) {
+            self.shrink(expected.len()..);
+        }
+        matched
+    }
+}
+
+impl EatCt for &mut [u8] {
+    fn eat_ct(&mut self, expected: &[u8]) -> Choice {
+        let matched = front_eq(self, expected);
+        if bool::from(matched) {
+            self.shrink(expected.len()..);
+        }
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatch_leaves_slice() {
+        let mut buffer = *b"tagbody";
+        let mut slice: &mut [u8] = &mut buffer;
+        assert!(!bool::from(slice.eat_ct(b"tab")));
+        assert!(!bool::from(slice.eat_ct(b"tagbodyx")));
+        assert_eq!(slice, b"tagbody");
+        assert!(bool::from(slice.eat_ct(b"")));
+        assert!(bool::from(slice.eat_ct(b"tag")));
+        assert_eq!(slice, b"body");
+    }
+}
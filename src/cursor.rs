@@ -0,0 +1,304 @@
+//! Cursors that keep track of how much of a slice has been consumed.
+
+use crate::{NeedMore, Shrink, ShrinkError, ShrinkExt, Take};
+use core::ops::{Deref, Index, RangeFrom};
+use core::slice::SliceIndex;
+
+/// A cursor that consumes a slice independently from the front and from the back.
+///
+/// Whatever neither end has consumed yet is the middle. Once the middle is empty, the two ends
+/// have met, and any further consumption fails.
+///
+/// ```rust
+/// use shrink_slice::cursor::DoubleEndedCursor;
+///
+/// let mut cursor = DoubleEndedCursor::new(&b"HDR[payload]FTR"[..]);
+/// assert_eq!(cursor.take_front(3), Ok(&b"HDR"[..]));
+/// assert_eq!(cursor.take_back(3), Ok(&b"FTR"[..]));
+/// assert_eq!(cursor.middle(), b"[payload]");
+/// assert_eq!((cursor.front_offset(), cursor.back_offset()), (3, 12));
+///
+/// assert!(cursor.take_back(10).is_err());
+/// cursor.take_front(9).unwrap();
+/// assert!(cursor.has_met());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DoubleEndedCursor<'a, T> {
+    middle: &'a [T],
+    front: usize,
+}
+
+impl<'a, T> DoubleEndedCursor<'a, T> {
+    /// A cursor over all of `slice`.
+    pub fn new(slice: &'a [T]) -> Self {
+        DoubleEndedCursor { middle: slice, front: 0 }
+    }
+
+    /// Consume `n` elements from the front.
+    ///
+    /// If fewer than `n` elements are left in the middle, nothing is consumed and the error
+    /// reports how many more would have been needed.
+    pub fn take_front(&mut self, n: usize) -> Result<&'a [T], NeedMore> {
+        let taken = self.middle.take_exact(n)?;
+        self.front += n;
+        Ok(taken)
+    }
+
+    /// Consume `n` elements from the back.
+    ///
+    /// If fewer than `n` elements are left in the middle, nothing is consumed and the error
+    /// reports how many more would have been needed.
+    pub fn take_back(&mut self, n: usize) -> Result<&'a [T], NeedMore> {
+        let len = self.middle.len();
+        if n > len {
+            return Err(NeedMore(n - len));
+        }
+        let (middle, taken) = self.middle.split_at(len - n);
+        self.middle = middle;
+        Ok(taken)
+    }
+
+    /// The elements that neither end has consumed yet.
+    pub fn middle(&self) -> &'a [T] {
+        self.middle
+    }
+
+    /// Whether the two ends have met, leaving nothing in the middle.
+    pub fn has_met(&self) -> bool {
+        self.middle.is_empty()
+    }
+
+    /// The number of elements consumed from the front, which is also where the middle starts.
+    pub fn front_offset(&self) -> usize {
+        self.front
+    }
+
+    /// Where the middle ends, counted from the front of the original slice.
+    pub fn back_offset(&self) -> usize {
+        self.front + self.middle.len()
+    }
+}
+
+/// A cursor over a string slice that keeps count of both the bytes and the chars consumed.
+///
+/// Each operation only counts the chars it consumes, so keeping both positions up to date costs
+/// nothing beyond the consumption itself.
+///
+/// ```rust
+/// use shrink_slice::cursor::TextCursor;
+///
+/// let mut cursor = TextCursor::new("h\u{e9}llo, w\u{f6}rld");
+/// assert_eq!(cursor.take_while(char::is_alphabetic), "h\u{e9}llo");
+/// assert_eq!((cursor.byte_offset(), cursor.char_offset()), (6, 5));
+/// cursor.advance(2).unwrap();
+/// assert_eq!(cursor.next_char(), Some('w'));
+/// assert_eq!(cursor.next_char(), Some('\u{f6}'));
+/// assert_eq!((cursor.byte_offset(), cursor.char_offset()), (11, 9));
+/// assert_eq!(cursor.rest(), "rld");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TextCursor<'a> {
+    rest: &'a str,
+    bytes: usize,
+    chars: usize,
+}
+
+impl<'a> TextCursor<'a> {
+    /// A cursor at the start of `text`.
+    pub fn new(text: &'a str) -> Self {
+        TextCursor { rest: text, bytes: 0, chars: 0 }
+    }
+
+    /// The text that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// The number of bytes consumed so far.
+    pub fn byte_offset(&self) -> usize {
+        self.bytes
+    }
+
+    /// The number of chars consumed so far.
+    pub fn char_offset(&self) -> usize {
+        self.chars
+    }
+
+    /// The next char, without consuming it.
+    pub fn peek_char(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    /// Consume the next char.
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.consume(c.len_utf8());
+        Some(c)
+    }
+
+    /// Consume `n` bytes and return them.
+    ///
+    /// If `n` is past the end or inside a multi-byte character, nothing is consumed.
+    pub fn advance(&mut self, n: usize) -> Result<&'a str, ShrinkError> {
+        let taken = self.rest.get(..n).ok_or_else(|| ShrinkError::for_str(..n, self.rest))?;
+        self.consume(n);
+        Ok(taken)
+    }
+
+    /// Consume chars for as long as `pred` returns `true`, and return them.
+    pub fn take_while<P: FnMut(char) -> bool>(&mut self, mut pred: P) -> &'a str {
+        let n = self.rest.find(|c| !pred(c)).unwrap_or(self.rest.len());
+        let taken = &self.rest[..n];
+        self.consume(n);
+        taken
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.chars += self.rest[..n].chars().count();
+        self.bytes += n;
+        self.rest.shrink(n..);
+    }
+}
+
+/// A cursor that decodes up to two items ahead without consuming them.
+///
+/// The decode function is handed what is left of the input and returns the next item along with
+/// the number of elements it spans, or `None` if no item can be decoded. Peeked items are cached
+/// until they are consumed with [`bump`](Self::bump), which is the only method that shrinks the
+/// input. Since decoding only ever looks at a shared view, this works for `&mut` slices too.
+///
+/// ```rust
+/// use shrink_slice::cursor::Lookahead;
+///
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// enum Token { Ident, Colon, Space }
+///
+/// fn token(input: &str) -> Option<(Token, usize)> {
+///     let c = input.chars().next()?;
+///     match c {
+///         ':' => Some((Token::Colon, 1)),
+///         ' ' => Some((Token::Space, 1)),
+///         c if c.is_alphabetic() => {
+///             let len = input.find(|c: char| !c.is_alphabetic()).unwrap_or(input.len());
+///             Some((Token::Ident, len))
+///         }
+///         _ => None,
+///     }
+/// }
+///
+/// let mut tokens = Lookahead::new("key: value", token);
+/// assert_eq!(tokens.peek2(), Some(&Token::Colon));
+/// assert_eq!(tokens.bump(), Some(Token::Ident));
+/// assert_eq!(tokens.get(), &": value");
+/// assert_eq!(tokens.peek(), Some(&Token::Colon));
+/// ```
+pub struct Lookahead<S, F, T> {
+    input: S,
+    decode: F,
+    // Cached items, in order, each with the number of elements it spans.
+    first: Option<(T, usize)>,
+    second: Option<(T, usize)>,
+}
+
+impl<S, F, T> Lookahead<S, F, T>
+where S: Shrink<Slice = <S as Deref>::Target> + Deref,
+      S::Target: Index<RangeFrom<usize>, Output = S::Target>,
+      RangeFrom<usize>: SliceIndex<S::Target, Output = S::Target>,
+      F: FnMut(&S::Target) -> Option<(T, usize)>,
+{
+    /// A cursor over `input` that decodes items with `decode`.
+    pub fn new(input: S, decode: F) -> Self {
+        Lookahead { input, decode, first: None, second: None }
+    }
+
+    /// The input that has not been consumed yet, including any peeked items.
+    pub fn get(&self) -> &S {
+        &self.input
+    }
+
+    /// Give up the cursor, discarding any peeked items, and return the unconsumed input.
+    pub fn into_inner(self) -> S {
+        self.input
+    }
+
+    /// The next item, without consuming it.
+    ///
+    /// If it cannot be decoded, `None` is returned and decoding is retried on the next call.
+    pub fn peek(&mut self) -> Option<&T> {
+        if self.first.is_none() {
+            self.first = (self.decode)(&self.input);
+        }
+        self.first.as_ref().map(|(item, _)| item)
+    }
+
+    /// The item after the next one, without consuming either.
+    pub fn peek2(&mut self) -> Option<&T> {
+        self.peek()?;
+        let len = self.first.as_ref().map_or(0, |&(_, len)| len);
+        if self.second.is_none() {
+            self.second = (self.decode)(&self.input[len..]);
+        }
+        self.second.as_ref().map(|(item, _)| item)
+    }
+
+    /// Consume the next item and return it, shrinking the input past it.
+    pub fn bump(&mut self) -> Option<T> {
+        self.peek()?;
+        let (item, len) = self.first.take()?;
+        self.input.try_shrink(len..).expect("decoded item is within the input");
+        self.first = self.second.take();
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_cursor_boundaries() {
+        let mut cursor = TextCursor::new("\u{1f980}x");
+        assert!(matches!(cursor.advance(1), Err(ShrinkError::NotCharBoundary { index: 1, .. })));
+        assert_eq!(cursor.advance(4), Ok("\u{1f980}"));
+        assert_eq!((cursor.byte_offset(), cursor.char_offset()), (4, 1));
+        assert!(matches!(cursor.advance(5), Err(ShrinkError::OutOfBounds { len: 1, .. })));
+        assert_eq!(cursor.take_while(|_| true), "x");
+        assert_eq!(cursor.next_char(), None);
+        assert_eq!(cursor.char_offset(), 2);
+    }
+
+    #[test]
+    fn ends_meet() {
+        let data = [1, 2, 3, 4];
+        let mut cursor = DoubleEndedCursor::new(&data[..]);
+        assert_eq!(cursor.take_back(1), Ok(&[4][..]));
+        assert_eq!(cursor.take_front(4), Err(NeedMore(1)));
+        assert_eq!(cursor.take_front(2), Ok(&[1, 2][..]));
+        assert_eq!(cursor.take_back(1), Ok(&[3][..]));
+        assert!(cursor.has_met());
+        assert_eq!(cursor.front_offset(), cursor.back_offset());
+        assert_eq!(cursor.take_back(0), Ok(&[][..]));
+        assert_eq!(cursor.take_back(1), Err(NeedMore(1)));
+    }
+
+    #[test]
+    fn lookahead_over_mut_slice() {
+        fn pair(input: &[u8]) -> Option<(u16, usize)> {
+            match *input {
+                [a, b, ..] => Some((u16::from_le_bytes([a, b]), 2)),
+                _ => None,
+            }
+        }
+
+        let mut buffer = [1, 0, 2, 0, 3];
+        let mut cursor = Lookahead::new(&mut buffer[..], pair);
+        assert_eq!(cursor.peek2(), Some(&2));
+        assert_eq!(cursor.bump(), Some(1));
+        assert_eq!(cursor.peek(), Some(&2));
+        assert_eq!(cursor.peek2(), None);
+        assert_eq!(cursor.bump(), Some(2));
+        assert_eq!(cursor.bump(), None);
+        cursor.into_inner()[0] = 9;
+        assert_eq!(buffer, [1, 0, 2, 0, 9]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Rendering where a shrunk slice sits within the slice it was shrunk from, for logging.
+//!
+//! ```rust
+//! use shrink_slice::debug::window_debug;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let input = b"GET /index.html HTTP/1.1\r\n";
+//! let mut rest: &[u8] = input;
+//! rest.shrink(4..);
+//! rest.shrink(..11);
+//! assert_eq!(
+//!     window_debug(input, rest).to_string(),
+//!     "[....###########...........] 4..15 (11 of 26)",
+//! );
+//! ```
+
+use crate::range;
+use core::fmt;
+use core::ops::Range;
+
+/// The default number of columns of the bar rendered by [`WindowDebug`].
+const DEFAULT_WIDTH: usize = 32;
+
+/// Describe where `current` lies within `original`, which it must have been shrunk from.
+///
+/// The result displays as a bar of `#` over the part covered by `current` and `.` over the rest,
+/// followed by its range and length and the length of `original`, all in elements. An empty
+/// `current` is marked by a single `|`. Pass the bytes of string slices to see byte offsets.
+///
+/// Slices shorter than the bar get one column per element, longer ones are scaled down, so a
+/// column is `#` if any of its elements are covered. If `current` does not lie within
+/// `original`, that is displayed instead.
+pub fn window_debug<T>(original: &[T], current: &[T]) -> WindowDebug {
+    let window = range::offset_in(original, current).map(|start| start..start + current.len());
+    WindowDebug { window, len: original.len(), width: DEFAULT_WIDTH }
+}
+
+/// The [`Display`](fmt::Display)able description of a shrunk slice returned by
+/// [`window_debug`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WindowDebug {
+    window: Option<Range<usize>>,
+    len: usize,
+    width: usize,
+}
+
+impl WindowDebug {
+    /// Render the bar with at most `width` columns instead of 32. A width of zero leaves out the
+    /// bar.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The range of the original slice that the shrunk slice covers, or `None` if it does not
+    /// lie within it.
+    pub fn window(&self) -> Option<Range<usize>> {
+        self.window.clone()
+    }
+
+    /// The character for the column covering the elements `cells`, where `last` is whether it is
+    /// the rightmost column, which also marks an empty window at the very end.
+    fn column(window: &Range<usize>, cells: Range<usize>, last: bool) -> char {
+        if window.is_empty() {
+            let at = cells.contains(&window.start) || (last && window.start == cells.end);
+            if at { '|' } else { '.' }
+        } else if cells.start < window.end && window.start < cells.end {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl fmt::Display for WindowDebug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let window = match &self.window {
+            Some(window) => window,
+            None => return write!(f, "not within the original {} elements", self.len),
+        };
+        let width = self.width.min(self.len);
+        if self.width != 0 {
+            f.write_str("[")?;
+            for i in 0..width {
+                let cells = i * self.len / width..(i + 1) * self.len / width;
+                write!(f, "{}", WindowDebug::column(window, cells, i + 1 == width))?;
+            }
+            f.write_str("] ")?;
+        }
+        write!(f, "{}..{} ({} of {})", window.start, window.end, window.len(), self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_and_empty() {
+        let data = [0u16; 100];
+        let debug = window_debug(&data, &data[10..60]).width(10);
+        assert_eq!(debug.window(), Some(10..60));
+        assert_eq!(debug.to_string(), "[.#####....] 10..60 (50 of 100)");
+        let at_end = window_debug(&data, &data[100..]).width(4);
+        assert_eq!(at_end.to_string(), "[...|] 100..100 (0 of 100)");
+        assert_eq!(window_debug(&data[..3], &data[..0]).to_string(), "[|..] 0..0 (0 of 3)");
+        assert_eq!(window_debug(&data[..2], &data[1..2]).width(0).to_string(), "1..2 (1 of 2)");
+        let outside = window_debug(&data[..2], &data[3..]);
+        assert_eq!(outside.to_string(), "not within the original 2 elements");
+    }
+}
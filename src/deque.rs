@@ -0,0 +1,200 @@
+//! Shrinking the contents of a ring buffer, which are split in two slices where they wrap around.
+//!
+//! A [`DequeWindow`] views the two slices of a `VecDeque`, or of any other ring buffer, as one
+//! sequence, so that it can be consumed with [`Shrink`] and [`Take`] like a contiguous slice.
+//! Ranges are counted across both slices, and crossing from one to the other is transparent.
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")]
+//! # fn main() {
+//! use std::collections::VecDeque;
+//! use shrink_slice::deque::DequeWindow;
+//! use shrink_slice::{Shrink, Take};
+//!
+//! let mut received = VecDeque::with_capacity(8);
+//! received.extend(b"xxxxxx\x00\x04");
+//! received.drain(..6);
+//! received.extend(b"ping");
+//! let mut input = DequeWindow::new(&received);
+//! let len = u16::from_be_bytes(input.get_array().unwrap());
+//! let payload = input.take_exact(usize::from(len)).unwrap();
+//! assert!(payload.iter().eq(b"ping"));
+//! assert!(input.is_empty());
+//! # }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+
+use crate::{range, NeedMore, Shrink, ShrinkError, ShrinkExt, Take};
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+use core::iter::Chain;
+use core::ops::RangeBounds;
+use core::slice::{self, SliceIndex};
+
+/// A shrinkable view of the contents of a ring buffer, which are split in a front and a back
+/// slice.
+///
+/// The front slice is only empty if the back one is too, so that the first element is always
+/// at the start of the front slice.
+#[derive(Debug)]
+pub struct DequeWindow<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+}
+
+impl<T> Clone for DequeWindow<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DequeWindow<'_, T> { }
+
+impl<'a, T> DequeWindow<'a, T> {
+    /// A window covering all of `deque`.
+    #[cfg(feature = "alloc")]
+    pub fn new(deque: &'a VecDeque<T>) -> Self {
+        let (front, back) = deque.as_slices();
+        DequeWindow::from_slices(front, back)
+    }
+
+    /// A window covering `front` followed by `back`.
+    pub fn from_slices(front: &'a [T], back: &'a [T]) -> Self {
+        if front.is_empty() {
+            DequeWindow { front: back, back: &[] }
+        } else {
+            DequeWindow { front, back }
+        }
+    }
+
+    /// The two slices the window covers, with the original lifetime.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.front, self.back)
+    }
+
+    /// The element at `index`, counting across both slices.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        match index.checked_sub(self.front.len()) {
+            None => self.front.get(index),
+            Some(index) => self.back.get(index),
+        }
+    }
+
+    /// Iterate over the elements of both slices.
+    pub fn iter(&self) -> Chain<slice::Iter<'a, T>, slice::Iter<'a, T>> {
+        self.front.iter().chain(self.back)
+    }
+
+    /// Copy the first `out.len()` elements into `out`, without consuming them.
+    ///
+    /// If fewer than that remain, nothing is copied and the error reports how many more are
+    /// needed.
+    pub fn copy_to_slice(&self, out: &mut [T]) -> Result<(), NeedMore>
+    where T: Copy,
+    {
+        self.ensure_remaining(out.len())?;
+        let split = out.len().min(self.front.len());
+        let (head, tail) = out.split_at_mut(split);
+        head.copy_from_slice(&self.front[..split]);
+        tail.copy_from_slice(&self.back[..tail.len()]);
+        Ok(())
+    }
+
+    /// Split off the first `N` elements as an array, crossing into the back slice if needed.
+    ///
+    /// If fewer than `N` remain, the window is left untouched and the error reports how many
+    /// more are needed.
+    pub fn get_array<const N: usize>(&mut self) -> Result<[T; N], NeedMore>
+    where T: Copy,
+    {
+        self.ensure_remaining(N)?;
+        let mut elements = self.iter();
+        let array = core::array::from_fn(|_| *elements.next().expect("N elements remain"));
+        self.shrink_front(N);
+        Ok(array)
+    }
+}
+
+impl<T> Shrink for DequeWindow<'_, T> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        let split = self.front.len();
+        let front = &self.front[range.start.min(split)..range.end.min(split)];
+        let back = &self.back[range.start.saturating_sub(split)..range.end.saturating_sub(split)];
+        *self = DequeWindow::from_slices(front, back);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<T> Take for DequeWindow<'_, T> {
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        self.ensure_remaining(n)?;
+        let mut head = *self;
+        head.shrink(..n);
+        self.shrink(n..);
+        Ok(head)
+    }
+
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        self.ensure_remaining(n)?;
+        let mut tail = *self;
+        let at = self.len() - n;
+        tail.shrink(at..);
+        self.shrink(..at);
+        Ok(tail)
+    }
+}
+
+impl<'a, T> IntoIterator for DequeWindow<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crosses_the_wrap_around() {
+        let window = DequeWindow::from_slices(&[1, 2, 3], &[4, 5]);
+        let mut middle = window;
+        middle.shrink(2..4);
+        assert_eq!(middle.as_slices(), (&[3][..], &[4][..]));
+        assert_eq!((middle.get(1), middle.get(2)), (Some(&4), None));
+        middle.shrink_front(1);
+        assert_eq!(middle.as_slices(), (&[4][..], &[][..]));
+
+        let mut rest = window;
+        let tail = rest.try_take_back(3).unwrap();
+        assert!(tail.iter().eq(&[3, 4, 5]));
+        assert_eq!(rest.take_exact(3).unwrap_err(), NeedMore(1));
+        assert_eq!(rest.get_array(), Ok([1, 2]));
+        assert_eq!(rest.get_array::<1>(), Err(NeedMore(1)));
+        assert_eq!(DequeWindow::from_slices(&[], &[7]).as_slices(), (&[7][..], &[][..]));
+    }
+
+    #[test]
+    fn arrays_of_non_default_elements() {
+        use core::num::NonZeroU8;
+
+        let digits: Vec<NonZeroU8> = (1..=5).filter_map(NonZeroU8::new).collect();
+        let mut window = DequeWindow::from_slices(&digits[..2], &digits[2..]);
+        let head: [NonZeroU8; 3] = window.get_array().unwrap();
+        assert_eq!(head.map(NonZeroU8::get), [1, 2, 3]);
+        assert_eq!(window.get_array::<3>(), Err(NeedMore(1)));
+        assert_eq!(window.len(), 2);
+    }
+}
@@ -0,0 +1,174 @@
+//! The `embedded-io` traits, for firmware that reads from and writes to borrowed buffers.
+//!
+//! [`SliceReader`] and [`SliceWriterRef`] borrow a slice variable and shrink it as they consume
+//! it, so the variable itself is left pointing at what has not been read or written yet, and can
+//! be used with the rest of this crate afterwards. [`SliceWriter`](crate::write::SliceWriter)
+//! implements [`Write`] too.
+//!
+//! ```rust
+//! use embedded_io::{Read, Write};
+//! use shrink_slice::embedded::{SliceReader, SliceWriterRef};
+//!
+//! let mut rx: &[u8] = b"PING\r\nrest";
+//! let mut command = [0; 6];
+//! SliceReader::new(&mut rx).read_exact(&mut command).unwrap();
+//! assert_eq!((&command, rx), (b"PING\r\n", &b"rest"[..]));
+//!
+//! let mut storage = [0; 8];
+//! let mut tx: &mut [u8] = &mut storage;
+//! SliceWriterRef::new(&mut tx).write_all(b"PONG").unwrap();
+//! assert_eq!(tx.len(), 4);
+//! assert_eq!(&storage[..4], b"PONG");
+//! ```
+
+use crate::write::{PutBytes, SliceWriter};
+use crate::Take;
+use core::convert::Infallible;
+use embedded_io::{BufRead, ErrorType, Read, ReadReady, SliceWriteError, Write, WriteReady};
+
+/// A reader that consumes a borrowed byte slice from the front.
+///
+/// Reading never fails. Once the slice is empty, reads return zero bytes.
+#[derive(Debug)]
+pub struct SliceReader<'r, 'a> {
+    input: &'r mut &'a [u8],
+}
+
+impl<'r, 'a> SliceReader<'r, 'a> {
+    /// Read from the front of `input`, shrinking it past every byte read.
+    pub fn new(input: &'r mut &'a [u8]) -> Self {
+        SliceReader { input }
+    }
+
+    /// The bytes that have not been read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl ErrorType for SliceReader<'_, '_> {
+    type Error = Infallible;
+}
+
+impl Read for SliceReader<'_, '_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+        let n = buf.len().min(self.input.len());
+        let head = self.input.take_exact(n).expect("the bytes are in the slice");
+        buf[..n].copy_from_slice(head);
+        Ok(n)
+    }
+}
+
+impl BufRead for SliceReader<'_, '_> {
+    fn fill_buf(&mut self) -> Result<&[u8], Infallible> {
+        Ok(self.input)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let n = amt.min(self.input.len());
+        let _ = self.input.take_exact(n);
+    }
+}
+
+impl ReadReady for SliceReader<'_, '_> {
+    fn read_ready(&mut self) -> Result<bool, Infallible> {
+        Ok(true)
+    }
+}
+
+/// A writer that fills a borrowed mutable byte slice from the front.
+///
+/// Writes that do not fit are cut short, and once the slice is full, writing anything fails
+/// with [`SliceWriteError::Full`], as the traits require.
+#[derive(Debug)]
+pub struct SliceWriterRef<'w, 'a> {
+    output: &'w mut &'a mut [u8],
+}
+
+impl<'w, 'a> SliceWriterRef<'w, 'a> {
+    /// Write to the front of `output`, shrinking it past every byte written.
+    pub fn new(output: &'w mut &'a mut [u8]) -> Self {
+        SliceWriterRef { output }
+    }
+
+    /// The number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.output.len()
+    }
+}
+
+/// Write as much of `buf` to `out` as fits, which must be something unless `buf` is empty.
+fn write_some<W: PutBytes>(out: &mut W, room: usize, buf: &[u8]) -> Result<usize, SliceWriteError> {
+    let n = buf.len().min(room);
+    if n == 0 && !buf.is_empty() {
+        return Err(SliceWriteError::Full);
+    }
+    out.put_slice(&buf[..n]).expect("the bytes fit in the buffer");
+    Ok(n)
+}
+
+impl ErrorType for SliceWriterRef<'_, '_> {
+    type Error = SliceWriteError;
+}
+
+impl Write for SliceWriterRef<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, SliceWriteError> {
+        let room = self.output.len();
+        write_some(self.output, room, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), SliceWriteError> {
+        Ok(())
+    }
+}
+
+impl WriteReady for SliceWriterRef<'_, '_> {
+    fn write_ready(&mut self) -> Result<bool, SliceWriteError> {
+        Ok(true)
+    }
+}
+
+impl ErrorType for SliceWriter<'_> {
+    type Error = SliceWriteError;
+}
+
+impl Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, SliceWriteError> {
+        let room = self.remaining();
+        write_some(self, room, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), SliceWriteError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_and_full() {
+        let mut input: &[u8] = b"abc";
+        let mut reader = SliceReader::new(&mut input);
+        assert_eq!(reader.fill_buf(), Ok(&b"abc"[..]));
+        reader.consume(1);
+        let mut buf = [0; 4];
+        assert_eq!(reader.read(&mut buf), Ok(2));
+        assert_eq!(reader.read(&mut buf), Ok(0));
+        assert!(input.is_empty());
+
+        let mut storage = [0; 3];
+        let mut output: &mut [u8] = &mut storage;
+        let mut writer = SliceWriterRef::new(&mut output);
+        assert_eq!(writer.write(b"hello"), Ok(3));
+        assert_eq!(writer.write(b""), Ok(0));
+        assert_eq!(writer.write(b"!"), Err(SliceWriteError::Full));
+        assert_eq!(&storage, b"hel");
+
+        let mut storage = [0; 2];
+        let mut writer = SliceWriter::new(&mut storage);
+        assert_eq!(Write::write(&mut writer, b"xyz"), Ok(2));
+        assert_eq!(writer.written(), 2);
+    }
+}
@@ -0,0 +1,135 @@
+//! Streaming encoders that write into a fixed output buffer.
+//!
+//! Each encoder consumes from the front of its input and writes to the front of its output,
+//! shrinking both past what was processed. Encoding stops cleanly when either side runs out,
+//! so a full output buffer can be flushed and the call repeated.
+//!
+//! ```rust
+//! use shrink_slice::encode::encode_hex;
+//!
+//! let mut input: &[u8] = b"\xde\xad\xbe\xef";
+//! let mut buffer = [0; 5];
+//! let mut output: &mut [u8] = &mut buffer;
+//!
+//! let progress = encode_hex(&mut input, &mut output);
+//! assert_eq!((progress.consumed, progress.produced), (2, 4));
+//! assert_eq!(input, b"\xbe\xef");
+//! assert_eq!(output.len(), 1);
+//! assert_eq!(&buffer[..4], b"dead");
+//! ```
+
+use crate::{Shrink, ShrinkExt};
+
+/// How much an encoder consumed and produced in one call.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Progress {
+    /// The number of input bytes consumed.
+    pub consumed: usize,
+    /// The number of output bytes written.
+    pub produced: usize,
+}
+
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+fn hex(input: &mut &[u8], output: &mut &mut [u8], digits: &[u8; 16]) -> Progress {
+    let n = input.len().min(output.len() / 2);
+    for (&byte, pair) in input[..n].iter().zip(output.chunks_exact_mut(2)) {
+        pair[0] = digits[(byte >> 4) as usize];
+        pair[1] = digits[(byte & 0xf) as usize];
+    }
+    input.shrink(n..);
+    output.shrink(2 * n..);
+    Progress { consumed: n, produced: 2 * n }
+}
+
+/// Encode bytes as lowercase hexadecimal, two output bytes per input byte.
+pub fn encode_hex(input: &mut &[u8], output: &mut &mut [u8]) -> Progress {
+    hex(input, output, HEX_LOWER)
+}
+
+/// Encode bytes as uppercase hexadecimal, two output bytes per input byte.
+pub fn encode_hex_upper(input: &mut &[u8], output: &mut &mut [u8]) -> Progress {
+    hex(input, output, HEX_UPPER)
+}
+
+/// Encode bytes as padded base32, using the RFC 4648 alphabet.
+///
+/// Input is consumed in groups of five bytes, each of which becomes eight output bytes. A group
+/// is only encoded if there is room for all of it. If `last` is `true`, a final group of fewer
+/// than five bytes is encoded too, padded with `=`; otherwise it is left in `input` until more
+/// arrives.
+///
+/// ```rust
+/// use shrink_slice::encode::encode_base32;
+///
+/// let mut input: &[u8] = b"foobar";
+/// let mut buffer = [0; 16];
+/// let mut output: &mut [u8] = &mut buffer;
+/// encode_base32(&mut input, &mut output, false);
+/// assert_eq!(input, b"r");
+/// encode_base32(&mut input, &mut output, true);
+/// assert_eq!(&buffer, b"MZXW6YTBOI======");
+/// ```
+pub fn encode_base32(input: &mut &[u8], output: &mut &mut [u8], last: bool) -> Progress {
+    let mut progress = Progress::default();
+    while output.len() >= 8 && (input.len() >= 5 || last && !input.is_empty()) {
+        let n = input.len().min(5);
+        let mut group = [0; 5];
+        group[..n].copy_from_slice(&input[..n]);
+        let bits = group.iter().fold(0u64, |acc, &b| acc << 8 | b as u64);
+
+        // Each output character holds five bits, so a group of n bytes fills ceil(8n / 5).
+        let chars = (8 * n).div_ceil(5);
+        for (i, out) in output[..8].iter_mut().enumerate() {
+            *out = if i < chars { BASE32[(bits >> (35 - 5 * i) & 0x1f) as usize] } else { b'=' };
+        }
+        input.shrink(n..);
+        output.shrink(8..);
+        progress.consumed += n;
+        progress.produced += 8;
+    }
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base32(data: &[u8]) -> Vec<u8> {
+        let mut input = data;
+        let mut buffer = vec![0; 64];
+        let mut output: &mut [u8] = &mut buffer;
+        let progress = encode_base32(&mut input, &mut output, true);
+        assert!(input.is_empty());
+        buffer.truncate(progress.produced);
+        buffer
+    }
+
+    #[test]
+    fn rfc4648_vectors() {
+        assert_eq!(base32(b""), b"");
+        assert_eq!(base32(b"f"), b"MY======");
+        assert_eq!(base32(b"fo"), b"MZXQ====");
+        assert_eq!(base32(b"foo"), b"MZXW6===");
+        assert_eq!(base32(b"foob"), b"MZXW6YQ=");
+        assert_eq!(base32(b"fooba"), b"MZXW6YTB");
+        assert_eq!(base32(b"foobar"), b"MZXW6YTBOI======");
+    }
+
+    #[test]
+    fn output_runs_out() {
+        let mut input: &[u8] = b"\x01\xab";
+        let mut buffer = [0; 3];
+        let mut output: &mut [u8] = &mut buffer;
+        let progress = encode_hex_upper(&mut input, &mut output);
+        assert_eq!(progress, Progress { consumed: 1, produced: 2 });
+        assert_eq!(encode_hex_upper(&mut input, &mut output), Progress::default());
+        assert_eq!(input, b"\xab");
+
+        let mut output: &mut [u8] = &mut [0; 7];
+        assert_eq!(encode_base32(&mut input, &mut output, true), Progress::default());
+        assert_eq!(&buffer[..2], b"01");
+    }
+}
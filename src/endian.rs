@@ -0,0 +1,132 @@
+//! Byte order.
+//!
+//! The byte order can be chosen at runtime with an [`Endian`] value, or made part of a type with
+//! the marker types [`LittleEndian`], [`BigEndian`] and [`NativeEndian`], which implement
+//! [`ByteOrder`]. The readers and writers take either, through methods that end in `_endian` and
+//! `_ordered` respectively.
+//!
+//! ```rust
+//! use shrink_slice::endian::{BigEndian, ByteOrder, LittleEndian};
+//! use shrink_slice::read::GetBytes;
+//! use shrink_slice::NeedMore;
+//!
+//! /// A decoder for a format that comes in both byte orders.
+//! fn entry<E: ByteOrder>(input: &mut &[u8]) -> Result<(u16, u32), NeedMore> {
+//!     Ok((input.get_ordered::<u16, E>()?, input.get_ordered::<u32, E>()?))
+//! }
+//!
+//! let mut input: &[u8] = b"\x00\x01\x00\x00\x00\x02\x01\x00\x02\x00\x00\x00";
+//! assert_eq!(entry::<BigEndian>(&mut input), Ok((1, 2)));
+//! assert_eq!(entry::<LittleEndian>(&mut input), Ok((1, 2)));
+//! ```
+
+/// The order in which the bytes of a multi-byte value are laid out.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Endian {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first, also known as network byte order.
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endian = Endian::Little;
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endian = Endian::Big;
+}
+
+/// A byte order chosen at compile time, as a type parameter.
+pub trait ByteOrder {
+    /// The byte order as a value.
+    const ENDIAN: Endian;
+}
+
+/// Least significant byte first, as a type. It has no values.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LittleEndian { }
+
+impl ByteOrder for LittleEndian {
+    const ENDIAN: Endian = Endian::Little;
+}
+
+/// Most significant byte first, as a type. It has no values.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BigEndian { }
+
+impl ByteOrder for BigEndian {
+    const ENDIAN: Endian = Endian::Big;
+}
+
+/// The byte order of the target platform, as a type.
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+/// The byte order of the target platform, as a type.
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::{GetBytes, ShrinkRead};
+    use crate::write::ShrinkWrite;
+    use crate::NeedMore;
+
+    /// Write a `u32` and an `i16` in the order `E`, check the layout and read them back, once with
+    /// the type parameter and once with its value.
+    fn round_trip<E: ByteOrder>(expected: [u8; 6]) {
+        let mut buffer = [0; 6];
+        let mut out: &mut [u8] = &mut buffer;
+        0x0102_0304u32.write_ordered::<E>(&mut out).unwrap();
+        (-2i16).write_to_endian(&mut out, E::ENDIAN).unwrap();
+        assert!(out.is_empty());
+        assert_eq!(buffer, expected);
+
+        let mut input: &[u8] = &buffer;
+        assert_eq!(input.get_ordered::<u32, E>(), Ok(0x0102_0304));
+        assert_eq!(input.get_endian::<i16>(E::ENDIAN), Ok(-2));
+        assert!(input.is_empty());
+
+        let mut input: &[u8] = &buffer;
+        assert_eq!(input.get_endian::<u32>(E::ENDIAN), Ok(0x0102_0304));
+        assert_eq!(i16::read_ordered::<E>(&mut input), Ok(-2));
+        assert!(input.is_empty());
+    }
+
+    /// Reading or writing past the end must fail with `NeedMore` and consume nothing.
+    fn truncated<E: ByteOrder>() {
+        let mut input: &[u8] = b"\x01\x02\x03";
+        assert_eq!(input.get_ordered::<u32, E>(), Err(NeedMore(1)));
+        assert_eq!(input.get_endian::<u64>(E::ENDIAN), Err(NeedMore(5)));
+        assert_eq!(input, b"\x01\x02\x03");
+
+        let mut buffer = [0; 3];
+        let mut out: &mut [u8] = &mut buffer;
+        assert_eq!(7u32.write_ordered::<E>(&mut out), Err(NeedMore(1)));
+        assert_eq!(out.len(), 3);
+    }
+
+    #[test]
+    fn little_endian() {
+        round_trip::<LittleEndian>([4, 3, 2, 1, 0xfe, 0xff]);
+        truncated::<LittleEndian>();
+    }
+
+    #[test]
+    fn big_endian() {
+        round_trip::<BigEndian>([1, 2, 3, 4, 0xff, 0xfe]);
+        truncated::<BigEndian>();
+    }
+
+    #[test]
+    fn native_endian() {
+        let mut expected = [0; 6];
+        expected[..4].copy_from_slice(&0x0102_0304u32.to_ne_bytes());
+        expected[4..].copy_from_slice(&(-2i16).to_ne_bytes());
+        round_trip::<NativeEndian>(expected);
+        truncated::<NativeEndian>();
+        assert_eq!(NativeEndian::ENDIAN, Endian::NATIVE);
+    }
+}
@@ -0,0 +1,550 @@
+//! The only place in the crate where `unsafe` is allowed.
+//!
+//! Everything here is compiled only with the `unsafe-fast` feature. Each function wraps a single
+//! unsafe operation behind a safe signature, checking its preconditions itself, so that the
+//! modules using them stay free of `unsafe` blocks. The exceptions are [`ShrinkUnchecked`],
+//! [`RawSlice`] and, with the `wasm` feature, `parse_view`, whose whole point is to let callers
+//! vouch for the preconditions instead, and [`FillBuf`], whose initialized prefix is an
+//! invariant of the whole type rather than of a single call.
+#![allow(unsafe_code)]
+
+use crate::{range, NeedMore, Shrink, ShrinkError, ShrinkExt};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Range, RangeBounds};
+use core::ptr::NonNull;
+use core::slice::{self, SliceIndex};
+use core::str;
+#[cfg(feature = "borrowed-buf")]
+use std::io;
+
+/// Reinterpret `bytes` as room for exactly `count` values of type `T`.
+///
+/// Panics if `bytes` is not aligned for `T`, or is not exactly `count` values long.
+pub(crate) fn cast_uninit<T>(bytes: &mut [MaybeUninit<u8>], count: usize)
+    -> &mut [MaybeUninit<T>]
+{
+    let size = mem::size_of::<T>().checked_mul(count).expect("size overflows usize");
+    assert_eq!(bytes.len(), size, "byte length must match the element count");
+    assert_eq!(bytes.as_ptr().align_offset(mem::align_of::<T>()), 0, "bytes must be aligned");
+    // SAFETY: the bytes are suitably aligned and exactly `count` elements long, as checked
+    // above. Any bytes are a valid `MaybeUninit<T>`, and the exclusive borrow of `bytes` is
+    // handed over to the returned slice.
+    unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), count) }
+}
+
+/// Shrink `slice` to `range` by building the new reference from the old one's pointer, rather
+/// than moving the old one out and leaving an empty slice behind in the meantime.
+///
+/// Panics if `range` is decreasing or out of bounds.
+pub(crate) fn narrow_mut<T>(slice: &mut &mut [T], range: Range<usize>) {
+    assert!(range.start <= range.end && range.end <= slice.len(), "range must be within the slice");
+    let start = slice.as_mut_ptr().wrapping_add(range.start);
+    // SAFETY: the range is within the slice, as checked above, so the new slice lies within the
+    // old one. The old reference is overwritten, so its exclusive borrow passes to the new one.
+    *slice = unsafe { slice::from_raw_parts_mut(start, range.end - range.start) };
+}
+
+/// Shrink `s` to `range` like [`narrow_mut`].
+///
+/// Panics if `range` is decreasing, out of bounds, or has an end within a multi-byte character.
+pub(crate) fn narrow_str_mut(s: &mut &mut str, range: Range<usize>) {
+    assert!(s.get(range.clone()).is_some(), "range must be within the string on char boundaries");
+    let start = s.as_mut_ptr().wrapping_add(range.start);
+    // SAFETY: the range is within the string and on char boundaries, as checked above, so the
+    // bytes it covers are valid UTF-8 within the old string, whose exclusive borrow passes to
+    // the new one when it is overwritten.
+    *s = unsafe {
+        str::from_utf8_unchecked_mut(slice::from_raw_parts_mut(start, range.end - range.start))
+    };
+}
+
+/// Hand `buf` to `fill` as a [`BorrowedCursor`](io::BorrowedCursor), then split off whatever
+/// was filled, shrinking `buf` past it.
+///
+/// If `fill` fails, its error is returned and `buf` is left as it was.
+#[cfg(feature = "borrowed-buf")]
+pub(crate) fn fill_front<'a, F>(buf: &mut &'a mut [MaybeUninit<u8>], fill: F)
+    -> io::Result<&'a mut [u8]>
+where F: FnOnce(io::BorrowedCursor<'_>) -> io::Result<()>,
+{
+    let whole = mem::take(buf);
+    let mut borrowed = io::BorrowedBuf::from(&mut *whole);
+    let result = fill(borrowed.unfilled());
+    let filled = borrowed.len();
+    if let Err(e) = result {
+        *buf = whole;
+        return Err(e);
+    }
+    let (head, tail) = whole.split_at_mut(filled);
+    *buf = tail;
+    // SAFETY: `BorrowedBuf` guarantees that its filled prefix has been initialized, and
+    // `BorrowedCursor` cannot be used to de-initialize it.
+    Ok(unsafe { slice::from_raw_parts_mut(head.as_mut_ptr().cast(), head.len()) })
+}
+
+/// Let `parse` consume from the front of `bytes`, and return what it returned, the number of
+/// bytes it consumed and a [`Uint8Array`](js_sys::Uint8Array) that views the bytes it left in
+/// WebAssembly memory, rather than a copy of them.
+///
+/// Only available with both the `wasm` and `unsafe-fast` features.
+///
+/// # Safety
+///
+/// The view is not tied to the lifetime of `bytes`, so JavaScript must stop using it before
+/// `bytes` is dropped or modified. Growing the WebAssembly memory, which any allocation can do,
+/// also leaves the view pointing at a detached buffer, so it has to be used up, or copied, before
+/// Rust allocates again.
+#[cfg(feature = "wasm")]
+pub unsafe fn parse_view<F, T>(bytes: &[u8], parse: F) -> (T, u32, js_sys::Uint8Array)
+where F: FnOnce(&mut &[u8]) -> T,
+{
+    let (result, consumed, rest) = crate::wasm::parse_rest(bytes, parse);
+    // SAFETY: the caller promises not to use the view past the lifetime of `bytes`, nor after
+    // the memory has grown or been written to.
+    (result, consumed, unsafe { js_sys::Uint8Array::view(rest) })
+}
+
+/// Extension trait for shrinking without bounds checks, for hot loops whose ranges have already
+/// been validated.
+///
+/// Only available with the `unsafe-fast` feature.
+///
+/// ```rust
+/// use shrink_slice::ShrinkUnchecked;
+///
+/// let mut input: &[u8] = b"\x03abcdef";
+/// let len = input[0] as usize;
+/// if input.len() > len {
+///     // SAFETY: the range was just checked to be within bounds.
+///     unsafe { input.shrink_unchecked(1..=len) };
+/// }
+/// assert_eq!(input, b"abc");
+/// ```
+pub trait ShrinkUnchecked: Shrink {
+    /// Shrink the slice to `range` like [`ShrinkExt::shrink`], without checking the range.
+    ///
+    /// In debug builds, the range is checked anyway, and an invalid one panics.
+    ///
+    /// # Safety
+    ///
+    /// The range must be within the bounds of the slice and not decreasing. For string slices,
+    /// both of its ends must also be on char boundaries. Anything else is undefined behavior.
+    unsafe fn shrink_unchecked<R>(&mut self, range: R)
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>;
+}
+
+impl<T> ShrinkUnchecked for &[T] {
+    unsafe fn shrink_unchecked<R>(&mut self, range: R)
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>,
+    {
+        debug_assert!(range::resolve(range::bounds(&range), self.len()).is_some());
+        // SAFETY: the caller promises that the range is within bounds.
+        *self = unsafe { self.get_unchecked(range) };
+    }
+}
+
+impl<T> ShrinkUnchecked for &mut [T] {
+    unsafe fn shrink_unchecked<R>(&mut self, range: R)
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>,
+    {
+        debug_assert!(range::resolve(range::bounds(&range), self.len()).is_some());
+        // SAFETY: the caller promises that the range is within bounds.
+        *self = unsafe { mem::take(self).get_unchecked_mut(range) };
+    }
+}
+
+/// Whether `range` could index `s`.
+fn str_range_ok<R: RangeBounds<usize>>(s: &str, range: &R) -> bool {
+    matches!(range::resolve(range::bounds(range), s.len()),
+             Some(r) if s.is_char_boundary(r.start) && s.is_char_boundary(r.end))
+}
+
+impl ShrinkUnchecked for &str {
+    unsafe fn shrink_unchecked<R>(&mut self, range: R)
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>,
+    {
+        debug_assert!(str_range_ok(self, &range));
+        // SAFETY: the caller promises that the range is within bounds and on char boundaries.
+        *self = unsafe { self.get_unchecked(range) };
+    }
+}
+
+impl ShrinkUnchecked for &mut str {
+    unsafe fn shrink_unchecked<R>(&mut self, range: R)
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>,
+    {
+        debug_assert!(str_range_ok(self, &range));
+        // SAFETY: the caller promises that the range is within bounds and on char boundaries.
+        *self = unsafe { mem::take(self).get_unchecked_mut(range) };
+    }
+}
+
+/// A raw pointer to a slice, which can be shrunk with the same bounds checks as a reference to
+/// one, but without ever creating a reference or reading an element.
+///
+/// This is for allocator and arena code that carves pieces out of a larger block before it is
+/// initialized. The pointer is only promised to stay within the block it was created from, so
+/// what it points to may be uninitialized, aliased, or already freed by the time it is used.
+///
+/// ```rust
+/// use core::ptr::NonNull;
+/// use shrink_slice::{RawSlice, Shrink, ShrinkExt};
+///
+/// let mut block = [0u8; 16];
+/// let ptr = NonNull::from(&mut block[..]);
+/// // SAFETY: the pointer covers `block`, which is a single allocation.
+/// let mut header = unsafe { RawSlice::new(ptr) };
+/// let mut body = header;
+/// header.shrink(..4);
+/// body.shrink_front(4);
+/// assert!(body.try_shrink(..13).is_err());
+/// assert_eq!((header.len(), body.len()), (4, 12));
+/// // SAFETY: `body` lies within `block`, which is still borrowed mutably through `ptr`.
+/// unsafe { body.as_non_null().as_mut().fill(1) };
+/// assert_eq!(block, [0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+/// ```
+#[derive(Debug)]
+pub struct RawSlice<T> {
+    ptr: NonNull<[T]>,
+}
+
+impl<T> RawSlice<T> {
+    /// Wrap `ptr` so that it can be shrunk.
+    ///
+    /// # Safety
+    ///
+    /// The `ptr.len()` elements that `ptr` points to must lie within a single allocated object,
+    /// so that they are at most `isize::MAX` bytes long. Nothing else is required of them: they
+    /// need not be initialized, nor still allocated, as long as the pointer is not dereferenced.
+    /// Shrinking only computes addresses, with wrapping arithmetic, so it is sound on freed
+    /// memory too.
+    ///
+    /// A dangling pointer with a length of zero, or with any length if `T` is zero-sized, is
+    /// also fine.
+    pub unsafe fn new(ptr: NonNull<[T]>) -> Self {
+        RawSlice { ptr }
+    }
+
+    /// Wrap `ptr`, or return `None` if it is null.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`new`](RawSlice::new).
+    pub unsafe fn from_raw(ptr: *mut [T]) -> Option<Self> {
+        NonNull::new(ptr).map(|ptr| RawSlice { ptr })
+    }
+
+    /// The pointer to what is left of the slice.
+    pub fn as_non_null(self) -> NonNull<[T]> {
+        self.ptr
+    }
+
+    /// The pointer to what is left of the slice, as a raw pointer.
+    pub fn as_ptr(self) -> *mut [T] {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T> Clone for RawSlice<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RawSlice<T> { }
+
+impl<T> Shrink for RawSlice<T> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        // The block may have been freed, so the start is found with wrapping arithmetic, which
+        // unlike `add` does not require the pointer to be in bounds of a live allocation.
+        let start = self.ptr.cast::<T>().as_ptr().wrapping_add(range.start);
+        // SAFETY: the range is within the slice, whose addresses lay within a single allocated
+        // object, as promised to `new`, so the offset neither wraps around nor reaches null.
+        let start = unsafe { NonNull::new_unchecked(start) };
+        self.ptr = NonNull::slice_from_raw_parts(start, range.end - range.start);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.ptr.len()
+    }
+}
+
+/// A buffer of possibly uninitialized bytes that is filled from the front, tracking how much of
+/// it is filled and how much is initialized.
+///
+/// Like the standard library's unstable `BorrowedBuf`, it is split into three regions: the
+/// filled bytes, then bytes that are initialized but not yet filled, then uninitialized ones.
+/// Appending data grows the filled region and shrinks the unfilled one after it. Only the
+/// filled bytes can be read, and the initialized ones are never handed out in a way that could
+/// de-initialize them, so all of it is safe to use.
+///
+/// Only available with the `unsafe-fast` feature.
+///
+/// ```rust
+/// use core::mem::MaybeUninit;
+/// use shrink_slice::FillBuf;
+///
+/// let mut storage = [MaybeUninit::uninit(); 8];
+/// let mut buf = FillBuf::new(&mut storage);
+/// let mut src: &[u8] = b"hello, world";
+/// assert_eq!(buf.append(&mut src), 8);
+/// assert_eq!((buf.filled(), src), (&b"hello, w"[..], &b"orld"[..]));
+///
+/// buf.clear();
+/// let unfilled = buf.unfilled_init();
+/// unfilled[..2].copy_from_slice(b"ok");
+/// buf.advance(2).unwrap();
+/// assert_eq!((buf.filled(), buf.init_len()), (&b"ok"[..], 8));
+/// ```
+#[derive(Debug)]
+pub struct FillBuf<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+    init: usize,
+}
+
+impl<'a> FillBuf<'a> {
+    /// Start filling `buf` from the front, treating all of it as uninitialized.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        FillBuf { buf, filled: 0, init: 0 }
+    }
+
+    /// Start filling `buf` from the front, knowing that all of it is initialized.
+    pub fn from_init(buf: &'a mut [u8]) -> Self {
+        let init = buf.len();
+        // SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, and the buffer is marked as
+        // initialized, so it is never de-initialized through the returned slice.
+        let buf = unsafe { slice::from_raw_parts_mut(buf.as_mut_ptr().cast(), init) };
+        FillBuf { buf, filled: 0, init }
+    }
+
+    /// The size of the whole buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The number of filled bytes.
+    pub fn len(&self) -> usize {
+        self.filled
+    }
+
+    /// Whether no bytes are filled.
+    pub fn is_empty(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// The number of initialized bytes, filled or not.
+    pub fn init_len(&self) -> usize {
+        self.init
+    }
+
+    /// The number of bytes that can still be filled.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.filled
+    }
+
+    /// The filled bytes.
+    pub fn filled(&self) -> &[u8] {
+        // SAFETY: the filled bytes are within the initialized prefix.
+        unsafe { slice::from_raw_parts(self.buf.as_ptr().cast(), self.filled) }
+    }
+
+    /// The filled bytes, mutably.
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the filled bytes are within the initialized prefix.
+        unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr().cast(), self.filled) }
+    }
+
+    /// Copy as much of `src` into the unfilled bytes as fits, shrinking `src` past it. Returns
+    /// the number of bytes copied.
+    pub fn append(&mut self, src: &mut &[u8]) -> usize {
+        let n = self.remaining().min(src.len());
+        let dst = &mut self.buf[self.filled..self.filled + n];
+        for (d, &s) in dst.iter_mut().zip(&src[..n]) {
+            d.write(s);
+        }
+        src.shrink(n..);
+        self.filled += n;
+        self.init = self.init.max(self.filled);
+        n
+    }
+
+    /// Initialize all of the unfilled bytes, zeroing those that were not initialized yet, and
+    /// return them, for handing to APIs like `Read::read`. Fill them with
+    /// [`advance`](FillBuf::advance).
+    ///
+    /// Bytes are only ever zeroed once, however often this is called.
+    pub fn unfilled_init(&mut self) -> &mut [u8] {
+        for b in &mut self.buf[self.init..] {
+            b.write(0);
+        }
+        self.init = self.buf.len();
+        let unfilled = &mut self.buf[self.filled..];
+        // SAFETY: every byte of the buffer was just initialized.
+        unsafe { slice::from_raw_parts_mut(unfilled.as_mut_ptr().cast(), unfilled.len()) }
+    }
+
+    /// Mark the next `n` initialized bytes as filled, as after writing them through
+    /// [`unfilled_init`](FillBuf::unfilled_init).
+    ///
+    /// If fewer than `n` of the unfilled bytes are initialized, nothing is marked and the error
+    /// reports how many more would have been needed.
+    pub fn advance(&mut self, n: usize) -> Result<(), NeedMore> {
+        let available = self.init - self.filled;
+        if n > available {
+            return Err(NeedMore(n - available));
+        }
+        self.filled += n;
+        Ok(())
+    }
+
+    /// Forget the filled bytes, keeping them initialized.
+    pub fn clear(&mut self) {
+        self.filled = 0;
+    }
+
+    /// Give up the buffer, returning the filled bytes with its original lifetime.
+    pub fn into_filled(self) -> &'a mut [u8] {
+        // SAFETY: the filled bytes are within the initialized prefix.
+        unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr().cast(), self.filled) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(align(8))]
+    struct Aligned([MaybeUninit<u8>; 16]);
+
+    #[test]
+    fn cast_uninit_checks() {
+        let mut storage = Aligned([MaybeUninit::uninit(); 16]);
+        let halves = cast_uninit::<u32>(&mut storage.0[..8], 2);
+        halves[1].write(7);
+        assert_eq!(halves.len(), 2);
+        let words = cast_uninit::<u64>(&mut storage.0, 2);
+        assert_eq!(words.len(), 2);
+
+        let units = cast_uninit::<()>(&mut [], usize::MAX);
+        assert_eq!(units.len(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic = "aligned"]
+    fn cast_uninit_misaligned() {
+        let mut storage = Aligned([MaybeUninit::uninit(); 16]);
+        cast_uninit::<u32>(&mut storage.0[1..5], 1);
+    }
+
+    #[test]
+    #[should_panic = "length"]
+    fn cast_uninit_wrong_length() {
+        let mut storage = Aligned([MaybeUninit::uninit(); 16]);
+        cast_uninit::<u64>(&mut storage.0[..12], 1);
+    }
+
+    #[test]
+    fn shrink_unchecked_matches_shrink() {
+        let mut buffer = *b"0123456789";
+        let mut checked: &[u8] = &buffer.clone();
+        let mut unchecked: &mut [u8] = &mut buffer;
+        for (start, end) in [(1, 9), (0, 8), (2, 2), (0, 0)] {
+            checked.shrink(start..end);
+            unsafe { unchecked.shrink_unchecked(start..end) };
+            assert_eq!(checked, unchecked);
+        }
+
+        let mut text: &str = "\u{e5}\u{e4}\u{f6}";
+        unsafe { text.shrink_unchecked(2..) };
+        unsafe { text.shrink_unchecked(..=1) };
+        assert_eq!(text, "\u{e4}");
+        let mut owned = String::from("abc");
+        let mut text: &mut str = &mut owned;
+        unsafe { text.shrink_unchecked(1..) };
+        assert_eq!(text, "bc");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn shrink_unchecked_debug_checks_boundaries() {
+        let mut text = "\u{e5}";
+        unsafe { text.shrink_unchecked(1..) };
+    }
+
+    #[test]
+    fn fill_buf_tracks_init() {
+        let mut storage = [MaybeUninit::uninit(); 6];
+        let mut buf = FillBuf::new(&mut storage);
+        assert_eq!(buf.advance(1), Err(NeedMore(1)));
+        let mut src: &[u8] = b"abc";
+        buf.append(&mut src);
+        buf.clear();
+        assert_eq!((buf.is_empty(), buf.init_len()), (true, 3));
+        buf.advance(2).unwrap();
+        buf.filled_mut()[0] = b'A';
+        assert_eq!(buf.unfilled_init(), b"c\0\0\0");
+        assert_eq!(buf.advance(5), Err(NeedMore(1)));
+        assert_eq!(buf.remaining(), 4);
+        assert_eq!(buf.into_filled(), b"Ab");
+
+        let mut bytes = *b"xyz";
+        let mut buf = FillBuf::from_init(&mut bytes);
+        buf.advance(3).unwrap();
+        assert_eq!((buf.len(), buf.capacity()), (3, 3));
+    }
+
+    #[test]
+    fn narrow_keeps_elements() {
+        let mut buffer = [1, 2, 3];
+        let mut slice: &mut [i32] = &mut buffer;
+        narrow_mut(&mut slice, 1..3);
+        slice[0] = 20;
+        assert_eq!(buffer, [1, 20, 3]);
+    }
+
+    #[test]
+    #[should_panic = "char boundaries"]
+    fn narrow_checks_boundaries() {
+        let mut owned = String::from("\u{e9}");
+        narrow_str_mut(&mut owned.as_mut_str(), 1..2);
+    }
+
+    #[test]
+    fn raw_slices_stay_within_bounds() {
+        let mut block = [0u64; 3];
+        let whole = NonNull::from(&mut block[..]);
+        let mut raw = unsafe { RawSlice::from_raw(whole.as_ptr()) }.unwrap();
+        let null = core::ptr::slice_from_raw_parts_mut(core::ptr::null_mut::<u8>(), 0);
+        assert!(unsafe { RawSlice::from_raw(null) }.is_none());
+        raw.shrink(1..);
+        assert_eq!(raw.as_ptr() as *mut u64, block[1..].as_mut_ptr());
+        assert_eq!(raw.try_shrink_back(3), (&block[1..]).try_shrink_back(3));
+        raw.shrink(2..);
+        assert_eq!((raw.len(), raw.as_ptr() as *mut u64), (0, block[3..].as_mut_ptr()));
+
+        let units = NonNull::slice_from_raw_parts(NonNull::<()>::dangling(), usize::MAX);
+        let mut units = unsafe { RawSlice::new(units) };
+        units.shrink(usize::MAX - 1..);
+        assert_eq!(units.len(), 1);
+    }
+
+    #[test]
+    fn raw_slices_shrink_after_free() {
+        let mut vec = vec![0u32; 4];
+        let ptr = NonNull::from(&mut vec[..]);
+        let addr = ptr.cast::<u32>().as_ptr() as usize;
+        let mut raw = unsafe { RawSlice::new(ptr) };
+        drop(vec);
+        raw.shrink(2..);
+        raw.shrink_back(1);
+        assert_eq!((raw.as_ptr() as *mut u32 as usize, raw.len()), (addr + 8, 1));
+    }
+}
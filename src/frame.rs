@@ -0,0 +1,427 @@
+//! Resumable readers for length-prefixed and tag-length-value frames.
+//!
+//! A [`FrameReader`] consumes frames from the front of a byte slice. When the input runs out in
+//! the middle of a frame, it returns [`Incomplete`] and remembers how far it got: a header that
+//! has already been decoded is not read again once more input arrives.
+//!
+//! ```rust
+//! use shrink_slice::frame::{FrameReader, Prefix};
+//!
+//! let mut reader = FrameReader::length_prefixed(Prefix::U8);
+//!
+//! let mut input: &[u8] = b"\x05hel";
+//! let incomplete = reader.read(&mut input).unwrap_err();
+//! assert_eq!(incomplete.length(), Some(5));
+//! assert_eq!(incomplete.needed(), 2);
+//! // The header has been consumed; only the partial body is left.
+//! assert_eq!(input, b"hel");
+//!
+//! let mut input: &[u8] = b"hello\x01!";
+//! assert_eq!(reader.read(&mut input).unwrap().value, b"hello");
+//! assert_eq!(reader.read(&mut input).unwrap().value, b"!");
+//! ```
+
+use crate::{NeedMore, Take};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The width and byte order of a frame's length field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Prefix {
+    /// A single byte.
+    U8,
+    /// Two bytes, little endian.
+    U16Le,
+    /// Two bytes, big endian.
+    U16Be,
+    /// Four bytes, little endian.
+    U32Le,
+    /// Four bytes, big endian.
+    U32Be,
+}
+
+impl Prefix {
+    /// The number of bytes the length field occupies.
+    pub fn width(self) -> usize {
+        match self {
+            Prefix::U8 => 1,
+            Prefix::U16Le | Prefix::U16Be => 2,
+            Prefix::U32Le | Prefix::U32Be => 4,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> usize {
+        match self {
+            Prefix::U8 => bytes[0] as usize,
+            Prefix::U16Le => u16::from_le_bytes([bytes[0], bytes[1]]) as usize,
+            Prefix::U16Be => u16::from_be_bytes([bytes[0], bytes[1]]) as usize,
+            Prefix::U32Le => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+            Prefix::U32Be => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+        }
+    }
+}
+
+/// A frame returned by [`FrameReader::read`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Frame<'a> {
+    /// The tag byte, for readers created with [`FrameReader::tlv`].
+    pub tag: Option<u8>,
+    /// The frame's payload.
+    pub value: &'a [u8],
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum State {
+    Header,
+    Body { tag: Option<u8>, len: usize },
+}
+
+/// Reads frames from the front of a byte slice, resuming where it left off when the input was
+/// truncated.
+#[derive(Clone, Debug)]
+pub struct FrameReader {
+    prefix: Prefix,
+    tagged: bool,
+    state: State,
+}
+
+impl FrameReader {
+    /// A reader for frames made up of a length field followed by that many bytes.
+    pub fn length_prefixed(prefix: Prefix) -> Self {
+        FrameReader { prefix, tagged: false, state: State::Header }
+    }
+
+    /// A reader for frames made up of a tag byte, a length field, and that many bytes.
+    pub fn tlv(prefix: Prefix) -> Self {
+        FrameReader { prefix, tagged: true, state: State::Header }
+    }
+
+    /// Consume one frame from the front of `input`.
+    ///
+    /// If the header is truncated, nothing is consumed. If the header is complete but the body
+    /// is not, the header is consumed and its contents are remembered, so the next call expects
+    /// `input` to continue with the body.
+    pub fn read<'a>(&mut self, input: &mut &'a [u8]) -> Result<Frame<'a>, Incomplete> {
+        let (tag, len) = match self.state {
+            State::Body { tag, len } => (tag, len),
+            State::Header => {
+                let header_len = self.tagged as usize + self.prefix.width();
+                let header = input.take_exact(header_len)
+                    .map_err(|need| Incomplete { length: None, need })?;
+                let (tag, len) = if self.tagged {
+                    (Some(header[0]), self.prefix.decode(&header[1..]))
+                } else {
+                    (None, self.prefix.decode(header))
+                };
+                self.state = State::Body { tag, len };
+                (tag, len)
+            }
+        };
+
+        let value = input.take_exact(len)
+            .map_err(|need| Incomplete { length: Some(len), need })?;
+        self.state = State::Header;
+        Ok(Frame { tag, value })
+    }
+
+    /// Forget any partially read frame, so that the next call to [`read`](Self::read) starts
+    /// from a header again.
+    pub fn reset(&mut self) {
+        self.state = State::Header;
+    }
+}
+
+/// A decoder that produces items from the front of a byte slice.
+///
+/// This is the interface that generic framing loops are written against: feed the decoder
+/// whatever input is buffered, and refill when it asks for more.
+pub trait Decode<'a> {
+    /// The type of item produced.
+    type Item;
+    /// The error returned for corrupt input.
+    type Error;
+
+    /// Decode one item from the front of `input`, shrinking past whatever was consumed.
+    ///
+    /// Returns `Ok(None)` if `input` ends before a complete item. Any state needed to resume is
+    /// kept in the decoder, and the next call expects `input` to continue where this one left
+    /// off.
+    fn decode(&mut self, input: &mut &'a [u8]) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// Forget any partially decoded item, so that decoding can restart at arbitrary input.
+    fn reset(&mut self) { }
+}
+
+impl<'a> Decode<'a> for FrameReader {
+    type Item = Frame<'a>;
+    type Error = core::convert::Infallible;
+
+    fn decode(&mut self, input: &mut &'a [u8]) -> Result<Option<Frame<'a>>, Self::Error> {
+        Ok(self.read(input).ok())
+    }
+
+    fn reset(&mut self) {
+        FrameReader::reset(self)
+    }
+}
+
+/// A frame whose payload has been copied out of the input, as decoded by [`OwnedFrames`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OwnedFrame {
+    /// The tag byte, for readers created with [`FrameReader::tlv`].
+    pub tag: Option<u8>,
+    /// The frame's payload.
+    pub value: Vec<u8>,
+}
+
+/// A [`FrameReader`] that copies out each frame's payload, for consumers of decoded items that
+/// cannot borrow from the input, like [`FrameStream`](crate::stream::FrameStream).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct OwnedFrames(FrameReader);
+
+#[cfg(feature = "alloc")]
+impl OwnedFrames {
+    /// Decode frames like `reader`, copying out their payloads.
+    pub fn new(reader: FrameReader) -> Self {
+        OwnedFrames(reader)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Decode<'a> for OwnedFrames {
+    type Item = OwnedFrame;
+    type Error = core::convert::Infallible;
+
+    fn decode(&mut self, input: &mut &'a [u8]) -> Result<Option<OwnedFrame>, Self::Error> {
+        let frame = self.0.read(input).ok();
+        Ok(frame.map(|Frame { tag, value }| OwnedFrame { tag, value: value.to_vec() }))
+    }
+
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+/// An iterator that decodes items from a byte slice, skipping over corrupt data.
+///
+/// Every item is expected to start with a sync marker. When the decoder reports an error, the
+/// iterator yields it as a [`Corrupt`] item, skips ahead to the next occurrence of the marker,
+/// resets the decoder and carries on from there. Iteration ends when the input runs out or only
+/// an incomplete item remains, which is then available from [`remaining`](Self::remaining).
+///
+/// ```rust
+/// use shrink_slice::frame::{Decode, Resync};
+///
+/// /// Records like `@3abc`: a marker, a length digit, and that many bytes.
+/// struct Record;
+///
+/// impl<'a> Decode<'a> for Record {
+///     type Item = &'a [u8];
+///     type Error = &'static str;
+///
+///     fn decode(&mut self, input: &mut &'a [u8]) -> Result<Option<&'a [u8]>, &'static str> {
+///         match *input {
+///             [b'@', len @ b'0'..=b'9', ref rest @ ..] => {
+///                 let len = (len - b'0') as usize;
+///                 if rest.len() < len {
+///                     return Ok(None);
+///                 }
+///                 *input = &rest[len..];
+///                 Ok(Some(&rest[..len]))
+///             }
+///             [b'@'] | [] => Ok(None),
+///             _ => Err("bad record"),
+///         }
+///     }
+/// }
+///
+/// let mut records = Resync::new(Record, b"@2hi@x!!@3abc@9trunc", b"@");
+/// assert_eq!(records.next(), Some(Ok(&b"hi"[..])));
+/// let corrupt = records.next().unwrap().unwrap_err();
+/// assert_eq!((corrupt.offset, corrupt.skipped, corrupt.error), (4, 4, "bad record"));
+/// assert_eq!(records.next(), Some(Ok(&b"abc"[..])));
+/// assert_eq!(records.next(), None);
+/// assert_eq!(records.remaining(), b"@9trunc");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Resync<'a, 's, D> {
+    decoder: D,
+    input: &'a [u8],
+    sync: &'s [u8],
+    offset: usize,
+}
+
+impl<'a, 's, D: Decode<'a>> Resync<'a, 's, D> {
+    /// Decode items from `input` with `decoder`, resynchronizing on `sync` after errors.
+    ///
+    /// Panics if `sync` is empty.
+    pub fn new(decoder: D, input: &'a [u8], sync: &'s [u8]) -> Self {
+        assert!(!sync.is_empty(), "sync marker must not be empty");
+        Resync { decoder, input, sync, offset: 0 }
+    }
+
+    /// The input that has not been decoded yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// The number of bytes consumed or skipped so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, 's, D: Decode<'a>> Iterator for Resync<'a, 's, D> {
+    type Item = Result<D::Item, Corrupt<D::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut view = self.input;
+        match self.decoder.decode(&mut view) {
+            Ok(Some(item)) => {
+                self.offset += self.input.len() - view.len();
+                self.input = view;
+                Some(Ok(item))
+            }
+            Ok(None) => None,
+            Err(error) => {
+                // Search from the second byte on, so that a corrupt item starting with the
+                // marker is not found again.
+                let skipped = self.input.windows(self.sync.len())
+                    .skip(1)
+                    .position(|window| window == self.sync)
+                    .map_or(self.input.len(), |i| i + 1);
+                let corrupt = Corrupt { offset: self.offset, skipped, error };
+                self.input.take_exact(skipped).expect("skipped bytes are within the input");
+                self.offset += skipped;
+                self.decoder.reset();
+                Some(Err(corrupt))
+            }
+        }
+    }
+}
+
+/// A stretch of input that a [`Resync`] iterator skipped because it could not be decoded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Corrupt<E> {
+    /// Where the skipped bytes start, relative to the start of the input.
+    pub offset: usize,
+    /// The number of bytes skipped.
+    pub skipped: usize,
+    /// The error reported by the decoder.
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for Corrupt<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Corrupt { offset, skipped, error } = self;
+        write!(f, "skipped {} corrupt bytes at offset {}: {}", skipped, offset, error)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for Corrupt<E> { }
+
+/// This error signifies that the input ended in the middle of a frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Incomplete {
+    length: Option<usize>,
+    need: NeedMore,
+}
+
+impl Incomplete {
+    /// The length of the frame's body, if its header has already been decoded.
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// How many more bytes are needed before the current part of the frame can be read.
+    pub fn needed(&self) -> usize {
+        self.need.0
+    }
+}
+
+impl fmt::Display for Incomplete {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.length {
+            None => write!(f, "incomplete frame header, {}", self.need),
+            Some(len) => write!(f, "incomplete frame body of length {}, {}", len, self.need),
+        }
+    }
+}
+
+impl core::error::Error for Incomplete { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_header_consumes_nothing() {
+        let mut reader = FrameReader::length_prefixed(Prefix::U16Be);
+        let mut input: &[u8] = b"\x00";
+        let incomplete = reader.read(&mut input).unwrap_err();
+        assert_eq!(incomplete.length(), None);
+        assert_eq!(incomplete.needed(), 1);
+        assert_eq!(input, b"\x00");
+    }
+
+    #[test]
+    fn tlv_resumes_after_refill() {
+        let mut reader = FrameReader::tlv(Prefix::U16Le);
+        let mut input: &[u8] = b"\x07\x03\x00a";
+        assert_eq!(reader.read(&mut input).unwrap_err().needed(), 2);
+        assert_eq!(input, b"a");
+
+        let mut input: &[u8] = b"abc\x08\x00\x00rest";
+        assert_eq!(reader.read(&mut input), Ok(Frame { tag: Some(7), value: b"abc" }));
+        assert_eq!(reader.read(&mut input), Ok(Frame { tag: Some(8), value: b"" }));
+        assert_eq!(input, b"rest");
+    }
+
+    #[test]
+    fn decode_loop() {
+        fn decode_all<'a, D: Decode<'a>>(d: &mut D, mut input: &'a [u8]) -> Vec<D::Item> {
+            let mut items = Vec::new();
+            while let Ok(Some(item)) = d.decode(&mut input) {
+                items.push(item);
+            }
+            items
+        }
+
+        let mut reader = FrameReader::length_prefixed(Prefix::U8);
+        let frames = decode_all(&mut reader, b"\x01a\x02bc\x03d");
+        let values: Vec<_> = frames.iter().map(|f| f.value).collect();
+        assert_eq!(values, [&b"a"[..], b"bc"]);
+    }
+
+    #[test]
+    fn resync_without_marker_skips_everything() {
+        struct Reject;
+
+        impl<'a> Decode<'a> for Reject {
+            type Item = ();
+            type Error = ();
+
+            fn decode(&mut self, input: &mut &'a [u8]) -> Result<Option<()>, ()> {
+                if input.is_empty() { Ok(None) } else { Err(()) }
+            }
+        }
+
+        let mut iter = Resync::new(Reject, b"#abc#", b"##");
+        assert_eq!(iter.next(), Some(Err(Corrupt { offset: 0, skipped: 5, error: () })));
+        assert_eq!(iter.next(), None);
+        assert_eq!((iter.offset(), iter.remaining()), (5, &b""[..]));
+    }
+
+    #[test]
+    fn reset_starts_over() {
+        let mut reader = FrameReader::length_prefixed(Prefix::U8);
+        let mut input: &[u8] = b"\x09";
+        assert!(reader.read(&mut input).is_err());
+        reader.reset();
+        let mut input: &[u8] = b"\x01x";
+        assert_eq!(reader.read(&mut input).unwrap().value, b"x");
+    }
+}
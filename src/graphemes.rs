@@ -0,0 +1,150 @@
+//! Shrinking string slices by grapheme clusters, the characters a user perceives.
+//!
+//! A grapheme cluster can be made up of several `char`s, like a letter followed by combining
+//! marks, an emoji with a skin tone modifier, or a flag made of two regional indicators. These
+//! operations never split one, which matters when text is consumed in the units a cursor moves
+//! by. Clusters are the extended grapheme clusters of the `unicode-segmentation` crate.
+//!
+//! ```rust
+//! use shrink_slice::graphemes::ShrinkGraphemes;
+//!
+//! let mut text = "e\u{301}\u{1f1f8}\u{1f1ea}!";
+//! assert_eq!(text.pop_grapheme(), Some("e\u{301}"));
+//! assert_eq!(text.pop_grapheme_back(), Some("!"));
+//! assert_eq!(text, "\u{1f1f8}\u{1f1ea}");
+//! ```
+
+use crate::{NeedMore, Shrink, ShrinkExt};
+use core::ops::Deref;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Extension trait for consuming string slices by grapheme clusters.
+pub trait ShrinkGraphemes: Shrink<Slice = str> + Deref<Target = str> {
+    /// A grapheme cluster split off the string, with its original lifetime.
+    type Grapheme;
+
+    /// Split off the first grapheme cluster and return it, or return `None` if the string is
+    /// empty.
+    fn pop_grapheme(&mut self) -> Option<Self::Grapheme>;
+
+    /// Split off the last grapheme cluster and return it, or return `None` if the string is
+    /// empty.
+    fn pop_grapheme_back(&mut self) -> Option<Self::Grapheme>;
+
+    /// Drop the first `n` grapheme clusters.
+    ///
+    /// If fewer than `n` remain, the string is left untouched and the error reports how many
+    /// more clusters would have been needed.
+    fn try_shrink_graphemes(&mut self, n: usize) -> Result<(), NeedMore> {
+        let start = match self.grapheme_indices(true).nth(n) {
+            Some((i, _)) => i,
+            None => {
+                enough(self, n)?;
+                self.len()
+            }
+        };
+        self.shrink(start..);
+        Ok(())
+    }
+
+    /// Drop the last `n` grapheme clusters, or report how many more would have been needed,
+    /// like [`try_shrink_graphemes`](ShrinkGraphemes::try_shrink_graphemes).
+    fn try_shrink_graphemes_back(&mut self, n: usize) -> Result<(), NeedMore> {
+        let end = match n.checked_sub(1) {
+            None => self.len(),
+            Some(last) => match self.grapheme_indices(true).nth_back(last) {
+                Some((i, _)) => i,
+                None => {
+                    enough(self, n)?;
+                    0
+                }
+            },
+        };
+        self.shrink(..end);
+        Ok(())
+    }
+}
+
+/// The methods of [`ShrinkGraphemes`] that panic when too few grapheme clusters remain,
+/// implemented for every [`ShrinkGraphemes`] string.
+pub trait ShrinkGraphemesExt: ShrinkGraphemes {
+    /// Drop the first `n` grapheme clusters, panicking if fewer than `n` remain.
+    #[track_caller]
+    fn shrink_graphemes(&mut self, n: usize) {
+        if let Err(NeedMore(missing)) = self.try_shrink_graphemes(n) {
+            panic!("cannot drop {} grapheme clusters, {} are missing", n, missing);
+        }
+    }
+
+    /// Drop the last `n` grapheme clusters, panicking if fewer than `n` remain.
+    #[track_caller]
+    fn shrink_graphemes_back(&mut self, n: usize) {
+        if let Err(NeedMore(missing)) = self.try_shrink_graphemes_back(n) {
+            panic!("cannot drop {} grapheme clusters, {} are missing", n, missing);
+        }
+    }
+}
+
+impl<S: ShrinkGraphemes + ?Sized> ShrinkGraphemesExt for S { }
+
+impl<'a> ShrinkGraphemes for &'a str {
+    type Grapheme = &'a str;
+
+    fn pop_grapheme(&mut self) -> Option<&'a str> {
+        let len = self.graphemes(true).next()?.len();
+        let (grapheme, rest) = self.split_at(len);
+        *self = rest;
+        Some(grapheme)
+    }
+
+    fn pop_grapheme_back(&mut self) -> Option<&'a str> {
+        let at = self.len() - self.graphemes(true).next_back()?.len();
+        let (rest, grapheme) = self.split_at(at);
+        *self = rest;
+        Some(grapheme)
+    }
+}
+
+impl<'a> ShrinkGraphemes for &'a mut str {
+    type Grapheme = &'a mut str;
+
+    fn pop_grapheme(&mut self) -> Option<&'a mut str> {
+        let len = self.graphemes(true).next()?.len();
+        let (grapheme, rest) = core::mem::take(self).split_at_mut(len);
+        *self = rest;
+        Some(grapheme)
+    }
+
+    fn pop_grapheme_back(&mut self) -> Option<&'a mut str> {
+        let at = self.len() - self.graphemes(true).next_back()?.len();
+        let (rest, grapheme) = core::mem::take(self).split_at_mut(at);
+        *self = rest;
+        Some(grapheme)
+    }
+}
+
+/// Check that `s` has at least `n` grapheme clusters, or report how many are missing.
+fn enough(s: &str, n: usize) -> Result<(), NeedMore> {
+    match s.graphemes(true).count() {
+        count if count < n => Err(NeedMore(n - count)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mut_never_splits_clusters() {
+        let mut owned = String::from("a\u{1f44d}\u{1f3fd}bc\u{308}\r\n");
+        let mut text: &mut str = &mut owned;
+        text.shrink_graphemes(1);
+        assert_eq!(text.pop_grapheme_back().map(|g| &*g), Some("\r\n"));
+        assert_eq!(text.try_shrink_graphemes_back(4), Err(NeedMore(1)));
+        text.shrink_graphemes_back(1);
+        text.shrink_graphemes(1);
+        text.make_ascii_uppercase();
+        assert_eq!(owned, "a\u{1f44d}\u{1f3fd}Bc\u{308}\r\n");
+    }
+}
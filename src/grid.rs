@@ -0,0 +1,124 @@
+//! Shrinking the rows and columns of two-dimensional arrays of the `ndarray` crate.
+//!
+//! ```rust
+//! use ndarray::Array2;
+//! use shrink_slice::grid::Shrink2Ext;
+//!
+//! // Zero the inside of every 4x4 tile of an image, leaving a one pixel border.
+//! let mut image = Array2::<u8>::ones((8, 8));
+//! for tile_row in 0..2 {
+//!     for tile_col in 0..2 {
+//!         let mut tile = image.view_mut();
+//!         tile.shrink2(tile_row * 4..tile_row * 4 + 4, tile_col * 4..tile_col * 4 + 4);
+//!         tile.shrink2(1..3, 1..3);
+//!         tile.fill(0);
+//!     }
+//! }
+//! assert_eq!(image.iter().filter(|&&p| p == 0).count(), 16);
+//! assert_eq!(image[(1, 1)], 0);
+//! assert_eq!(image[(3, 1)], 1);
+//! ```
+
+use crate::{range, ShrinkError};
+use core::ops::{Range, RangeBounds};
+use ndarray::{ArrayBase, Axis, Ix2, RawData, Slice};
+
+/// Extension trait for shrinking two-dimensional arrays, and views of them, along their axes.
+///
+/// Like [`Shrink`](crate::Shrink), views keep the lifetime of the data they view as they shrink,
+/// so there is no reborrow to outlive. Owned arrays keep their data, and only narrow which part
+/// of it they present.
+pub trait Shrink2 {
+    /// Shrink to the rows within `rows`, or return an error and leave the array untouched if they
+    /// are out of bounds.
+    fn try_shrink_rows<R: RangeBounds<usize>>(&mut self, rows: R) -> Result<(), ShrinkError> {
+        self.try_shrink2(rows, ..)
+    }
+
+    /// Shrink to the columns within `cols`, like [`try_shrink_rows`](Shrink2::try_shrink_rows).
+    fn try_shrink_cols<C: RangeBounds<usize>>(&mut self, cols: C) -> Result<(), ShrinkError> {
+        self.try_shrink2(.., cols)
+    }
+
+    /// Shrink to the rows within `rows` and the columns within `cols`.
+    ///
+    /// Both ranges are checked before either axis is shrunk, so on error the array is left
+    /// untouched.
+    fn try_shrink2<R, C>(&mut self, rows: R, cols: C) -> Result<(), ShrinkError>
+    where R: RangeBounds<usize>, C: RangeBounds<usize>;
+}
+
+/// The methods of [`Shrink2`] that panic on ranges that are out of bounds, implemented for
+/// every two-dimensional array and view.
+pub trait Shrink2Ext: Shrink2 {
+    /// Shrink to the rows within `rows`, panicking if they are out of bounds.
+    #[track_caller]
+    fn shrink_rows<R: RangeBounds<usize>>(&mut self, rows: R) {
+        if let Err(e) = self.try_shrink_rows(rows) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Shrink to the columns within `cols`, panicking if they are out of bounds.
+    #[track_caller]
+    fn shrink_cols<C: RangeBounds<usize>>(&mut self, cols: C) {
+        if let Err(e) = self.try_shrink_cols(cols) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Shrink to the rows within `rows` and the columns within `cols`, panicking if either is out
+    /// of bounds.
+    #[track_caller]
+    fn shrink2<R, C>(&mut self, rows: R, cols: C)
+    where R: RangeBounds<usize>, C: RangeBounds<usize>,
+    {
+        if let Err(e) = self.try_shrink2(rows, cols) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<S: Shrink2 + ?Sized> Shrink2Ext for S { }
+
+impl<S: RawData> Shrink2 for ArrayBase<S, Ix2> {
+    fn try_shrink2<R, C>(&mut self, rows: R, cols: C) -> Result<(), ShrinkError>
+    where R: RangeBounds<usize>, C: RangeBounds<usize>,
+    {
+        let rows = range::try_resolve(rows, self.nrows())?;
+        let cols = range::try_resolve(cols, self.ncols())?;
+        shrink_axis(self, Axis(0), rows);
+        shrink_axis(self, Axis(1), cols);
+        Ok(())
+    }
+}
+
+/// Shrink `array` along `axis` to `range`, which is within bounds.
+fn shrink_axis<S: RawData>(array: &mut ArrayBase<S, Ix2>, axis: Axis, range: Range<usize>) {
+    if range != (0..array.len_of(axis)) {
+        array.slice_axis_inplace(axis, Slice::from(range));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr2, ArrayViewMut2};
+
+    fn narrow(view: &mut ArrayViewMut2<'_, i32>) {
+        view.shrink_rows(1..);
+        view.shrink_cols(..1);
+    }
+
+    #[test]
+    fn view_mut_keeps_lifetime() {
+        let mut grid = arr2(&[[1, 2], [3, 4], [5, 6]]);
+        let mut view = grid.view_mut();
+        narrow(&mut view);
+        assert_eq!((view.nrows(), view.ncols()), (2, 1));
+        assert!(view.try_shrink2(..1, 1..3).is_err());
+        assert_eq!(view.dim(), (2, 1));
+        view[(0, 0)] = 30;
+        assert_eq!(grid, arr2(&[[1, 2], [30, 4], [5, 6]]));
+    }
+}
@@ -0,0 +1,97 @@
+//! Consuming HTTP/1.x header lines.
+
+use crate::ShrinkExt;
+use alloc::borrow::Cow;
+
+/// Extension trait for consuming header lines from the front of a byte slice.
+pub trait HeaderLines<'a> {
+    /// Consume one logical header line, including any obsolete line folding, and return it
+    /// without its terminator.
+    ///
+    /// Lines end in CRLF or a bare LF. A line followed by one that starts with a space or tab is
+    /// continued by it; each such fold is replaced by a single space, in which case the returned
+    /// line is owned. The empty line that ends a header block is returned as an empty slice.
+    ///
+    /// Returns `None`, consuming nothing, if the input does not yet hold a complete line. Since a
+    /// continuation could still follow, that includes a line that ends exactly at the end of the
+    /// input.
+    ///
+    /// ```rust
+    /// use shrink_slice::http::HeaderLines;
+    ///
+    /// let mut head: &[u8] = b"Host: example.com\r\nX-Long: a\r\n  b\r\n\r\nbody";
+    /// assert_eq!(head.read_header_line().unwrap(), &b"Host: example.com"[..]);
+    /// assert_eq!(head.read_header_line().unwrap(), &b"X-Long: a b"[..]);
+    /// assert_eq!(head.read_header_line().unwrap(), &b""[..]);
+    /// assert_eq!(head, b"body");
+    /// ```
+    fn read_header_line(&mut self) -> Option<Cow<'a, [u8]>>;
+}
+
+impl<'a> HeaderLines<'a> for &'a [u8] {
+    fn read_header_line(&mut self) -> Option<Cow<'a, [u8]>> {
+        let input: &'a [u8] = self;
+        let mut line = Cow::Borrowed(&[][..]);
+        let mut start = 0;
+
+        loop {
+            let newline = start + crate::scan::find_byte(b'\n', &input[start..])?;
+            let end = match newline.checked_sub(1) {
+                Some(cr) if input[cr] == b'\r' && cr >= start => cr,
+                _ => newline,
+            };
+            let next = newline + 1;
+
+            if start == 0 {
+                line = Cow::Borrowed(&input[..end]);
+                if end == 0 {
+                    // The empty line that ends the header block can't be continued.
+                    self.shrink(next..);
+                    return Some(line);
+                }
+            } else {
+                let segment = &input[start..end];
+                let owned = line.to_mut();
+                owned.push(b' ');
+                owned.extend_from_slice(segment);
+            }
+
+            match input.get(next) {
+                None => return None,
+                Some(b' ') | Some(b'\t') => {
+                    let indent = input[next..].iter().take_while(|&&b| b == b' ' || b == b'\t');
+                    start = next + indent.count();
+                }
+                Some(_) => {
+                    self.shrink(next..);
+                    return Some(line);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_newlines() {
+        let mut head: &[u8] = b"A: 1\nB: 2\n\tthree\n\n";
+        assert_eq!(head.read_header_line().unwrap(), &b"A: 1"[..]);
+        let folded = head.read_header_line().unwrap();
+        assert!(matches!(folded, Cow::Owned(_)));
+        assert_eq!(folded, &b"B: 2 three"[..]);
+        assert_eq!(head.read_header_line().unwrap(), &b""[..]);
+        assert!(head.is_empty());
+    }
+
+    #[test]
+    fn incomplete_lines() {
+        for input in [&b"A: 1"[..], b"A: 1\r\n", b"A: 1\r\n b", b"A: 1\r\n b\r\n"] {
+            let mut head = input;
+            assert_eq!(head.read_header_line(), None);
+            assert_eq!(head, input);
+        }
+    }
+}
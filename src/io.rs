@@ -0,0 +1,269 @@
+//! Using shrinking slices as cursors for `std::io` readers and writers.
+//!
+//! A `&mut [u8]` window over a fixed buffer is naturally "the space still to fill": each read
+//! goes into its front and shrinks it past what was read. Likewise, a `&[u8]` is "the bytes
+//! still to send", shrinking past whatever a writer accepts.
+//!
+//! The crate's errors also convert into [`io::Error`], so that the byte readers and writers can
+//! be used with `?` in functions returning [`io::Result`]. Running out of input, whether as a
+//! [`NeedMore`] or as a range past the end of a slice, becomes
+//! [`UnexpectedEof`](ErrorKind::UnexpectedEof). Ranges that can never be valid become
+//! [`InvalidInput`](ErrorKind::InvalidInput), and malformed data becomes
+//! [`InvalidData`](ErrorKind::InvalidData). The original error is kept as the
+//! [`io::Error`]'s inner error, from which it can be downcast again.
+//!
+//! ```rust
+//! use shrink_slice::read::GetBytes;
+//! use shrink_slice::varint::GetVarint;
+//! use std::io::{self, ErrorKind};
+//!
+//! fn parse_header(mut input: &[u8]) -> io::Result<(u16, u64)> {
+//!     Ok((input.get_u16_le()?, input.get_varint_u64()?))
+//! }
+//!
+//! assert_eq!(parse_header(b"\x01\x00\x05").unwrap(), (1, 5));
+//! assert_eq!(parse_header(b"\x01").unwrap_err().kind(), ErrorKind::UnexpectedEof);
+//! assert_eq!(parse_header(b"\x01\x00\x80\x00").unwrap_err().kind(), ErrorKind::InvalidData);
+//! ```
+
+use crate::utf8::Utf8ShrinkError;
+use crate::varint::VarintError;
+use crate::{NeedMore, Shrink, ShrinkError, ShrinkExt};
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Read once from `reader` into the front of `buf`, shrink `buf` past the bytes that were read,
+/// and return how many there were.
+///
+/// As with [`Read::read`], a return value of 0 means the reader is exhausted or `buf` is empty.
+/// On error, `buf` is left as it was.
+///
+/// ```rust
+/// use shrink_slice::io::read_into;
+///
+/// let mut buffer = [0; 8];
+/// let mut free: &mut [u8] = &mut buffer;
+/// let mut reader: &[u8] = b"hello";
+/// while read_into(&mut reader, &mut free).unwrap() > 0 {}
+/// let left = free.len();
+/// let filled = buffer.len() - left;
+/// assert_eq!(&buffer[..filled], b"hello");
+/// ```
+pub fn read_into<R: Read + ?Sized>(reader: &mut R, buf: &mut &mut [u8]) -> io::Result<usize> {
+    let n = reader.read(buf)?;
+    buf.shrink(n..);
+    Ok(n)
+}
+
+/// Read from `reader` into the front of `buf` until it is full or the reader is exhausted,
+/// shrinking it past everything that was read, and return the total.
+///
+/// Reads that are [`Interrupted`](ErrorKind::Interrupted) are retried. If any other error
+/// occurs, it is returned, and `buf` has still been shrunk past the bytes read before it.
+pub fn read_into_full<R>(reader: &mut R, buf: &mut &mut [u8]) -> io::Result<usize>
+where R: Read + ?Sized,
+{
+    let mut total = 0;
+    while !buf.is_empty() {
+        match read_into(reader, buf) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
+/// Write from the front of `buf` to `writer`, shrink `buf` past the bytes the writer accepted,
+/// and return how many there were.
+///
+/// A write that is [`Interrupted`](ErrorKind::Interrupted) is retried. On any other error, `buf`
+/// is left as it was, so a non-blocking writer that returns
+/// [`WouldBlock`](ErrorKind::WouldBlock) can simply be tried again later.
+pub fn write_from<W: Write + ?Sized>(writer: &mut W, buf: &mut &[u8]) -> io::Result<usize> {
+    loop {
+        match writer.write(buf) {
+            Ok(n) => {
+                buf.shrink(n..);
+                return Ok(n);
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Write all of `buf` to `writer`, shrinking it past each part the writer accepts.
+///
+/// Unlike [`Write::write_all`], an error leaves `buf` holding exactly the bytes that were not
+/// written, so sending can pick up where it left off. A writer that accepts nothing results in
+/// an error of kind [`WriteZero`](ErrorKind::WriteZero).
+///
+/// ```rust
+/// use std::io::{self, ErrorKind, Write};
+/// use shrink_slice::io::write_all_from;
+///
+/// /// A socket send buffer with room for three bytes.
+/// struct Socket(Vec<u8>);
+///
+/// impl Write for Socket {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         let n = buf.len().min(3 - self.0.len());
+///         if n == 0 {
+///             return Err(ErrorKind::WouldBlock.into());
+///         }
+///         self.0.extend_from_slice(&buf[..n]);
+///         Ok(n)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut socket = Socket(Vec::new());
+/// let mut pending: &[u8] = b"hello";
+/// let err = write_all_from(&mut socket, &mut pending).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::WouldBlock);
+/// assert_eq!((&*socket.0, pending), (&b"hel"[..], &b"lo"[..]));
+/// ```
+pub fn write_all_from<W: Write + ?Sized>(writer: &mut W, buf: &mut &[u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        if write_from(writer, buf)? == 0 {
+            return Err(io::Error::new(ErrorKind::WriteZero, "failed to write the whole buffer"));
+        }
+    }
+    Ok(())
+}
+
+/// Ranges past the end of the slice are [`UnexpectedEof`](ErrorKind::UnexpectedEof), while
+/// decreasing ranges and those that split a character or do not reach the end of a front-only
+/// slice are [`InvalidInput`](ErrorKind::InvalidInput).
+impl From<ShrinkError> for io::Error {
+    fn from(e: ShrinkError) -> Self {
+        let kind = match e {
+            ShrinkError::OutOfBounds { start, end, .. } if start <= end => ErrorKind::UnexpectedEof,
+            _ => ErrorKind::InvalidInput,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+/// Always [`UnexpectedEof`](ErrorKind::UnexpectedEof), including when a writer runs out of
+/// room.
+impl From<NeedMore> for io::Error {
+    fn from(e: NeedMore) -> Self {
+        io::Error::new(ErrorKind::UnexpectedEof, e)
+    }
+}
+
+/// A truncated varint is [`UnexpectedEof`](ErrorKind::UnexpectedEof), and a malformed one is
+/// [`InvalidData`](ErrorKind::InvalidData).
+impl From<VarintError> for io::Error {
+    fn from(e: VarintError) -> Self {
+        let kind = match e {
+            VarintError::NeedMore(_) => ErrorKind::UnexpectedEof,
+            VarintError::Overlong | VarintError::Overflow => ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+/// A truncated character is [`UnexpectedEof`](ErrorKind::UnexpectedEof), and invalid UTF-8 is
+/// [`InvalidData`](ErrorKind::InvalidData).
+impl From<Utf8ShrinkError> for io::Error {
+    fn from(e: Utf8ShrinkError) -> Self {
+        let kind = match e {
+            Utf8ShrinkError::NeedMore(_) => ErrorKind::UnexpectedEof,
+            Utf8ShrinkError::Invalid { .. } => ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads a byte at a time, failing every other call.
+    struct Flaky<'a>(&'a [u8], ErrorKind, bool);
+
+    impl Read for Flaky<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.2 = !self.2;
+            if self.2 {
+                return Err(self.1.into());
+            }
+            let n = self.0.len().min(buf.len()).min(1);
+            self.0.read(&mut buf[..n])
+        }
+    }
+
+    #[test]
+    fn fill_retries_interrupts() {
+        let mut buffer = [0; 4];
+        let mut free: &mut [u8] = &mut buffer;
+        let mut reader = Flaky(b"abcdef", ErrorKind::Interrupted, false);
+        assert_eq!(read_into_full(&mut reader, &mut free).unwrap(), 4);
+        assert!(free.is_empty());
+
+        let mut free: &mut [u8] = &mut buffer;
+        let mut reader = Flaky(b"xy", ErrorKind::WouldBlock, true);
+        let err = read_into_full(&mut reader, &mut free).unwrap_err();
+        assert_eq!((err.kind(), free.len()), (ErrorKind::WouldBlock, 3));
+        let mut reader = Flaky(b"", ErrorKind::Interrupted, false);
+        assert_eq!(read_into_full(&mut reader, &mut free).unwrap(), 0);
+        assert_eq!(&buffer, b"xbcd");
+    }
+
+    /// Accepts a byte at a time, failing every other call.
+    struct FlakySink(Vec<u8>, ErrorKind, bool);
+
+    impl Write for FlakySink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.2 = !self.2;
+            if self.2 {
+                return Err(self.1.into());
+            }
+            self.0.write(&buf[..buf.len().min(1)])
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn drain_retries_interrupts() {
+        let mut sink = FlakySink(Vec::new(), ErrorKind::Interrupted, false);
+        let mut pending: &[u8] = b"abc";
+        write_all_from(&mut sink, &mut pending).unwrap();
+        assert_eq!((&*sink.0, pending), (&b"abc"[..], &b""[..]));
+
+        let mut full: &mut [u8] = &mut [];
+        let mut pending: &[u8] = b"x";
+        let err = write_all_from(&mut full, &mut pending).unwrap_err();
+        assert_eq!((err.kind(), pending), (ErrorKind::WriteZero, &b"x"[..]));
+        let mut sink = FlakySink(Vec::new(), ErrorKind::BrokenPipe, false);
+        assert_eq!(write_from(&mut sink, &mut pending).unwrap_err().kind(), ErrorKind::BrokenPipe);
+        assert_eq!(pending, b"x");
+    }
+
+    #[test]
+    fn error_kinds() {
+        let mut input: &[u8] = b"ab";
+        let err = io::Error::from(input.try_shrink(1..3).unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        let (start, end) = (2, 1);
+        assert_eq!(io::Error::from(input.try_shrink(start..end).unwrap_err()).kind(),
+                   ErrorKind::InvalidInput);
+        let mut text = "\u{e9}";
+        let err = io::Error::from(text.try_shrink(1..).unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let inner = err.into_inner().unwrap().downcast::<ShrinkError>().unwrap();
+        assert!(matches!(*inner, ShrinkError::NotCharBoundary { index: 1, .. }));
+        let invalid = Utf8ShrinkError::Invalid { len: 1 };
+        let err = io::Error::from(invalid);
+        assert_eq!((err.kind(), err.to_string()), (ErrorKind::InvalidData, invalid.to_string()));
+    }
+}
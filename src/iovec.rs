@@ -0,0 +1,154 @@
+//! Shrinking buffers for vectored IO.
+//!
+//! With the `std` feature, `IoSlice` and `IoSliceMut`, which can only be advanced, implement
+//! [`Shrink`] for ranges that reach the end of the buffer, and refuse any others with
+//! [`ShrinkError::FrontOnly`](crate::ShrinkError::FrontOnly). [`advance_slices`] consumes a
+//! number of elements across a list of such buffers, or of any other slices, as after a
+//! partial vectored write.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! use std::io::IoSlice;
+//! use shrink_slice::{Shrink, ShrinkError, ShrinkExt};
+//!
+//! let mut buf = IoSlice::new(b"partially written");
+//! buf.shrink(10..);
+//! assert_eq!(&*buf, b"written");
+//! assert!(matches!(buf.try_shrink(..3), Err(ShrinkError::FrontOnly { .. })));
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+
+use crate::{NeedMore, Shrink, ShrinkExt, Take};
+#[cfg(feature = "std")]
+use crate::{range, ShrinkError};
+#[cfg(feature = "std")]
+use core::ops::RangeBounds;
+#[cfg(feature = "std")]
+use core::slice::SliceIndex;
+#[cfg(feature = "std")]
+use std::io::{IoSlice, IoSliceMut};
+
+/// Consume `n` elements from the front of a list of buffers.
+///
+/// Buffers that are consumed entirely are removed from the list, along with any empty ones
+/// before the first buffer that still has elements left. That buffer is shrunk past what
+/// remains of `n`. If the buffers hold fewer than `n` elements in total, the list is left
+/// untouched and the error reports how many more would have been needed.
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use std::io::{IoSlice, Write};
+/// use shrink_slice::iovec::advance_slices;
+///
+/// let mut out = [0; 5];
+/// let mut bufs = [b"ab", &b""[..], b"cde", b"f"].map(IoSlice::new);
+/// let mut bufs = &mut bufs[..];
+/// let mut written = 0;
+/// while !bufs.is_empty() && written < out.len() {
+///     let n = (&mut out[written..]).write_vectored(bufs).unwrap();
+///     written += n;
+///     advance_slices(&mut bufs, n).unwrap();
+/// }
+/// assert_eq!(&out, b"abcde");
+/// assert_eq!(&*bufs[0], b"f");
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+pub fn advance_slices<B, T>(bufs: &mut &mut [B], n: usize) -> Result<(), NeedMore>
+where B: Shrink<Slice = [T]>,
+{
+    let mut left = n;
+    let mut consumed = 0;
+    for buf in bufs.iter() {
+        if buf.len() > left {
+            break;
+        }
+        left -= buf.len();
+        consumed += 1;
+    }
+    if consumed == bufs.len() && left > 0 {
+        return Err(NeedMore(left));
+    }
+    bufs.take_exact(consumed)?;
+    if let Some(first) = bufs.first_mut() {
+        first.shrink_front(left);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+impl Shrink for IoSlice<'_> {
+    type Slice = [u8];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[u8], Output = [u8]> + RangeBounds<usize>
+    {
+        let n = range::front_only(range, self.len())?;
+        self.advance(n);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Shrink for IoSliceMut<'_> {
+    type Slice = [u8];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[u8], Output = [u8]> + RangeBounds<usize>
+    {
+        let n = range::front_only(range, self.len())?;
+        self.advance(n);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn mut_front_only() {
+        let mut buffer = *b"abcdef";
+        let mut buf = IoSliceMut::new(&mut buffer);
+        let err = ShrinkError::FrontOnly { start: 1, end: 5, len: 6 };
+        assert_eq!(buf.try_shrink(1..5), Err(err));
+        assert_eq!(err.to_string(), "range 1..5 does not reach the end of slice of length 6");
+        assert!(buf.try_shrink(7..).is_err());
+        buf.shrink(2..=5);
+        buf.shrink_front(1);
+        buf[0] = b'D';
+        assert_eq!(buf.len(), 3);
+        assert_eq!(&buffer, b"abcDef");
+    }
+
+    #[test]
+    fn advance_generic_slices() {
+        let (mut a, mut b) = (*b"ab", *b"cd");
+        let mut bufs: [&mut [u8]; 3] = [&mut a, &mut [], &mut b];
+        let mut bufs = &mut bufs[..];
+        assert_eq!(advance_slices(&mut bufs, 5), Err(NeedMore(1)));
+        assert_eq!(bufs.len(), 3);
+        advance_slices(&mut bufs, 2).unwrap();
+        assert_eq!(bufs.len(), 1);
+        advance_slices(&mut bufs, 1).unwrap();
+        bufs[0][0] = b'D';
+        advance_slices(&mut bufs, 1).unwrap();
+        assert!(bufs.is_empty());
+        assert_eq!(advance_slices(&mut bufs, 0), Ok(()));
+        assert_eq!(&b, b"cD");
+    }
+}
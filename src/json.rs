@@ -0,0 +1,304 @@
+//! Skipping over JSON values without parsing them.
+
+use crate::ShrinkExt;
+use core::fmt;
+
+/// How deeply arrays and objects may nest before the skipper gives up.
+const MAX_DEPTH: usize = 128;
+
+fn is_ws(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+struct Scanner<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Result<u8, JsonError> {
+        self.input.get(self.pos).copied().ok_or(JsonError::Incomplete)
+    }
+
+    fn invalid(&self) -> JsonError {
+        JsonError::Invalid { offset: self.pos }
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.input.get(self.pos), Some(&b) if is_ws(b)) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.peek()? != byte {
+            return Err(self.invalid());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn value(&mut self, depth: usize) -> Result<(), JsonError> {
+        match self.peek()? {
+            b'{' | b'[' if depth == MAX_DEPTH => Err(JsonError::TooDeep),
+            b'{' => self.object(depth + 1),
+            b'[' => self.array(depth + 1),
+            b'"' => self.string(),
+            b't' => self.literal(b"true"),
+            b'f' => self.literal(b"false"),
+            b'n' => self.literal(b"null"),
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<(), JsonError> {
+        self.expect(b'{')?;
+        self.skip_ws();
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_ws();
+            self.string()?;
+            self.skip_ws();
+            self.expect(b':')?;
+            self.skip_ws();
+            self.value(depth)?;
+            self.skip_ws();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.invalid()),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<(), JsonError> {
+        self.expect(b'[')?;
+        self.skip_ws();
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_ws();
+            self.value(depth)?;
+            self.skip_ws();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.invalid()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<(), JsonError> {
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    match self.peek()? {
+                        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => self.pos += 1,
+                        b'u' => {
+                            self.pos += 1;
+                            for _ in 0..4 {
+                                if !self.peek()?.is_ascii_hexdigit() {
+                                    return Err(self.invalid());
+                                }
+                                self.pos += 1;
+                            }
+                        }
+                        _ => return Err(self.invalid()),
+                    }
+                }
+                0..=0x1f => return Err(self.invalid()),
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn literal(&mut self, word: &[u8]) -> Result<(), JsonError> {
+        for &expected in word {
+            self.expect(expected)?;
+        }
+        Ok(())
+    }
+
+    fn digits(&mut self) -> Result<(), JsonError> {
+        if !self.peek()?.is_ascii_digit() {
+            return Err(self.invalid());
+        }
+        while matches!(self.input.get(self.pos), Some(b) if b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    /// Scan a number. The end of the input is taken to end the number, since the scanner has no
+    /// way of knowing whether more digits are on their way.
+    fn number(&mut self) -> Result<(), JsonError> {
+        if self.peek()? == b'-' {
+            self.pos += 1;
+        }
+        if self.peek()? == b'0' {
+            self.pos += 1;
+        } else {
+            self.digits()?;
+        }
+        if self.input.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            self.digits()?;
+        }
+        if let Some(b'e') | Some(b'E') = self.input.get(self.pos) {
+            self.pos += 1;
+            if let b'+' | b'-' = self.peek()? {
+                self.pos += 1;
+            }
+            self.digits()?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the extent of the first JSON value in `input`, returning its start and end.
+fn scan(input: &[u8]) -> Result<(usize, usize), JsonError> {
+    let mut scanner = Scanner { input, pos: 0 };
+    scanner.skip_ws();
+    let start = scanner.pos;
+    scanner.value(0)?;
+    Ok((start, scanner.pos))
+}
+
+/// Extension trait for consuming JSON values as raw text.
+pub trait SkipJson: Sized {
+    /// Skip any leading whitespace, consume exactly one well-formed JSON value, and return its
+    /// raw text. Whitespace after the value is left in place.
+    ///
+    /// On error the slice is left untouched. Running out of input in the middle of a value is
+    /// reported as [`JsonError::Incomplete`], so a streaming reader knows to wait for more.
+    ///
+    /// ```rust
+    /// use shrink_slice::json::SkipJson;
+    ///
+    /// let mut ndjson = "{\"a\": [1, 2.5e3, \"}\"]}\n true\n";
+    /// assert_eq!(ndjson.skip_json_value(), Ok("{\"a\": [1, 2.5e3, \"}\"]}"));
+    /// assert_eq!(ndjson.skip_json_value(), Ok("true"));
+    /// assert_eq!(ndjson, "\n");
+    /// ```
+    fn skip_json_value(&mut self) -> Result<Self, JsonError>;
+}
+
+impl SkipJson for &[u8] {
+    fn skip_json_value(&mut self) -> Result<Self, JsonError> {
+        let (start, end) = scan(self)?;
+        let value = &self[start..end];
+        self.shrink(end..);
+        Ok(value)
+    }
+}
+
+impl SkipJson for &str {
+    fn skip_json_value(&mut self) -> Result<Self, JsonError> {
+        // The scanner only stops next to ASCII bytes, but the cuts are still checked like any
+        // other `str` range rather than trusting that, so a bad cut is an error and not a panic.
+        let (start, end) = scan(self.as_bytes())?;
+        let offset = if self.is_char_boundary(start) { end } else { start };
+        let value = self.get(start..end).ok_or(JsonError::Invalid { offset })?;
+        self.shrink(end..);
+        Ok(value)
+    }
+}
+
+/// This error signifies that the input did not start with a well-formed JSON value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JsonError {
+    /// The input ended before the value did.
+    Incomplete,
+    /// The input is not valid JSON at the given byte offset.
+    Invalid {
+        /// The offset of the offending byte.
+        offset: usize,
+    },
+    /// Arrays or objects were nested too deeply.
+    TooDeep,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Incomplete => f.write_str("incomplete JSON value"),
+            JsonError::Invalid { offset } => write!(f, "invalid JSON at byte {}", offset),
+            JsonError::TooDeep => f.write_str("JSON value nested too deeply"),
+        }
+    }
+}
+
+impl core::error::Error for JsonError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalars() {
+        let mut input: &[u8] = b" null -0.5E+2 \"a\\u00e9\\n\" 12\tfalse";
+        for expected in [&b"null"[..], b"-0.5E+2", b"\"a\\u00e9\\n\"", b"12", b"false"] {
+            assert_eq!(input.skip_json_value(), Ok(expected));
+        }
+        assert_eq!(input.skip_json_value(), Err(JsonError::Incomplete));
+    }
+
+    #[test]
+    fn errors_leave_input() {
+        for (input, err) in [
+            ("[1, 2", JsonError::Incomplete),
+            ("{\"a\" 1}", JsonError::Invalid { offset: 5 }),
+            ("[1,]", JsonError::Invalid { offset: 3 }),
+            ("tru", JsonError::Incomplete),
+            ("\"\\x\"", JsonError::Invalid { offset: 2 }),
+        ] {
+            let mut slice = input;
+            assert_eq!(slice.skip_json_value(), Err(err));
+            assert_eq!(slice, input);
+        }
+    }
+
+    #[test]
+    fn leading_zero_ends_number() {
+        let mut input = "01";
+        assert_eq!(input.skip_json_value(), Ok("0"));
+        assert_eq!(input, "1");
+    }
+
+    #[test]
+    fn depth_limit() {
+        let deep = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+        assert_eq!(deep.as_str().skip_json_value(), Err(JsonError::TooDeep));
+        let ok = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        assert_eq!(ok.as_str().skip_json_value(), Ok(ok.as_str()));
+    }
+
+    #[test]
+    fn multibyte_text() {
+        let mut input = " \"\u{e9}\u{1f600}\"\u{fc} 7\u{e9}";
+        assert_eq!(input.skip_json_value(), Ok("\"\u{e9}\u{1f600}\""));
+        assert_eq!(input.skip_json_value(), Err(JsonError::Invalid { offset: 0 }));
+        assert_eq!(input, "\u{fc} 7\u{e9}");
+        input = &input[2..];
+        assert_eq!(input.skip_json_value(), Ok("7"));
+        assert_eq!(input, "\u{e9}");
+    }
+}
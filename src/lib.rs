@@ -1,9 +1,14 @@
+// Tests use the standard library for its collections and formatting macros, even without `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "unsafe-fast"), forbid(unsafe_code))]
+#![cfg_attr(feature = "unsafe-fast", deny(unsafe_code))]
+#![cfg_attr(feature = "borrowed-buf", feature(core_io_borrowed_buf, read_buf))]
 //! # shrink_slice
 //!
 //! Provides an extension trait that allows you to shrink slices, like this:
 //! ```rust
-//! use shrink_slice::Shrink;
+//! use shrink_slice::ShrinkExt;
 //! let mut slice: &[u8] = b"Hello, world!";
 //! slice.shrink(1..(slice.len() - 1));
 //! assert_eq!(slice, b"ello, world");
@@ -27,7 +32,7 @@
 //!     assign_byte(i);
 //! }
 //! ```
-//! To get around this, you could use [`std::mem::take`] to move the slice out of the variable, and
+//! To get around this, you could use [`core::mem::take`] to move the slice out of the variable, and
 //! then reassign it.
 //! ```rust
 //! # const BUF_LEN: usize = 100;
@@ -36,7 +41,7 @@
 //! #
 //! let mut assign_byte = |byte| {
 //!     slice[0] = byte;
-//!     slice = &mut std::mem::take(&mut slice)[1..];
+//!     slice = &mut core::mem::take(&mut slice)[1..];
 //! };
 //! ```
 //!
@@ -46,17 +51,239 @@
 //! # let mut buffer = [0; BUF_LEN];
 //! # let mut slice: &mut [u8] = &mut buffer;
 //! #
-//! use shrink_slice::Shrink;
+//! use shrink_slice::ShrinkExt;
 //!
 //! let mut assign_byte = |byte| {
 //!     slice[0] = byte;
 //!     slice.shrink(1..);
 //! };
 //! ```
+//!
+//! Beyond [`Shrink`] and [`ShrinkExt`], the crate has extension traits for taking, reading
+//! and writing, spread over its modules. The [`prelude`] brings the common ones into scope
+//! with a single import.
+//!
+//! ## Zero-sized types
+//!
+//! Slices of zero-sized types, like `&[()]`, occupy no memory, so their length can be anything
+//! up to `usize::MAX`. Shrinking them only ever changes the length, and every operation in this
+//! crate that shrinks or splits a slice does so in constant time regardless of the element size.
+//! Operations that hand out one piece per call, like
+//! [`ConsumeChunks`](chunks::ConsumeChunks), still visit every piece, so choose chunk sizes
+//! accordingly.
+//! ```rust
+//! use shrink_slice::{ShrinkExt, Take};
+//!
+//! let units = vec![(); usize::MAX];
+//! let mut slice: &[()] = &units;
+//! slice.shrink(1..);
+//! assert_eq!(slice.take_exact(usize::MAX / 2).unwrap().len(), usize::MAX / 2);
+//! assert_eq!(slice.len(), usize::MAX - 1 - usize::MAX / 2);
+//! ```
+//!
+//! ## `no_std` support
+//!
+//! The crate is `no_std` when its default `std` feature is disabled. The `alloc` feature brings
+//! back everything that needs an allocator, such as narrowing a `Vec` with
+//! [`OwnedShrink`](owned::OwnedShrink) and the shared slices, while `std` additionally enables
+//! the [`std::io`] integrations.
+//!
+//! ## Unsafe code
+//!
+//! By default the crate is built with `#![forbid(unsafe_code)]`, and everything is implemented
+//! with safe standard library APIs. A few features cannot be, such as handing out typed
+//! allocations from [`Bump`](bump::Bump), shrinking without bounds checks through
+//! `ShrinkUnchecked`, or shrinking raw slice pointers through `RawSlice`. Those are only
+//! available with the `unsafe-fast` feature, which confines all `unsafe` code to a single
+//! private module.
+//!
+//! ## Avoiding panics
+//!
+//! [`Shrink`] only has methods that report failure through a `Result`, like
+//! [`try_shrink`](Shrink::try_shrink). Their panicking counterparts, like
+//! [`shrink`](ShrinkExt::shrink), are in [`ShrinkExt`], which is implemented for every
+//! [`Shrink`] type and imported by the [`prelude`] along with it. None of the methods of
+//! [`Take`] panic, since those that give up the error return an `Option` instead.
+//!
+//! The crate's other extension traits are split the same way. Each panicking method, like
+//! [`shrink_chars`](chars::ShrinkCharsExt::shrink_chars), is in an `Ext` trait next to the one
+//! with its `try_` counterpart, here [`ShrinkCharsExt`](chars::ShrinkCharsExt) next to
+//! [`ShrinkChars`](chars::ShrinkChars).
+//!
+//! Code that must not panic can import [`Shrink`] and [`Take`] on their own, so that calling a
+//! panicking method is a compile error rather than something to catch in review:
+//!
+//! ```rust,compile_fail
+//! use shrink_slice::Shrink;
+//!
+//! let mut slice: &[u8] = b"abc";
+//! slice.try_shrink(1..).unwrap_or_default();
+//! slice.shrink(..5);
+//! ```
+//!
+//! The free function [`shrink()`] and the [`shrink!`] macro panic too. To keep all of them out
+//! of a codebase that may import the prelude, list them in the `disallowed-methods` of its
+//! `clippy.toml`:
+//!
+//! ```toml
+//! disallowed-methods = [
+//!     { path = "shrink_slice::shrink", reason = "panics, use try_shrink" },
+//!     { path = "shrink_slice::ShrinkExt::shrink", reason = "panics, use try_shrink" },
+//! ]
+//! ```
 
+use core::ops::{Range, RangeBounds, RangeFrom, RangeTo};
 use core::slice::SliceIndex;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Lets the derive macros' `::shrink_slice` paths resolve within this crate too.
+#[cfg(feature = "derive")]
+extern crate self as shrink_slice;
+
+pub mod affix;
+pub mod align;
+pub mod anchor;
+pub mod ansi;
+pub mod ascii;
+#[cfg(feature = "tokio")]
+pub mod async_io;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod backtrack;
+pub mod balanced;
+pub mod batch;
+#[cfg(feature = "bitvec")]
+mod bits;
+pub mod bom;
+mod bounds;
+#[cfg(feature = "borrowed-buf")]
+pub mod borrowed;
+#[cfg(feature = "bstr")]
+mod bstring;
+pub mod budget;
+pub mod bump;
+#[cfg(feature = "bytes")]
+mod bytes_buf;
+pub mod case;
+pub mod cell;
+pub mod chars;
+pub mod chunks;
+pub mod clamp;
+#[cfg(feature = "unicode-normalization")]
+pub mod combining;
+pub mod const_fn;
+pub mod context;
+pub mod copy;
+mod cstr;
+#[cfg(feature = "subtle")]
+pub mod ct;
+pub mod cursor;
+pub mod debug;
+pub mod deque;
+#[cfg(feature = "embedded-io")]
+pub mod embedded;
+pub mod encode;
+pub mod endian;
+#[cfg(feature = "unsafe-fast")]
+mod fast;
+pub mod frame;
+#[cfg(feature = "unicode-segmentation")]
+pub mod graphemes;
+#[cfg(feature = "ndarray")]
+pub mod grid;
+#[cfg(feature = "alloc")]
+pub mod http;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod iovec;
+pub mod json;
+pub mod located;
+#[cfg(feature = "lock_api")]
+pub mod lock;
+pub mod log;
+pub mod number;
+#[cfg(feature = "ops")]
+pub mod ops;
+#[cfg(any(feature = "alloc", feature = "arrayvec", feature = "bytes", feature = "heapless",
+          feature = "smallvec"))]
+pub mod owned;
+#[cfg(feature = "std")]
+pub mod path;
+pub mod pattern;
+pub mod percent;
+#[cfg(feature = "bytemuck")]
+pub mod pod;
+pub mod prelude;
+pub mod range;
+pub mod read;
+#[cfg(feature = "regex")]
+pub mod regex;
+pub mod reserve;
+pub mod rotate;
+pub mod run;
+pub mod scan;
+pub mod scoped;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+#[cfg(feature = "alloc")]
+pub mod shared;
+#[cfg(feature = "alloc")]
+pub mod shell;
+pub mod slice_like;
+pub mod split;
+pub mod spsc;
+#[cfg(feature = "stream")]
+pub mod stream;
+pub mod strided;
+pub mod subslice;
+mod take;
+pub mod traced;
+pub mod tracked;
+pub mod trim;
+pub mod trivia;
+pub mod utf16;
+pub mod utf8;
+pub mod varint;
+#[cfg(feature = "alloc")]
+pub mod vecbuf;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod window;
+#[cfg(feature = "winnow")]
+pub mod winnow;
+pub mod write;
+pub mod zip;
+
+pub use bounds::{ShrinkBytes, ShrinkStr};
+#[cfg(feature = "unsafe-fast")]
+pub use fast::{FillBuf, RawSlice, ShrinkUnchecked};
+#[cfg(feature = "derive")]
+pub use shrink_slice_derive::Shrink;
+pub use slice_like::{SliceLike, SliceLikeMut};
+pub use take::{LengthMismatch, NeedMore, Shrinking, Take, TakeArray, TakeElement, TakeWhile};
+
 /// The extension trait that allows you to shrink a slice.
+///
+/// With the `derive` feature, wrapper structs can derive it to forward to one of their fields.
+///
+/// ```rust
+/// # #[cfg(feature = "derive")] {
+/// use shrink_slice::{Shrink, ShrinkExt};
+///
+/// #[derive(Shrink)]
+/// struct Header<'a>(&'a [u8]);
+///
+/// let mut header = Header(b"\x01\x02\x03");
+/// header.shrink_front(1);
+/// assert_eq!(header.0, b"\x02\x03");
+/// # }
+/// ```
+///
+/// References to slices and string slices implement it through [`SliceLike`], which is also the
+/// easiest way to make references to your own slice-like types shrinkable. Other types can
+/// implement it directly; see the [`slice_like`] module for what may change between releases.
 pub trait Shrink {
     /// The type of slice that gets shrunk.
     type Slice: ?Sized;
@@ -66,18 +293,159 @@ pub trait Shrink {
     /// If the range is outside the bounds of `[0, self.len()]`, an error is returned.
     /// For string slices, it may also error if either end of the range lands within a multi-byte
     /// character.
-    #[must_use = "consider using Shrink::shrink which panics upon error"]
+    #[must_use = "consider using ShrinkExt::shrink which panics upon error"]
     fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
-    where R: SliceIndex<Self::Slice, Output = Self::Slice>;
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>;
+
+    /// The length of the slice, in elements, or in bytes for string slices.
+    fn len(&self) -> usize;
+
+    /// Whether the slice is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop the first `n` elements.
+    ///
+    /// If fewer than `n` remain, or for string slices, if `n` lands within a multi-byte
+    /// character, an error is returned and the slice is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::Shrink;
+    ///
+    /// let mut slice: &[u8] = b"Hello, world!";
+    /// slice.try_shrink_front(7).unwrap();
+    /// slice.try_shrink_back(1).unwrap();
+    /// assert_eq!(slice, b"world");
+    /// assert!(slice.try_shrink_back(6).is_err());
+    /// assert_eq!(slice, b"world");
+    /// ```
+    #[must_use = "consider using ShrinkExt::shrink_front which panics upon error"]
+    #[track_caller]
+    fn try_shrink_front(&mut self, n: usize) -> Result<(), ShrinkError>
+    where RangeFrom<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        self.try_shrink(n..)
+    }
 
+    /// Drop the last `n` elements.
+    ///
+    /// If fewer than `n` remain, or for string slices, if the new end lands within a multi-byte
+    /// character, an error is returned and the slice is left untouched. Too large an `n` is
+    /// reported as the range `0..n` being out of bounds.
+    #[must_use = "consider using ShrinkExt::shrink_back which panics upon error"]
+    #[track_caller]
+    fn try_shrink_back(&mut self, n: usize) -> Result<(), ShrinkError>
+    where RangeTo<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        let len = self.len();
+        let end = len.checked_sub(n).ok_or(ShrinkError::OutOfBounds { start: 0, end: n, len })?;
+        self.try_shrink(..end)
+    }
+
+    /// Shrink the slice to any range of indices, including ones that cannot index it directly,
+    /// like a custom range type.
+    ///
+    /// The range is first converted to a `start..end` range, which fails if it is decreasing, out
+    /// of bounds, or has an inclusive bound of `usize::MAX`, and then shrinks the slice as
+    /// [`try_shrink`](Shrink::try_shrink) would.
+    ///
+    /// ```rust
+    /// use core::ops::{Bound, RangeBounds};
+    /// use shrink_slice::Shrink;
+    ///
+    /// /// Everything after a separator.
+    /// struct After(usize);
+    ///
+    /// impl RangeBounds<usize> for After {
+    ///     fn start_bound(&self) -> Bound<&usize> {
+    ///         Bound::Excluded(&self.0)
+    ///     }
+    ///
+    ///     fn end_bound(&self) -> Bound<&usize> {
+    ///         Bound::Unbounded
+    ///     }
+    /// }
+    ///
+    /// let mut text = "key=value";
+    /// text.try_shrink_bounds(After(3)).unwrap();
+    /// assert_eq!(text, "value");
+    /// assert!(text.try_shrink_bounds((Bound::Excluded(usize::MAX), Bound::Unbounded)).is_err());
+    /// ```
+    #[must_use = "consider using ShrinkExt::shrink_bounds which panics upon error"]
+    #[track_caller]
+    fn try_shrink_bounds<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError>
+    where Range<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.try_shrink(range)
+    }
+
+    /// Shrink the slice to a range that is resolved against its length, like a range of
+    /// [`FromEnd`](range::FromEnd) indices.
+    ///
+    /// An index that would land before the front of the slice is reported as the range `0..n`
+    /// being out of bounds, like [`try_shrink_back`](Shrink::try_shrink_back) does.
+    #[must_use = "consider using ShrinkExt::shrink_index which panics upon error"]
+    #[track_caller]
+    fn try_shrink_index<I: range::ShrinkIndex>(&mut self, index: I) -> Result<(), ShrinkError>
+    where Range<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        let range = index.try_resolve(self.len())?;
+        self.try_shrink(range)
+    }
+
+    /// Shrink the slice to `range` and return it, for narrowing in expressions rather than
+    /// statements.
+    ///
+    /// ```rust
+    /// use shrink_slice::{Shrink, ShrinkError};
+    ///
+    /// fn payload(packet: &[u8]) -> Result<&[u8], ShrinkError> {
+    ///     let len = packet.first().copied().unwrap_or(0) as usize;
+    ///     packet.try_shrunk(1..)?.try_shrunk(..len)
+    /// }
+    ///
+    /// assert_eq!(payload(b"\x02hi!"), Ok(&b"hi"[..]));
+    /// assert!(payload(b"\x09hi!").is_err());
+    /// ```
+    #[track_caller]
+    fn try_shrunk<R>(mut self, range: R) -> Result<Self, ShrinkError>
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>,
+          Self: Sized,
+    {
+        self.try_shrink(range)?;
+        Ok(self)
+    }
+}
+
+/// The panicking counterparts of the methods of [`Shrink`], for when a failure is a bug.
+///
+/// Every method here is a `#[track_caller]` wrapper around a method of [`Shrink`] that returns
+/// a `Result` instead, and panics with the error's message. They live in a trait of their own,
+/// implemented for every [`Shrink`] type, so that code which must not panic can import
+/// [`Shrink`] alone and have no way to call them. The [`prelude`] imports both, and the other
+/// extension traits with panicking methods are split in the same way.
+pub trait ShrinkExt: Shrink {
     /// Shrink the slice so that it refers to a subslice of its old range.
     ///
     /// Panics if the range is outside the bounds of `[0, self.len()]`, or for string slices, if
     /// either end of the range lands within a multi-byte character.
+    ///
+    /// Returns `self`, so that calls can be chained, as can those of
+    /// [`shrink_front`](ShrinkExt::shrink_front) and [`shrink_back`](ShrinkExt::shrink_back):
+    ///
+    /// ```rust
+    /// use shrink_slice::ShrinkExt;
+    ///
+    /// let mut record: &[u8] = b"[a,b,c]\n";
+    /// record.shrink_back(1).shrink(1..).shrink_back(1);
+    /// assert_eq!(record, b"a,b,c");
+    /// ```
     #[inline]
     #[track_caller]
-    fn shrink<R>(&mut self, range: R)
-    where R: SliceIndex<Self::Slice, Output = Self::Slice>,
+    fn shrink<R>(&mut self, range: R) -> &mut Self
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>,
           ShrinkError: fmt::Display,
     {
         #[cold]
@@ -90,65 +458,288 @@ pub trait Shrink {
         if let Err(e) = self.try_shrink(range) {
             fail(e);
         }
+        self
     }
-}
 
-impl<T> Shrink for &[T] {
-    type Slice = [T];
+    /// Drop the first `n` elements, returning `self`.
+    ///
+    /// Panics if fewer than `n` remain, or for string slices, if `n` lands within a multi-byte
+    /// character.
+    #[inline]
+    #[track_caller]
+    fn shrink_front(&mut self, n: usize) -> &mut Self
+    where RangeFrom<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        self.shrink(n..)
+    }
 
-    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
-    where R: SliceIndex<[T], Output = [T]>
+    /// Drop the last `n` elements, returning `self`.
+    ///
+    /// Panics if fewer than `n` remain, or for string slices, if the new end lands within a
+    /// multi-byte character.
+    #[inline]
+    #[track_caller]
+    fn shrink_back(&mut self, n: usize) -> &mut Self
+    where RangeTo<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
     {
-        *self = self.get(range).ok_or(ShrinkError)?;
-        Ok(())
+        if let Err(e) = self.try_shrink_back(n) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// Shrink the slice to `range` and return it, panicking like [`shrink`](ShrinkExt::shrink) if
+    /// the range cannot index it.
+    #[inline]
+    #[track_caller]
+    fn shrunk<R>(mut self, range: R) -> Self
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>,
+          Self: Sized,
+    {
+        self.shrink(range);
+        self
     }
-}
 
-impl<T> Shrink for &mut [T] {
-    type Slice = [T];
+    /// Shrink the slice to any range of indices, like
+    /// [`try_shrink_bounds`](Shrink::try_shrink_bounds).
+    ///
+    /// Panics if the range is decreasing or out of bounds, or for string slices, if either end of
+    /// it lands within a multi-byte character.
+    #[inline]
+    #[track_caller]
+    fn shrink_bounds<R: RangeBounds<usize>>(&mut self, range: R)
+    where Range<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        if let Err(e) = self.try_shrink_bounds(range) {
+            panic!("{}", e);
+        }
+    }
 
-    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
-    where R: SliceIndex<[T], Output = [T]> {
-        *self = std::mem::take(self).get_mut(range).ok_or(ShrinkError)?;
-        Ok(())
+    /// Shrink the slice to a range that is resolved against its length, like
+    /// [`try_shrink_index`](Shrink::try_shrink_index).
+    ///
+    /// Panics if the range is decreasing or out of bounds, or for string slices, if either end of
+    /// it lands within a multi-byte character.
+    #[inline]
+    #[track_caller]
+    fn shrink_index<I: range::ShrinkIndex>(&mut self, index: I)
+    where Range<usize>: SliceIndex<Self::Slice, Output = Self::Slice>,
+    {
+        if let Err(e) = self.try_shrink_index(index) {
+            panic!("{}", e);
+        }
     }
 }
 
-impl Shrink for &str {
-    type Slice = str;
+impl<S: Shrink + ?Sized> ShrinkExt for S { }
 
-    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
-    where R: SliceIndex<str, Output = str> {
-        *self = self.get(range).ok_or(ShrinkError)?;
-        Ok(())
+/// Shrink `slice` to `range`, like [`Shrink::try_shrink`].
+///
+/// Call forms like this one, and the [`try_shrink!`] macro, take the place to shrink explicitly,
+/// so they work the same whether it is a local, a field of `self` or an element of an array,
+/// without relying on method resolution to pick the right reference.
+#[must_use = "consider using shrink which panics upon error"]
+pub fn try_shrink<S, R>(slice: &mut S, range: R) -> Result<(), ShrinkError>
+where
+    S: Shrink + ?Sized,
+    R: SliceIndex<S::Slice, Output = S::Slice> + RangeBounds<usize>,
+{
+    slice.try_shrink(range)
+}
+
+/// Shrink `slice` to `range`, like [`ShrinkExt::shrink`].
+///
+/// # Panics
+///
+/// Panics if the range is out of bounds, or for string slices, if either end of it lands within
+/// a multi-byte character.
+#[inline]
+#[track_caller]
+pub fn shrink<S, R>(slice: &mut S, range: R)
+where
+    S: Shrink + ?Sized,
+    R: SliceIndex<S::Slice, Output = S::Slice> + RangeBounds<usize>,
+{
+    if let Err(e) = slice.try_shrink(range) {
+        panic!("{}", e);
     }
 }
 
-impl Shrink for &mut str {
-    type Slice = str;
+/// Shrink a place expression to a range, panicking on error.
+///
+/// `shrink!(place, range)` is short for [`shrink(&mut place, range)`](shrink()), and borrows
+/// nothing but `place`.
+///
+/// ```rust
+/// use shrink_slice::shrink;
+///
+/// struct Parser<'a> {
+///     input: &'a [u8],
+///     fields: [&'a str; 2],
+/// }
+///
+/// impl Parser<'_> {
+///     fn skip(&mut self, n: usize) {
+///         shrink!(self.input, n..);
+///         shrink!(self.fields[1], ..1);
+///     }
+/// }
+///
+/// let mut parser = Parser { input: b"abc", fields: ["x", "yz"] };
+/// parser.skip(2);
+/// assert_eq!((parser.input, parser.fields), (&b"c"[..], ["x", "y"]));
+/// ```
+#[macro_export]
+macro_rules! shrink {
+    ($place:expr, $range:expr $(,)?) => {
+        $crate::shrink(&mut $place, $range)
+    };
+}
 
-    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
-    where R: SliceIndex<str, Output = str> {
-        *self = std::mem::take(self).get_mut(range).ok_or(ShrinkError)?;
-        Ok(())
-    }
+/// Shrink a place expression to a range, returning a `Result`.
+///
+/// `try_shrink!(place, range)` is short for [`try_shrink(&mut place, range)`](try_shrink()).
+#[macro_export]
+macro_rules! try_shrink {
+    ($place:expr, $range:expr $(,)?) => {
+        $crate::try_shrink(&mut $place, $range)
+    };
 }
 
 /// This error signifies that the provided range cannot index the provided slice,
 /// either because it was out of bounds, or in the case of strings, because one or more bounds
-/// falls within a multi-byte character.
-#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
-pub struct ShrinkError;
+/// falls within a multi-byte character. Some slice types that can only be shrunk from the front
+/// also refuse ranges that stop short of the end.
+///
+/// Every variant carries the attempted range, with unbounded ends resolved against the slice and
+/// inclusive ends made exclusive.
+///
+/// ```rust
+/// use shrink_slice::{Shrink, ShrinkError};
+///
+/// let mut text = "na\u{ef}ve";
+/// let err = text.try_shrink(..3).unwrap_err();
+/// assert_eq!(err, ShrinkError::NotCharBoundary { start: 0, end: 3, len: 6, index: 3 });
+/// assert_eq!(err.to_string(), "byte index 3 of range 0..3 is not a char boundary");
+/// ```
+///
+/// With the `defmt` feature, it implements `defmt::Format`, fields included.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ShrinkError {
+    /// The range is decreasing, or extends past the end of the slice.
+    OutOfBounds {
+        /// The start of the attempted range.
+        start: usize,
+        /// The end of the attempted range.
+        end: usize,
+        /// The length of the slice. For a range of character indices, like
+        /// [`Chars`](chars::Chars), it is the number of characters in the string instead, and so
+        /// are the start and end.
+        len: usize,
+    },
+    /// The range is within bounds, but one of its ends lands within a multi-byte character of
+    /// a string slice.
+    NotCharBoundary {
+        /// The start of the attempted range.
+        start: usize,
+        /// The end of the attempted range.
+        end: usize,
+        /// The length of the string in bytes.
+        len: usize,
+        /// The end of the range that is not on a char boundary.
+        index: usize,
+    },
+    /// The range is within bounds, but does not reach the end of a slice that can only be
+    /// shrunk from the front.
+    FrontOnly {
+        /// The start of the attempted range.
+        start: usize,
+        /// The end of the attempted range.
+        end: usize,
+        /// The length of the slice.
+        len: usize,
+    },
+}
+
+impl ShrinkError {
+    /// The error for indexing a slice of length `len` by `range`, which is out of bounds.
+    pub fn out_of_bounds<R: RangeBounds<usize>>(range: R, len: usize) -> Self {
+        let (start, end) = range::endpoints(&range, len);
+        ShrinkError::OutOfBounds { start, end, len }
+    }
+
+    /// The error for indexing `s` by `range`, which is either out of bounds or not on char
+    /// boundaries.
+    pub fn for_str<R: RangeBounds<usize>>(range: R, s: &str) -> Self {
+        let (start, end) = range::endpoints(&range, s.len());
+        let len = s.len();
+        if start > end || end > len {
+            ShrinkError::OutOfBounds { start, end, len }
+        } else {
+            let index = if s.is_char_boundary(start) { end } else { start };
+            ShrinkError::NotCharBoundary { start, end, len, index }
+        }
+    }
+
+    /// A small number identifying the kind of error, for passing it on where the whole error
+    /// would take too much room, like a message queue between tasks.
+    ///
+    /// The codes are stable, and distinguish the same cases as the error message:
+    ///
+    /// | Code | Error |
+    /// |------|-------|
+    /// | 1 | [`OutOfBounds`](ShrinkError::OutOfBounds), with the start past the end of the slice |
+    /// | 2 | [`OutOfBounds`](ShrinkError::OutOfBounds), with the end past the end of the slice |
+    /// | 3 | [`OutOfBounds`](ShrinkError::OutOfBounds), with a decreasing range |
+    /// | 4 | [`NotCharBoundary`](ShrinkError::NotCharBoundary) |
+    /// | 5 | [`FrontOnly`](ShrinkError::FrontOnly) |
+    ///
+    /// Zero is never used, so that it can stand for success.
+    ///
+    /// ```rust
+    /// use shrink_slice::Shrink;
+    ///
+    /// let mut slice: &[u8] = b"abc";
+    /// assert_eq!(slice.try_shrink(1..5).unwrap_err().code(), 2);
+    /// ```
+    pub const fn code(&self) -> u8 {
+        match *self {
+            ShrinkError::OutOfBounds { start, len, .. } if start > len => 1,
+            ShrinkError::OutOfBounds { end, len, .. } if end > len => 2,
+            ShrinkError::OutOfBounds { .. } => 3,
+            ShrinkError::NotCharBoundary { .. } => 4,
+            ShrinkError::FrontOnly { .. } => 5,
+        }
+    }
+}
 
 use core::fmt;
 
 impl fmt::Display for ShrinkError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("cannot index slice by this range")
+        match *self {
+            ShrinkError::OutOfBounds { start, len, .. } if start > len => {
+                write!(f, "range start index {} out of range for slice of length {}", start, len)
+            }
+            ShrinkError::OutOfBounds { end, len, .. } if end > len => {
+                write!(f, "range end index {} out of range for slice of length {}", end, len)
+            }
+            ShrinkError::OutOfBounds { start, end, .. } => {
+                write!(f, "slice index starts at {} but ends at {}", start, end)
+            }
+            ShrinkError::NotCharBoundary { start, end, index, .. } => {
+                write!(f, "byte index {} of range {}..{} is not a char boundary", index, start, end)
+            }
+            ShrinkError::FrontOnly { start, end, len } => {
+                let message = "does not reach the end of slice of length";
+                write!(f, "range {}..{} {} {}", start, end, message, len)
+            }
+        }
     }
 }
 
-impl std::error::Error for ShrinkError { }
+impl core::error::Error for ShrinkError { }
 
 #[cfg(test)]
 mod tests {
@@ -195,6 +786,19 @@ mod tests {
         assert_eq!(slice, "ello, world");
     }
 
+    #[test]
+    fn failure_keeps_mut() {
+        let mut buffer = *b"abc";
+        let mut slice: &mut [u8] = &mut buffer;
+        assert!(slice.try_shrink(1..4).is_err());
+        assert_eq!(slice, b"abc");
+
+        let mut buffer = "h\u{e9}llo".to_string();
+        let mut slice = buffer.as_mut_str();
+        assert!(slice.try_shrink(2..).is_err());
+        assert_eq!(slice, "h\u{e9}llo");
+    }
+
     #[test]
     fn string_mut() {
         let mut buffer = "Hello, world!".to_string();
@@ -203,9 +807,162 @@ mod tests {
         assert_eq!(slice, "ello, world");
     }
 
+    #[test]
+    fn zero_sized() {
+        let mut units = vec![(); usize::MAX];
+        let mut slice: &mut [()] = &mut units;
+        slice.shrink(..usize::MAX - 1);
+        slice.shrink(1..);
+        assert_eq!(slice.len(), usize::MAX - 2);
+        assert!(slice.try_shrink(..usize::MAX).is_err());
+        assert_eq!(slice.len(), usize::MAX - 2);
+        slice.shrink(usize::MAX - 2..);
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn front_and_back() {
+        let mut buffer = *b"[abc]";
+        let mut slice: &mut [u8] = &mut buffer;
+        slice.shrink_front(1);
+        slice.shrink_back(1);
+        assert_eq!(slice, b"abc");
+        let err = ShrinkError::OutOfBounds { start: 0, end: usize::MAX, len: 3 };
+        assert_eq!(slice.try_shrink_back(usize::MAX), Err(err));
+
+        let mut text = "\u{e9}t\u{e9}";
+        let err = text.try_shrink_back(1).unwrap_err();
+        assert!(matches!(err, ShrinkError::NotCharBoundary { index: 4, .. }));
+        text.shrink_back(2);
+        assert!(matches!(text.try_shrink_front(1), Err(ShrinkError::NotCharBoundary { .. })));
+        assert_eq!(text, "\u{e9}t");
+    }
+
+    #[test]
+    fn error_messages() {
+        use core::ops::Bound::{self, *};
+
+        let slice: &[u8] = b"abc";
+        let message = |range: (Bound<usize>, Bound<usize>)| {
+            let mut slice = slice;
+            slice.try_shrink(range).unwrap_err().to_string()
+        };
+        assert_eq!(message((Included(4), Unbounded)),
+                   "range start index 4 out of range for slice of length 3");
+        assert_eq!(message((Unbounded, Included(3))),
+                   "range end index 4 out of range for slice of length 3");
+        assert_eq!(message((Included(2), Excluded(1))), "slice index starts at 2 but ends at 1");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panik_back() {
+        let mut slice: &[u8] = b"abc";
+        slice.shrink_back(4);
+    }
+
     #[test]
     #[should_panic]
     fn panik_unicode() {
         "😬".shrink(1..);
     }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived() {
+        #[derive(Shrink)]
+        struct Labelled<'a, S> where S: Copy {
+            #[allow(dead_code)]
+            label: &'a str,
+            #[shrink(inner)]
+            data: S,
+        }
+
+        let mut buffer = [1, 2, 3, 4];
+        let mut labelled = Labelled { label: "counts", data: &buffer[..] };
+        labelled.shrink(1..3);
+        assert!(labelled.try_shrink(..3).is_err());
+        assert_eq!((labelled.len(), labelled.data), (2, &[2, 3][..]));
+
+        #[derive(Shrink)]
+        struct Field<'a>(&'a mut [u8]);
+
+        let mut field = Field(&mut buffer);
+        field.shrink_back(1);
+        field.0[0] = 10;
+        assert_eq!(buffer, [10, 2, 3, 4]);
+    }
+
+    #[test]
+    fn place_expressions() {
+        let mut buffer = [1, 2, 3];
+        let mut slices: [&mut [i32]; 1] = [&mut buffer];
+        crate::shrink!(slices[0], 1..);
+        assert!(crate::try_shrink!(slices[0], ..3).is_err());
+        slices[0][0] = 20;
+
+        let mut pair = ("hello", 0);
+        crate::shrink(&mut pair.0, 1..4);
+        assert_eq!(crate::try_shrink(&mut pair.0, 1..), Ok(()));
+        assert_eq!((pair.0, buffer), ("ll", [1, 20, 3]));
+    }
+
+    #[test]
+    fn from_end() {
+        use crate::range::FromEnd;
+
+        let mut buffer = *b"..abc\r\n";
+        let mut line: &mut [u8] = &mut buffer;
+        line.shrink_index(..FromEnd(2));
+        line.shrink_index(FromEnd(3)..);
+        line[0] = b'A';
+        assert_eq!(&buffer, b"..Abc\r\n");
+
+        let mut text = "caf\u{e9}";
+        assert!(text.try_shrink_index(FromEnd(1)..).is_err());
+        text.shrink_index(FromEnd(3)..=FromEnd(3));
+        assert_eq!(text, "f");
+    }
+
+    #[test]
+    fn bounds_pairs() {
+        use core::ops::Bound::{Excluded, Included, Unbounded};
+
+        let mut buffer = [0, 1, 2, 3, 4];
+        let mut slice: &mut [i32] = &mut buffer;
+        slice.shrink_bounds((Excluded(0), Included(3)));
+        assert_eq!(slice, [1, 2, 3]);
+        let err = slice.try_shrink_bounds((Included(2), Included(usize::MAX))).unwrap_err();
+        assert_eq!(err, ShrinkError::OutOfBounds { start: 2, end: usize::MAX, len: 3 });
+
+        let mut text = "\u{e9}t\u{e9}";
+        assert!(text.try_shrink_bounds((Excluded(0), Unbounded)).is_err());
+        text.shrink_bounds((Unbounded, Excluded(3)));
+        assert_eq!(text, "\u{e9}t");
+    }
+
+    #[test]
+    fn chained() {
+        let mut buffer = *b"(key=value)";
+        let mut slice: &mut [u8] = &mut buffer;
+        slice.shrink_front(1).shrink_back(1).shrink(4..)[0] = b'V';
+        assert_eq!(slice, b"Value");
+        let word = "na\u{ef}ve".shrunk(..2);
+        assert_eq!(word, "na");
+        assert!("na\u{ef}ve".try_shrunk(..3).is_err());
+    }
+
+    #[test]
+    fn error_codes() {
+        let mut slice: &[u8] = b"abc";
+        let (start, end) = (2, 1);
+        let codes = [
+            slice.try_shrink(4..).unwrap_err().code(),
+            slice.try_shrink(..4).unwrap_err().code(),
+            slice.try_shrink(start..end).unwrap_err().code(),
+            "\u{e9}".try_shrink(1..).unwrap_err().code(),
+        ];
+        assert_eq!(codes, [1, 2, 3, 4]);
+        assert_eq!(ShrinkError::FrontOnly { start: 0, end: 1, len: 2 }.code(), 5);
+    }
 }
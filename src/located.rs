@@ -0,0 +1,144 @@
+//! String slices that know which line and column they start at.
+//!
+//! ```rust
+//! use shrink_slice::located::{LocatedStr, Location};
+//! use shrink_slice::{ShrinkExt, TakeWhile};
+//!
+//! let mut source = LocatedStr::new("[server]\r\nport = 8080\n");
+//! source.shrink(10..);
+//! let key = source.take_while(|c| c.is_alphabetic());
+//! source.shrink(3..);
+//! assert_eq!(key.location(), Location { line: 2, column: 1 });
+//! assert_eq!(source.location(), Location { line: 2, column: 8 });
+//! ```
+
+use crate::{range, Shrink, ShrinkError, TakeWhile};
+use core::fmt;
+use core::ops::{Deref, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A position in a text, counting from 1.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Location {
+    /// The line number. Lines end in LF, so CRLF line endings are counted once.
+    pub line: usize,
+    /// The column number, in chars.
+    pub column: usize,
+}
+
+impl Location {
+    /// The start of a text.
+    pub const START: Location = Location { line: 1, column: 1 };
+
+    /// The location just past `text`, if it were to start at this one.
+    pub fn advance(self, text: &str) -> Location {
+        match text.rfind('\n') {
+            Some(i) => Location {
+                line: self.line + text.bytes().filter(|&b| b == b'\n').count(),
+                column: 1 + text[i + 1..].chars().count(),
+            },
+            None => Location { line: self.line, column: self.column + text.chars().count() },
+        }
+    }
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location::START
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A shrinkable string slice together with the location it starts at.
+///
+/// Shrinking from the front moves the location past the removed text, which costs time
+/// proportional to its length.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct LocatedStr<'a> {
+    pub(crate) view: &'a str,
+    pub(crate) location: Location,
+}
+
+impl<'a> LocatedStr<'a> {
+    /// A string slice starting at line 1, column 1.
+    pub fn new(view: &'a str) -> Self {
+        LocatedStr::with_location(view, Location::START)
+    }
+
+    /// A string slice starting at `location`.
+    pub fn with_location(view: &'a str, location: Location) -> Self {
+        LocatedStr { view, location }
+    }
+
+    /// Where the slice starts.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// The string slice itself.
+    pub fn as_str(&self) -> &'a str {
+        self.view
+    }
+}
+
+impl Deref for LocatedStr<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.view
+    }
+}
+
+impl Shrink for LocatedStr<'_> {
+    type Slice = str;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>
+    {
+        let (start, _) = range::endpoints(&range, self.view.len());
+        let mut view = self.view;
+        view.try_shrink(range)?;
+        self.location = self.location.advance(&self.view[..start]);
+        self.view = view;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.view.len()
+    }
+}
+
+impl TakeWhile for LocatedStr<'_> {
+    type Item = char;
+
+    fn take_while<P: FnMut(&char) -> bool>(&mut self, pred: P) -> Self {
+        let taken = LocatedStr::with_location(self.view.take_while(pred), self.location);
+        self.location = self.location.advance(&taken);
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn multibyte_and_line_endings() {
+        let mut text = LocatedStr::new("\u{e9}\u{e9}\r\n\n\u{1f980}x");
+        text.shrink(2..);
+        assert_eq!(text.location(), Location { line: 1, column: 2 });
+        assert!(text.try_shrink(1..).is_err());
+        text.shrink(3..);
+        assert_eq!(text.location().to_string(), "1:4");
+        text.shrink(1..);
+        text.shrink(5..);
+        assert_eq!(text.as_str(), "x");
+        assert_eq!(text.location(), Location { line: 3, column: 2 });
+    }
+}
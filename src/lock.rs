@@ -0,0 +1,133 @@
+//! Shrinking slices behind the mapped lock guards of `lock_api`, which `parking_lot` uses.
+//!
+//! Mapping a guard consumes it, so a guard has to be wrapped in a [`ShrinkMapped`] to be shrunk
+//! in place, like a [`RefMut`](core::cell::RefMut) in a
+//! [`ShrinkRefMut`](crate::cell::ShrinkRefMut). Guards for mutexes and for read and write
+//! locks are supported, to slices and to string slices. The standard library's mapped guards
+//! are still unstable, so only those of `lock_api` are.
+//!
+//! ```rust
+//! use parking_lot::{Mutex, MutexGuard};
+//! use shrink_slice::lock::ShrinkMapped;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let buffer = Mutex::new(b"HDRpayload".to_vec());
+//! let mut body = ShrinkMapped::new(MutexGuard::map(buffer.lock(), |vec| &mut vec[..]));
+//! body.shrink(3..);
+//! body.make_ascii_uppercase();
+//! drop(body);
+//! assert_eq!(*buffer.lock(), b"HDRPAYLOAD");
+//! ```
+
+use crate::{range, Shrink, ShrinkError};
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::slice::SliceIndex;
+use lock_api::{MappedMutexGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard};
+use lock_api::{RawMutex, RawRwLock};
+
+/// A shrinkable mapped lock guard to a slice or string slice.
+#[derive(Debug)]
+pub struct ShrinkMapped<G> {
+    // Only `None` while the guard is being remapped.
+    guard: Option<G>,
+}
+
+impl<G: Deref> ShrinkMapped<G> {
+    /// Wrap `guard` so that it can be shrunk.
+    pub fn new(guard: G) -> Self {
+        ShrinkMapped { guard: Some(guard) }
+    }
+
+    /// Give up the wrapper, returning the guard.
+    pub fn into_inner(self) -> G {
+        self.guard.expect("guard is present")
+    }
+}
+
+impl<G: Deref> Deref for ShrinkMapped<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &G::Target {
+        self.guard.as_ref().expect("guard is present")
+    }
+}
+
+impl<G: DerefMut> DerefMut for ShrinkMapped<G> {
+    fn deref_mut(&mut self) -> &mut G::Target {
+        self.guard.as_mut().expect("guard is present")
+    }
+}
+
+/// Implement [`Shrink`] for a wrapped guard type, to both slices and string slices, by remapping
+/// it with its own `map`, whose closure takes a reference with the given mutability.
+macro_rules! impl_shrink_mapped {
+    ($guard:ident<$raw:ident>, $($mut:tt)?) => {
+        impl<R: $raw, T> Shrink for ShrinkMapped<$guard<'_, R, [T]>> {
+            type Slice = [T];
+
+            fn try_shrink<I>(&mut self, range: I) -> Result<(), ShrinkError>
+            where I: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+            {
+                let range = range::try_resolve(range, self.len())?;
+                let guard = self.guard.take().expect("guard is present");
+                self.guard = Some($guard::map(guard, |slice| &$($mut)? slice[range]));
+                Ok(())
+            }
+
+            fn len(&self) -> usize {
+                <[T]>::len(self)
+            }
+        }
+
+        impl<R: $raw> Shrink for ShrinkMapped<$guard<'_, R, str>> {
+            type Slice = str;
+
+            fn try_shrink<I>(&mut self, range: I) -> Result<(), ShrinkError>
+            where I: SliceIndex<str, Output = str> + RangeBounds<usize>
+            {
+                let mut view: &str = self;
+                view.try_shrink(range)?;
+                let start = range::offset_in(self.as_bytes(), view.as_bytes())
+                    .expect("substring is within the string");
+                let end = start + view.len();
+                let guard = self.guard.take().expect("guard is present");
+                self.guard = Some($guard::map(guard, |s| &$($mut)? s[start..end]));
+                Ok(())
+            }
+
+            fn len(&self) -> usize {
+                str::len(self)
+            }
+        }
+    };
+}
+
+impl_shrink_mapped!(MappedMutexGuard<RawMutex>, mut);
+impl_shrink_mapped!(MappedRwLockReadGuard<RawRwLock>,);
+impl_shrink_mapped!(MappedRwLockWriteGuard<RawRwLock>, mut);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+    use crate::ShrinkExt;
+
+    #[test]
+    fn read_and_write_guards() {
+        let lock = RwLock::new(String::from("\u{e9}t\u{e9}"));
+        {
+            let guard = RwLockWriteGuard::map(lock.write(), String::as_mut_str);
+            let mut text = ShrinkMapped::new(guard);
+            assert!(text.try_shrink(1..).is_err());
+            text.shrink(2..);
+            text.make_ascii_uppercase();
+            assert_eq!(&*text.into_inner(), "T\u{e9}");
+        }
+        let mut first = ShrinkMapped::new(RwLockReadGuard::map(lock.read(), |s| s.as_bytes()));
+        let mut second = ShrinkMapped::new(RwLockReadGuard::map(lock.read(), String::as_str));
+        first.shrink(..2);
+        second.shrink(2..);
+        assert_eq!((&*first, &*second), (&b"\xc3\xa9"[..], "T\u{e9}"));
+        assert!(lock.try_write().is_none());
+    }
+}
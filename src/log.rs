@@ -0,0 +1,149 @@
+//! A fixed-size text log that overwrites its oldest content when full.
+//!
+//! [`RingLog`] needs no allocation, so it can be used from panic handlers, bootloaders and other
+//! places where a regular logger is unavailable. Write to it with [`write!`] and [`writeln!`].
+//!
+//! ```rust
+//! use core::fmt::Write;
+//! use shrink_slice::log::RingLog;
+//!
+//! let mut log = RingLog::new([0; 16]);
+//! writeln!(log, "boot").unwrap();
+//! writeln!(log, "stage {} ok", 2).unwrap();
+//! writeln!(log, "done").unwrap();
+//!
+//! let (older, newer) = log.as_slices();
+//! assert_eq!([older, newer].concat(), b"stage 2 ok\ndone\n");
+//! assert_eq!(log.overwritten(), 5);
+//! ```
+
+use crate::ShrinkExt;
+use core::fmt;
+
+/// A text log over a fixed buffer, which evicts the oldest bytes to make room for new ones.
+///
+/// The buffer can be borrowed, as in `RingLog<&mut [u8]>`, or owned, as in `RingLog<[u8; N]>`.
+#[derive(Clone, Debug)]
+pub struct RingLog<B> {
+    buf: B,
+    start: usize,
+    len: usize,
+    overwritten: usize,
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> RingLog<B> {
+    /// An empty log over `buf`. Its current contents are ignored.
+    pub fn new(buf: B) -> Self {
+        RingLog { buf, start: 0, len: 0, overwritten: 0 }
+    }
+
+    /// The number of bytes the log can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// The number of bytes currently held.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether nothing has been logged since the log was created or cleared.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The total number of bytes that have been evicted to make room for newer ones.
+    pub fn overwritten(&self) -> usize {
+        self.overwritten
+    }
+
+    /// The logged bytes, oldest first, as two slices that are to be read one after the other.
+    ///
+    /// If anything has been overwritten, the oldest line is likely to be cut short, possibly in
+    /// the middle of a multi-byte character.
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let buf = self.buf.as_ref();
+        let end = self.start + self.len;
+        if end <= buf.len() {
+            (&buf[self.start..end], &[])
+        } else {
+            (&buf[self.start..], &buf[..end - buf.len()])
+        }
+    }
+
+    /// Discard everything that has been logged.
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+        self.overwritten = 0;
+    }
+
+    /// Append raw bytes to the log, evicting old ones as needed.
+    pub fn write_bytes(&mut self, mut bytes: &[u8]) {
+        let cap = self.capacity();
+        if cap == 0 {
+            self.overwritten += bytes.len();
+            return;
+        }
+        if bytes.len() > cap {
+            // Only the tail would survive anyway.
+            let skip = bytes.len() - cap;
+            self.overwritten += skip;
+            bytes.shrink(skip..);
+        }
+        while !bytes.is_empty() {
+            let buf = self.buf.as_mut();
+            let write_at = (self.start + self.len) % cap;
+            let n = bytes.len().min(cap - write_at);
+            buf[write_at..write_at + n].copy_from_slice(&bytes[..n]);
+            bytes.shrink(n..);
+
+            let evicted = (self.len + n).saturating_sub(cap);
+            self.start = (self.start + evicted) % cap;
+            self.len += n - evicted;
+            self.overwritten += evicted;
+        }
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> fmt::Write for RingLog<B> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    fn contents<B: AsRef<[u8]> + AsMut<[u8]>>(log: &RingLog<B>) -> Vec<u8> {
+        let (a, b) = log.as_slices();
+        [a, b].concat()
+    }
+
+    #[test]
+    fn borrowed_buffer_wraps() {
+        let mut buffer = [0; 5];
+        let mut log = RingLog::new(&mut buffer[..]);
+        log.write_str("abc").unwrap();
+        assert_eq!(contents(&log), b"abc");
+        log.write_str("defg").unwrap();
+        assert_eq!(log.as_slices(), (&b"cde"[..], &b"fg"[..]));
+        log.write_str("0123456789").unwrap();
+        assert_eq!(contents(&log), b"56789");
+        assert_eq!(log.overwritten(), 12);
+        assert_eq!(log.len(), log.capacity());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut log = RingLog::new([0; 0]);
+        write!(log, "{}", 42).unwrap();
+        assert!(log.is_empty());
+        assert_eq!(log.overwritten(), 2);
+        log.clear();
+        assert_eq!(log.overwritten(), 0);
+    }
+}
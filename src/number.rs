@@ -0,0 +1,153 @@
+//! Consuming numeric literals from the front of a string slice.
+
+use crate::ShrinkExt;
+use core::{fmt, str::FromStr};
+
+fn digits(bytes: &[u8], start: usize) -> usize {
+    bytes[start..].iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+fn sign(bytes: &[u8]) -> usize {
+    matches!(bytes.first(), Some(b'+') | Some(b'-')) as usize
+}
+
+/// The length of the longest integer literal at the front of `bytes`, or `None` if there is no
+/// digit after the optional sign.
+fn scan_int(bytes: &[u8]) -> Option<usize> {
+    let start = sign(bytes);
+    match digits(bytes, start) {
+        0 => None,
+        n => Some(start + n),
+    }
+}
+
+/// The length of the longest float literal at the front of `bytes`, or `None` if the mantissa has
+/// no digits.
+fn scan_float(bytes: &[u8]) -> Option<usize> {
+    let mut end = sign(bytes);
+    let int = digits(bytes, end);
+    end += int;
+    let mut frac = 0;
+    if bytes.get(end) == Some(&b'.') {
+        frac = digits(bytes, end + 1);
+        end += 1 + frac;
+    }
+    if int + frac == 0 {
+        return None;
+    }
+    // An exponent only counts if it has digits, so `2em` scans as `2`.
+    if let Some(b'e') | Some(b'E') = bytes.get(end) {
+        let exp_start = end + 1 + sign(&bytes[end + 1..]);
+        match digits(bytes, exp_start) {
+            0 => {}
+            n => end = exp_start + n,
+        }
+    }
+    Some(end)
+}
+
+fn take<T: FromStr>(s: &mut &str, scan: fn(&[u8]) -> Option<usize>) -> Result<T, NumError<T::Err>> {
+    let len = scan(s.as_bytes()).ok_or_else(|| NumError::NoDigits { found: s.chars().next() })?;
+    let value = s[..len].parse().map_err(NumError::Parse)?;
+    s.shrink(len..);
+    Ok(value)
+}
+
+/// Extension trait for lexing numbers at the front of a string slice.
+///
+/// Both methods scan the longest literal they can, parse it with [`FromStr`], and shrink past it.
+/// Whatever stopped the scan is left at the front of the slice. On error, nothing is consumed.
+///
+/// ```rust
+/// use shrink_slice::number::{NumError, TakeNumber};
+///
+/// let mut expr = "-12+3.5e2*x";
+/// assert_eq!(expr.take_int::<i32>(), Ok(-12));
+/// assert_eq!(expr.take_float::<f64>(), Ok(350.0));
+/// assert_eq!(expr, "*x");
+/// assert!(matches!(expr.take_int::<i32>(), Err(NumError::NoDigits { found: Some('*') })));
+/// ```
+pub trait TakeNumber {
+    /// Consume an integer literal: an optional `+` or `-` followed by decimal digits.
+    ///
+    /// A literal that doesn't fit in `T`, or a sign that `T` doesn't accept, is reported as
+    /// [`NumError::Parse`].
+    fn take_int<T: FromStr>(&mut self) -> Result<T, NumError<T::Err>>;
+
+    /// Consume a decimal float literal: an optional sign, digits with an optional fractional
+    /// part, and an optional exponent.
+    fn take_float<T: FromStr>(&mut self) -> Result<T, NumError<T::Err>>;
+}
+
+impl TakeNumber for &str {
+    fn take_int<T: FromStr>(&mut self) -> Result<T, NumError<T::Err>> {
+        take(self, scan_int)
+    }
+
+    fn take_float<T: FromStr>(&mut self) -> Result<T, NumError<T::Err>> {
+        take(self, scan_float)
+    }
+}
+
+/// This error signifies that no number could be taken from the front of a string.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumError<E> {
+    /// The string did not start with a numeric literal.
+    NoDigits {
+        /// The character that stopped the scan, or `None` at the end of the string.
+        found: Option<char>,
+    },
+    /// The literal was scanned but could not be parsed, typically because it overflowed.
+    Parse(E),
+}
+
+impl<E: fmt::Display> fmt::Display for NumError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NumError::NoDigits { found: Some(c) } => write!(f, "expected a number, found {:?}", c),
+            NumError::NoDigits { found: None } => {
+                f.write_str("expected a number, found end of input")
+            }
+            NumError::Parse(e) => write!(f, "invalid number: {}", e),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for NumError<E> { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_leaves_input() {
+        let mut input = "300,";
+        assert!(matches!(input.take_int::<u8>(), Err(NumError::Parse(_))));
+        assert_eq!(input, "300,");
+        assert_eq!(input.take_int::<u16>(), Ok(300));
+        assert_eq!(input, ",");
+    }
+
+    #[test]
+    fn sign_alone_is_not_a_number() {
+        let mut input = "-x";
+        assert!(matches!(input.take_int::<i8>(), Err(NumError::NoDigits { found: Some('-') })));
+        let mut input = "";
+        assert!(matches!(input.take_float::<f32>(), Err(NumError::NoDigits { found: None })));
+    }
+
+    #[test]
+    fn float_shapes() {
+        for (input, value, rest) in [
+            ("1.", 1.0, ""),
+            (".25x", 0.25, "x"),
+            ("2em", 2.0, "em"),
+            ("1e-2.", 0.01, "."),
+            ("+7E+1", 70.0, ""),
+        ] {
+            let mut slice = input;
+            assert_eq!(slice.take_float::<f64>(), Ok(value));
+            assert_eq!(slice, rest);
+        }
+    }
+}
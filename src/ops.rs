@@ -0,0 +1,81 @@
+//! `-=` as shorthand for [`ShrinkExt::shrink_front`], for terse cursor code.
+//!
+//! This module is only available with the `ops` feature. It has no items of its own: it
+//! implements [`SubAssign<usize>`] for the crate's shrinkable wrappers, so that `input -= n`
+//! drops `n` elements from the front, panicking exactly when `shrink_front(n)` would. Operators
+//! cannot be implemented for plain slice references, since neither the trait nor the type
+//! belongs to this crate, so wrap them in a [`Tracked`] instead, which also keeps count of what
+//! was dropped.
+//!
+//! ```rust
+//! use shrink_slice::tracked::TrackedSlice;
+//!
+//! let mut input = TrackedSlice::new(&b"\x00\x04LISTdata"[..]);
+//! input -= 2;
+//! assert_eq!(&input[..4], b"LIST");
+//! input -= 4;
+//! assert_eq!((*input, input.offset()), (&b"data"[..], 6));
+//! ```
+
+use crate::deque::DequeWindow;
+use crate::located::LocatedStr;
+use crate::strided::Strided;
+use crate::tracked::Tracked;
+use crate::window::Window;
+use crate::{Shrink, ShrinkExt};
+use core::ops::{RangeFrom, SubAssign};
+use core::slice::SliceIndex;
+
+impl<S: Shrink> SubAssign<usize> for Tracked<S>
+where RangeFrom<usize>: SliceIndex<S::Slice, Output = S::Slice>,
+{
+    #[track_caller]
+    fn sub_assign(&mut self, n: usize) {
+        self.shrink_front(n);
+    }
+}
+
+macro_rules! sub_assign {
+    ($($ty:ty),*) => {
+        $(
+            impl<T> SubAssign<usize> for $ty {
+                #[track_caller]
+                fn sub_assign(&mut self, n: usize) {
+                    self.shrink_front(n);
+                }
+            }
+        )*
+    };
+}
+
+sub_assign!(DequeWindow<'_, T>, Window<'_, T>, Strided<'_, T>);
+
+impl SubAssign<usize> for LocatedStr<'_> {
+    #[track_caller]
+    fn sub_assign(&mut self, n: usize) {
+        self.shrink_front(n);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_from_the_front() {
+        let mut text = LocatedStr::new("\u{e9}t\u{e9}");
+        text -= 2;
+        assert_eq!(text.location().column, 2);
+        let data = [1, 2, 3];
+        let mut window = Window::new(&data[..]);
+        window -= 3;
+        assert!(window.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "byte index 2 of range 2..3 is not a char boundary"]
+    fn panics_like_shrink_front() {
+        let mut text = LocatedStr::new("t\u{e9}");
+        text -= 2;
+    }
+}
@@ -0,0 +1,413 @@
+//! Narrowing owned containers to a range of their contents.
+//!
+//! Unlike [`Shrink`](crate::Shrink), which only moves a reference, these operations drop the
+//! elements outside the range and move the remaining ones to the front of the buffer, so they
+//! cost time proportional to the number of elements involved. A `Cow` is shrunk either way,
+//! depending on whether it is borrowed or owned.
+//!
+//! The impls for `Vec`, `String`, `Box<[T]>` and `Cow` need the `alloc` feature. With the
+//! `heapless`, `arrayvec` and `smallvec` features, the vectors of those crates are narrowed the
+//! same way, keeping their inline storage, and so are the strings of `heapless`. With the
+//! `bytes` feature, so are `Bytes` and `BytesMut`.
+
+use crate::{range, Shrink, ShrinkError};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::mem;
+#[cfg(any(feature = "arrayvec", feature = "heapless", feature = "smallvec"))]
+use core::ops::Range;
+use core::ops::RangeBounds;
+#[cfg(any(feature = "alloc", feature = "arrayvec", feature = "heapless", feature = "smallvec"))]
+use core::slice::SliceIndex;
+
+/// Extension trait for owned containers whose contents can be narrowed to a range.
+///
+/// This is the one trait to be generic over when any owned buffer will do, whichever crate it
+/// comes from. Every implementation keeps exactly the elements in the range, in order, but what
+/// happens to the storage differs:
+///
+/// - `Vec`, `String`, and the vectors and strings of `arrayvec`, `heapless` and `smallvec` move
+///   the kept elements to the front and keep their capacity, so they never allocate.
+/// - `BytesMut` gives up the bytes before the range without moving the rest, so its capacity
+///   shrinks by that many, and keeps the capacity after it.
+/// - `Bytes` only narrows its view of the shared buffer.
+/// - `Box<[T]>` has no spare capacity, so it is reallocated to fit its new length.
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use shrink_slice::owned::OwnedShrink;
+/// use shrink_slice::ShrinkError;
+///
+/// /// Strip a length-prefixed header off any owned buffer of bytes.
+/// fn strip_header<B: OwnedShrink + AsRef<[u8]>>(buf: &mut B) -> Result<(), ShrinkError> {
+///     let header = usize::from(buf.as_ref().first().copied().unwrap_or(0));
+///     buf.try_shrink_owned(1 + header..)
+/// }
+///
+/// let mut vec = b"\x02hibody".to_vec();
+/// strip_header(&mut vec).unwrap();
+/// assert_eq!(vec, b"body");
+/// let mut boxed: Box<[u8]> = b"\x09short".to_vec().into();
+/// assert!(strip_header(&mut boxed).is_err());
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+pub trait OwnedShrink {
+    /// Keep only the elements in `range`, dropping the rest.
+    ///
+    /// If the range is out of bounds, or for strings, if either end lands within a multi-byte
+    /// character, an error is returned and the container is left untouched.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() {
+    /// use shrink_slice::owned::OwnedShrink;
+    ///
+    /// let mut line = String::from("  key = value\n");
+    /// line.try_shrink_owned(2..13).unwrap();
+    /// assert_eq!(line, "key = value");
+    /// assert!(line.try_shrink_owned(..20).is_err());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError>;
+}
+
+/// The panicking counterpart of [`try_shrink_owned`](OwnedShrink::try_shrink_owned),
+/// implemented for every [`OwnedShrink`] container.
+pub trait OwnedShrinkExt: OwnedShrink {
+    /// Keep only the elements in `range`, panicking if it is decreasing or out of bounds, or for
+    /// strings, if either end lands within a multi-byte character.
+    #[track_caller]
+    fn shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) {
+        if let Err(e) = self.try_shrink_owned(range) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<S: OwnedShrink + ?Sized> OwnedShrinkExt for S { }
+
+#[cfg(feature = "alloc")]
+impl<T> OwnedShrink for Vec<T> {
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range, self.len())?;
+        self.truncate(range.end);
+        self.drain(..range.start);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedShrink for String {
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range, self.len())?;
+        if !self.is_char_boundary(range.start) || !self.is_char_boundary(range.end) {
+            return Err(ShrinkError::for_str(range, self));
+        }
+        self.truncate(range.end);
+        self.drain(..range.start);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> OwnedShrink for Box<[T]> {
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range, self.len())?;
+        let mut vec = Vec::from(mem::take(self));
+        vec.truncate(range.end);
+        vec.drain(..range.start);
+        *self = vec.into_boxed_slice();
+        Ok(())
+    }
+}
+
+macro_rules! impl_shrink_owned {
+    ($(#[$attr:meta])* [$($generics:tt)*] $ty:ty, $slice:ty) => {
+        $(#[$attr])*
+        impl<$($generics)*> Shrink for $ty {
+            type Slice = $slice;
+
+            fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+            where R: SliceIndex<$slice, Output = $slice> + RangeBounds<usize>
+            {
+                self.try_shrink_owned(range)
+            }
+
+            fn len(&self) -> usize {
+                <$slice>::len(self)
+            }
+        }
+    };
+}
+
+impl_shrink_owned! {
+    /// Shrinking drops the elements outside the range, like
+    /// [`try_shrink_owned`](OwnedShrink::try_shrink_owned), so that the vector can be used where a
+    /// shrinkable slice is expected.
+    #[cfg(feature = "alloc")]
+    [T] Vec<T>, [T]
+}
+impl_shrink_owned!(#[cfg(feature = "alloc")] [T] &mut Vec<T>, [T]);
+impl_shrink_owned! {
+    /// Shrinking drops the text outside the range, like
+    /// [`try_shrink_owned`](OwnedShrink::try_shrink_owned), so that the string can be used where a
+    /// shrinkable string slice is expected.
+    #[cfg(feature = "alloc")]
+    [] String, str
+}
+impl_shrink_owned!(#[cfg(feature = "alloc")] [] &mut String, str);
+
+/// Move the elements of `slice` that are in `range` to its front, returning how many there are.
+/// The others end up after them, ready to be truncated away.
+#[cfg(any(feature = "arrayvec", feature = "heapless", feature = "smallvec"))]
+fn move_to_front<T>(slice: &mut [T], range: Range<usize>) -> usize {
+    slice[..range.end].rotate_left(range.start);
+    range.len()
+}
+
+/// Implement [`OwnedShrink`] for an inline vector type by rotating the kept elements to the
+/// front and truncating, so that no allocation is involved.
+macro_rules! impl_owned_inline {
+    ($(#[$attr:meta])* [$($generics:tt)*] $ty:ty) => {
+        $(#[$attr])*
+        impl<$($generics)*> OwnedShrink for $ty {
+            fn try_shrink_owned<R>(&mut self, range: R) -> Result<(), ShrinkError>
+            where R: RangeBounds<usize>,
+            {
+                let range = range::try_resolve(range, self.len())?;
+                let kept = move_to_front(self, range);
+                self.truncate(kept);
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_owned_inline!(#[cfg(feature = "arrayvec")] [T, const CAP: usize] arrayvec::ArrayVec<T, CAP>);
+impl_shrink_owned! {
+    #[cfg(feature = "arrayvec")]
+    [T, const CAP: usize] arrayvec::ArrayVec<T, CAP>, [T]
+}
+impl_shrink_owned! {
+    #[cfg(feature = "arrayvec")]
+    [T, const CAP: usize] &mut arrayvec::ArrayVec<T, CAP>, [T]
+}
+impl_owned_inline!(#[cfg(feature = "heapless")] [T, const N: usize] heapless::Vec<T, N>);
+impl_shrink_owned!(#[cfg(feature = "heapless")] [T, const N: usize] heapless::Vec<T, N>, [T]);
+impl_shrink_owned!(#[cfg(feature = "heapless")] [T, const N: usize] &mut heapless::Vec<T, N>, [T]);
+#[cfg(feature = "heapless")]
+impl<const N: usize> OwnedShrink for heapless::String<N> {
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range, self.len())?;
+        if !self.is_char_boundary(range.start) || !self.is_char_boundary(range.end) {
+            return Err(ShrinkError::for_str(range, self));
+        }
+        let mut kept = heapless::String::new();
+        // The kept text is no longer than the whole, so it always fits.
+        let _ = kept.push_str(&self[range]);
+        *self = kept;
+        Ok(())
+    }
+}
+impl_shrink_owned!(#[cfg(feature = "heapless")] [const N: usize] heapless::String<N>, str);
+impl_shrink_owned!(#[cfg(feature = "heapless")] [const N: usize] &mut heapless::String<N>, str);
+impl_owned_inline!(#[cfg(feature = "smallvec")] [A: smallvec::Array] smallvec::SmallVec<A>);
+impl_shrink_owned! {
+    #[cfg(feature = "smallvec")]
+    [A: smallvec::Array] smallvec::SmallVec<A>, [A::Item]
+}
+impl_shrink_owned! {
+    #[cfg(feature = "smallvec")]
+    [A: smallvec::Array] &mut smallvec::SmallVec<A>, [A::Item]
+}
+
+#[cfg(feature = "bytes")]
+impl OwnedShrink for bytes::Bytes {
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range, self.len())?;
+        self.try_shrink(range)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl OwnedShrink for bytes::BytesMut {
+    fn try_shrink_owned<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range, self.len())?;
+        self.try_shrink(range)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Borrowed slices are shrunk in place, while owned ones are narrowed like a `Vec`.
+impl<T: Clone> Shrink for Cow<'_, [T]> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        match self {
+            Cow::Borrowed(slice) => slice.try_shrink(range),
+            Cow::Owned(vec) => vec.try_shrink_owned(range),
+        }
+    }
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Borrowed string slices are shrunk in place, while owned ones are narrowed like a `String`.
+impl Shrink for Cow<'_, str> {
+    type Slice = str;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<str, Output = str> + RangeBounds<usize>
+    {
+        match self {
+            Cow::Borrowed(s) => s.try_shrink(range),
+            Cow::Owned(string) => string.try_shrink_owned(range),
+        }
+    }
+
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vec_keeps_capacity() {
+        let mut vec = Vec::with_capacity(16);
+        vec.extend_from_slice(b"0123456789");
+        vec.try_shrink_owned(3..=5).unwrap();
+        assert_eq!(vec, b"345");
+        assert_eq!(vec.capacity(), 16);
+        let err = ShrinkError::OutOfBounds { start: 2, end: 4, len: 3 };
+        assert_eq!(vec.try_shrink_owned(2..4), Err(err));
+        assert_eq!(vec, b"345");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn string_boundaries() {
+        let mut text = String::from("\u{e5}\u{e4}\u{f6}");
+        let err = ShrinkError::NotCharBoundary { start: 1, end: 6, len: 6, index: 1 };
+        assert_eq!(text.try_shrink_owned(1..), Err(err));
+        assert_eq!(text, "\u{e5}\u{e4}\u{f6}");
+        text.try_shrink_owned(2..4).unwrap();
+        assert_eq!(text, "\u{e4}");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed_slice() {
+        let mut boxed: Box<[String]> = vec!["a".into(), "b".into(), "c".into()].into();
+        boxed.try_shrink_owned(1..).unwrap();
+        assert_eq!(&*boxed, ["b", "c"]);
+        assert!(boxed.try_shrink_owned(..=2).is_err());
+        assert_eq!(boxed.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cow_either_way() {
+        let text = "  padded  ";
+        let mut borrowed = Cow::Borrowed(text);
+        borrowed.shrink(2..8);
+        assert!(matches!(borrowed, Cow::Borrowed(s) if s.as_ptr() == text[2..].as_ptr()));
+        let mut owned: Cow<str> = Cow::Owned(String::from("\u{e9}t\u{e9}"));
+        assert!(owned.try_shrink(1..).is_err());
+        owned.shrink(2..);
+        assert!(matches!(&owned, Cow::Owned(s) if s == "t\u{e9}"));
+
+        let mut bytes: Cow<[u8]> = Cow::Owned(b"abc".to_vec());
+        bytes.shrink(..2);
+        assert_eq!((&*bytes, bytes.len()), (&b"ab"[..], 2));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn generic_over_owned() {
+        fn middle<S: Shrink<Slice = [u8]>>(mut buf: S) -> S {
+            buf.shrink(1..buf.len() - 1);
+            buf
+        }
+
+        let mut vec = b"[abc]".to_vec();
+        middle(&mut vec);
+        assert_eq!(vec, b"abc");
+        assert_eq!(middle(&b"(x)"[..]), b"x");
+
+        let mut string = String::from("\u{e9}t\u{e9}");
+        let view: &mut String = &mut string;
+        assert!(view.try_shrink(1..).is_err());
+        view.shrink(..3);
+        assert_eq!(string, "\u{e9}t");
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn arrayvec_vectors() {
+        let mut array: arrayvec::ArrayVec<u8, 8> = b"<abc>".iter().copied().collect();
+        array.shrink(1..4);
+        assert_eq!(&array[..], b"abc");
+        assert!(array.try_shrink_owned(..4).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn heapless_vectors() {
+        let mut heapless: heapless::Vec<String, 4> = heapless::Vec::new();
+        for s in ["a", "b", "c"] {
+            heapless.push(s.into()).unwrap();
+        }
+        let view = &mut heapless;
+        view.shrink(1..);
+        assert_eq!(heapless, ["b", "c"]);
+
+        let mut text: heapless::String<8> = heapless::String::new();
+        text.push_str("\u{e9}t\u{e9}").unwrap();
+        assert!(text.try_shrink_owned(1..).is_err());
+        text.shrink_owned(2..);
+        assert_eq!(text, "t\u{e9}");
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn smallvec_vectors() {
+        let mut small: smallvec::SmallVec<[i32; 2]> = smallvec::smallvec![1, 2, 3, 4];
+        small.try_shrink_owned(2..=2).unwrap();
+        assert_eq!((&small[..], small.capacity() >= 4), (&[3][..], true));
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes_give_up_the_front() {
+        let mut buf = bytes::BytesMut::with_capacity(16);
+        buf.extend_from_slice(b"xxpayload");
+        buf.shrink_owned(2..8);
+        assert_eq!((&buf[..], buf.capacity()), (&b"payloa"[..], 14));
+        let mut shared = buf.freeze();
+        assert!(shared.try_shrink_owned(..7).is_err());
+        shared.shrink_owned(..4);
+        assert_eq!(shared, b"payl"[..]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Walking paths one component at a time, by narrowing a borrowed `&Path` in place.
+//!
+//! Components are found with [`Path::components`], so separators and prefixes are recognized
+//! the way the platform does. Like that iterator, narrowing skips redundant separators and `.`
+//! components, except for a `.` at the very start of a relative path.
+
+use crate::NeedMore;
+use std::ffi::OsStr;
+use std::path::{Component, Path};
+
+/// Extension trait for stripping components off the ends of a borrowed path.
+///
+/// It is implemented for `&Path` and for `&OsStr`, which is treated as a path.
+///
+/// ```rust
+/// use std::path::{Component, Path};
+/// use shrink_slice::path::ShrinkPath;
+///
+/// let mut route = Path::new("/static/css/site.css");
+/// assert_eq!(route.strip_first_component(), Some(Component::RootDir));
+/// assert_eq!(route.strip_last_component(), Some(Component::Normal("site.css".as_ref())));
+/// assert_eq!(route, Path::new("static/css"));
+/// route.shrink_components(1).unwrap();
+/// assert_eq!(route, Path::new("css"));
+/// ```
+pub trait ShrinkPath<'a> {
+    /// Remove the first component and return it, or return `None` if there are none left.
+    fn strip_first_component(&mut self) -> Option<Component<'a>>;
+
+    /// Remove the last component and return it, or return `None` if there are none left.
+    fn strip_last_component(&mut self) -> Option<Component<'a>>;
+
+    /// Remove the first `n` components.
+    ///
+    /// If fewer than `n` remain, the path is left untouched and the error reports how many more
+    /// components would have been needed.
+    fn shrink_components(&mut self, n: usize) -> Result<(), NeedMore>;
+
+    /// Remove the last `n` components, or report how many more would have been needed, like
+    /// [`shrink_components`](ShrinkPath::shrink_components).
+    fn shrink_components_back(&mut self, n: usize) -> Result<(), NeedMore>;
+}
+
+impl<'a> ShrinkPath<'a> for &'a Path {
+    fn strip_first_component(&mut self) -> Option<Component<'a>> {
+        let mut components = self.components();
+        let first = components.next()?;
+        *self = components.as_path();
+        Some(first)
+    }
+
+    fn strip_last_component(&mut self) -> Option<Component<'a>> {
+        let mut components = self.components();
+        let last = components.next_back()?;
+        *self = components.as_path();
+        Some(last)
+    }
+
+    fn shrink_components(&mut self, n: usize) -> Result<(), NeedMore> {
+        let mut components = self.components();
+        for taken in 0..n {
+            components.next().ok_or(NeedMore(n - taken))?;
+        }
+        *self = components.as_path();
+        Ok(())
+    }
+
+    fn shrink_components_back(&mut self, n: usize) -> Result<(), NeedMore> {
+        let mut components = self.components();
+        for taken in 0..n {
+            components.next_back().ok_or(NeedMore(n - taken))?;
+        }
+        *self = components.as_path();
+        Ok(())
+    }
+}
+
+impl<'a> ShrinkPath<'a> for &'a OsStr {
+    fn strip_first_component(&mut self) -> Option<Component<'a>> {
+        with_path(self, |path| path.strip_first_component())
+    }
+
+    fn strip_last_component(&mut self) -> Option<Component<'a>> {
+        with_path(self, |path| path.strip_last_component())
+    }
+
+    fn shrink_components(&mut self, n: usize) -> Result<(), NeedMore> {
+        with_path(self, |path| path.shrink_components(n))
+    }
+
+    fn shrink_components_back(&mut self, n: usize) -> Result<(), NeedMore> {
+        with_path(self, |path| path.shrink_components_back(n))
+    }
+}
+
+/// Narrow `s` as a path with `f`.
+fn with_path<'a, T>(s: &mut &'a OsStr, f: impl FnOnce(&mut &'a Path) -> T) -> T {
+    let mut path = Path::new(*s);
+    let result = f(&mut path);
+    *s = path.as_os_str();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_os_str() {
+        let mut path = Path::new("./a//b/./c/");
+        assert_eq!(path.shrink_components(5), Err(NeedMore(1)));
+        assert_eq!(path, Path::new("./a//b/./c/"));
+        path.shrink_components_back(1).unwrap();
+        assert_eq!(path.strip_first_component(), Some(Component::CurDir));
+        assert_eq!(path, Path::new("a/b"));
+
+        let mut name = OsStr::new("dir/file.txt");
+        assert_eq!(name.strip_first_component(), Some(Component::Normal("dir".as_ref())));
+        assert_eq!(name, "file.txt");
+        name.shrink_components(1).unwrap();
+        assert_eq!((name, name.strip_last_component()), (OsStr::new(""), None));
+    }
+}
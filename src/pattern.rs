@@ -0,0 +1,148 @@
+//! Consuming string slices by pattern, for hand-written lexers.
+//!
+//! The standard library's `Pattern` trait is unstable, so [`Pattern`] stands in for it, with
+//! impls for the same kinds of patterns: a `char`, a `&str`, an array or slice of `char`s that
+//! each match, and a closure that picks `char`s.
+//!
+//! ```rust
+//! use shrink_slice::pattern::ConsumePattern;
+//!
+//! let mut input = "let answer = 42;";
+//! assert_eq!(input.consume("let"), Some("let"));
+//! input.consume(char::is_whitespace);
+//! assert_eq!(input.take_until_pat([' ', '=']), Some("answer"));
+//! assert_eq!(input.consume(" = "), Some(" = "));
+//! assert_eq!(input.take_until_pat(';'), Some("42"));
+//! assert_eq!((input.consume(';'), input), (Some(";"), ""));
+//! ```
+
+use crate::ShrinkStr;
+use core::mem;
+
+/// Something that can be searched for in a string slice.
+///
+/// Patterns that match single `char`s only ever match one at a time, so to skip a run of
+/// whitespace, use [`TakeWhile`](crate::TakeWhile) instead.
+pub trait Pattern {
+    /// The length in bytes of the match at the very start of `haystack`, if there is one.
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize>;
+
+    /// The byte index of the start of the first match in `haystack`, if there is one.
+    fn find_in(&mut self, haystack: &str) -> Option<usize>;
+}
+
+impl Pattern for char {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        haystack.starts_with(*self).then_some(self.len_utf8())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<usize> {
+        haystack.find(*self)
+    }
+}
+
+impl Pattern for &str {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        haystack.starts_with(*self).then_some(self.len())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<usize> {
+        haystack.find(*self)
+    }
+}
+
+impl<const N: usize> Pattern for [char; N] {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        self.as_slice().prefix_len(haystack)
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<usize> {
+        haystack.find(*self)
+    }
+}
+
+impl Pattern for &[char] {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        let c = haystack.chars().next().filter(|c| self.contains(c))?;
+        Some(c.len_utf8())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<usize> {
+        haystack.find(*self)
+    }
+}
+
+impl<F: FnMut(char) -> bool> Pattern for F {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        let c = haystack.chars().next().filter(|&c| self(c))?;
+        Some(c.len_utf8())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<usize> {
+        haystack.find(self)
+    }
+}
+
+/// Extension trait for shrinking string slices past text that matches a [`Pattern`].
+///
+/// The text that is consumed is returned with the slice's original lifetime. If the pattern does
+/// not match, `None` is returned and the slice is left untouched.
+pub trait ConsumePattern: ShrinkStr + Sized {
+    /// If the slice starts with a match of `pat`, split it off and return it.
+    fn consume<P: Pattern>(&mut self, pat: P) -> Option<Self>;
+
+    /// Split off everything before the first match of `pat`, leaving the match at the front of
+    /// the slice.
+    fn take_until_pat<P: Pattern>(&mut self, pat: P) -> Option<Self>;
+}
+
+impl ConsumePattern for &str {
+    fn consume<P: Pattern>(&mut self, mut pat: P) -> Option<Self> {
+        let (head, tail) = self.split_at(pat.prefix_len(self)?);
+        *self = tail;
+        Some(head)
+    }
+
+    fn take_until_pat<P: Pattern>(&mut self, mut pat: P) -> Option<Self> {
+        let (head, tail) = self.split_at(pat.find_in(self)?);
+        *self = tail;
+        Some(head)
+    }
+}
+
+impl ConsumePattern for &mut str {
+    fn consume<P: Pattern>(&mut self, mut pat: P) -> Option<Self> {
+        let at = pat.prefix_len(self)?;
+        let (head, tail) = mem::take(self).split_at_mut(at);
+        *self = tail;
+        Some(head)
+    }
+
+    fn take_until_pat<P: Pattern>(&mut self, mut pat: P) -> Option<Self> {
+        let at = pat.find_in(self)?;
+        let (head, tail) = mem::take(self).split_at_mut(at);
+        *self = tail;
+        Some(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_of_pattern() {
+        let mut owned = String::from("\u{e9}t\u{e9}: \u{1f980}");
+        let mut text: &mut str = &mut owned;
+        assert_eq!(text.consume('t'), None);
+        text.consume('\u{e9}').unwrap().make_ascii_uppercase();
+        assert_eq!(text.take_until_pat(&[':', ' '][..]).as_deref(), Some("t\u{e9}"));
+        assert_eq!(text.take_until_pat("x"), None);
+        assert_eq!(text.consume("").as_deref(), Some(""));
+        assert_eq!(text.consume([' ', ':']).as_deref(), Some(":"));
+        let mut spaces = 0;
+        text.consume(|c: char| { spaces += 1; c == ' ' }).unwrap();
+        assert_eq!((&*text, spaces), ("\u{1f980}", 1));
+        assert_eq!(text.take_until_pat(|c: char| !c.is_ascii()).as_deref(), Some(""));
+    }
+}
@@ -0,0 +1,127 @@
+//! Percent-decoding URL components into caller-provided buffers.
+
+use crate::ShrinkExt;
+use core::{fmt, str};
+
+fn hex(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode `input` up to the first `until` byte into `out`, returning how much input was read
+/// along with the decoded text.
+fn decode<'o>(input: &[u8], until: u8, out: &'o mut [u8]) -> Result<(usize, &'o str), PctError> {
+    let end = input.iter().position(|&b| b == until).unwrap_or(input.len());
+    let mut written = 0;
+    let mut i = 0;
+    while i < end {
+        let byte = match input[i] {
+            b'%' => {
+                let digits = input[..end].get(i + 1..i + 3);
+                let decoded = digits.and_then(|d| Some(hex(d[0])? << 4 | hex(d[1])?));
+                i += 3;
+                decoded.ok_or(PctError::InvalidEscape)?
+            }
+            b => {
+                i += 1;
+                b
+            }
+        };
+        *out.get_mut(written).ok_or(PctError::BufferTooSmall)? = byte;
+        written += 1;
+    }
+    let text = str::from_utf8(&out[..written]).map_err(|_| PctError::InvalidUtf8)?;
+    Ok((end, text))
+}
+
+/// Extension trait for consuming percent-encoded URL components.
+pub trait PctDecode {
+    /// Consume input up to, but not including, the first `until` byte (or the end of the input),
+    /// percent-decoding it into `out` and returning the decoded text.
+    ///
+    /// On error the input is left untouched, although `out` may have been partially written.
+    ///
+    /// ```rust
+    /// use shrink_slice::percent::PctDecode;
+    ///
+    /// let mut path = "caf%C3%A9/menu%20items";
+    /// let mut buf = [0; 32];
+    /// assert_eq!(path.take_pct_decoded(b'/', &mut buf), Ok("café"));
+    /// assert_eq!(path, "/menu%20items");
+    /// ```
+    fn take_pct_decoded<'o>(&mut self, until: u8, out: &'o mut [u8]) -> Result<&'o str, PctError>;
+}
+
+impl PctDecode for &[u8] {
+    fn take_pct_decoded<'o>(&mut self, until: u8, out: &'o mut [u8]) -> Result<&'o str, PctError> {
+        let (consumed, text) = decode(self, until, out)?;
+        self.shrink(consumed..);
+        Ok(text)
+    }
+}
+
+impl PctDecode for &str {
+    /// Panics if `until` is not an ASCII byte, since the input could then be cut in the middle of
+    /// a character.
+    fn take_pct_decoded<'o>(&mut self, until: u8, out: &'o mut [u8]) -> Result<&'o str, PctError> {
+        assert!(until.is_ascii(), "delimiter must be ASCII");
+        let (consumed, text) = decode(self.as_bytes(), until, out)?;
+        self.shrink(consumed..);
+        Ok(text)
+    }
+}
+
+/// This error signifies that a URL component could not be decoded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PctError {
+    /// A `%` was not followed by two hexadecimal digits.
+    InvalidEscape,
+    /// The decoded component did not fit in the output buffer.
+    BufferTooSmall,
+    /// The decoded component was not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for PctError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PctError::InvalidEscape => "invalid percent escape",
+            PctError::BufferTooSmall => "output buffer too small",
+            PctError::InvalidUtf8 => "decoded component is not valid UTF-8",
+        })
+    }
+}
+
+impl core::error::Error for PctError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_input() {
+        let mut query: &[u8] = b"a%2fb";
+        let mut buf = [0; 8];
+        assert_eq!(query.take_pct_decoded(b'&', &mut buf), Ok("a/b"));
+        assert!(query.is_empty());
+    }
+
+    #[test]
+    fn errors_leave_input() {
+        let mut buf = [0; 4];
+        for (input, err) in [
+            ("ab%4/c", PctError::InvalidEscape),
+            ("ab%4", PctError::InvalidEscape),
+            ("abcde", PctError::BufferTooSmall),
+            ("%FF", PctError::InvalidUtf8),
+        ] {
+            let mut slice = input;
+            assert_eq!(slice.take_pct_decoded(b'/', &mut buf), Err(err));
+            assert_eq!(slice, input);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Zero-copy reads of plain old data types, using the `bytemuck` crate.
+
+use crate::{NeedMore, ShrinkExt};
+use bytemuck::AnyBitPattern;
+use core::{fmt, mem};
+
+/// Extension trait for reading typed values out of the front of a byte slice without copying.
+///
+/// The values are references into the original bytes, with their lifetime, so the slice can keep
+/// shrinking while they are in use. Every byte pattern must be valid for the type, which
+/// [`AnyBitPattern`] guarantees.
+///
+/// ```rust
+/// use shrink_slice::pod::TakePod;
+///
+/// let mut input: &[u8] = b"PODS\x02\x00abcd!";
+/// let magic: &[u8; 4] = input.take_pod().unwrap();
+/// let count: &[u8; 2] = input.take_pod().unwrap();
+/// assert_eq!(magic, b"PODS");
+/// let records: &[[u8; 2]] = input.take_pod_slice(u16::from_le_bytes(*count).into()).unwrap();
+/// assert_eq!(records, [*b"ab", *b"cd"]);
+/// assert_eq!(input, b"!");
+/// ```
+pub trait TakePod<'a> {
+    /// Split a `T` off the front of the slice.
+    ///
+    /// If the slice is too short, or its start is not aligned for `T`, it is left untouched.
+    fn take_pod<T: AnyBitPattern>(&mut self) -> Result<&'a T, PodError>;
+
+    /// Split `count` consecutive `T`s off the front of the slice, under the same conditions as
+    /// [`take_pod`](TakePod::take_pod).
+    ///
+    /// For a zero-sized `T`, the returned slice is always empty.
+    fn take_pod_slice<T: AnyBitPattern>(&mut self, count: usize) -> Result<&'a [T], PodError>;
+}
+
+impl<'a> TakePod<'a> for &'a [u8] {
+    fn take_pod<T: AnyBitPattern>(&mut self) -> Result<&'a T, PodError> {
+        let head = prefix(self, mem::size_of::<T>())?;
+        let value = bytemuck::try_from_bytes(head).map_err(|_| misaligned::<T>())?;
+        self.shrink(head.len()..);
+        Ok(value)
+    }
+
+    fn take_pod_slice<T: AnyBitPattern>(&mut self, count: usize) -> Result<&'a [T], PodError> {
+        let head = prefix(self, mem::size_of::<T>().saturating_mul(count))?;
+        let values = bytemuck::try_cast_slice(head).map_err(|_| misaligned::<T>())?;
+        self.shrink(head.len()..);
+        Ok(values)
+    }
+}
+
+/// The first `size` bytes of `bytes`, or how many are missing.
+fn prefix(bytes: &[u8], size: usize) -> Result<&[u8], PodError> {
+    bytes.get(..size).ok_or_else(|| PodError::NeedMore(size - bytes.len()))
+}
+
+/// The error for a slice that is not aligned for `T`.
+fn misaligned<T>() -> PodError {
+    PodError::Misaligned { align: mem::align_of::<T>() }
+}
+
+/// The error returned when a value cannot be read by [`TakePod`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PodError {
+    /// The slice was too short, by the given number of bytes.
+    NeedMore(usize),
+    /// The start of the slice was not aligned for the type.
+    Misaligned {
+        /// The alignment the type requires.
+        align: usize,
+    },
+}
+
+impl From<NeedMore> for PodError {
+    fn from(NeedMore(n): NeedMore) -> Self {
+        PodError::NeedMore(n)
+    }
+}
+
+impl fmt::Display for PodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PodError::NeedMore(n) => NeedMore(*n).fmt(f),
+            PodError::Misaligned { align } => {
+                write!(f, "slice is not aligned to {} bytes", align)
+            }
+        }
+    }
+}
+
+impl core::error::Error for PodError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(align(4))]
+    struct Aligned([u8; 12]);
+
+    #[test]
+    fn errors_leave_the_slice() {
+        let storage = Aligned(*b"\x01\x00\x00\x00\x02\x00\x00\x00\x03\x00\x00\x00");
+        let mut input: &[u8] = &storage.0[..10];
+        assert_eq!(input.take_pod::<u32>(), Ok(&1));
+        assert_eq!(input.take_pod_slice::<u32>(2), Err(PodError::NeedMore(2)));
+        let mut odd = &input[1..];
+        assert_eq!(odd.take_pod::<u16>(), Err(PodError::Misaligned { align: 2 }));
+        assert_eq!(odd.len(), 5);
+        assert_eq!(input.take_pod_slice::<u16>(3), Ok(&[2, 0, 3][..]));
+        assert_eq!(input.take_pod_slice::<u8>(usize::MAX), Err(PodError::NeedMore(usize::MAX)));
+    }
+}
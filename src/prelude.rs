@@ -0,0 +1,71 @@
+//! The crate's extension traits and most common types, for importing all at once.
+//!
+//! ```rust
+//! use shrink_slice::prelude::*;
+//!
+//! let mut packet: &[u8] = b"\x2a\x01\x00\x00\x00 GET /index.html\r\n";
+//! assert_eq!(packet.get_u8(), Ok(42));
+//! assert_eq!(packet.get_u32_le(), Ok(1));
+//! packet.trim_ascii_start_in_place();
+//! assert_eq!(packet.take_until_byte(b' '), Some(&b"GET"[..]));
+//! packet.shrink_front(1);
+//! let line = packet.next_line().unwrap();
+//! assert_eq!((line.text, line.terminated), (&b"/index.html"[..], true));
+//! ```
+//!
+//! Only the traits for consuming, reading and writing slices and string slices are included,
+//! along with the wrapper types and errors they are used with most. Traits that methods of other
+//! crates could easily clash with, like the integrations with `bytes` or `winnow`, and those
+//! that need `unsafe` code, are left out and imported from their own modules instead.
+
+pub use crate::chars::{ShrinkChars, ShrinkCharsExt};
+pub use crate::chunks::{NextChunk, NextChunkArray};
+pub use crate::clamp::ShrinkClamped;
+pub use crate::endian::{BigEndian, ByteOrder, Endian, LittleEndian, NativeEndian};
+#[cfg(any(feature = "alloc", feature = "arrayvec", feature = "bytes", feature = "heapless",
+          feature = "smallvec"))]
+pub use crate::owned::{OwnedShrink, OwnedShrinkExt};
+pub use crate::pattern::ConsumePattern;
+pub use crate::read::{GetBytes, ShrinkRead};
+pub use crate::scan::{FindAndConsume, NextLine, ScanBytes};
+pub use crate::split::{SplitBy, SplitDisjointMut, SplitN, SplitThree, TakeMiddleMut};
+pub use crate::subslice::{OffsetFrom, ShrinkToSubslice, ShrinkToSubsliceExt};
+pub use crate::tracked::Tracked;
+pub use crate::trim::{TrimAscii, TrimByte, TrimWhile, TrimWhitespace};
+pub use crate::varint::{GetVarint, PutVarint};
+pub use crate::window::Window;
+pub use crate::write::{PutBytes, ShrinkWrite, SliceWriter};
+pub use crate::{NeedMore, Shrink, ShrinkBytes, ShrinkError, ShrinkExt, ShrinkStr};
+pub use crate::{Take, TakeArray, TakeElement, TakeWhile};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_ambiguous_methods() {
+        let mut text = "  key=\u{e9}t\u{e9} ";
+        text.trim_in_place();
+        assert_eq!(text.consume("key"), Some("key"));
+        text.shrink_chars(1);
+        assert_eq!((text.pop_char(), text.len()), (Some('\u{e9}'), 3));
+
+        let mut buffer = [0; 4];
+        let mut out: &mut [u8] = &mut buffer;
+        out.put_u16_le(0x0201).unwrap();
+        out.put_varint_u64(3).unwrap();
+        assert_eq!(out.len(), 1);
+        let mut input: &[u8] = &buffer;
+        assert_eq!(input.get_u16_le(), Ok(0x0201));
+        assert_eq!(input.get_varint_u64(), Ok(3));
+        assert_eq!(input.take_exact(2), Err(NeedMore(1)));
+    }
+
+    #[test]
+    fn panicking_methods_included() {
+        let mut input: &[u8] = b"\x01abc";
+        input.shrink_front(1);
+        assert_eq!(input.take_front(2), Some(&b"ab"[..]));
+        assert_eq!((input.take_back(2), input), (None, &b"c"[..]));
+    }
+}
@@ -0,0 +1,547 @@
+//! Arithmetic on ranges that are relative to shrunken views.
+//!
+//! After a slice has been shrunk a few times, indices into it no longer line up with indices into
+//! the buffer it came from. These helpers translate between the two.
+//!
+//! ```rust
+//! use shrink_slice::range;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let original = "let x = 1 +;";
+//! let mut view = original;
+//! view.shrink(8..);
+//! view.shrink(2..);
+//!
+//! // An error span found relative to the view...
+//! let span = 1..2;
+//! // ...can be mapped back onto the original text.
+//! let offset = range::offset_in(original.as_bytes(), view.as_bytes()).unwrap();
+//! let span = range::rebase(span, offset).unwrap();
+//! assert_eq!(&original[span], ";");
+//! ```
+
+use crate::{Shrink, ShrinkError};
+use core::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive,
+};
+use core::convert::TryFrom;
+use core::slice::SliceIndex;
+use core::str::FromStr;
+use core::{fmt, mem};
+
+/// Copy the bounds out of `range`, so that they outlive it.
+pub(crate) fn bounds<R: RangeBounds<usize>>(range: &R) -> (Bound<usize>, Bound<usize>) {
+    (range.start_bound().cloned(), range.end_bound().cloned())
+}
+
+/// The start and exclusive end of `range` over a slice of length `len`, saturating instead of
+/// overflowing. Unlike [`resolve`], this never fails, so it can describe invalid ranges too.
+pub(crate) fn endpoints<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i.saturating_add(1),
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
+
+/// Like [`resolve`], but describe a range that does not fit with a [`ShrinkError`].
+pub(crate) fn try_resolve<R>(range: R, len: usize) -> Result<Range<usize>, ShrinkError>
+where R: RangeBounds<usize>,
+{
+    let bounds = bounds(&range);
+    resolve(range, len).ok_or_else(|| ShrinkError::out_of_bounds(bounds, len))
+}
+
+/// The number of elements to drop from the front to shrink a slice of length `len` to `range`,
+/// for slices that can only be shrunk from the front.
+pub(crate) fn front_only<R>(range: R, len: usize) -> Result<usize, ShrinkError>
+where R: RangeBounds<usize>,
+{
+    let range = try_resolve(range, len)?;
+    if range.end != len {
+        return Err(ShrinkError::FrontOnly { start: range.start, end: range.end, len });
+    }
+    Ok(range.start)
+}
+
+/// Resolve any range of indices against a slice of length `len`.
+///
+/// Returns `None` if the range is decreasing, extends past `len`, or cannot be represented.
+pub fn resolve<R: RangeBounds<usize>>(range: R, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i.checked_add(1)?,
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+        return None;
+    }
+    Some(start..end)
+}
+
+/// Shift a range that is relative to a view starting at `offset` into the coordinates of the
+/// buffer the view was taken from.
+///
+/// Returns `None` on overflow.
+pub fn rebase(range: Range<usize>, offset: usize) -> Option<Range<usize>> {
+    Some(range.start.checked_add(offset)?..range.end.checked_add(offset)?)
+}
+
+/// Combine two successive shrinks into one: `inner` is relative to the view selected by `outer`,
+/// and the result selects the same elements relative to whatever `outer` was relative to.
+///
+/// Returns `None` if `inner` does not fit within `outer`.
+///
+/// ```rust
+/// use shrink_slice::range::compose;
+///
+/// let data = b"0123456789";
+/// assert_eq!(compose(2..8, 1..3), Some(3..5));
+/// assert_eq!(&data[2..8][1..3], &data[3..5]);
+/// assert_eq!(compose(2..8, 4..7), None);
+/// ```
+pub fn compose(outer: Range<usize>, inner: Range<usize>) -> Option<Range<usize>> {
+    let len = outer.end.checked_sub(outer.start)?;
+    let inner = resolve(inner, len)?;
+    rebase(inner, outer.start)
+}
+
+/// A window of indices that shrinks like the slices it indexes, so the same narrowing logic can
+/// drive both.
+///
+/// The window is shrunk to a sub-range relative to its own start, and its length is the number
+/// of indices it covers, zero for a decreasing range. It accepts the same ranges as slices of
+/// any element type, and `Slice` is `[()]` to stand for them.
+///
+/// Windows are made from exclusive ranges with `From`, and from inclusive ones, which may end
+/// too late to convert, with `TryFrom`.
+///
+/// ```rust
+/// use shrink_slice::range::IndexWindow;
+/// use shrink_slice::{Shrink, ShrinkExt};
+///
+/// let data = b"GET /index.html HTTP/1.1";
+/// let mut path = IndexWindow(0..data.len());
+/// path.shrink(4..);
+/// path.shrink_back(9);
+/// assert_eq!((path.len(), &data[path.range()]), (11, &b"/index.html"[..]));
+/// assert!(path.try_shrink(..12).is_err());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct IndexWindow(pub Range<usize>);
+
+impl IndexWindow {
+    /// The indices in the window.
+    pub fn range(&self) -> Range<usize> {
+        self.0.clone()
+    }
+}
+
+impl From<Range<usize>> for IndexWindow {
+    fn from(range: Range<usize>) -> Self {
+        IndexWindow(range)
+    }
+}
+
+/// Convert an inclusive range of indices to the window just past its end, or report it as out of
+/// bounds if it ends at `usize::MAX`, which no exclusive range can include.
+impl TryFrom<RangeInclusive<usize>> for IndexWindow {
+    type Error = ShrinkError;
+
+    fn try_from(range: RangeInclusive<usize>) -> Result<Self, ShrinkError> {
+        if *range.end() == usize::MAX && !range.is_empty() {
+            return Err(ShrinkError::out_of_bounds(range, usize::MAX));
+        }
+        let (start, end) = endpoints(&range, usize::MAX);
+        Ok(IndexWindow(start..end))
+    }
+}
+
+impl From<IndexWindow> for Range<usize> {
+    fn from(IndexWindow(range): IndexWindow) -> Self {
+        range
+    }
+}
+
+impl Shrink for IndexWindow {
+    type Slice = [()];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[()], Output = [()]> + RangeBounds<usize>
+    {
+        let range = try_resolve(range, self.len())?;
+        let start = self.0.start;
+        self.0 = start + range.start..start + range.end;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.0.end.saturating_sub(self.0.start)
+    }
+}
+
+/// Find where `view` starts within `original`, in elements, by comparing their addresses.
+///
+/// Returns `None` unless `view` lies entirely within `original`. For zero-sized elements, whose
+/// slices carry no address information, this is always `Some(0)` when `view` is no longer than
+/// `original`.
+pub fn offset_in<T>(original: &[T], view: &[T]) -> Option<usize> {
+    offset_of_raw(original, view)
+}
+
+/// Find where `view` starts within `original`, like [`offset_in`], for slices that may not be
+/// borrowed right now. Only their addresses and lengths are used.
+pub(crate) fn offset_of_raw<T>(original: *const [T], view: *const [T]) -> Option<usize> {
+    let size = mem::size_of::<T>();
+    if size == 0 {
+        return if view.len() <= original.len() { Some(0) } else { None };
+    }
+    let start = original as *const T as usize;
+    let byte_offset = (view as *const T as usize).checked_sub(start)?;
+    if byte_offset % size != 0 {
+        return None;
+    }
+    let offset = byte_offset / size;
+    if offset.checked_add(view.len())? > original.len() {
+        return None;
+    }
+    Some(offset)
+}
+
+/// An index into a slice, counted from the front or from the back.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Index {
+    Front(usize),
+    Back(usize),
+}
+
+impl Index {
+    fn resolve(self, len: usize) -> Option<usize> {
+        match self {
+            Index::Front(i) => Some(i),
+            Index::Back(i) => len.checked_sub(i),
+        }
+    }
+
+    /// Like [`Index::resolve`], but an index before the front is reported as the range `0..i`
+    /// being out of bounds, like [`Shrink::try_shrink_back`](crate::Shrink::try_shrink_back)
+    /// does.
+    fn try_resolve(self, len: usize) -> Result<usize, ShrinkError> {
+        self.resolve(len).ok_or(match self {
+            Index::Front(i) | Index::Back(i) => ShrinkError::OutOfBounds { start: 0, end: i, len },
+        })
+    }
+}
+
+/// Resolve a range of indices counted from either end against a slice of length `len`.
+fn try_resolve_indices(
+    start: Option<Index>,
+    end: Option<Index>,
+    inclusive: bool,
+    len: usize,
+) -> Result<Range<usize>, ShrinkError> {
+    let start = match start {
+        Some(i) => Bound::Included(i.try_resolve(len)?),
+        None => Bound::Unbounded,
+    };
+    let end = match end {
+        Some(i) if inclusive => Bound::Included(i.try_resolve(len)?),
+        Some(i) => Bound::Excluded(i.try_resolve(len)?),
+        None => Bound::Unbounded,
+    };
+    try_resolve((start, end), len)
+}
+
+/// An index counted from the end of a slice, so that `FromEnd(0)` is its length and
+/// `FromEnd(1)` is its last element.
+///
+/// Ranges of these are resolved against the length of the slice they shrink by
+/// [`Shrink::try_shrink_index`](crate::Shrink::try_shrink_index), so there is no need to call
+/// `len` first.
+///
+/// ```rust
+/// use shrink_slice::range::FromEnd;
+/// use shrink_slice::{Shrink, ShrinkExt};
+///
+/// let mut packet: &[u8] = b"payload\xca\xfe\xba\xbe";
+/// packet.shrink_index(..FromEnd(4));
+/// assert_eq!(packet, b"payload");
+/// packet.shrink_index(FromEnd(4)..);
+/// assert_eq!(packet, b"load");
+/// assert!(packet.try_shrink_index(FromEnd(5)..).is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct FromEnd(pub usize);
+
+/// A range that is resolved against the length of the slice it is used on, like the ranges of
+/// [`FromEnd`] indices.
+pub trait ShrinkIndex {
+    /// Resolve the range against a slice of length `len`, failing if it is decreasing or does
+    /// not fit.
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError>;
+}
+
+impl ShrinkIndex for Range<FromEnd> {
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError> {
+        let (start, end) = (Index::Back(self.start.0), Index::Back(self.end.0));
+        try_resolve_indices(Some(start), Some(end), false, len)
+    }
+}
+
+impl ShrinkIndex for RangeInclusive<FromEnd> {
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError> {
+        let (start, end) = (Index::Back(self.start().0), Index::Back(self.end().0));
+        try_resolve_indices(Some(start), Some(end), true, len)
+    }
+}
+
+impl ShrinkIndex for RangeFrom<FromEnd> {
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError> {
+        try_resolve_indices(Some(Index::Back(self.start.0)), None, false, len)
+    }
+}
+
+impl ShrinkIndex for RangeTo<FromEnd> {
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError> {
+        try_resolve_indices(None, Some(Index::Back(self.end.0)), false, len)
+    }
+}
+
+impl ShrinkIndex for RangeToInclusive<FromEnd> {
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError> {
+        try_resolve_indices(None, Some(Index::Back(self.end.0)), true, len)
+    }
+}
+
+impl ShrinkIndex for RangeExpr {
+    fn try_resolve(self, len: usize) -> Result<Range<usize>, ShrinkError> {
+        try_resolve_indices(self.start, self.end, self.inclusive, len)
+    }
+}
+
+impl FromStr for Index {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, ParseRangeError> {
+        let (index, digits): (fn(usize) -> Index, _) = match s.strip_prefix('-') {
+            Some(digits) => (Index::Back, digits),
+            None => (Index::Front, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseRangeError::InvalidIndex);
+        }
+        digits.parse().map(index).map_err(|_| ParseRangeError::IndexTooLarge)
+    }
+}
+
+/// A range parsed from a string, whose ends may be counted from the back of the slice.
+///
+/// Both Rust syntax (`a..b`, `a..=b`, `a..`, `..b`, `..=b`, `..`) and Python syntax (`a:b`, `a:`,
+/// `:b`, `:`) are accepted. A negative index counts from the back, so `-2` is two elements
+/// before the end and `-0` is the end itself. Whitespace around the indices is ignored.
+///
+/// ```rust
+/// use shrink_slice::range::RangeExpr;
+/// use shrink_slice::ShrinkExt;
+///
+/// let mut line = "[section]";
+/// let expr: RangeExpr = "1..-1".parse().unwrap();
+/// line.shrink(expr.resolve(line.len()).unwrap());
+/// assert_eq!(line, "section");
+///
+/// let expr: RangeExpr = ":-4".parse().unwrap();
+/// assert_eq!(expr.resolve(line.len()), Some(0..3));
+/// assert!("1..=".parse::<RangeExpr>().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RangeExpr {
+    start: Option<Index>,
+    end: Option<Index>,
+    inclusive: bool,
+}
+
+impl RangeExpr {
+    /// Resolve the expression against a slice of length `len`.
+    ///
+    /// Returns `None` if the range is decreasing or does not fit within `len`.
+    pub fn resolve(&self, len: usize) -> Option<Range<usize>> {
+        let start = match self.start {
+            Some(i) => Bound::Included(i.resolve(len)?),
+            None => Bound::Unbounded,
+        };
+        let end = match self.end {
+            Some(i) if self.inclusive => Bound::Included(i.resolve(len)?),
+            Some(i) => Bound::Excluded(i.resolve(len)?),
+            None => Bound::Unbounded,
+        };
+        resolve((start, end), len)
+    }
+}
+
+impl FromStr for RangeExpr {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, ParseRangeError> {
+        let (start, end, inclusive) = if let Some((start, end)) = s.split_once("..") {
+            match end.strip_prefix('=') {
+                Some(end) => (start, end, true),
+                None => (start, end, false),
+            }
+        } else if let Some((start, end)) = s.split_once(':') {
+            (start, end, false)
+        } else {
+            return Err(ParseRangeError::MissingSeparator);
+        };
+        let index = |s: &str| match s.trim() {
+            "" => Ok(None),
+            s => s.parse().map(Some),
+        };
+        let (start, end) = (index(start)?, index(end)?);
+        if inclusive && end.is_none() {
+            return Err(ParseRangeError::MissingInclusiveEnd);
+        }
+        Ok(RangeExpr { start, end, inclusive })
+    }
+}
+
+/// The error returned when a [`RangeExpr`] cannot be parsed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseRangeError {
+    /// There was neither `..` nor `:` between the indices.
+    MissingSeparator,
+    /// An index was not an optionally negated decimal integer.
+    InvalidIndex,
+    /// An index does not fit in a `usize`.
+    IndexTooLarge,
+    /// An inclusive range (`..=`) had no end.
+    MissingInclusiveEnd,
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ParseRangeError::MissingSeparator => "expected `..`, `..=` or `:` in range",
+            ParseRangeError::InvalidIndex => "range index is not an integer",
+            ParseRangeError::IndexTooLarge => "range index is too large",
+            ParseRangeError::MissingInclusiveEnd => "inclusive range has no end",
+        })
+    }
+}
+
+impl core::error::Error for ParseRangeError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn resolve_bounds() {
+        assert_eq!(resolve(.., 4), Some(0..4));
+        assert_eq!(resolve(1..=2, 4), Some(1..3));
+        assert_eq!(resolve((Bound::Excluded(1), Bound::Unbounded), 4), Some(2..4));
+        assert_eq!(resolve(3..5, 4), None);
+        assert_eq!(resolve(..=usize::MAX, usize::MAX), None);
+        assert_eq!(resolve((Bound::Included(3), Bound::Excluded(2)), 4), None);
+    }
+
+    #[test]
+    fn offsets() {
+        let data = [0u32; 8];
+        assert_eq!(offset_in(&data, &data[3..5]), Some(3));
+        assert_eq!(offset_in(&data, &data[8..]), Some(8));
+        assert_eq!(offset_in(&data[2..], &data), None);
+        assert_eq!(offset_in(&data[..4], &data[3..5]), None);
+    }
+
+    #[test]
+    fn rebase_overflow() {
+        assert_eq!(rebase(1..2, 10), Some(11..12));
+        assert_eq!(rebase(0..1, usize::MAX), None);
+    }
+
+    #[test]
+    fn range_expressions() {
+        let resolve = |s: &str| s.parse::<RangeExpr>().unwrap().resolve(10);
+        assert_eq!(resolve(".."), Some(0..10));
+        assert_eq!(resolve(" 2 .. -2 "), Some(2..8));
+        assert_eq!(resolve("..=-1"), Some(0..10));
+        assert_eq!(resolve("-3.."), Some(7..10));
+        assert_eq!(resolve("-0:"), Some(10..10));
+        assert_eq!(resolve("1:-0"), Some(1..10));
+        assert_eq!(resolve("-11.."), None);
+        assert_eq!(resolve("5..4"), None);
+        assert_eq!(resolve("..=10"), None);
+    }
+
+    #[test]
+    fn range_expression_errors() {
+        let parse = |s: &str| s.parse::<RangeExpr>().unwrap_err();
+        assert_eq!(parse("3"), ParseRangeError::MissingSeparator);
+        assert_eq!(parse("a..b"), ParseRangeError::InvalidIndex);
+        assert_eq!(parse("+1.."), ParseRangeError::InvalidIndex);
+        assert_eq!(parse("--1.."), ParseRangeError::InvalidIndex);
+        assert_eq!(parse("1..2..3"), ParseRangeError::InvalidIndex);
+        assert_eq!(parse("..99999999999999999999999"), ParseRangeError::IndexTooLarge);
+        assert_eq!(parse("..="), ParseRangeError::MissingInclusiveEnd);
+    }
+
+    #[test]
+    fn from_end_ranges() {
+        assert_eq!(ShrinkIndex::try_resolve(FromEnd(3)..FromEnd(1), 5), Ok(2..4));
+        assert_eq!(ShrinkIndex::try_resolve(FromEnd(3)..=FromEnd(1), 5), Ok(2..5));
+        assert_eq!(ShrinkIndex::try_resolve(..=FromEnd(0), 5).unwrap_err(),
+            ShrinkError::OutOfBounds { start: 0, end: 6, len: 5 });
+        assert_eq!(ShrinkIndex::try_resolve(FromEnd(6).., 5).unwrap_err(),
+            ShrinkError::OutOfBounds { start: 0, end: 6, len: 5 });
+        let (start, end) = (FromEnd(1), FromEnd(2));
+        assert!(ShrinkIndex::try_resolve(start..end, 5).is_err());
+        let expr: RangeExpr = "1..-1".parse().unwrap();
+        assert_eq!(ShrinkIndex::try_resolve(expr, 5), Ok(1..4));
+    }
+
+    #[test]
+    fn index_windows_shrink() {
+        let (start, end) = (5, 3);
+        let mut window = IndexWindow(start..end);
+        assert_eq!(window.len(), 0);
+        window.shrink(..);
+        assert_eq!(window, IndexWindow(5..5));
+
+        let mut window = IndexWindow::from(10..20);
+        window.shrink(2..=3);
+        window.shrink_front(1);
+        assert_eq!(Range::from(window.clone()), 13..14);
+        assert!(window.try_shrink(..2).is_err());
+        window.shrink(1..);
+        assert_eq!((window.range(), window.is_empty()), (14..14, true));
+    }
+
+    #[test]
+    fn index_windows_from_inclusive_ranges() {
+        let mut window = IndexWindow::try_from(2..=5).unwrap();
+        assert_eq!((window.range(), window.len()), (2..6, 4));
+        window.shrink(1..=2);
+        assert_eq!(window, IndexWindow(3..5));
+        let (start, end) = (7, 6);
+        assert_eq!(IndexWindow::try_from(start..=end).map(|w| w.len()), Ok(0));
+        assert_eq!(
+            IndexWindow::try_from(1..=usize::MAX),
+            Err(ShrinkError::OutOfBounds { start: 1, end: usize::MAX, len: usize::MAX }),
+        );
+        let mut exhausted = 3..=3;
+        exhausted.next();
+        assert_eq!(IndexWindow::try_from(exhausted).map(|w| w.len()), Ok(0));
+    }
+}
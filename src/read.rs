@@ -0,0 +1,432 @@
+//! Reading binary data from the front of a byte slice.
+//!
+//! [`ShrinkRead`] describes values, like headers, that know how to read themselves, consuming
+//! their encoding from the front of the input. With the `derive` feature it can be derived for
+//! structs whose fields all implement it. For reading one number at a time, [`GetBytes`] adds
+//! methods like `get_u16_le` to byte slices.
+//!
+//! ```rust
+//! use shrink_slice::read::ShrinkRead;
+//!
+//! let mut input: &[u8] = b"\x01\x00\x02\x00\x03\x00rest";
+//! let values = <[u16; 3]>::read_from(&mut input).unwrap();
+//! assert_eq!(values, [1, 2, 3]);
+//! assert_eq!(input, b"rest");
+//! ```
+
+use crate::endian::{ByteOrder, Endian};
+use crate::{NeedMore, Shrink, ShrinkExt, Take};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+
+#[cfg(feature = "derive")]
+pub use shrink_slice_derive::ShrinkRead;
+
+/// A value that can read itself from the front of a byte slice.
+///
+/// Implementations must leave the input untouched when they fail.
+pub trait ShrinkRead: Sized {
+    /// Read a value, using `endian` for any multi-byte values that do not specify their own byte
+    /// order.
+    fn read_from_endian(input: &mut &[u8], endian: Endian) -> Result<Self, NeedMore>;
+
+    /// Read a value, in little endian byte order unless specified otherwise.
+    fn read_from(input: &mut &[u8]) -> Result<Self, NeedMore> {
+        Self::read_from_endian(input, Endian::Little)
+    }
+
+    /// Read a value, in the byte order `E` unless specified otherwise.
+    fn read_ordered<E: ByteOrder>(input: &mut &[u8]) -> Result<Self, NeedMore> {
+        Self::read_from_endian(input, E::ENDIAN)
+    }
+}
+
+/// The first `N` bytes of `input`, which both the consuming and the peeking reads check with.
+fn peek_bytes<const N: usize>(input: &[u8]) -> Result<[u8; N], NeedMore> {
+    input.first_chunk().copied().ok_or_else(|| NeedMore(N - input.len()))
+}
+
+fn take_bytes<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], NeedMore> {
+    let bytes = peek_bytes(input)?;
+    input.shrink_front(N);
+    Ok(bytes)
+}
+
+/// The longest prefix of `input` whose bytes all satisfy `pred`.
+fn prefix_while<P: FnMut(&u8) -> bool>(input: &[u8], mut pred: P) -> &[u8] {
+    let n = input.iter().position(|b| !pred(b)).unwrap_or(input.len());
+    &input[..n]
+}
+
+macro_rules! shrink_read_primitive {
+    ($($ty:ident),*) => {
+        $(
+            impl ShrinkRead for $ty {
+                fn read_from_endian(input: &mut &[u8], endian: Endian) -> Result<Self, NeedMore> {
+                    let bytes = take_bytes(input)?;
+                    Ok(match endian {
+                        Endian::Little => $ty::from_le_bytes(bytes),
+                        Endian::Big => $ty::from_be_bytes(bytes),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+shrink_read_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl<T: ShrinkRead, const N: usize> ShrinkRead for [T; N] {
+    fn read_from_endian(input: &mut &[u8], endian: Endian) -> Result<Self, NeedMore> {
+        let mut view: &[u8] = input;
+        let mut failed = None;
+        let items: [Option<T>; N] = core::array::from_fn(|_| {
+            if failed.is_some() {
+                return None;
+            }
+            T::read_from_endian(&mut view, endian).map_err(|e| failed = Some(e)).ok()
+        });
+        if let Some(e) = failed {
+            return Err(e);
+        }
+        *input = view;
+        Ok(items.map(|item| item.expect("every item was read")))
+    }
+}
+
+/// Read a length of type `L`, followed by that many values of type `T`.
+///
+/// The input is left untouched on failure. A length that does not fit in a `usize` is reported
+/// as needing `usize::MAX` more bytes.
+///
+/// ```rust
+/// use shrink_slice::endian::Endian;
+/// use shrink_slice::read::read_len_prefixed;
+///
+/// let mut input: &[u8] = b"\x00\x02\xaa\xbb\xcc";
+/// let items: Vec<u8> = read_len_prefixed::<u16, _>(&mut input, Endian::Big).unwrap();
+/// assert_eq!(items, [0xaa, 0xbb]);
+/// assert_eq!(input, b"\xcc");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_len_prefixed<L, T>(input: &mut &[u8], endian: Endian) -> Result<Vec<T>, NeedMore>
+where L: ShrinkRead + Into<u64>,
+      T: ShrinkRead,
+{
+    let mut view: &[u8] = input;
+    let len = L::read_from_endian(&mut view, endian)?.into();
+    let len = usize::try_from(len).map_err(|_| NeedMore(usize::MAX))?;
+    // Don't trust the length for allocating: each element takes at least a byte, except for
+    // zero-sized ones, which take no space anyway.
+    let mut items = Vec::with_capacity(len.min(view.len()));
+    for _ in 0..len {
+        items.push(T::read_from_endian(&mut view, endian)?);
+    }
+    *input = view;
+    Ok(items)
+}
+
+macro_rules! get_methods {
+    ($($ty:ident: $le:ident, $be:ident, $le_back:ident, $be_back:ident;)*) => {
+        $(
+            #[doc = concat!("Read a little endian `", stringify!($ty), "`.")]
+            fn $le(&mut self) -> Result<$ty, NeedMore> {
+                self.get_array().map($ty::from_le_bytes)
+            }
+
+            #[doc = concat!("Read a big endian `", stringify!($ty), "`.")]
+            fn $be(&mut self) -> Result<$ty, NeedMore> {
+                self.get_array().map($ty::from_be_bytes)
+            }
+
+            #[doc = concat!("Read a little endian `", stringify!($ty), "` from the back.")]
+            fn $le_back(&mut self) -> Result<$ty, NeedMore> {
+                self.get_array_back().map($ty::from_le_bytes)
+            }
+
+            #[doc = concat!("Read a big endian `", stringify!($ty), "` from the back.")]
+            fn $be_back(&mut self) -> Result<$ty, NeedMore> {
+                self.get_array_back().map($ty::from_be_bytes)
+            }
+        )*
+    };
+}
+
+/// Extension trait for reading fixed-width numbers from the front of a byte slice, in the style
+/// of `bytes::Buf`.
+///
+/// Every method consumes the bytes it reads. If there are not enough of them, the slice is left
+/// untouched and the error reports how many more are needed. The methods ending in `_back` read
+/// from the back of the slice instead, for formats with trailers, like the end of a ZIP archive.
+///
+/// ```rust
+/// use shrink_slice::read::GetBytes;
+/// use shrink_slice::NeedMore;
+///
+/// let mut input: &[u8] = b"\x2a\x01\x00\x00\x00\x00\x10\x00\x00\x00";
+/// assert_eq!(input.get_u8(), Ok(42));
+/// assert_eq!(input.get_u32_le(), Ok(1));
+/// assert_eq!(input.get_u16_be(), Ok(0x0010));
+/// assert_eq!(input.get_u64_le(), Err(NeedMore(5)));
+/// assert_eq!(input.len(), 3);
+///
+/// let mut record: &[u8] = b"payload\x07\x00\xbe\xef";
+/// assert_eq!(record.get_u16_be_back(), Ok(0xbeef));
+/// assert_eq!(record.get_u16_le_back(), Ok(7));
+/// assert_eq!(record, b"payload");
+///
+/// let mut message: &[u8] = b"\x02\x00\x05hello";
+/// if message.peek_u8() == Ok(2) {
+///     assert_eq!(message.peek_array(), Ok([2, 0]));
+///     assert_eq!(message.get_u16_le(), Ok(2));
+/// }
+/// assert_eq!(message.peek_until(|b| b.is_ascii_alphabetic()), b"\x05");
+/// assert_eq!(message.len(), 6);
+/// ```
+pub trait GetBytes {
+    /// Read the next `N` bytes.
+    fn get_array<const N: usize>(&mut self) -> Result<[u8; N], NeedMore>;
+
+    /// Read the last `N` bytes.
+    fn get_array_back<const N: usize>(&mut self) -> Result<[u8; N], NeedMore>;
+
+    /// Read a `u8`.
+    fn get_u8(&mut self) -> Result<u8, NeedMore> {
+        self.get_array().map(|[b]| b)
+    }
+
+    /// Read an `i8`.
+    fn get_i8(&mut self) -> Result<i8, NeedMore> {
+        self.get_array().map(i8::from_le_bytes)
+    }
+
+    /// Read a `u8` from the back.
+    fn get_u8_back(&mut self) -> Result<u8, NeedMore> {
+        self.get_array_back().map(|[b]| b)
+    }
+
+    /// Read an `i8` from the back.
+    fn get_i8_back(&mut self) -> Result<i8, NeedMore> {
+        self.get_array_back().map(i8::from_le_bytes)
+    }
+
+    /// Read a value of type `T`, in the given byte order unless `T` specifies its own.
+    fn get_endian<T: ShrinkRead>(&mut self, endian: Endian) -> Result<T, NeedMore>;
+
+    /// Read a value of type `T`, in the byte order `E` unless `T` specifies its own.
+    ///
+    /// This is named for the byte order rather than just `get`, which would call the slice's own
+    /// `get` instead.
+    fn get_ordered<T: ShrinkRead, E: ByteOrder>(&mut self) -> Result<T, NeedMore> {
+        self.get_endian(E::ENDIAN)
+    }
+
+    /// Read the next `N` bytes without consuming them.
+    fn peek_array<const N: usize>(&self) -> Result<[u8; N], NeedMore>;
+
+    /// Read the next `u8` without consuming it.
+    fn peek_u8(&self) -> Result<u8, NeedMore> {
+        self.peek_array().map(|[b]| b)
+    }
+
+    /// Read a value of type `T` like [`get_endian`](GetBytes::get_endian), without consuming it.
+    fn peek_endian<T: ShrinkRead>(&self, endian: Endian) -> Result<T, NeedMore>;
+
+    /// Read a value of type `T` like [`get_ordered`](GetBytes::get_ordered), without consuming
+    /// it.
+    fn peek_ordered<T: ShrinkRead, E: ByteOrder>(&self) -> Result<T, NeedMore> {
+        self.peek_endian(E::ENDIAN)
+    }
+
+    /// The longest prefix whose bytes all satisfy `pred`, without consuming it.
+    fn peek_while<P: FnMut(&u8) -> bool>(&self, pred: P) -> &[u8];
+
+    /// The longest prefix whose bytes all fail `pred`, without consuming it.
+    fn peek_until<P: FnMut(&u8) -> bool>(&self, mut pred: P) -> &[u8] {
+        self.peek_while(|b| !pred(b))
+    }
+
+    get_methods! {
+        u16: get_u16_le, get_u16_be, get_u16_le_back, get_u16_be_back;
+        u32: get_u32_le, get_u32_be, get_u32_le_back, get_u32_be_back;
+        u64: get_u64_le, get_u64_be, get_u64_le_back, get_u64_be_back;
+        u128: get_u128_le, get_u128_be, get_u128_le_back, get_u128_be_back;
+        i16: get_i16_le, get_i16_be, get_i16_le_back, get_i16_be_back;
+        i32: get_i32_le, get_i32_be, get_i32_le_back, get_i32_be_back;
+        i64: get_i64_le, get_i64_be, get_i64_le_back, get_i64_be_back;
+        i128: get_i128_le, get_i128_be, get_i128_le_back, get_i128_be_back;
+        f32: get_f32_le, get_f32_be, get_f32_le_back, get_f32_be_back;
+        f64: get_f64_le, get_f64_be, get_f64_le_back, get_f64_be_back;
+    }
+}
+
+impl GetBytes for &[u8] {
+    fn get_array<const N: usize>(&mut self) -> Result<[u8; N], NeedMore> {
+        take_bytes(self)
+    }
+
+    fn get_array_back<const N: usize>(&mut self) -> Result<[u8; N], NeedMore> {
+        let (rest, tail) = self.split_last_chunk().ok_or_else(|| NeedMore(N - self.len()))?;
+        *self = rest;
+        Ok(*tail)
+    }
+
+    fn get_endian<T: ShrinkRead>(&mut self, endian: Endian) -> Result<T, NeedMore> {
+        T::read_from_endian(self, endian)
+    }
+
+    fn peek_array<const N: usize>(&self) -> Result<[u8; N], NeedMore> {
+        peek_bytes(self)
+    }
+
+    fn peek_endian<T: ShrinkRead>(&self, endian: Endian) -> Result<T, NeedMore> {
+        let mut view: &[u8] = self;
+        T::read_from_endian(&mut view, endian)
+    }
+
+    fn peek_while<P: FnMut(&u8) -> bool>(&self, pred: P) -> &[u8] {
+        prefix_while(self, pred)
+    }
+}
+
+impl GetBytes for &mut [u8] {
+    fn get_array<const N: usize>(&mut self) -> Result<[u8; N], NeedMore> {
+        let bytes = peek_bytes(self)?;
+        self.shrink_front(N);
+        Ok(bytes)
+    }
+
+    fn get_array_back<const N: usize>(&mut self) -> Result<[u8; N], NeedMore> {
+        let mut view: &[u8] = self;
+        let value = view.get_array_back()?;
+        self.shrink_back(N);
+        Ok(value)
+    }
+
+    fn get_endian<T: ShrinkRead>(&mut self, endian: Endian) -> Result<T, NeedMore> {
+        let mut view: &[u8] = self;
+        let value = T::read_from_endian(&mut view, endian)?;
+        let consumed = self.len() - view.len();
+        self.take_exact(consumed).expect("consumed bytes are within the slice");
+        Ok(value)
+    }
+
+    fn peek_array<const N: usize>(&self) -> Result<[u8; N], NeedMore> {
+        peek_bytes(self)
+    }
+
+    fn peek_endian<T: ShrinkRead>(&self, endian: Endian) -> Result<T, NeedMore> {
+        let mut view: &[u8] = self;
+        T::read_from_endian(&mut view, endian)
+    }
+
+    fn peek_while<P: FnMut(&u8) -> bool>(&self, pred: P) -> &[u8] {
+        prefix_while(self, pred)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_keep_input() {
+        let mut input: &[u8] = b"\x03\x02\x01";
+        assert_eq!(<[u16; 2]>::read_from(&mut input), Err(NeedMore(1)));
+        assert_eq!(input.len(), 3);
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(read_len_prefixed::<u8, u8>(&mut input, Endian::Big), Err(NeedMore(1)));
+            assert_eq!(input.len(), 3);
+        }
+        assert_eq!(i16::read_from_endian(&mut input, Endian::Big), Ok(0x0302));
+        assert_eq!(input, b"\x01");
+    }
+
+    #[cfg(all(feature = "derive", feature = "alloc"))]
+    #[test]
+    fn derived() {
+        #[derive(ShrinkRead, Debug, PartialEq)]
+        struct Version(u8, u8);
+
+        #[derive(ShrinkRead, Debug, PartialEq)]
+        #[shrink(big_endian)]
+        struct Header {
+            magic: [u8; 2],
+            version: Version,
+            #[shrink(little_endian)]
+            flags: u16,
+            #[shrink(skip)]
+            cached: Option<u32>,
+            #[shrink(len = u8)]
+            names: Vec<u16>,
+        }
+
+        let mut input: &[u8] = b"SS\x01\x02\x01\x00\x02\x00\x0a\x00\x0brest";
+        let header = Header::read_from(&mut input).unwrap();
+        assert_eq!(header, Header {
+            magic: *b"SS",
+            version: Version(1, 2),
+            flags: 1,
+            cached: None,
+            names: vec![10, 11],
+        });
+        assert_eq!(input, b"rest");
+
+        let mut truncated: &[u8] = b"SS\x01\x02\x01\x00\x02\x00\x0a";
+        assert_eq!(Header::read_from(&mut truncated), Err(NeedMore(2)));
+        assert_eq!(truncated.len(), 9);
+    }
+
+    #[test]
+    fn get_from_mut() {
+        let mut buffer = *b"\x00\x00\x80\x3f\xff\xfe\x07";
+        let mut input: &mut [u8] = &mut buffer;
+        assert_eq!(input.get_f32_le(), Ok(1.0));
+        assert_eq!(input.get_i16_be(), Ok(-2));
+        assert_eq!(input.get_endian::<u16>(Endian::Big), Err(NeedMore(1)));
+        assert_eq!(input.get_endian::<[u8; 1]>(Endian::Big), Ok([7]));
+        assert_eq!(input.get_i8(), Err(NeedMore(1)));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn get_from_the_back() {
+        let mut buffer = *b"\x01\x02\x00\x00\x80\x3f";
+        let mut input: &mut [u8] = &mut buffer;
+        assert_eq!(input.get_f32_le_back(), Ok(1.0));
+        assert_eq!(input.get_u32_be_back(), Err(NeedMore(2)));
+        assert_eq!(input.get_i8_back(), Ok(2));
+        input[0] = 5;
+        assert_eq!((input.get_u8_back(), input.get_u8_back()), (Ok(5), Err(NeedMore(1))));
+        let mut empty: &[u8] = &[];
+        assert_eq!(empty.get_u64_le_back(), Err(NeedMore(8)));
+    }
+
+    #[test]
+    fn peeking_consumes_nothing() {
+        let mut buffer = *b"\x01\x00\x00\x00 ";
+        let input: &mut [u8] = &mut buffer;
+        assert_eq!(input.peek_endian::<u32>(Endian::Little), Ok(1));
+        assert_eq!(input.peek_endian::<u64>(Endian::Big), Err(NeedMore(3)));
+        assert_eq!(input.peek_array::<6>(), Err(NeedMore(1)));
+        assert_eq!(input.peek_while(|&b| b < 2), b"\x01\x00\x00\x00");
+        assert_eq!(input.len(), 5);
+        let empty: &[u8] = &[];
+        assert_eq!((empty.peek_u8(), empty.peek_until(|_| true)), (Err(NeedMore(1)), &[][..]));
+    }
+
+    #[test]
+    fn ordered_by_type() {
+        use crate::endian::{BigEndian, NativeEndian};
+
+        let mut input: &[u8] = b"\x12\x34\x56";
+        assert_eq!(input.peek_ordered::<u16, BigEndian>(), Ok(0x1234));
+        assert_eq!(input.get_ordered::<[u16; 2], NativeEndian>(), Err(NeedMore(1)));
+        let native = u16::from_ne_bytes([0x12, 0x34]);
+        assert_eq!(u16::read_ordered::<NativeEndian>(&mut input), Ok(native));
+        assert_eq!(input, b"\x56");
+    }
+}
@@ -0,0 +1,98 @@
+//! Consuming regular expression matches from the front of a slice, for tokenizers.
+//!
+//! This module is only available with the `regex` feature. Expressions are compiled with the
+//! meta [`Regex`] of `regex-automata`, re-exported here, since it can search in anchored mode:
+//! a match is only looked for at the very start of the slice, and an expression that does not
+//! match there fails without scanning the rest.
+//!
+//! ```rust
+//! use shrink_slice::regex::{ConsumeRegex, Regex};
+//!
+//! let number = Regex::new(r"[0-9]+(\.[0-9]+)?").unwrap();
+//! let space = Regex::new(r"\s*").unwrap();
+//! let mut input = "3.14 + 2";
+//! assert_eq!(input.consume_regex(&number), Some("3.14"));
+//! input.consume_regex(&space);
+//! assert!(input.consume_regex(&number).is_none());
+//! assert_eq!(input, "+ 2");
+//! ```
+
+use crate::ShrinkExt;
+use regex_automata::{Anchored, Input};
+
+pub use regex_automata::meta::Regex;
+
+/// Extension trait for shrinking past a match of a regular expression at the very start of the
+/// slice.
+///
+/// The search is anchored, so the expression does not need to start with `^` or `\A`, and a
+/// match that would start later is never found. The match is found within the slice alone, so
+/// assertions like `\b` and `^` see its start as the start of the text.
+pub trait ConsumeRegex: Sized {
+    /// If `re` matches at the start of the slice, shrink past the match and return it.
+    /// Otherwise, return `None` and leave the slice untouched.
+    fn consume_regex(&mut self, re: &Regex) -> Option<Self>;
+}
+
+/// The end of a match of `re` that starts at the front of `haystack`.
+fn anchored_end(re: &Regex, haystack: &[u8]) -> Option<usize> {
+    let input = Input::new(haystack).anchored(Anchored::Yes);
+    re.find(input).map(|m| m.end())
+}
+
+/// For expressions that can match inside a multi-byte character, only matches that end on a
+/// character boundary are consumed.
+impl<'a> ConsumeRegex for &'a str {
+    fn consume_regex(&mut self, re: &Regex) -> Option<&'a str> {
+        let end = anchored_end(re, self.as_bytes())?;
+        let lexeme = self.get(..end)?;
+        self.shrink(end..);
+        Some(lexeme)
+    }
+}
+
+impl<'a> ConsumeRegex for &'a [u8] {
+    fn consume_regex(&mut self, re: &Regex) -> Option<&'a [u8]> {
+        let end = anchored_end(re, self)?;
+        let lexeme = &self[..end];
+        self.shrink(end..);
+        Some(lexeme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_at_the_front() {
+        let word = Regex::new(r"^\w+").unwrap();
+        let mut input: &[u8] = b"  hi";
+        assert!(input.consume_regex(&word).is_none());
+        input.shrink(2..);
+        assert_eq!(input.consume_regex(&word), Some(&b"hi"[..]));
+        assert!(input.is_empty());
+
+        let later = Regex::new("b").unwrap();
+        let empty = Regex::new("x*").unwrap();
+        let mut text = "ab";
+        assert!(text.consume_regex(&later).is_none());
+        assert_eq!(text.consume_regex(&empty), Some(""));
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn anchored_without_caret() {
+        // Unanchored, the leftmost match of `b+` in "abb" would be found at 1, after a scan.
+        let run = Regex::new("b+").unwrap();
+        let mut text = "abb";
+        assert!(text.consume_regex(&run).is_none());
+        assert_eq!(text, "abb");
+        text.shrink(1..);
+        assert_eq!(text.consume_regex(&run), Some("bb"));
+
+        let word = Regex::new(r"\b\w").unwrap();
+        let mut rest = &b"xy"[1..];
+        assert_eq!(rest.consume_regex(&word), Some(&b"y"[..]));
+    }
+}
@@ -0,0 +1,173 @@
+//! Setting aside the back of a slice, so that consuming the front can never reach it.
+//!
+//! Framing code often writes a body into a buffer and then appends a trailer, like a checksum,
+//! into whatever is left. Wrapping the buffer in a [`Reserved`] makes every operation that would
+//! leave less than the trailer needs fail with a distinct error, instead of having to check at
+//! each call site.
+//!
+//! ```rust
+//! use shrink_slice::reserve::{Reserved, ReserveError};
+//!
+//! let mut buffer = [0u8; 8];
+//! let mut out = Reserved::new(&mut buffer[..], 2).unwrap();
+//! out.take_exact(1).unwrap()[0] = 5;
+//! out.take_exact(5).unwrap().copy_from_slice(b"hello");
+//! let err = out.take_exact(1).unwrap_err();
+//! assert_eq!(err, ReserveError::IntoReserve { reserved: 2, left: 1 });
+//!
+//! let trailer = out.into_inner();
+//! trailer.copy_from_slice(&0xbeefu16.to_be_bytes());
+//! assert_eq!(&buffer, b"\x05hello\xbe\xef");
+//! ```
+
+use crate::{range, NeedMore, Shrink, ShrinkError, Take};
+use core::fmt;
+use core::ops::RangeBounds;
+use core::slice::SliceIndex;
+
+/// A slice of which at least a given number of elements must always remain.
+///
+/// The reserve counts from the back of the slice, whatever that currently is, so shrinking the
+/// back is allowed as long as enough is left too.
+#[derive(Clone, Debug)]
+pub struct Reserved<S> {
+    inner: S,
+    reserved: usize,
+}
+
+impl<S: Shrink> Reserved<S> {
+    /// Wrap `inner`, reserving its last `reserved` elements, or report how many more elements it
+    /// would need to have that many.
+    pub fn new(inner: S, reserved: usize) -> Result<Self, NeedMore> {
+        if inner.len() < reserved {
+            return Err(NeedMore(reserved - inner.len()));
+        }
+        Ok(Reserved { inner, reserved })
+    }
+
+    /// The slice that is left, including the reserve.
+    pub fn get(&self) -> &S {
+        &self.inner
+    }
+
+    /// Unwrap the slice that is left, including the reserve.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// The number of elements that must remain.
+    pub fn reserved(&self) -> usize {
+        self.reserved
+    }
+
+    /// The number of elements that may still be consumed.
+    pub fn available(&self) -> usize {
+        self.inner.len() - self.reserved
+    }
+
+    fn check(&self, left: usize) -> Result<(), ReserveError> {
+        if left < self.reserved {
+            return Err(ReserveError::IntoReserve { reserved: self.reserved, left });
+        }
+        Ok(())
+    }
+
+    /// Shrink the slice to `range`, unless that would leave fewer than the reserved number of
+    /// elements.
+    ///
+    /// On error, the slice is left untouched. A range that is out of bounds is reported as such,
+    /// even if it would also cut into the reserve.
+    pub fn try_shrink<R>(&mut self, range: R) -> Result<(), ReserveError>
+    where R: SliceIndex<S::Slice, Output = S::Slice> + RangeBounds<usize>,
+    {
+        let (start, end) = range::endpoints(&range, self.inner.len());
+        if start <= end && end <= self.inner.len() {
+            self.check(end - start)?;
+        }
+        Ok(self.inner.try_shrink(range)?)
+    }
+
+    /// Split off exactly `n` elements from the front of the slice and return them, unless that
+    /// would leave fewer than the reserved number of elements.
+    ///
+    /// On error, the slice is left untouched. If the slice is too short for `n` elements at all,
+    /// that is what gets reported.
+    pub fn take_exact(&mut self, n: usize) -> Result<S, ReserveError>
+    where S: Take,
+    {
+        self.inner.ensure_remaining(n)?;
+        self.check(self.inner.len() - n)?;
+        Ok(self.inner.take_exact(n)?)
+    }
+}
+
+/// The error returned when a [`Reserved`] slice cannot be consumed as requested.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReserveError {
+    /// Consuming would have cut into the reserve.
+    IntoReserve {
+        /// The number of elements that must remain.
+        reserved: usize,
+        /// The number of elements that would have remained.
+        left: usize,
+    },
+    /// The range was out of bounds.
+    OutOfRange(ShrinkError),
+    /// The slice was too short, by the given number of elements.
+    NeedMore(usize),
+}
+
+impl From<ShrinkError> for ReserveError {
+    fn from(e: ShrinkError) -> Self {
+        ReserveError::OutOfRange(e)
+    }
+}
+
+impl From<NeedMore> for ReserveError {
+    fn from(NeedMore(n): NeedMore) -> Self {
+        ReserveError::NeedMore(n)
+    }
+}
+
+impl fmt::Display for ReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReserveError::IntoReserve { reserved, left } => {
+                write!(f, "{} elements are reserved, but only {} would be left", reserved, left)
+            }
+            ReserveError::OutOfRange(e) => e.fmt(f),
+            ReserveError::NeedMore(n) => NeedMore(*n).fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ReserveError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_holds_at_both_ends() {
+        assert_eq!(Reserved::new("ab", 3).unwrap_err(), NeedMore(1));
+        let mut text = Reserved::new("caf\u{e9}!", 3).unwrap();
+        assert_eq!(text.available(), 3);
+        assert_eq!(text.try_shrink(..2), Err(ReserveError::IntoReserve { reserved: 3, left: 2 }));
+        assert!(matches!(text.try_shrink(..9), Err(ReserveError::OutOfRange(_))));
+        text.try_shrink(1..).unwrap();
+        assert!(matches!(
+            text.try_shrink(..3),
+            Err(ReserveError::OutOfRange(ShrinkError::NotCharBoundary { .. })),
+        ));
+        assert_eq!(*text.get(), "af\u{e9}!");
+    }
+
+    #[test]
+    fn short_input_is_not_a_reserve_error() {
+        let mut input = Reserved::new(&b"abcd"[..], 2).unwrap();
+        assert_eq!(input.take_exact(5), Err(ReserveError::NeedMore(1)));
+        assert_eq!(input.take_exact(3), Err(ReserveError::IntoReserve { reserved: 2, left: 1 }));
+        assert_eq!(input.take_exact(2), Ok(&b"ab"[..]));
+        assert_eq!(input.available(), 0);
+    }
+}
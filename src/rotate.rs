@@ -0,0 +1,76 @@
+//! Queue-like consumption of mutable slices by rotating elements out of the way.
+
+use crate::NeedMore;
+use core::mem;
+
+/// Extension trait for consuming elements from one end of a mutable slice while keeping the
+/// remaining elements where that end used to be.
+///
+/// The consumed elements are rotated to the other end and split off, so the slice works as a
+/// simple queue over its own storage.
+pub trait RotateConsume: Sized {
+    /// Consume the first `n` elements: rotate them to the back, then split them off and return
+    /// them in their original order. The remaining elements end up at the front of the original
+    /// storage.
+    ///
+    /// If fewer than `n` elements remain, the slice is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::rotate::RotateConsume;
+    ///
+    /// let mut buffer = [1, 2, 3, 4, 5];
+    /// let mut queue: &mut [i32] = &mut buffer;
+    /// assert_eq!(queue.rotate_consume_front(2).unwrap(), [1, 2]);
+    /// assert_eq!(queue, [3, 4, 5]);
+    /// assert_eq!(buffer, [3, 4, 5, 1, 2]);
+    /// ```
+    fn rotate_consume_front(&mut self, n: usize) -> Result<Self, NeedMore>;
+
+    /// Consume the last `n` elements: rotate them to the front, then split them off and return
+    /// them in their original order. The remaining elements end up at the back of the original
+    /// storage.
+    ///
+    /// If fewer than `n` elements remain, the slice is left untouched.
+    fn rotate_consume_back(&mut self, n: usize) -> Result<Self, NeedMore>;
+}
+
+impl<T> RotateConsume for &mut [T] {
+    fn rotate_consume_front(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let len = self.len();
+        if n > len {
+            return Err(NeedMore(n - len));
+        }
+        self.rotate_left(n);
+        let (rest, consumed) = mem::take(self).split_at_mut(len - n);
+        *self = rest;
+        Ok(consumed)
+    }
+
+    fn rotate_consume_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let len = self.len();
+        if n > len {
+            return Err(NeedMore(n - len));
+        }
+        self.rotate_right(n);
+        let (consumed, rest) = mem::take(self).split_at_mut(n);
+        *self = rest;
+        Ok(consumed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consume_back() {
+        let mut buffer = *b"abcde";
+        let mut queue: &mut [u8] = &mut buffer;
+        assert_eq!(queue.rotate_consume_back(6), Err(NeedMore(1)));
+        assert_eq!(queue.rotate_consume_back(2).unwrap(), b"de");
+        assert_eq!(queue, b"abc");
+        assert_eq!(queue.rotate_consume_front(1).unwrap(), b"a");
+        assert_eq!(queue, b"bc");
+        assert_eq!(&buffer, b"debca");
+    }
+}
@@ -0,0 +1,58 @@
+//! Consuming runs of equal elements.
+
+use crate::ShrinkExt;
+
+/// Extension trait for consuming the run of equal elements at the front of a slice.
+pub trait TakeRun<'a, T> {
+    /// Consume the first element together with every element immediately after it that is
+    /// equal to it. Returns that first element and the length of the run, or `None` if the slice
+    /// is empty.
+    ///
+    /// ```rust
+    /// use shrink_slice::run::TakeRun;
+    ///
+    /// let mut pixels: &[u8] = &[7, 7, 7, 0, 0, 7];
+    /// let mut encoded = Vec::new();
+    /// while let Some((&value, count)) = pixels.take_dedup_prefix() {
+    ///     encoded.push((value, count));
+    /// }
+    /// assert_eq!(encoded, [(7, 3), (0, 2), (7, 1)]);
+    /// ```
+    fn take_dedup_prefix(&mut self) -> Option<(&'a T, usize)>
+    where T: PartialEq,
+    {
+        self.take_dedup_prefix_by(|a, b| a == b)
+    }
+
+    /// Like [`take_dedup_prefix`](Self::take_dedup_prefix), but with `same` deciding whether an
+    /// element belongs to the run. It is passed the first element and each candidate, in that
+    /// order.
+    fn take_dedup_prefix_by<F>(&mut self, same: F) -> Option<(&'a T, usize)>
+    where F: FnMut(&T, &T) -> bool;
+}
+
+impl<'a, T> TakeRun<'a, T> for &'a [T] {
+    fn take_dedup_prefix_by<F>(&mut self, mut same: F) -> Option<(&'a T, usize)>
+    where F: FnMut(&T, &T) -> bool,
+    {
+        let (first, rest) = self.split_first()?;
+        let count = 1 + rest.iter().take_while(|x| same(first, x)).count();
+        self.shrink(count..);
+        Some((first, count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_predicate() {
+        let mut words: &[&str] = &["apple", "avocado", "banana", "blueberry", "cherry"];
+        let same_initial = |a: &&str, b: &&str| a.as_bytes()[0] == b.as_bytes()[0];
+        assert_eq!(words.take_dedup_prefix_by(same_initial), Some((&"apple", 2)));
+        assert_eq!(words.take_dedup_prefix_by(same_initial), Some((&"banana", 2)));
+        assert_eq!(words.take_dedup_prefix_by(same_initial), Some((&"cherry", 1)));
+        assert_eq!(words.take_dedup_prefix_by(same_initial), None);
+    }
+}
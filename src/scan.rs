@@ -0,0 +1,319 @@
+//! Consuming byte slices up to a delimiter, and byte and string slices line by line.
+//!
+//! With the `memchr` feature, the searching is done by the `memchr` crate, which uses SIMD
+//! instructions where available. Without it, the bytes are compared one at a time.
+//!
+//! ```rust
+//! use shrink_slice::scan::ScanBytes;
+//!
+//! let mut log: &[u8] = b"GET /a 200\nGET /b 404\r\npartial";
+//! let mut statuses = Vec::new();
+//! while let Some(mut line) = log.take_line() {
+//!     line.take_through_byte(b' ');
+//!     line.take_through_byte(b' ');
+//!     statuses.push(line);
+//! }
+//! assert_eq!(statuses, [&b"200"[..], &b"404"[..]]);
+//! assert_eq!(log, b"partial");
+//! ```
+
+use crate::{Shrink, ShrinkExt, Take};
+use core::mem;
+
+/// The index of the first occurrence of `byte` in `haystack`.
+pub(crate) fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    {
+        memchr::memchr(byte, haystack)
+    }
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.iter().position(|&b| b == byte)
+    }
+}
+
+/// Extension trait for consuming byte slices up to a delimiter.
+///
+/// Every method returns `None`, consuming nothing, if the delimiter does not occur, so that a
+/// streaming parser can wait for more input.
+pub trait ScanBytes: Take {
+    /// Split off everything before the first `byte`, leaving `byte` at the front of `self`.
+    ///
+    /// ```rust
+    /// use shrink_slice::scan::ScanBytes;
+    ///
+    /// let mut record: &[u8] = b"key=value";
+    /// assert_eq!(record.take_until_byte(b'='), Some(&b"key"[..]));
+    /// assert_eq!(record, b"=value");
+    /// assert_eq!(record.take_until_byte(b';'), None);
+    /// ```
+    fn take_until_byte(&mut self, byte: u8) -> Option<Self>;
+
+    /// Split off everything before the first `byte`, and drop `byte` itself.
+    fn take_through_byte(&mut self, byte: u8) -> Option<Self> {
+        let head = self.take_until_byte(byte)?;
+        self.take_front(1)?;
+        Some(head)
+    }
+
+    /// Split off a line ending in LF or CRLF, and return it without its terminator.
+    fn take_line(&mut self) -> Option<Self>;
+}
+
+impl ScanBytes for &[u8] {
+    fn take_until_byte(&mut self, byte: u8) -> Option<Self> {
+        let i = find_byte(byte, self)?;
+        self.take_front(i)
+    }
+
+    fn take_line(&mut self) -> Option<Self> {
+        let mut line = self.take_through_byte(b'\n')?;
+        if line.last() == Some(&b'\r') {
+            line.shrink_back(1);
+        }
+        Some(line)
+    }
+}
+
+impl ScanBytes for &mut [u8] {
+    fn take_until_byte(&mut self, byte: u8) -> Option<Self> {
+        let i = find_byte(byte, self)?;
+        self.take_front(i)
+    }
+
+    fn take_line(&mut self) -> Option<Self> {
+        let mut line = self.take_through_byte(b'\n')?;
+        if line.last() == Some(&b'\r') {
+            line.shrink_back(1);
+        }
+        Some(line)
+    }
+}
+
+/// Extension trait for searching a slice and consuming everything up to and including the first
+/// match, in a single pass.
+///
+/// Slices are searched element by element, and string slices char by char. On success, the
+/// position of the match within the slice as it was and the part before the match are returned,
+/// the latter with the slice's original lifetime, and the slice is shrunk to the part after it.
+/// If nothing matches, `None` is returned and the slice is left untouched.
+///
+/// ```rust
+/// use shrink_slice::scan::FindAndConsume;
+///
+/// let mut record: &[u8] = b"name\0value\0";
+/// assert_eq!(record.find_and_consume(&0), Some((4, &b"name"[..])));
+/// assert_eq!(record, b"value\0");
+///
+/// let mut text = "x = 1 # note";
+/// let (at, code) = text.find_and_consume_by(|&c| c == '#').unwrap();
+/// assert_eq!((at, code, text), (6, "x = 1 ", " note"));
+/// assert_eq!(text.find_and_consume(&'#'), None);
+/// ```
+pub trait FindAndConsume: Sized {
+    /// The type of the elements that are searched.
+    type Item;
+
+    /// Find the first element for which `pred` returns `true`, and consume everything up to and
+    /// including it.
+    fn find_and_consume_by<P>(&mut self, pred: P) -> Option<(usize, Self)>
+    where P: FnMut(&Self::Item) -> bool;
+
+    /// Find the first element equal to `needle`, and consume everything up to and including it.
+    fn find_and_consume(&mut self, needle: &Self::Item) -> Option<(usize, Self)>
+    where Self::Item: PartialEq
+    {
+        self.find_and_consume_by(|item| item == needle)
+    }
+}
+
+impl<T> FindAndConsume for &[T] {
+    type Item = T;
+
+    fn find_and_consume_by<P>(&mut self, pred: P) -> Option<(usize, Self)>
+    where P: FnMut(&T) -> bool
+    {
+        let i = self.iter().position(pred)?;
+        let (prefix, rest) = self.split_at(i);
+        *self = &rest[1..];
+        Some((i, prefix))
+    }
+}
+
+impl<T> FindAndConsume for &mut [T] {
+    type Item = T;
+
+    fn find_and_consume_by<P>(&mut self, pred: P) -> Option<(usize, Self)>
+    where P: FnMut(&T) -> bool
+    {
+        let i = self.iter().position(pred)?;
+        let (prefix, rest) = mem::take(self).split_at_mut(i);
+        *self = &mut rest[1..];
+        Some((i, prefix))
+    }
+}
+
+impl FindAndConsume for &str {
+    type Item = char;
+
+    fn find_and_consume_by<P>(&mut self, mut pred: P) -> Option<(usize, Self)>
+    where P: FnMut(&char) -> bool
+    {
+        let (i, c) = self.char_indices().find(|(_, c)| pred(c))?;
+        let (prefix, rest) = self.split_at(i);
+        *self = &rest[c.len_utf8()..];
+        Some((i, prefix))
+    }
+}
+
+impl FindAndConsume for &mut str {
+    type Item = char;
+
+    fn find_and_consume_by<P>(&mut self, mut pred: P) -> Option<(usize, Self)>
+    where P: FnMut(&char) -> bool
+    {
+        let (i, c) = self.char_indices().find(|(_, c)| pred(c))?;
+        let (prefix, rest) = mem::take(self).split_at_mut(i);
+        *self = &mut rest[c.len_utf8()..];
+        Some((i, prefix))
+    }
+}
+
+/// A line split off by [`NextLine::next_line`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Line<S> {
+    /// The line, without its terminator.
+    pub text: S,
+    /// Whether the line ended in LF or CRLF, rather than at the end of the input.
+    pub terminated: bool,
+}
+
+/// Extension trait for consuming byte and string slices one line at a time, for all inputs.
+///
+/// Unlike [`ScanBytes::take_line`], which waits for a terminator, this also returns a last line
+/// that has none, and says so, so that a streaming reader can keep it for the next buffer while
+/// a reader at the end of its input can take it as it is. A CR that is not followed by LF is part
+/// of the line, as it may be the start of a CRLF whose LF has not arrived yet.
+///
+/// ```rust
+/// use shrink_slice::scan::{Line, NextLine};
+///
+/// let mut log = "started\r\n\nwaiting";
+/// assert_eq!(log.next_line(), Some(Line { text: "started", terminated: true }));
+/// assert_eq!(log.next_line(), Some(Line { text: "", terminated: true }));
+/// assert_eq!(log.next_line(), Some(Line { text: "waiting", terminated: false }));
+/// assert_eq!(log.next_line(), None);
+/// ```
+pub trait NextLine: Sized {
+    /// Split off the next line and shrink past its terminator, or return `None` if the slice is
+    /// empty.
+    fn next_line(&mut self) -> Option<Line<Self>>;
+}
+
+/// The length of the first line in `bytes` without its terminator, the length with it, and
+/// whether it has one.
+fn line_bounds(bytes: &[u8]) -> (usize, usize, bool) {
+    match find_byte(b'\n', bytes) {
+        Some(i) if i > 0 && bytes[i - 1] == b'\r' => (i - 1, i + 1, true),
+        Some(i) => (i, i + 1, true),
+        None => (bytes.len(), bytes.len(), false),
+    }
+}
+
+impl NextLine for &[u8] {
+    fn next_line(&mut self) -> Option<Line<Self>> {
+        if self.is_empty() {
+            return None;
+        }
+        let (len, end, terminated) = line_bounds(self);
+        let (line, rest) = self.split_at(end);
+        *self = rest;
+        Some(Line { text: &line[..len], terminated })
+    }
+}
+
+impl NextLine for &mut [u8] {
+    fn next_line(&mut self) -> Option<Line<Self>> {
+        if self.is_empty() {
+            return None;
+        }
+        let (len, end, terminated) = line_bounds(self);
+        let (line, rest) = mem::take(self).split_at_mut(end);
+        *self = rest;
+        Some(Line { text: &mut line[..len], terminated })
+    }
+}
+
+impl NextLine for &str {
+    fn next_line(&mut self) -> Option<Line<Self>> {
+        if self.is_empty() {
+            return None;
+        }
+        let (len, end, terminated) = line_bounds(self.as_bytes());
+        let (line, rest) = self.split_at(end);
+        *self = rest;
+        Some(Line { text: &line[..len], terminated })
+    }
+}
+
+impl NextLine for &mut str {
+    fn next_line(&mut self) -> Option<Line<Self>> {
+        if self.is_empty() {
+            return None;
+        }
+        let (len, end, terminated) = line_bounds(self.as_bytes());
+        let (line, rest) = mem::take(self).split_at_mut(end);
+        *self = rest;
+        Some(Line { text: &mut line[..len], terminated })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_in_place() {
+        let mut buffer = *b"ab\r\n\ncd";
+        let mut input: &mut [u8] = &mut buffer;
+        input.take_line().unwrap().make_ascii_uppercase();
+        assert_eq!(input.take_line().unwrap(), b"");
+        assert_eq!(input.take_line(), None);
+        assert_eq!(input.take_through_byte(b'd').unwrap(), b"c");
+        assert!(input.is_empty());
+        assert_eq!(&buffer, b"AB\r\n\ncd");
+    }
+
+    #[test]
+    fn next_line_edge_cases() {
+        let mut input: &[u8] = b"\r\n\r\r\n\n";
+        let mut lines = Vec::new();
+        while let Some(line) = input.next_line() {
+            lines.push((line.text, line.terminated));
+        }
+        assert_eq!(lines, [(&b""[..], true), (&b"\r"[..], true), (&b""[..], true)]);
+
+        let mut owned = String::from("tail\r");
+        let mut text: &mut str = &mut owned;
+        let line = text.next_line().unwrap();
+        assert_eq!((&*line.text, line.terminated), ("tail\r", false));
+        assert!(text.is_empty());
+    }
+
+    #[test]
+    fn find_and_consume_mut() {
+        let mut owned = String::from("\u{e9}=\u{1f980}=");
+        let mut text: &mut str = &mut owned;
+        let (at, prefix) = text.find_and_consume_by(|c| !c.is_ascii() && *c != '\u{e9}').unwrap();
+        assert_eq!((at, &*prefix, &*text), (3, "\u{e9}=", "="));
+        assert_eq!(text.find_and_consume(&'x'), None);
+
+        let mut buffer = [3, 1, 4, 1];
+        let mut slice: &mut [i32] = &mut buffer;
+        let (at, prefix) = slice.find_and_consume(&1).unwrap();
+        prefix[0] = 30;
+        assert_eq!((at, slice.find_and_consume(&1).map(|(at, _)| at)), (1, Some(1)));
+        assert!(slice.is_empty());
+        assert_eq!(buffer, [30, 1, 4, 1]);
+    }
+}
@@ -0,0 +1,180 @@
+//! Shrinking a slice for the length of a scope, and getting the old window back afterwards.
+//!
+//! This is for running a sub-parser on a bounded region, like a length-prefixed record, and then
+//! carrying on from where the region started. To carry on from where it ended instead, split the
+//! region off with [`Take`](crate::Take).
+//!
+//! ```rust
+//! use shrink_slice::scoped::ShrinkScoped;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let mut input: &[u8] = b"\x03abc\x01z";
+//! let total: u32 = input.with_shrunk(1..4, |record| {
+//!     let sum = record.iter().map(|&b| u32::from(b)).sum();
+//!     record.shrink(3..);
+//!     sum
+//! }).unwrap();
+//! assert_eq!(total, 294);
+//! assert_eq!(input, b"\x03abc\x01z");
+//! ```
+
+use crate::{Shrink, ShrinkError, SliceLike, SliceLikeMut};
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::slice::SliceIndex;
+
+/// Extension trait for temporarily shrinking a slice.
+///
+/// It is implemented for shared and mutable references to every [`SliceLike`] type.
+pub trait ShrinkScoped: Shrink {
+    /// A shorter-lived copy of the slice, or reborrow for mutable slices.
+    type Reborrow<'r>: Shrink<Slice = Self::Slice>
+    where Self: 'r;
+
+    /// Borrow the slice for a shorter lifetime, without shrinking it.
+    fn reborrow(&mut self) -> Self::Reborrow<'_>;
+
+    /// Shrink a reborrow of the slice to `range`, and pass it to `f`, returning what `f` returns.
+    /// The slice itself is left as it was, however far `f` shrinks the reborrow, and even if `f`
+    /// panics.
+    ///
+    /// If the range cannot index the slice, `f` is not called.
+    ///
+    /// ```rust
+    /// use shrink_slice::scoped::ShrinkScoped;
+    ///
+    /// let mut buffer = *b"key=value";
+    /// let mut fields: &mut [u8] = &mut buffer;
+    /// fields.with_shrunk(4.., |value| value.make_ascii_uppercase()).unwrap();
+    /// fields[0] = b'K';
+    /// assert_eq!(&buffer, b"Key=VALUE");
+    /// ```
+    fn with_shrunk<'s, R, U, F>(&'s mut self, range: R, f: F) -> Result<U, ShrinkError>
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>,
+          F: FnOnce(&mut Self::Reborrow<'s>) -> U,
+    {
+        let mut narrow = self.reborrow();
+        narrow.try_shrink(range)?;
+        Ok(f(&mut narrow))
+    }
+
+    /// Shrink the slice to `range` until the returned guard is dropped, which puts the old
+    /// window back.
+    ///
+    /// The guard dereferences to the shrunk slice, which can be shrunk further through it. Only
+    /// shared slices can be saved this way; shrink mutable ones with
+    /// [`with_shrunk`](ShrinkScoped::with_shrunk) instead.
+    ///
+    /// ```rust
+    /// use shrink_slice::scoped::ShrinkScoped;
+    /// use shrink_slice::ShrinkExt;
+    ///
+    /// let mut text = "[inner] rest";
+    /// {
+    ///     let mut inner = text.shrink_scoped(1..6).unwrap();
+    ///     inner.shrink(..3);
+    ///     assert_eq!(*inner, "inn");
+    /// }
+    /// assert_eq!(text, "[inner] rest");
+    /// ```
+    fn shrink_scoped<R>(&mut self, range: R) -> Result<ShrinkGuard<'_, Self>, ShrinkError>
+    where R: SliceIndex<Self::Slice, Output = Self::Slice> + RangeBounds<usize>,
+          Self: Clone,
+    {
+        let saved = self.clone();
+        self.try_shrink(range)?;
+        Ok(ShrinkGuard { slot: self, saved: Some(saved) })
+    }
+}
+
+impl<S: SliceLike + ?Sized> ShrinkScoped for &S {
+    type Reborrow<'r> = &'r S
+    where Self: 'r;
+
+    fn reborrow(&mut self) -> &S {
+        self
+    }
+}
+
+impl<S: SliceLikeMut + ?Sized> ShrinkScoped for &mut S {
+    type Reborrow<'r> = &'r mut S
+    where Self: 'r;
+
+    fn reborrow(&mut self) -> &mut S {
+        self
+    }
+}
+
+/// A slice that has been shrunk by [`ShrinkScoped::shrink_scoped`], and is put back the way it
+/// was when this is dropped.
+#[derive(Debug)]
+pub struct ShrinkGuard<'g, S: Clone> {
+    slot: &'g mut S,
+    saved: Option<S>,
+}
+
+impl<S: Clone> ShrinkGuard<'_, S> {
+    /// Keep the slice shrunk to the guard's current window, instead of putting the old one back.
+    ///
+    /// ```rust
+    /// use shrink_slice::scoped::ShrinkScoped;
+    ///
+    /// let mut input: &[u8] = b"  body";
+    /// for prefix in [&b"h"[..], b"b"] {
+    ///     let guard = input.shrink_scoped(2..).unwrap();
+    ///     if guard.starts_with(prefix) {
+    ///         guard.keep();
+    ///     }
+    /// }
+    /// assert_eq!(input, b"body");
+    /// ```
+    pub fn keep(mut self) {
+        self.saved = None;
+    }
+}
+
+impl<S: Clone> Deref for ShrinkGuard<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        self.slot
+    }
+}
+
+impl<S: Clone> DerefMut for ShrinkGuard<'_, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        self.slot
+    }
+}
+
+impl<S: Clone> Drop for ShrinkGuard<'_, S> {
+    fn drop(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            *self.slot = saved;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn restored_after_errors_and_panics() {
+        let mut text = "\u{e9}t\u{e9}";
+        assert!(text.with_shrunk(1.., |_| unreachable!()).is_err());
+        assert!(text.shrink_scoped(..1).is_err());
+        assert_eq!(text, "\u{e9}t\u{e9}");
+
+        let mut buffer = [1, 2, 3];
+        let mut slice: &mut [i32] = &mut buffer;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            slice.with_shrunk(1.., |narrow| {
+                narrow.shrink(1..)[0] = 30;
+                panic!("sub-parser failed");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(slice, [1, 2, 30]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Extracting key material from a buffer while wiping it behind you.
+//!
+//! Wiping is done by [`zeroize`], whose writes the optimizer is not allowed to elide.
+
+use crate::ShrinkExt;
+use core::fmt;
+use zeroize::{Zeroize, Zeroizing};
+
+/// A fixed-size byte array that is wiped when dropped.
+///
+/// The bytes can be borrowed through [`expose`](Secret::expose), or handed on still wrapped in
+/// [`Zeroizing`] through [`into_inner`](Secret::into_inner), so that no unwiped copies are made
+/// by accident. Its [`Debug`](fmt::Debug) output never includes the contents.
+pub struct Secret<const N: usize>(Zeroizing<[u8; N]>);
+
+impl<const N: usize> Secret<N> {
+    /// Wrap an array, taking over responsibility for wiping it.
+    ///
+    /// The array is moved in, which may leave a copy behind wherever it came from, so prefer
+    /// [`TakeSecret::take_secret`] for extracting it from a buffer.
+    pub fn new(bytes: [u8; N]) -> Self {
+        Secret(Zeroizing::new(bytes))
+    }
+
+    /// Borrow the secret bytes.
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0[..]
+    }
+
+    /// Borrow the secret bytes as an array.
+    pub fn expose(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Unwrap the array, which stays in a [`Zeroizing`] and is still wiped when that is dropped.
+    pub fn into_inner(self) -> Zeroizing<[u8; N]> {
+        self.0
+    }
+}
+
+impl<const N: usize> fmt::Debug for Secret<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret<{}>(..)", N)
+    }
+}
+
+/// Extension trait for moving secrets out of a mutable byte slice.
+pub trait TakeSecret {
+    /// Copy the first `N` bytes into a [`Secret`], zero them in the source, and shrink past them.
+    ///
+    /// Returns `None` without touching anything if fewer than `N` bytes remain.
+    ///
+    /// ```rust
+    /// use shrink_slice::secret::TakeSecret;
+    ///
+    /// let mut buffer = *b"keyrest";
+    /// let mut slice: &mut [u8] = &mut buffer;
+    /// let key = slice.take_secret::<3>().unwrap();
+    /// assert_eq!(key.expose_secret(), b"key");
+    /// assert_eq!(slice, b"rest");
+    /// assert_eq!(&buffer, b"\0\0\0rest");
+    /// ```
+    fn take_secret<const N: usize>(&mut self) -> Option<Secret<N>>;
+}
+
+impl TakeSecret for &mut [u8] {
+    fn take_secret<const N: usize>(&mut self) -> Option<Secret<N>> {
+        let source = self.get_mut(..N)?;
+        let mut secret = Secret(Zeroizing::new([0; N]));
+        secret.0.copy_from_slice(source);
+        source.zeroize();
+        self.shrink(N..);
+        Some(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_short() {
+        let mut buffer = *b"ab";
+        let mut slice: &mut [u8] = &mut buffer;
+        assert!(slice.take_secret::<3>().is_none());
+        assert_eq!(slice, b"ab");
+    }
+
+    #[test]
+    fn redacted_debug() {
+        let secret = Secret::new(*b"hunter2");
+        assert_eq!(format!("{:?}", secret), "Secret<7>(..)");
+    }
+
+    #[test]
+    fn exposed_by_reference() {
+        let mut buffer = *b"k3y";
+        let mut slice: &mut [u8] = &mut buffer;
+        let secret = slice.take_secret::<3>().unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(secret.expose_secret(), b"k3y");
+        assert_eq!(buffer, [0; 3]);
+    }
+
+    #[test]
+    fn reaching_the_array() {
+        let mut buffer = *b"abcd";
+        let mut slice: &mut [u8] = &mut buffer;
+        let secret = slice.take_secret::<2>().unwrap();
+        assert_eq!(secret.expose(), b"ab");
+        let inner: Zeroizing<[u8; 2]> = secret.into_inner();
+        assert_eq!(*inner, *b"ab");
+        assert_eq!(slice, b"cd");
+    }
+}
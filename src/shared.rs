@@ -0,0 +1,346 @@
+//! Shrinkable views into reference-counted slices.
+//!
+//! An `Arc<[T]>` always refers to its whole allocation. [`ArcSlice`] and [`RcSlice`] pair one
+//! with the range of it that is currently in view, so every clone can be shrunk independently
+//! while sharing the same allocation. [`ArcStr`] and [`RcStr`] do the same for string slices.
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use shrink_slice::shared::ArcSlice;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let message: ArcSlice<u8> = ArcSlice::from(Arc::<[u8]>::from(&b"HDR:payload"[..]));
+//! let mut payload = message.clone();
+//! payload.shrink(4..);
+//! assert_eq!(&*payload, b"payload");
+//! assert_eq!(&*message, b"HDR:payload");
+//! assert_eq!(payload.range(), 4..11);
+//! ```
+
+use crate::{range, NeedMore, Shrink, ShrinkError, Take};
+use core::fmt;
+use core::ops::{Deref, Range, RangeBounds};
+use core::slice::SliceIndex;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+macro_rules! shared_slice {
+    ($(#[$attr:meta])* $name:ident, $ptr:ident) => {
+        $(#[$attr])*
+        pub struct $name<T> {
+            buf: $ptr<[T]>,
+            range: Range<usize>,
+        }
+
+        impl<T> $name<T> {
+            /// The shared allocation that this is a view into.
+            pub fn buffer(&self) -> &$ptr<[T]> {
+                &self.buf
+            }
+
+            /// The range of [`buffer`](Self::buffer) that is in view.
+            ///
+            /// For zero-sized `T`, where all elements are indistinguishable, only the length of
+            /// this range is meaningful.
+            pub fn range(&self) -> Range<usize> {
+                self.range.clone()
+            }
+        }
+
+        impl<T> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                $name { buf: $ptr::clone(&self.buf), range: self.range.clone() }
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = [T];
+
+            fn deref(&self) -> &[T] {
+                &self.buf[self.range.clone()]
+            }
+        }
+
+        impl<T> AsRef<[T]> for $name<T> {
+            fn as_ref(&self) -> &[T] {
+                self
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&**self, f)
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for $name<T> {
+            fn eq(&self, other: &Self) -> bool {
+                **self == **other
+            }
+        }
+
+        impl<T: Eq> Eq for $name<T> { }
+
+        impl<T> From<$ptr<[T]>> for $name<T> {
+            fn from(buf: $ptr<[T]>) -> Self {
+                let range = 0..buf.len();
+                $name { buf, range }
+            }
+        }
+
+        impl From<$ptr<str>> for $name<u8> {
+            fn from(buf: $ptr<str>) -> Self {
+                $ptr::<[u8]>::from(buf).into()
+            }
+        }
+
+        impl<T> From<Vec<T>> for $name<T> {
+            fn from(vec: Vec<T>) -> Self {
+                $ptr::<[T]>::from(vec).into()
+            }
+        }
+
+        impl<T> Shrink for $name<T> {
+            type Slice = [T];
+
+            fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+            where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+            {
+                let view: &[T] = self;
+                let bounds = range::bounds(&range);
+                let sub = view.get(range)
+                    .ok_or_else(|| ShrinkError::out_of_bounds(bounds, view.len()))?;
+                let start = range::offset_in(view, sub).expect("subslice is within the slice");
+                let start = self.range.start + start;
+                self.range = start..start + sub.len();
+                Ok(())
+            }
+
+            fn len(&self) -> usize {
+                self.range.len()
+            }
+        }
+
+        /// Taking splits the view in two, both sharing the allocation.
+        impl<T> Take for $name<T> {
+            fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+                if n > self.len() {
+                    return Err(NeedMore(n - self.len()));
+                }
+                let mid = self.range.start + n;
+                let head = $name { buf: $ptr::clone(&self.buf), range: self.range.start..mid };
+                self.range.start = mid;
+                Ok(head)
+            }
+
+            fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+                if n > self.len() {
+                    return Err(NeedMore(n - self.len()));
+                }
+                let mid = self.range.end - n;
+                let tail = $name { buf: $ptr::clone(&self.buf), range: mid..self.range.end };
+                self.range.end = mid;
+                Ok(tail)
+            }
+        }
+    };
+}
+
+macro_rules! shared_str {
+    ($(#[$attr:meta])* $name:ident, $ptr:ident) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name {
+            buf: $ptr<str>,
+            range: Range<usize>,
+        }
+
+        impl $name {
+            /// The shared allocation that this is a view into.
+            pub fn buffer(&self) -> &$ptr<str> {
+                &self.buf
+            }
+
+            /// The byte range of [`buffer`](Self::buffer) that is in view.
+            pub fn range(&self) -> Range<usize> {
+                self.range.clone()
+            }
+
+            /// The current view.
+            pub fn as_str(&self) -> &str {
+                &self.buf[self.range.clone()]
+            }
+
+            /// Split off the first `n` bytes as a view of their own, sharing the allocation.
+            ///
+            /// If `n` is past the end or inside a multi-byte character, nothing is taken.
+            pub fn try_take(&mut self, n: usize) -> Result<Self, ShrinkError> {
+                let mut head = self.clone();
+                head.try_shrink(..n)?;
+                self.range.start += n;
+                Ok(head)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(self.as_str(), f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self.as_str(), f)
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        impl Eq for $name { }
+
+        impl From<$ptr<str>> for $name {
+            fn from(buf: $ptr<str>) -> Self {
+                let range = 0..buf.len();
+                $name { buf, range }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(string: String) -> Self {
+                $ptr::<str>::from(string).into()
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                $ptr::<str>::from(s).into()
+            }
+        }
+
+        impl Shrink for $name {
+            type Slice = str;
+
+            fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+            where R: SliceIndex<str, Output = str> + RangeBounds<usize>
+            {
+                let view = self.as_str();
+                let bounds = range::bounds(&range);
+                let sub = view.get(range).ok_or_else(|| ShrinkError::for_str(bounds, view))?;
+                let start = range::offset_in(view.as_bytes(), sub.as_bytes())
+                    .expect("substring is within the string");
+                let start = self.range.start + start;
+                self.range = start..start + sub.len();
+                Ok(())
+            }
+
+            fn len(&self) -> usize {
+                self.range.len()
+            }
+        }
+    };
+}
+
+shared_slice! {
+    /// A shrinkable view into an `Arc<[T]>`, which can be cloned and sent across threads.
+    ArcSlice, Arc
+}
+
+shared_slice! {
+    /// A shrinkable view into an `Rc<[T]>`.
+    RcSlice, Rc
+}
+
+shared_str! {
+    /// A shrinkable view into an `Arc<str>`, which can be cloned and sent across threads.
+    ///
+    /// ```rust
+    /// use shrink_slice::shared::ArcStr;
+    /// use shrink_slice::ShrinkExt;
+    ///
+    /// let mut line = ArcStr::from("key: value");
+    /// let key = line.try_take(3).unwrap();
+    /// line.shrink(2..);
+    /// assert_eq!((&*key, &*line), ("key", "value"));
+    /// assert_eq!(line.range(), 5..10);
+    /// ```
+    ArcStr, Arc
+}
+
+shared_str! {
+    /// A shrinkable view into an `Rc<str>`.
+    RcStr, Rc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn clones_shrink_independently() {
+        let whole = RcSlice::from(vec![1, 2, 3, 4, 5]);
+        let mut a = whole.clone();
+        let mut b = whole.clone();
+        a.shrink(1..4);
+        a.shrink(1..);
+        b.shrink(..=1);
+        assert_eq!(*a, [3, 4]);
+        assert_eq!(a.range(), 2..4);
+        assert_eq!(*b, [1, 2]);
+        assert_eq!(Rc::strong_count(whole.buffer()), 3);
+    }
+
+    #[test]
+    fn failed_shrink_keeps_view() {
+        let mut text = ArcSlice::from(Arc::<str>::from("abc"));
+        text.shrink(1..);
+        let err = ShrinkError::OutOfBounds { start: 0, end: 3, len: 2 };
+        assert_eq!(text.try_shrink(..3), Err(err));
+        assert_eq!(&*text, b"bc");
+    }
+
+    #[test]
+    fn zero_sized() {
+        let mut units = ArcSlice::from(vec![(); 10]);
+        units.shrink(3..8);
+        units.shrink(1..);
+        assert_eq!(units.len(), 4);
+        assert_eq!(units.range().len(), 4);
+    }
+
+    #[test]
+    fn take_shares_allocation() {
+        let mut packet = ArcSlice::from(vec![1u8, 2, 3, 4, 5]);
+        let header = packet.take_exact(2).unwrap();
+        let trailer = packet.try_take_back(1).unwrap();
+        assert_eq!(packet.try_take_back(3), Err(NeedMore(1)));
+        assert_eq!((&*header, &*packet, &*trailer), (&[1, 2][..], &[3, 4][..], &[5][..]));
+        assert_eq!(trailer.range(), 4..5);
+        assert_eq!(Arc::strong_count(packet.buffer()), 3);
+
+        let mut text = RcStr::from(String::from("\u{e9}t\u{e9}"));
+        assert!(text.try_take(1).is_err());
+        assert_eq!(text.try_take(2).unwrap().to_string(), "\u{e9}");
+        assert_eq!(text.range(), 2..5);
+    }
+}
@@ -0,0 +1,155 @@
+//! Splitting a string into shell-style arguments.
+
+use crate::ShrinkExt;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// Extension trait for consuming shell-style arguments from the front of a string slice.
+pub trait ShellArgs<'a> {
+    /// Consume the next argument, skipping any whitespace before it.
+    ///
+    /// Single quotes preserve everything up to the closing quote. Within double quotes, a
+    /// backslash escapes `"` and `\`, and is kept literally before any other character. Outside
+    /// of quotes, a backslash escapes whatever character follows it. An argument without any
+    /// quotes or escapes is borrowed from the input.
+    ///
+    /// Returns `Ok(None)` once only whitespace remains. On error, the input is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::shell::ShellArgs;
+    ///
+    /// let mut line = r#"  cp "my file" it\'s.txt "#;
+    /// assert_eq!(line.next_arg().unwrap().unwrap(), "cp");
+    /// assert_eq!(line.next_arg().unwrap().unwrap(), "my file");
+    /// assert_eq!(line.next_arg().unwrap().unwrap(), "it's.txt");
+    /// assert_eq!(line.next_arg(), Ok(None));
+    /// ```
+    fn next_arg(&mut self) -> Result<Option<Cow<'a, str>>, ShellError>;
+}
+
+impl<'a> ShellArgs<'a> for &'a str {
+    fn next_arg(&mut self) -> Result<Option<Cow<'a, str>>, ShellError> {
+        let input = self.trim_start();
+        if input.is_empty() {
+            *self = input;
+            return Ok(None);
+        }
+
+        // Only allocate once the argument turns out to differ from its source text.
+        let mut owned: Option<String> = None;
+        let mut chars = input.char_indices();
+        let mut end = input.len();
+
+        while let Some((i, c)) = chars.next() {
+            if c.is_whitespace() {
+                end = i;
+                break;
+            }
+            match c {
+                '\\' => {
+                    let buf = owned.get_or_insert_with(|| input[..i].to_string());
+                    let (_, escaped) = chars.next().ok_or(ShellError::TrailingBackslash)?;
+                    buf.push(escaped);
+                }
+                '\'' => {
+                    let buf = owned.get_or_insert_with(|| input[..i].to_string());
+                    loop {
+                        match chars.next() {
+                            Some((_, '\'')) => break,
+                            Some((_, c)) => buf.push(c),
+                            None => return Err(ShellError::UnterminatedQuote),
+                        }
+                    }
+                }
+                '"' => {
+                    let buf = owned.get_or_insert_with(|| input[..i].to_string());
+                    loop {
+                        match chars.next() {
+                            Some((_, '"')) => break,
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, c @ '"')) | Some((_, c @ '\\')) => buf.push(c),
+                                Some((_, c)) => {
+                                    buf.push('\\');
+                                    buf.push(c);
+                                }
+                                None => return Err(ShellError::UnterminatedQuote),
+                            },
+                            Some((_, c)) => buf.push(c),
+                            None => return Err(ShellError::UnterminatedQuote),
+                        }
+                    }
+                }
+                c => if let Some(buf) = &mut owned {
+                    buf.push(c);
+                },
+            }
+        }
+
+        let arg = match owned {
+            Some(buf) => Cow::Owned(buf),
+            None => Cow::Borrowed(&input[..end]),
+        };
+        *self = input;
+        self.shrink(end..);
+        Ok(Some(arg))
+    }
+}
+
+/// This error signifies that an argument was not properly terminated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShellError {
+    /// A single or double quote was never closed.
+    UnterminatedQuote,
+    /// The input ended with an unquoted backslash.
+    TrailingBackslash,
+}
+
+impl fmt::Display for ShellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ShellError::UnterminatedQuote => "unterminated quote",
+            ShellError::TrailingBackslash => "trailing backslash",
+        })
+    }
+}
+
+impl core::error::Error for ShellError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(mut line: &str) -> Vec<Cow<'_, str>> {
+        let mut args = Vec::new();
+        while let Some(arg) = line.next_arg().unwrap() {
+            args.push(arg);
+        }
+        args
+    }
+
+    #[test]
+    fn borrows_plain_words() {
+        let args = split("echo  hello\tworld\n");
+        assert_eq!(args, ["echo", "hello", "world"]);
+        assert!(args.iter().all(|arg| matches!(arg, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn quotes_and_escapes() {
+        let args = split(r#"a"b c"d 'e\f' "g\"h\i" j\ k ''"#);
+        assert_eq!(args, ["ab cd", r"e\f", r#"g"h\i"#, "j k", ""]);
+    }
+
+    #[test]
+    fn errors_leave_input() {
+        let mut line = "one 'two";
+        assert_eq!(line.next_arg().unwrap().unwrap(), "one");
+        assert_eq!(line.next_arg(), Err(ShellError::UnterminatedQuote));
+        assert_eq!(line, " 'two");
+
+        let mut line = r"three\";
+        assert_eq!(line.next_arg(), Err(ShellError::TrailingBackslash));
+        assert_eq!(line, r"three\");
+    }
+}
@@ -0,0 +1,254 @@
+//! The extension point for making slice-like types shrinkable.
+//!
+//! [`Shrink`] can be implemented directly for any type, but for references to a dynamically
+//! sized, slice-like type it is easier to implement [`SliceLike`], and [`SliceLikeMut`] for
+//! mutable references. Shared and mutable references to such a type then implement [`Shrink`]
+//! through the blanket impls in this module, which is how the crate's own impls for slices and
+//! string slices are provided too.
+//!
+//! ```rust
+//! use core::ops::Range;
+//! use shrink_slice::{Shrink, ShrinkExt, SliceLike, SliceLikeMut};
+//!
+//! /// A network packet, which is just bytes with a few methods of its own.
+//! #[repr(transparent)]
+//! struct Packet([u8]);
+//!
+//! impl Packet {
+//!     fn new(bytes: &[u8]) -> &Packet {
+//!         // SAFETY: `Packet` is a transparent wrapper around `[u8]`.
+//!         unsafe { &*(bytes as *const [u8] as *const Packet) }
+//!     }
+//!
+//!     fn new_mut(bytes: &mut [u8]) -> &mut Packet {
+//!         // SAFETY: `Packet` is a transparent wrapper around `[u8]`.
+//!         unsafe { &mut *(bytes as *mut [u8] as *mut Packet) }
+//!     }
+//! }
+//!
+//! impl SliceLike for Packet {
+//!     type Index = [u8];
+//!
+//!     fn len(&self) -> usize {
+//!         self.0.len()
+//!     }
+//!
+//!     fn slice(&self, range: Range<usize>) -> &Packet {
+//!         Packet::new(&self.0[range])
+//!     }
+//! }
+//!
+//! impl SliceLikeMut for Packet {
+//!     fn empty_mut<'a>() -> &'a mut Packet {
+//!         Packet::new_mut(&mut [])
+//!     }
+//!
+//!     fn slice_mut(&mut self, range: Range<usize>) -> &mut Packet {
+//!         Packet::new_mut(&mut self.0[range])
+//!     }
+//! }
+//!
+//! let mut buffer = *b"\x02\x00hi";
+//! let mut packet = Packet::new_mut(&mut buffer);
+//! packet.shrink_front(2);
+//! packet.0[0] = b'H';
+//! assert_eq!(&buffer, b"\x02\x00Hi");
+//!
+//! let mut packet = Packet::new(&buffer);
+//! assert!(packet.try_shrink(..5).is_err());
+//! packet.shrink(1..3);
+//! assert_eq!(&packet.0, b"\x00H");
+//! ```
+//!
+//! ## Stability
+//!
+//! Both traits are meant to be implemented outside this crate. Their required methods, and the
+//! way the blanket impls call them, only change in breaking releases. Provided methods may be
+//! added at any time, so only call them through the traits, not through same-named methods of
+//! your own.
+
+use crate::{range, Shrink, ShrinkError};
+use core::ops::{Range, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A dynamically sized type that can be sliced by ranges of indices, like `[T]` or `str`.
+///
+/// Implementing this makes `&Self` implement [`Shrink`]. The blanket impl resolves every range
+/// against [`len`](SliceLike::len), rejecting those that are out of bounds, then passes the rest
+/// to [`check_range`](SliceLike::check_range) and finally to [`slice`](SliceLike::slice).
+pub trait SliceLike {
+    /// The type whose ranges are accepted by [`Shrink::try_shrink`], that is, the
+    /// [`Shrink::Slice`] of references to `Self`.
+    ///
+    /// The standard library's `SliceIndex` is sealed, so types other than `[T]` and `str` pick
+    /// whichever of those their indices behave like, usually `[T]` of their element type.
+    type Index: ?Sized;
+
+    /// The length, in the units that ranges count.
+    fn len(&self) -> usize;
+
+    /// Whether the length is zero.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reject ranges that are within bounds but still cannot be sliced, like ranges that split a
+    /// character of a string slice. Every range is accepted by default.
+    fn check_range(&self, range: &Range<usize>) -> Result<(), ShrinkError> {
+        let _ = range;
+        Ok(())
+    }
+
+    /// The part of `self` within `range`, which is within bounds and has passed
+    /// [`check_range`](SliceLike::check_range).
+    fn slice(&self, range: Range<usize>) -> &Self;
+}
+
+/// A [`SliceLike`] type that can also be sliced mutably, making `&mut Self` implement
+/// [`Shrink`] too.
+pub trait SliceLikeMut: SliceLike {
+    /// An empty value, left behind while a mutable reference is being shrunk.
+    fn empty_mut<'a>() -> &'a mut Self;
+
+    /// The mutable part of `self` within `range`, under the same guarantees as
+    /// [`SliceLike::slice`].
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut Self;
+
+    /// Shrink `slice` to the part within `range`, under the same guarantees as
+    /// [`SliceLike::slice`]. This is what the blanket impl of [`Shrink`] for `&mut Self` calls
+    /// once the range has been checked.
+    ///
+    /// By default, the reference is moved out of `slice`, leaving
+    /// [`empty_mut`](SliceLikeMut::empty_mut) behind until
+    /// [`slice_mut`](SliceLikeMut::slice_mut) returns. The store of the empty value is usually
+    /// optimized out, but if `slice_mut` panics, it is what `slice` is left with. With the
+    /// `unsafe-fast` feature, `[T]` and `str` override this to compute the new reference from
+    /// the old one's pointer and length instead, which compiles to an add and a store of the new
+    /// length, and never stores an empty value.
+    fn shrink_mut(slice: &mut &mut Self, range: Range<usize>) {
+        *slice = core::mem::replace(slice, Self::empty_mut()).slice_mut(range);
+    }
+}
+
+impl<S: SliceLike + ?Sized> Shrink for &S {
+    type Slice = S::Index;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<S::Index, Output = S::Index> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.check_range(&range)?;
+        *self = self.slice(range);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        S::len(self)
+    }
+}
+
+impl<S: SliceLikeMut + ?Sized> Shrink for &mut S {
+    type Slice = S::Index;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<S::Index, Output = S::Index> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, S::len(self))?;
+        self.check_range(&range)?;
+        S::shrink_mut(self, range);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        S::len(self)
+    }
+}
+
+impl<T> SliceLike for [T] {
+    type Index = [T];
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+}
+
+impl<T> SliceLikeMut for [T] {
+    fn empty_mut<'a>() -> &'a mut Self {
+        &mut []
+    }
+
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut Self {
+        &mut self[range]
+    }
+
+    #[cfg(feature = "unsafe-fast")]
+    fn shrink_mut(slice: &mut &mut Self, range: Range<usize>) {
+        crate::fast::narrow_mut(slice, range);
+    }
+}
+
+impl SliceLike for str {
+    type Index = str;
+
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    fn check_range(&self, range: &Range<usize>) -> Result<(), ShrinkError> {
+        if self.is_char_boundary(range.start) && self.is_char_boundary(range.end) {
+            Ok(())
+        } else {
+            Err(ShrinkError::for_str(range.clone(), self))
+        }
+    }
+
+    fn slice(&self, range: Range<usize>) -> &Self {
+        &self[range]
+    }
+}
+
+impl SliceLikeMut for str {
+    fn empty_mut<'a>() -> &'a mut Self {
+        Default::default()
+    }
+
+    fn slice_mut(&mut self, range: Range<usize>) -> &mut Self {
+        &mut self[range]
+    }
+
+    #[cfg(feature = "unsafe-fast")]
+    fn shrink_mut(slice: &mut &mut Self, range: Range<usize>) {
+        crate::fast::narrow_str_mut(slice, range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::RangeTo;
+
+    fn halve<S: SliceLikeMut + ?Sized>(slice: &mut &mut S) -> Result<(), ShrinkError>
+    where RangeTo<usize>: SliceIndex<S::Index, Output = S::Index>,
+    {
+        let half = S::len(slice) / 2;
+        slice.try_shrink_back(half)
+    }
+
+    #[test]
+    fn generic_over_slice_like() {
+        let mut buffer = [1, 2, 3, 4, 5];
+        let mut slice: &mut [i32] = &mut buffer;
+        halve(&mut slice).unwrap();
+        slice[2] = 30;
+        assert_eq!(buffer, [1, 2, 30, 4, 5]);
+
+        let mut owned = String::from("a\u{e9}");
+        let mut text: &mut str = &mut owned;
+        assert!(matches!(halve(&mut text), Err(ShrinkError::NotCharBoundary { index: 2, .. })));
+        assert_eq!(text, "a\u{e9}");
+    }
+}
@@ -0,0 +1,508 @@
+//! Splitting mutable slices into pieces that keep the original lifetime.
+
+use crate::pattern::Pattern;
+use crate::{range, ShrinkError};
+use core::{fmt, mem};
+use core::ops::{Range, RangeBounds};
+
+/// Extension trait for carving a window out of the middle of a mutable slice.
+pub trait TakeMiddleMut: Sized {
+    /// Split the slice into the parts before, inside and after `range`. The part before is left
+    /// in `self`, and the middle and the part after are returned, in that order.
+    ///
+    /// If the range is out of bounds, the slice is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::split::TakeMiddleMut;
+    ///
+    /// let mut buffer = *b"<<body>>";
+    /// let mut slice: &mut [u8] = &mut buffer;
+    /// let (body, after) = slice.take_middle_mut(2..6).unwrap();
+    /// body.make_ascii_uppercase();
+    /// after[0] = b']';
+    /// slice[1] = b'[';
+    /// assert_eq!(&buffer, b"<[BODY]>");
+    /// ```
+    fn take_middle_mut<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>;
+}
+
+impl<T> TakeMiddleMut for &mut [T] {
+    fn take_middle_mut<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        let (rest, after) = mem::take(self).split_at_mut(range.end);
+        let (before, middle) = rest.split_at_mut(range.start);
+        *self = before;
+        Ok((middle, after))
+    }
+}
+
+/// Extension trait for shrinking a slice to a range while keeping the parts around it.
+pub trait SplitThree: Sized {
+    /// Shrink the slice to `range`, and return the parts before and after it, in that order, with
+    /// the slice's original lifetime.
+    ///
+    /// If the range is out of bounds, or for string slices, if either end of it lands within a
+    /// multi-byte character, the slice is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::split::SplitThree;
+    ///
+    /// let mut line = "key = value ; comment";
+    /// let (head, tail) = line.split_three(6..11).unwrap();
+    /// assert_eq!((head, line, tail), ("key = ", "value", " ; comment"));
+    ///
+    /// let mut buffer = *b"[ab]";
+    /// let mut inner: &mut [u8] = &mut buffer;
+    /// let (open, close) = inner.split_three(1..3).unwrap();
+    /// inner.make_ascii_uppercase();
+    /// (open[0], close[0]) = (b'(', b')');
+    /// assert_eq!(&buffer, b"(AB)");
+    /// ```
+    fn split_three<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>;
+}
+
+impl<T> SplitThree for &[T] {
+    fn split_three<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        let (rest, tail) = self.split_at(range.end);
+        let (head, middle) = rest.split_at(range.start);
+        *self = middle;
+        Ok((head, tail))
+    }
+}
+
+impl<T> SplitThree for &mut [T] {
+    fn split_three<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        let (rest, tail) = mem::take(self).split_at_mut(range.end);
+        let (head, middle) = rest.split_at_mut(range.start);
+        *self = middle;
+        Ok((head, tail))
+    }
+}
+
+/// Resolve `range` against `s`, checking that both of its ends are char boundaries.
+fn str_range<R: RangeBounds<usize>>(range: R, s: &str) -> Result<Range<usize>, ShrinkError> {
+    let range = range::try_resolve(range, s.len())?;
+    if s.is_char_boundary(range.start) && s.is_char_boundary(range.end) {
+        Ok(range)
+    } else {
+        Err(ShrinkError::for_str(range, s))
+    }
+}
+
+impl SplitThree for &str {
+    fn split_three<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>
+    {
+        let range = str_range(range, self)?;
+        let (rest, tail) = self.split_at(range.end);
+        let (head, middle) = rest.split_at(range.start);
+        *self = middle;
+        Ok((head, tail))
+    }
+}
+
+impl SplitThree for &mut str {
+    fn split_three<R: RangeBounds<usize>>(&mut self, range: R)
+        -> Result<(Self, Self), ShrinkError>
+    {
+        let range = str_range(range, self)?;
+        let (rest, tail) = mem::take(self).split_at_mut(range.end);
+        let (head, middle) = rest.split_at_mut(range.start);
+        *self = middle;
+        Ok((head, tail))
+    }
+}
+
+/// Extension trait for splitting a slice into several disjoint parts at once, like handing one
+/// part of a buffer to each of a fixed number of workers.
+///
+/// ```rust
+/// use shrink_slice::split::SplitN;
+///
+/// let mut pixels = [0u8; 10];
+/// let mut rest: &mut [u8] = &mut pixels;
+/// let [header, mut body] = rest.split_at_many([2, 8]).unwrap();
+/// header.fill(0xff);
+/// std::thread::scope(|s| {
+///     for (part, value) in IntoIterator::into_iter(body.split_n::<3>()).zip(1..) {
+///         s.spawn(move || part.fill(value));
+///     }
+/// });
+/// rest.fill(0xee);
+/// assert_eq!(pixels, [0xff, 0xff, 1, 1, 2, 2, 3, 3, 0xee, 0xee]);
+/// ```
+pub trait SplitN: Sized {
+    /// Split the whole slice into `N` parts of nearly equal length, leaving `self` empty. The
+    /// first `len % N` parts are one element longer than the others.
+    ///
+    /// If `N` is zero, no parts are returned and the slice is left untouched.
+    fn split_n<const N: usize>(&mut self) -> [Self; N];
+
+    /// Split `N` parts off the front of the slice, the `i`th of them ending at `ends[i]`, and
+    /// leave the rest in `self`.
+    ///
+    /// The ends are offsets into the slice, not lengths of the parts. If they are decreasing or
+    /// out of bounds, the slice is left untouched.
+    fn split_at_many<const N: usize>(&mut self, ends: [usize; N])
+        -> Result<[Self; N], ShrinkError>;
+}
+
+/// The ends of `N` parts of nearly equal length covering `0..len`.
+fn even_ends<const N: usize>(len: usize) -> [usize; N] {
+    let (size, extra) = match N {
+        0 => (0, 0),
+        n => (len / n, len % n),
+    };
+    core::array::from_fn(|i| (i + 1) * size + extra.min(i + 1))
+}
+
+/// Check that `ends` is increasing and within `len`, reporting the first part that is not.
+fn check_ends(ends: &[usize], len: usize) -> Result<(), ShrinkError> {
+    let mut start = 0;
+    for &end in ends {
+        start = range::try_resolve(start..end, len)?.end;
+    }
+    Ok(())
+}
+
+impl<T> SplitN for &[T] {
+    fn split_n<const N: usize>(&mut self) -> [Self; N] {
+        let ends = even_ends(self.len());
+        self.split_at_many(ends).expect("even parts are within the slice")
+    }
+
+    fn split_at_many<const N: usize>(&mut self, ends: [usize; N])
+        -> Result<[Self; N], ShrinkError>
+    {
+        check_ends(&ends, self.len())?;
+        let mut start = 0;
+        Ok(core::array::from_fn(|i| {
+            let (part, rest) = self.split_at(ends[i] - start);
+            (*self, start) = (rest, ends[i]);
+            part
+        }))
+    }
+}
+
+impl<T> SplitN for &mut [T] {
+    fn split_n<const N: usize>(&mut self) -> [Self; N] {
+        let ends = even_ends(self.len());
+        self.split_at_many(ends).expect("even parts are within the slice")
+    }
+
+    fn split_at_many<const N: usize>(&mut self, ends: [usize; N])
+        -> Result<[Self; N], ShrinkError>
+    {
+        check_ends(&ends, self.len())?;
+        let mut start = 0;
+        Ok(core::array::from_fn(|i| {
+            let (part, rest) = mem::take(self).split_at_mut(ends[i] - start);
+            (*self, start) = (rest, ends[i]);
+            part
+        }))
+    }
+}
+
+/// Extension trait for carving several disjoint mutable parts out of a slice at once, like
+/// `get_disjoint_mut` but consuming the slice as it goes.
+///
+/// ```rust
+/// use shrink_slice::split::{DisjointError, SplitDisjointMut};
+///
+/// let mut frame = [0u8; 10];
+/// let mut free: &mut [u8] = &mut frame;
+/// let [payload, header] = free.split_disjoint_mut([3..7, 0..2]).unwrap();
+/// payload.fill(b'p');
+/// header.copy_from_slice(b"HD");
+/// free.fill(b'-');
+/// assert_eq!(&frame, b"HD\0pppp---");
+///
+/// let mut free: &mut [u8] = &mut frame;
+/// let err = free.split_disjoint_mut([0..4, 3..5]).unwrap_err();
+/// assert_eq!(err, DisjointError::Overlapping(0, 1));
+/// assert_eq!(free.len(), 10);
+/// ```
+pub trait SplitDisjointMut: Sized {
+    /// Split off the parts of the slice in `ranges`, which may come in any order, returning them
+    /// in the same order. The slice is shrunk past the end of the last of them, so that it is
+    /// left with what follows all of them. What lies between them is dropped.
+    ///
+    /// If any range is decreasing or out of bounds, or two non-empty ranges overlap, the slice
+    /// is left untouched. Empty ranges never overlap anything.
+    fn split_disjoint_mut<const N: usize>(&mut self, ranges: [Range<usize>; N])
+        -> Result<[Self; N], DisjointError>;
+}
+
+impl<T> SplitDisjointMut for &mut [T] {
+    fn split_disjoint_mut<const N: usize>(&mut self, ranges: [Range<usize>; N])
+        -> Result<[Self; N], DisjointError>
+    {
+        for range in &ranges {
+            range::try_resolve(range.clone(), self.len())?;
+        }
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_unstable_by_key(|&i| ranges[i].start);
+        let mut last: Option<usize> = None;
+        for &i in order.iter().filter(|&&i| !ranges[i].is_empty()) {
+            match last {
+                Some(prev) if ranges[i].start < ranges[prev].end => {
+                    return Err(DisjointError::Overlapping(prev.min(i), prev.max(i)));
+                }
+                _ => last = Some(i),
+            }
+        }
+
+        let mut parts: [Option<Self>; N] = core::array::from_fn(|_| None);
+        let mut offset = 0;
+        for &i in &order {
+            let Range { start, end } = ranges[i];
+            if start == end {
+                parts[i] = Some(&mut []);
+                continue;
+            }
+            let (part, rest) = mem::take(self)[start - offset..].split_at_mut(end - start);
+            parts[i] = Some(part);
+            (*self, offset) = (rest, end);
+        }
+        Ok(parts.map(|part| part.expect("every range has been split off")))
+    }
+}
+
+/// Extension trait for splitting fields separated by a delimiter off the front of a slice, one at
+/// a time, so that parsing can stop after any field and carry on differently with the rest.
+///
+/// Slices are split by an element, and string slices by a [`Pattern`]. A delimiter that matches
+/// empty text would split off empty fields forever, so it panics instead.
+///
+/// ```rust
+/// use shrink_slice::split::SplitBy;
+///
+/// let mut request = "GET /index.html HTTP/1.1\r\nHost: example.com\r\n";
+/// let line: Vec<_> = request.split_by(' ').take(2).collect();
+/// assert_eq!(line, ["GET", "/index.html"]);
+/// assert_eq!(request.next_field(&mut "\r\n"), Some("HTTP/1.1"));
+///
+/// let mut csv: &[u8] = b"1,22,333";
+/// let sizes: Vec<_> = csv.split_by(b',').map(<[u8]>::len).collect();
+/// assert_eq!((sizes, csv), (vec![1, 2, 3], &b""[..]));
+/// ```
+pub trait SplitBy<P>: Sized {
+    /// Split off everything before the first match of `delim`, and drop the match itself.
+    ///
+    /// If there is no match, `None` is returned and the slice is left untouched. The delimiter is
+    /// borrowed mutably so that closures can be called again for the next field.
+    fn next_field(&mut self, delim: &mut P) -> Option<Self>;
+
+    /// An iterator over the fields separated by `delim`, splitting each one off the slice as it
+    /// is returned. After the last delimiter, the rest of the slice is returned as the final
+    /// field, like the standard library's `split` methods do.
+    ///
+    /// Dropping the iterator before it is done leaves the fields that were not returned in the
+    /// slice.
+    fn split_by(&mut self, delim: P) -> Fields<'_, Self, P> {
+        Fields { rest: self, delim, done: false }
+    }
+}
+
+/// The iterator returned by [`SplitBy::split_by`].
+#[derive(Debug)]
+pub struct Fields<'s, S, P> {
+    rest: &'s mut S,
+    delim: P,
+    done: bool,
+}
+
+impl<S: SplitBy<P> + Default, P> Iterator for Fields<'_, S, P> {
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        if self.done {
+            return None;
+        }
+        let field = self.rest.next_field(&mut self.delim);
+        if field.is_none() {
+            self.done = true;
+        }
+        field.or_else(|| Some(mem::take(self.rest)))
+    }
+}
+
+impl<T: PartialEq> SplitBy<T> for &[T] {
+    fn next_field(&mut self, delim: &mut T) -> Option<Self> {
+        let i = self.iter().position(|x| x == delim)?;
+        let (field, rest) = self.split_at(i);
+        *self = &rest[1..];
+        Some(field)
+    }
+}
+
+impl<T: PartialEq> SplitBy<T> for &mut [T] {
+    fn next_field(&mut self, delim: &mut T) -> Option<Self> {
+        let i = self.iter().position(|x| x == delim)?;
+        let (field, rest) = mem::take(self).split_at_mut(i);
+        *self = &mut rest[1..];
+        Some(field)
+    }
+}
+
+/// The start and end of the first match of `delim` in `s`, which must not be empty.
+#[track_caller]
+fn find_delim<P: Pattern>(s: &str, delim: &mut P) -> Option<(usize, usize)> {
+    let start = delim.find_in(s)?;
+    let len = delim.prefix_len(&s[start..]).unwrap_or(0);
+    assert!(len != 0, "the delimiter matched empty text");
+    Some((start, start + len))
+}
+
+impl<P: Pattern> SplitBy<P> for &str {
+    #[track_caller]
+    fn next_field(&mut self, delim: &mut P) -> Option<Self> {
+        let (start, end) = find_delim(self, delim)?;
+        let field = &self[..start];
+        *self = &self[end..];
+        Some(field)
+    }
+}
+
+impl<P: Pattern> SplitBy<P> for &mut str {
+    #[track_caller]
+    fn next_field(&mut self, delim: &mut P) -> Option<Self> {
+        let (start, end) = find_delim(self, delim)?;
+        let (field, rest) = mem::take(self).split_at_mut(start);
+        *self = &mut rest[end - start..];
+        Some(field)
+    }
+}
+
+/// The error returned when a slice cannot be split into the given disjoint parts.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisjointError {
+    /// A range was decreasing or out of bounds.
+    OutOfRange(ShrinkError),
+    /// The ranges at these two positions overlap.
+    Overlapping(usize, usize),
+}
+
+impl From<ShrinkError> for DisjointError {
+    fn from(e: ShrinkError) -> Self {
+        DisjointError::OutOfRange(e)
+    }
+}
+
+impl fmt::Display for DisjointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DisjointError::OutOfRange(e) => e.fmt(f),
+            DisjointError::Overlapping(a, b) => write!(f, "ranges {} and {} overlap", a, b),
+        }
+    }
+}
+
+impl core::error::Error for DisjointError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_bounds_keeps_slice() {
+        let mut buffer = [1, 2, 3];
+        let mut slice: &mut [i32] = &mut buffer;
+        assert!(slice.take_middle_mut(1..4).is_err());
+        assert_eq!(slice, [1, 2, 3]);
+        let (middle, after) = slice.take_middle_mut(..).unwrap();
+        assert_eq!((middle.len(), after.len(), slice.len()), (3, 0, 0));
+    }
+
+    #[test]
+    fn three_way_errors_keep_slice() {
+        let mut owned = String::from("\u{e9}t\u{e9}");
+        let mut text: &mut str = &mut owned;
+        let error = text.split_three(1..3).unwrap_err();
+        assert!(matches!(error, ShrinkError::NotCharBoundary { index: 1, .. }));
+        let (head, tail) = text.split_three(2..3).unwrap();
+        assert_eq!((&*head, &*text, &*tail), ("\u{e9}", "t", "\u{e9}"));
+
+        let data = [1, 2, 3];
+        let mut slice: &[i32] = &data;
+        let (start, end) = (2, 1);
+        assert!(slice.split_three(start..end).is_err());
+        let (head, tail) = slice.split_three(3..).unwrap();
+        assert_eq!((head.len(), slice.len(), tail.len()), (3, 0, 0));
+    }
+
+    #[test]
+    fn even_and_explicit_parts() {
+        let data = [1, 2, 3, 4, 5];
+        let mut slice: &[i32] = &data;
+        let [a, b, c] = slice.split_n();
+        assert_eq!((a, b, c, slice), (&[1, 2][..], &[3, 4][..], &[5][..], &[][..]));
+        let mut slice: &[i32] = &data[..1];
+        let [] = slice.split_n();
+        assert_eq!(slice.split_n::<3>().map(<[i32]>::len), [1, 0, 0]);
+
+        let mut slice: &[i32] = &data;
+        let error = slice.split_at_many([1, 4, 3]).unwrap_err();
+        assert_eq!(error, ShrinkError::OutOfBounds { start: 4, end: 3, len: 5 });
+        assert!(slice.split_at_many([6]).is_err());
+        assert_eq!(slice, data);
+        let [a, b] = slice.split_at_many([0, 4]).unwrap();
+        assert_eq!((a.len(), b.len(), slice), (0, 4, &[5][..]));
+    }
+
+    #[test]
+    fn fields_stop_midway() {
+        let mut owned = String::from("a;b;;c");
+        let mut text: &mut str = &mut owned;
+        let mut fields = text.split_by(';');
+        fields.next().unwrap().make_ascii_uppercase();
+        assert_eq!(fields.next().as_deref(), Some("b"));
+        assert_eq!(text, ";c");
+        let rest: Vec<_> = text.split_by(|c| c == ';').map(|f| f.len()).collect();
+        assert_eq!(rest, [0, 1]);
+        assert_eq!(owned, "A;b;;c");
+
+        let mut empty: &[u8] = b"";
+        assert_eq!(empty.split_by(b',').count(), 1);
+        let mut buffer = [1, 0, 2];
+        let mut slice: &mut [i32] = &mut buffer;
+        assert_eq!(slice.next_field(&mut 0).map(|f| f.len()), Some(1));
+        assert_eq!(slice.next_field(&mut 0), None);
+        assert_eq!(slice, [2]);
+    }
+
+    #[test]
+    #[should_panic = "empty text"]
+    fn empty_delimiter() {
+        "abc".split_by("").next();
+    }
+
+    #[test]
+    fn disjoint_in_any_order() {
+        let mut buffer = [0; 8];
+        let mut rest: &mut [i32] = &mut buffer;
+        let [a, empty, b, c] = rest.split_disjoint_mut([5..6, 5..5, 1..3, 3..5]).unwrap();
+        (a[0], b[1], c[0]) = (5, 2, 3);
+        assert!(empty.is_empty());
+        rest[1] = 7;
+        assert_eq!(buffer, [0, 0, 2, 3, 0, 5, 0, 7]);
+
+        let mut rest: &mut [i32] = &mut buffer;
+        let err = ShrinkError::OutOfBounds { start: 7, end: 9, len: 8 };
+        assert_eq!(rest.split_disjoint_mut([0..1, 7..9]), Err(DisjointError::OutOfRange(err)));
+        let overlap = DisjointError::Overlapping(1, 2);
+        assert_eq!(rest.split_disjoint_mut([4..8, 0..2, 1..5]), Err(overlap));
+        let [] = rest.split_disjoint_mut([]).unwrap();
+        assert_eq!(rest.len(), 8);
+    }
+}
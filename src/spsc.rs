@@ -0,0 +1,162 @@
+//! A byte buffer split into a filled region for a reader and a free region for a writer.
+//!
+//! [`SpscBuf`] lays a single `&mut [u8]` out like this:
+//!
+//! ```text
+//! [ consumed | filled | written | free ]
+//! ```
+//!
+//! The writer appends into the free region, which shrinks from the front as it goes. Nothing it
+//! writes is visible to the reader until it is [published](SpscBuf::publish), at which point the
+//! filled region grows to cover it. The reader shrinks the filled region from the front as it
+//! consumes. Both views can be borrowed at once with [`SpscBuf::split`], since they never overlap.
+//!
+//! ```rust
+//! use shrink_slice::spsc::SpscBuf;
+//!
+//! let mut storage = [0; 16];
+//! let mut buf = SpscBuf::new(&mut storage);
+//!
+//! buf.write(b"hello");
+//! assert_eq!(buf.filled(), b"");
+//! buf.publish();
+//! assert_eq!(buf.filled(), b"hello");
+//!
+//! buf.consume(2).unwrap();
+//! assert_eq!(buf.filled(), b"llo");
+//! ```
+
+use crate::{Shrink, ShrinkError};
+
+/// A single-producer, single-consumer view over a byte buffer.
+///
+/// See the [module documentation](self) for an overview.
+#[derive(Debug)]
+pub struct SpscBuf<'a> {
+    buf: &'a mut [u8],
+    read: usize,
+    filled: usize,
+    written: usize,
+}
+
+impl<'a> SpscBuf<'a> {
+    /// Wrap a buffer, treating all of it as free space.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SpscBuf { buf, read: 0, filled: 0, written: 0 }
+    }
+
+    /// The published bytes that have not been consumed yet.
+    #[inline]
+    pub fn filled(&self) -> &[u8] {
+        &self.buf[self.read..self.filled]
+    }
+
+    /// The bytes that have been written but not yet published.
+    #[inline]
+    pub fn pending(&self) -> &[u8] {
+        &self.buf[self.filled..self.written]
+    }
+
+    /// The space that is still available to the writer.
+    #[inline]
+    pub fn free(&mut self) -> &mut [u8] {
+        &mut self.buf[self.written..]
+    }
+
+    /// Borrow the filled view and the free view at the same time.
+    pub fn split(&mut self) -> (&[u8], &mut [u8]) {
+        let (front, free) = self.buf.split_at_mut(self.written);
+        (&front[self.read..self.filled], free)
+    }
+
+    /// Copy as much of `data` as fits into the free space, returning the number of bytes copied.
+    ///
+    /// The copied bytes stay pending until [`publish`](Self::publish) is called.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        let free = self.free();
+        let n = data.len().min(free.len());
+        free[..n].copy_from_slice(&data[..n]);
+        self.written += n;
+        n
+    }
+
+    /// Mark the first `n` bytes of the free space as written, after filling them in through
+    /// [`free`](Self::free) or [`split`](Self::split).
+    ///
+    /// Returns an error if fewer than `n` bytes are free.
+    pub fn advance(&mut self, n: usize) -> Result<(), ShrinkError> {
+        let mut free = self.free();
+        free.try_shrink(n..)?;
+        self.written += n;
+        Ok(())
+    }
+
+    /// Make every pending byte visible to the reader.
+    #[inline]
+    pub fn publish(&mut self) {
+        self.filled = self.written;
+    }
+
+    /// Drop the first `n` filled bytes.
+    ///
+    /// Returns an error if fewer than `n` bytes are filled.
+    pub fn consume(&mut self, n: usize) -> Result<(), ShrinkError> {
+        let mut filled = self.filled();
+        filled.try_shrink(n..)?;
+        self.read += n;
+        Ok(())
+    }
+
+    /// Move the filled and pending bytes to the start of the buffer, reclaiming the space that
+    /// has already been consumed.
+    pub fn compact(&mut self) {
+        if self.read == 0 {
+            return;
+        }
+        self.buf.copy_within(self.read..self.written, 0);
+        self.filled -= self.read;
+        self.written -= self.read;
+        self.read = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_publish_consume() {
+        let mut storage = [0; 8];
+        let mut buf = SpscBuf::new(&mut storage);
+
+        assert_eq!(buf.write(b"abcdef"), 6);
+        assert_eq!(buf.pending(), b"abcdef");
+        buf.publish();
+        assert_eq!(buf.write(b"ghij"), 2);
+        assert_eq!(buf.filled(), b"abcdef");
+
+        buf.consume(4).unwrap();
+        assert_eq!(buf.filled(), b"ef");
+        assert!(buf.consume(3).is_err());
+
+        buf.compact();
+        assert_eq!(buf.free().len(), 4);
+        buf.publish();
+        assert_eq!(buf.filled(), b"efgh");
+    }
+
+    #[test]
+    fn split_views() {
+        let mut storage = [0; 8];
+        let mut buf = SpscBuf::new(&mut storage);
+        buf.write(b"ab");
+        buf.publish();
+
+        let (filled, free) = buf.split();
+        free[..2].copy_from_slice(filled);
+        buf.advance(2).unwrap();
+        assert!(buf.advance(5).is_err());
+        buf.publish();
+        assert_eq!(buf.filled(), b"abab");
+    }
+}
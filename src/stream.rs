@@ -0,0 +1,261 @@
+//! A [`Stream`] of items decoded from a tokio async reader.
+//!
+//! [`FrameStream`] packages the loop that every async protocol implementation ends up writing:
+//! read into a buffer, decode as many items as the buffered bytes hold, and read more once the
+//! decoder runs out. Any [`Decode`] implementation can be used, as long as its items do not
+//! borrow from the input, which is what [`OwnedFrames`](crate::frame::OwnedFrames) is for.
+//!
+//! ```rust
+//! use core::future::poll_fn;
+//! use core::pin::Pin;
+//! use futures_core::Stream;
+//! use shrink_slice::frame::{FrameReader, OwnedFrames, Prefix};
+//! use shrink_slice::stream::FrameStream;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let socket: &[u8] = b"\x02hi\x05world";
+//! let decoder = OwnedFrames::new(FrameReader::length_prefixed(Prefix::U8));
+//! let mut frames = FrameStream::new(socket, decoder);
+//!
+//! let mut values = Vec::new();
+//! while let Some(frame) = poll_fn(|cx| Pin::new(&mut frames).poll_next(cx)).await {
+//!     values.push(frame.unwrap().value);
+//! }
+//! assert_eq!(values, [b"hi".to_vec(), b"world".to_vec()]);
+//! # }
+//! ```
+
+use crate::frame::Decode;
+use crate::vecbuf::VecBuf;
+use crate::{Shrink, ShrinkExt};
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use std::io;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// How many bytes [`FrameStream::new`] reads at a time.
+const DEFAULT_CHUNK: usize = 8 * 1024;
+
+/// A stream of the items that a [`Decode`] implementation finds in the bytes of an async reader.
+///
+/// Bytes are read into a buffer, a chunk at a time, whenever the decoder needs more than is
+/// buffered. Decoded bytes are dropped from the front of the buffer, and their space is reused
+/// for later reads. The stream ends when the reader does, or with a
+/// [`Truncated`](StreamError::Truncated) error if that happens in the middle of an item.
+///
+/// After any other error, the stream can still be polled, and carries on where it left off.
+pub struct FrameStream<R, D> {
+    reader: R,
+    decoder: D,
+    buf: VecBuf<u8>,
+    chunk: usize,
+    partial: bool,
+    done: bool,
+}
+
+impl<R: AsyncRead + Unpin, D> FrameStream<R, D> {
+    /// Decode items from `reader` with `decoder`, reading 8 KiB at a time.
+    pub fn new(reader: R, decoder: D) -> Self {
+        FrameStream::with_chunk_size(reader, decoder, DEFAULT_CHUNK)
+    }
+
+    /// Decode items from `reader` with `decoder`, reading at most `chunk` bytes at a time.
+    ///
+    /// Panics if `chunk` is zero.
+    pub fn with_chunk_size(reader: R, decoder: D, chunk: usize) -> Self {
+        assert!(chunk > 0, "chunk size must not be zero");
+        let buf = VecBuf::with_capacity(chunk);
+        FrameStream { reader, decoder, buf, chunk, partial: false, done: false }
+    }
+
+    /// The bytes that have been read but not decoded yet.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The decoder.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Give up the stream, returning the reader, the decoder and the bytes that were buffered.
+    pub fn into_parts(self) -> (R, D, Vec<u8>) {
+        (self.reader, self.decoder, self.buf.into_vec())
+    }
+
+    /// Read another chunk into the buffer, returning the number of bytes read.
+    fn refill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let (reader, chunk) = (&mut self.reader, self.chunk);
+        let mut result = Poll::Pending;
+        self.buf.reserve(chunk);
+        self.buf.write_spare(chunk, |spare| {
+            let mut spare = ReadBuf::new(spare);
+            result = Pin::new(reader).poll_read(cx, &mut spare);
+            spare.filled().len()
+        });
+        result.map_ok(|()| self.buf.len())
+    }
+}
+
+impl<R, D, T, E> Stream for FrameStream<R, D>
+where R: AsyncRead + Unpin,
+      D: for<'a> Decode<'a, Item = T, Error = E> + Unpin,
+{
+    type Item = Result<T, StreamError<E>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let mut view: &[u8] = &this.buf;
+            let decoded = this.decoder.decode(&mut view);
+            let consumed = this.buf.len() - view.len();
+            this.buf.shrink_front(consumed);
+            match decoded {
+                Ok(Some(item)) => {
+                    this.partial = false;
+                    return Poll::Ready(Some(Ok(item)));
+                }
+                Ok(None) => this.partial |= consumed > 0 || !this.buf.is_empty(),
+                Err(e) => {
+                    this.partial = false;
+                    return Poll::Ready(Some(Err(StreamError::Decode(e))));
+                }
+            }
+
+            let before = this.buf.len();
+            match this.refill(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(StreamError::Io(e)))),
+                Poll::Ready(Ok(len)) if len == before => {
+                    this.done = true;
+                    if this.partial {
+                        let buffered = this.buf.len();
+                        return Poll::Ready(Some(Err(StreamError::Truncated { buffered })));
+                    }
+                }
+                Poll::Ready(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+impl<R, D: fmt::Debug> fmt::Debug for FrameStream<R, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FrameStream")
+            .field("decoder", &self.decoder)
+            .field("buffered", &self.buf.len())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The error yielded by a [`FrameStream`].
+#[derive(Debug)]
+pub enum StreamError<E> {
+    /// Reading failed.
+    Io(io::Error),
+    /// The decoder rejected the input.
+    Decode(E),
+    /// The reader ended in the middle of an item, with the given number of bytes still buffered.
+    Truncated {
+        /// The number of bytes that were read but not decoded.
+        buffered: usize,
+    },
+}
+
+impl<E> From<io::Error> for StreamError<E> {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for StreamError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => e.fmt(f),
+            StreamError::Decode(e) => e.fmt(f),
+            StreamError::Truncated { buffered } => {
+                write!(f, "input ended in the middle of an item, {} bytes buffered", buffered)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for StreamError<E> { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{FrameReader, OwnedFrame, OwnedFrames, Prefix};
+    use core::future::poll_fn;
+
+    /// Hands out one byte per read, and is pending before every other read.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        ready: bool,
+    }
+
+    impl AsyncRead for Trickle<'_> {
+        fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>)
+            -> Poll<io::Result<()>>
+        {
+            self.ready = !self.ready;
+            if !self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if let Some((&b, rest)) = self.bytes.split_first() {
+                buf.put_slice(&[b]);
+                self.bytes = rest;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn tlv(bytes: &[u8]) -> FrameStream<Trickle<'_>, OwnedFrames> {
+        let reader = Trickle { bytes, ready: false };
+        FrameStream::with_chunk_size(reader, OwnedFrames::new(FrameReader::tlv(Prefix::U8)), 2)
+    }
+
+    async fn collect<S: Stream + Unpin>(stream: &mut S) -> Vec<S::Item> {
+        let mut items = Vec::new();
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[tokio::test]
+    async fn partial_reads() {
+        let mut frames = tlv(b"\x01\x03abc\x02\x00\x03\x05hello");
+        let frames: Vec<_> = collect(&mut frames).await.into_iter().map(Result::unwrap).collect();
+        assert_eq!(frames, [
+            OwnedFrame { tag: Some(1), value: b"abc".to_vec() },
+            OwnedFrame { tag: Some(2), value: Vec::new() },
+            OwnedFrame { tag: Some(3), value: b"hello".to_vec() },
+        ]);
+    }
+
+    #[tokio::test]
+    async fn eof_mid_frame() {
+        let mut frames = tlv(b"\x01\x01a\x02\x04ab");
+        let items = collect(&mut frames).await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[1], Err(StreamError::Truncated { buffered: 2 })));
+        assert_eq!(frames.buffered(), b"ab");
+
+        let mut frames = tlv(b"\x01");
+        let items = collect(&mut frames).await;
+        assert!(matches!(items[..], [Err(StreamError::Truncated { buffered: 1 })]));
+
+        let mut frames = tlv(b"");
+        assert!(collect(&mut frames).await.is_empty());
+    }
+}
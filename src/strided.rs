@@ -0,0 +1,195 @@
+//! Views of every `step`th element of a slice, like one channel of interleaved audio.
+//!
+//! ```rust
+//! use shrink_slice::strided::ShrinkStepExt;
+//! use shrink_slice::{Shrink, ShrinkExt};
+//!
+//! // Interleaved stereo frames: left, right, left, right, ...
+//! let frames = [0.1, -0.1, 0.2, -0.2, 0.3, -0.3, 0.4, -0.4];
+//! let mut right = (&frames[..]).shrink_step(1.., 2);
+//! assert_eq!(right.iter().copied().collect::<Vec<_>>(), [-0.1, -0.2, -0.3, -0.4]);
+//! right.shrink(1..3);
+//! assert_eq!(right.get(0), Some(&-0.2));
+//! assert_eq!(right.len(), 2);
+//! ```
+
+use crate::{range, Shrink, ShrinkError};
+use core::fmt;
+use core::iter::StepBy;
+use core::ops::RangeBounds;
+use core::slice::{self, SliceIndex};
+
+/// A shrinkable view of every `step`th element of a slice.
+///
+/// Its length and the ranges it is shrunk by count the elements in the view, not in the slice it
+/// was taken from.
+#[derive(Debug)]
+pub struct Strided<'a, T> {
+    // Starts at the first element of the view and ends just after its last, or is empty.
+    slice: &'a [T],
+    step: usize,
+}
+
+impl<T> Clone for Strided<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Strided<'_, T> { }
+
+impl<'a, T> Strided<'a, T> {
+    /// A view of every `step`th element of `slice`, starting with the first.
+    ///
+    /// Panics if `step` is zero.
+    #[track_caller]
+    pub fn new(slice: &'a [T], step: usize) -> Self {
+        assert!(step != 0, "the step of a strided view must be positive");
+        let end = match slice.len() {
+            0 => 0,
+            len => (len - 1) / step * step + 1,
+        };
+        Strided { slice: &slice[..end], step }
+    }
+
+    /// The distance between consecutive elements of the view, in elements of the slice.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The `index`th element of the view, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.slice.get(index.checked_mul(self.step)?)
+    }
+
+    /// An iterator over the elements of the view, with the original slice's lifetime.
+    pub fn iter(&self) -> StepBy<slice::Iter<'a, T>> {
+        self.slice.iter().step_by(self.step)
+    }
+}
+
+impl<'a, T> IntoIterator for Strided<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StepBy<slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Shrink for Strided<'_, T> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        let start = range.start.saturating_mul(self.step).min(self.slice.len());
+        self.slice = if range.is_empty() {
+            &self.slice[start..start]
+        } else {
+            &self.slice[start..=(range.end - 1) * self.step]
+        };
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        match self.slice.len() {
+            0 => 0,
+            len => (len - 1) / self.step + 1,
+        }
+    }
+}
+
+/// Extension trait for taking a [`Strided`] view of part of a slice.
+pub trait ShrinkStep<'a, T> {
+    /// A view of every `step`th element of `range`, starting with its first, or an error if
+    /// `step` is zero or if `range` is decreasing or out of bounds.
+    fn try_shrink_step<R>(&self, range: R, step: usize) -> Result<Strided<'a, T>, StepError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>;
+}
+
+/// The panicking counterpart of [`try_shrink_step`](ShrinkStep::try_shrink_step).
+pub trait ShrinkStepExt<'a, T>: ShrinkStep<'a, T> {
+    /// A view of every `step`th element of `range`, panicking if `step` is zero or if `range` is
+    /// decreasing or out of bounds.
+    #[track_caller]
+    fn shrink_step<R>(&self, range: R, step: usize) -> Strided<'a, T>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        match self.try_shrink_step(range, step) {
+            Ok(strided) => strided,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<'a, T, S: ShrinkStep<'a, T> + ?Sized> ShrinkStepExt<'a, T> for S { }
+
+impl<'a, T> ShrinkStep<'a, T> for &'a [T] {
+    fn try_shrink_step<R>(&self, range: R, step: usize) -> Result<Strided<'a, T>, StepError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        if step == 0 {
+            return Err(StepError::ZeroStep);
+        }
+        let mut slice = *self;
+        slice.try_shrink(range)?;
+        Ok(Strided::new(slice, step))
+    }
+}
+
+/// The error returned when a [`Strided`] view cannot be taken.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StepError {
+    /// The step was zero.
+    ZeroStep,
+    /// The range was out of bounds.
+    OutOfRange(ShrinkError),
+}
+
+impl From<ShrinkError> for StepError {
+    fn from(e: ShrinkError) -> Self {
+        StepError::OutOfRange(e)
+    }
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StepError::ZeroStep => f.write_str("the step of a strided view must be positive"),
+            StepError::OutOfRange(e) => e.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for StepError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn shrink_by_view_indices() {
+        let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut view = (&data[..]).shrink_step(2.., 3);
+        assert_eq!((view.len(), view.step()), (3, 3));
+        assert!(view.iter().eq(&[2, 5, 8]));
+        assert!(view.try_shrink(..4).is_err());
+        view.shrink(1..);
+        assert_eq!((view.get(0), view.get(1), view.get(2)), (Some(&5), Some(&8), None));
+        view.shrink(2..);
+        assert!(view.is_empty());
+        assert_eq!(view.iter().count(), 0);
+        assert_eq!(Strided::new(&data[..1], 4).len(), 1);
+    }
+
+    #[test]
+    fn zero_step_is_an_error() {
+        let data = [0, 1, 2];
+        assert_eq!((&data[..]).try_shrink_step(.., 0).unwrap_err(), StepError::ZeroStep);
+        assert_eq!((&data[..]).try_shrink_step(4.., 0).unwrap_err(), StepError::ZeroStep);
+        assert!(matches!((&data[..]).try_shrink_step(4.., 1), Err(StepError::OutOfRange(_))));
+    }
+}
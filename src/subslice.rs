@@ -0,0 +1,278 @@
+//! Shrinking a slice to a subslice of it that was found some other way, like the remainder
+//! returned by a parser, and finding where a shrunk slice lies within the one it came from, or
+//! where two slices overlap.
+
+use crate::{range, Shrink, ShrinkExt};
+use core::{fmt, mem};
+
+/// Extension trait for shrinking a slice to exactly the region of a subslice of it.
+///
+/// The subslice is passed as a raw pointer, to which shared references coerce, so it can have
+/// been borrowed from the very slice being shrunk, even a mutable one:
+///
+/// ```rust
+/// use shrink_slice::subslice::{ShrinkToSubslice, ShrinkToSubsliceExt};
+///
+/// fn skip_digits(input: &[u8]) -> &[u8] {
+///     let n = input.iter().take_while(|b| b.is_ascii_digit()).count();
+///     &input[n..]
+/// }
+///
+/// let mut buffer = *b"42abc";
+/// let mut window: &mut [u8] = &mut buffer;
+/// window.shrink_to_subslice(skip_digits(window));
+/// window.make_ascii_uppercase();
+/// assert_eq!(&buffer, b"42ABC");
+///
+/// let copy = String::from("42abc");
+/// let mut text = "42abc";
+/// assert!(text.try_shrink_to_subslice(&copy[2..]).is_err());
+/// ```
+pub trait ShrinkToSubslice: Shrink {
+    /// Shrink to the region of `sub`, or return an error and leave the slice untouched if `sub`
+    /// does not lie entirely within it.
+    ///
+    /// Containment is checked by address. For zero-sized elements, whose slices carry no address
+    /// information, any `sub` that is no longer than the slice is taken to be at its front.
+    fn try_shrink_to_subslice(&mut self, sub: *const Self::Slice) -> Result<(), NotSubslice>;
+}
+
+/// The panicking counterpart of
+/// [`try_shrink_to_subslice`](ShrinkToSubslice::try_shrink_to_subslice), for subslices that are
+/// known to lie within the slice.
+pub trait ShrinkToSubsliceExt: ShrinkToSubslice {
+    /// Shrink to the region of `sub`, panicking if it does not lie entirely within the slice.
+    #[track_caller]
+    fn shrink_to_subslice(&mut self, sub: *const Self::Slice) {
+        if let Err(e) = self.try_shrink_to_subslice(sub) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<S: ShrinkToSubslice + ?Sized> ShrinkToSubsliceExt for S { }
+
+impl<T> ShrinkToSubslice for &[T] {
+    fn try_shrink_to_subslice(&mut self, sub: *const [T]) -> Result<(), NotSubslice> {
+        let start = range::offset_of_raw(&**self, sub).ok_or(NotSubslice)?;
+        self.shrink(start..start + sub.len());
+        Ok(())
+    }
+}
+
+impl<T> ShrinkToSubslice for &mut [T] {
+    fn try_shrink_to_subslice(&mut self, sub: *const [T]) -> Result<(), NotSubslice> {
+        let start = range::offset_of_raw(&**self, sub).ok_or(NotSubslice)?;
+        self.shrink(start..start + sub.len());
+        Ok(())
+    }
+}
+
+impl ShrinkToSubslice for &str {
+    fn try_shrink_to_subslice(&mut self, sub: *const str) -> Result<(), NotSubslice> {
+        let sub = sub as *const [u8];
+        let start = range::offset_of_raw(self.as_bytes(), sub).ok_or(NotSubslice)?;
+        self.try_shrink(start..start + sub.len()).map_err(|_| NotSubslice)
+    }
+}
+
+impl ShrinkToSubslice for &mut str {
+    fn try_shrink_to_subslice(&mut self, sub: *const str) -> Result<(), NotSubslice> {
+        let sub = sub as *const [u8];
+        let start = range::offset_of_raw(self.as_bytes(), sub).ok_or(NotSubslice)?;
+        self.try_shrink(start..start + sub.len()).map_err(|_| NotSubslice)
+    }
+}
+
+/// Extension trait for finding how far a shrunk slice has moved from the slice it was shrunk
+/// from, for turning positions within it back into absolute offsets.
+///
+/// Like [`ShrinkToSubslice`], the origin is passed as a raw pointer, so it can be kept around
+/// while a mutable slice is being shrunk:
+///
+/// ```rust
+/// use shrink_slice::subslice::OffsetFrom;
+/// use shrink_slice::ShrinkExt;
+///
+/// let mut buffer = *b"HEADERbody";
+/// let mut input: &mut [u8] = &mut buffer;
+/// let origin: *const [u8] = &*input;
+/// input.shrink_front(6);
+/// input[0] = b'B';
+/// assert_eq!(input.offset_from(origin), Some(6));
+///
+/// let text = "abc";
+/// assert_eq!(text.offset_from(&text[1..]), None);
+/// ```
+pub trait OffsetFrom: Shrink {
+    /// The number of elements, or bytes for string slices, between the start of `origin` and
+    /// the start of the slice, or `None` unless the slice lies entirely within `origin`.
+    ///
+    /// Containment is checked by address, as for
+    /// [`try_shrink_to_subslice`](ShrinkToSubslice::try_shrink_to_subslice). Only the address
+    /// and length of `origin` are used, so it does not need to be valid any more.
+    fn offset_from(&self, origin: *const Self::Slice) -> Option<usize>;
+}
+
+impl<T> OffsetFrom for &[T] {
+    fn offset_from(&self, origin: *const [T]) -> Option<usize> {
+        range::offset_of_raw(origin, *self)
+    }
+}
+
+impl<T> OffsetFrom for &mut [T] {
+    fn offset_from(&self, origin: *const [T]) -> Option<usize> {
+        range::offset_of_raw(origin, *self)
+    }
+}
+
+impl OffsetFrom for &str {
+    fn offset_from(&self, origin: *const str) -> Option<usize> {
+        range::offset_of_raw(origin as *const [u8], self.as_bytes())
+    }
+}
+
+impl OffsetFrom for &mut str {
+    fn offset_from(&self, origin: *const str) -> Option<usize> {
+        range::offset_of_raw(origin as *const [u8], self.as_bytes())
+    }
+}
+
+/// Shrink `a` and `b` to the memory they have in common, like two windows into the same mapped
+/// file.
+///
+/// The overlap is found by address, and each slice is then shrunk by index, so both keep the
+/// provenance they started with. If the slices do not overlap by at least one element, or they
+/// overlap but their elements do not line up, both are left untouched. For zero-sized elements,
+/// whose slices carry no address information, both are taken to start at the same place.
+///
+/// ```rust
+/// use shrink_slice::subslice::{retain_overlap, Overlap};
+///
+/// let file = b"0123456789";
+/// let (mut first, mut second) = (&file[2..7], &file[5..]);
+/// retain_overlap(&mut first, &mut second).unwrap();
+/// assert_eq!((first, second), (&b"56"[..], &b"56"[..]));
+///
+/// let (mut first, mut second) = (&file[..3], &file[3..]);
+/// assert_eq!(retain_overlap(&mut first, &mut second), Err(Overlap::Disjoint));
+/// ```
+pub fn retain_overlap<T>(a: &mut &[T], b: &mut &[T]) -> Result<(), Overlap> {
+    let size = mem::size_of::<T>();
+    if size == 0 {
+        let len = a.len().min(b.len());
+        a.shrink(..len);
+        b.shrink(..len);
+        return Ok(());
+    }
+    let (a_start, b_start) = (a.as_ptr() as usize, b.as_ptr() as usize);
+    let start = a_start.max(b_start);
+    let end = (a_start + mem::size_of_val(*a)).min(b_start + mem::size_of_val(*b));
+    if start >= end {
+        return Err(Overlap::Disjoint);
+    }
+    if (start - a_start) % size != 0 || (start - b_start) % size != 0 {
+        return Err(Overlap::Misaligned);
+    }
+    let len = (end - start) / size;
+    let (a_offset, b_offset) = ((start - a_start) / size, (start - b_start) / size);
+    a.shrink(a_offset..a_offset + len);
+    b.shrink(b_offset..b_offset + len);
+    Ok(())
+}
+
+/// The error returned by [`retain_overlap`] when two slices have no elements in common.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overlap {
+    /// The slices do not overlap, and may not even be in the same allocation.
+    Disjoint,
+    /// The slices overlap, but at an offset that is not a whole number of elements.
+    Misaligned,
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Overlap::Disjoint => f.write_str("the slices do not overlap"),
+            Overlap::Misaligned => {
+                f.write_str("the slices overlap, but their elements do not line up")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Overlap { }
+
+/// The error returned when shrinking to a slice that does not lie within the slice being shrunk.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NotSubslice;
+
+impl fmt::Display for NotSubslice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the given slice is not a subslice of the one being shrunk")
+    }
+}
+
+impl core::error::Error for NotSubslice { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn containment() {
+        let data = [1, 2, 3, 4, 5];
+        let mut view: &[i32] = &data[1..4];
+        assert_eq!(view.try_shrink_to_subslice(&data[..2]), Err(NotSubslice));
+        assert_eq!(view.try_shrink_to_subslice(&data[3..5]), Err(NotSubslice));
+        assert_eq!(view.len(), 3);
+        view.shrink_to_subslice(&data[2..4]);
+        assert_eq!(view, [3, 4]);
+        view.shrink_to_subslice(&data[4..4]);
+        assert!(view.is_empty());
+
+        let mut owned = String::from("key = value");
+        let mut text: &mut str = &mut owned;
+        text.shrink_to_subslice(text.split('=').nth(1).unwrap().trim());
+        text.make_ascii_uppercase();
+        assert_eq!(owned, "key = VALUE");
+    }
+
+    #[test]
+    fn offsets_by_address() {
+        let data = [0u32; 6];
+        let origin: *const [u32] = &data[1..];
+        let mut view: &[u32] = &data;
+        assert_eq!(view.offset_from(origin), None);
+        view.shrink(3..5);
+        assert_eq!(view.offset_from(origin), Some(2));
+        assert_eq!(view.offset_from(&data[..4]), None);
+
+        let text = "\u{e9}t\u{e9}";
+        let mut view = text;
+        view.shrink(2..);
+        assert_eq!(view.offset_from(text), Some(2));
+    }
+
+    #[test]
+    fn overlap_edge_cases() {
+        let data = [0u16; 8];
+        let (mut a, mut b) = (&data[1..3], &data[..]);
+        retain_overlap(&mut a, &mut b).unwrap();
+        assert_eq!((b.as_ptr(), b.len()), (data[1..].as_ptr(), 2));
+
+        let bytes = [0u8; 9];
+        let words: &[[u8; 2]] = &[[0; 2]; 4];
+        let (mut a, mut b) = (&words[..2], &words[1..]);
+        retain_overlap(&mut a, &mut b).unwrap();
+        assert_eq!((a.len(), b.len()), (1, 1));
+        let (mut a, mut b) = (&bytes[..0], &bytes[..]);
+        assert_eq!(retain_overlap(&mut a, &mut b), Err(Overlap::Disjoint));
+        assert_eq!(b.len(), 9);
+
+        let units = [(); 5];
+        let (mut a, mut b) = (&units[..2], &units[1..]);
+        retain_overlap(&mut a, &mut b).unwrap();
+        assert_eq!((a.len(), b.len()), (2, 2));
+    }
+}
@@ -0,0 +1,622 @@
+//! Consuming the ends of a slice while keeping hold of what was consumed.
+
+use crate::{range, Shrink};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+
+/// Extension trait for splitting elements off the ends of a slice.
+///
+/// Unlike [`Shrink`], which discards whatever falls outside the new range, these methods hand
+/// the removed part back to the caller with the slice's original lifetime.
+pub trait Take: Shrink + Sized {
+    /// Split off exactly `n` elements from the front of the slice and return them, leaving the
+    /// rest in `self`.
+    ///
+    /// If fewer than `n` elements remain, the slice is left untouched and the error reports how
+    /// many more are needed.
+    ///
+    /// ```rust
+    /// use shrink_slice::{Take, NeedMore};
+    ///
+    /// let mut input: &[u8] = b"abc";
+    /// assert_eq!(input.take_exact(2), Ok(&b"ab"[..]));
+    /// assert_eq!(input.take_exact(4), Err(NeedMore(3)));
+    /// assert_eq!(input, b"c");
+    /// ```
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore>;
+
+    /// Split off the first `n` elements and return them, leaving the rest in `self`, or return
+    /// `None` without touching the slice if fewer than `n` remain.
+    ///
+    /// The returned prefix keeps the slice's original lifetime, so it can be used after `self`
+    /// has been shrunk further.
+    ///
+    /// ```rust
+    /// use shrink_slice::Take;
+    ///
+    /// fn field<'a>(input: &mut &'a [u8]) -> Option<&'a [u8]> {
+    ///     let len = *input.take_front(1)?.first()? as usize;
+    ///     input.take_front(len)
+    /// }
+    ///
+    /// let mut input: &[u8] = b"\x02hi\x05world";
+    /// let first = field(&mut input).unwrap();
+    /// let second = field(&mut input).unwrap();
+    /// assert_eq!((first, second), (&b"hi"[..], &b"world"[..]));
+    /// assert_eq!(field(&mut input), None);
+    /// ```
+    fn take_front(&mut self, n: usize) -> Option<Self> {
+        self.take_exact(n).ok()
+    }
+
+    /// Return the elements in `range`, leaving everything after it in `self`. Whatever comes
+    /// before the range is dropped.
+    ///
+    /// Returns `None` without touching the slice if the range is out of bounds.
+    ///
+    /// ```rust
+    /// use shrink_slice::Take;
+    ///
+    /// let mut input: &[u8] = b"GET /index.html HTTP/1.1";
+    /// let path = Take::take(&mut input, 4..15).unwrap();
+    /// assert_eq!(path, b"/index.html");
+    /// assert_eq!(input, b" HTTP/1.1");
+    /// ```
+    ///
+    /// Byte slices also have [`Read::take`](std::io::Read::take), which takes precedence when
+    /// [`Read`](std::io::Read) is in scope, so calling this as `Take::take` is clearer there.
+    fn take<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Self> {
+        let range = range::resolve(range, self.len())?;
+        let mut taken = self.take_front(range.end)?;
+        taken.take_front(range.start)?;
+        Some(taken)
+    }
+
+    /// Split off exactly `n` elements from the back of the slice and return them, leaving the
+    /// rest in `self`.
+    ///
+    /// If fewer than `n` elements remain, the slice is left untouched and the error reports how
+    /// many more are needed.
+    ///
+    /// ```rust
+    /// use shrink_slice::Take;
+    ///
+    /// let mut record: &[u8] = b"payload\x00\x07";
+    /// let trailer = record.try_take_back(2).unwrap();
+    /// assert_eq!(u16::from_be_bytes([trailer[0], trailer[1]]), 7);
+    /// assert_eq!(record, b"payload");
+    /// ```
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore>;
+
+    /// Split off the last `n` elements and return them, or return `None` without touching the
+    /// slice if fewer than `n` remain.
+    fn take_back(&mut self, n: usize) -> Option<Self> {
+        self.try_take_back(n).ok()
+    }
+
+    /// Drop exactly `n` elements from the front of the slice.
+    ///
+    /// If fewer than `n` elements remain, the slice is left untouched and the error reports how
+    /// many more are needed, just like [`take_exact`](Take::take_exact).
+    ///
+    /// ```rust
+    /// use shrink_slice::{Take, NeedMore};
+    ///
+    /// let mut packet: &[u8] = b"\x00\x04skip\x02ok";
+    /// packet.try_shrink_exact(2).unwrap();
+    /// packet.try_shrink_exact(4).unwrap();
+    /// assert_eq!(packet.try_shrink_exact(4), Err(NeedMore(1)));
+    /// assert_eq!(packet, b"\x02ok");
+    /// ```
+    #[track_caller]
+    fn try_shrink_exact(&mut self, n: usize) -> Result<(), NeedMore> {
+        self.take_exact(n).map(drop)
+    }
+
+    /// Check that at least `n` elements remain, without consuming anything.
+    ///
+    /// This is useful for validating a length prefix before committing to parsing the frame it
+    /// describes.
+    ///
+    /// ```rust
+    /// use shrink_slice::{Take, NeedMore};
+    ///
+    /// let mut input: &[u8] = b"\x05abc";
+    /// let len = input.take_exact(1).unwrap()[0] as usize;
+    /// assert_eq!(input.ensure_remaining(len), Err(NeedMore(2)));
+    /// assert_eq!(input, b"abc");
+    /// ```
+    fn ensure_remaining(&self, n: usize) -> Result<(), NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        Ok(())
+    }
+}
+
+impl<T> Take for &[T] {
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        let (head, tail) = self.split_at(n);
+        *self = tail;
+        Ok(head)
+    }
+
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let at = self.len().checked_sub(n).ok_or_else(|| NeedMore(n - self.len()))?;
+        let (head, tail) = self.split_at(at);
+        *self = head;
+        Ok(tail)
+    }
+}
+
+impl<T> Take for &mut [T] {
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        if n > self.len() {
+            return Err(NeedMore(n - self.len()));
+        }
+        let (head, tail) = core::mem::take(self).split_at_mut(n);
+        *self = tail;
+        Ok(head)
+    }
+
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let at = self.len().checked_sub(n).ok_or_else(|| NeedMore(n - self.len()))?;
+        let (head, tail) = core::mem::take(self).split_at_mut(at);
+        *self = head;
+        Ok(tail)
+    }
+}
+
+/// Extension trait for consuming single elements from a slice.
+///
+/// The elements are returned as references with the slice's original lifetime, shared or
+/// mutable depending on the slice.
+pub trait TakeElement: Take {
+    /// A reference to one element of the slice.
+    type Ref;
+
+    /// Remove the first element and return it, or return `None` if the slice is empty.
+    ///
+    /// ```rust
+    /// use shrink_slice::TakeElement;
+    ///
+    /// let mut buffer = [1, 2, 3];
+    /// let mut slice: &mut [i32] = &mut buffer;
+    /// let first = slice.shift().unwrap();
+    /// *slice.shift().unwrap() *= 10;
+    /// *first = 0;
+    /// assert_eq!(slice, [3]);
+    /// assert_eq!(buffer, [0, 20, 3]);
+    /// ```
+    fn shift(&mut self) -> Option<Self::Ref>;
+
+    /// Remove the last element and return it, or return `None` if the slice is empty.
+    ///
+    /// ```rust
+    /// use shrink_slice::TakeElement;
+    ///
+    /// let mut path: &[&str] = &["usr", "local", "bin"];
+    /// assert_eq!(path.pop_back(), Some(&"bin"));
+    /// assert_eq!(path, ["usr", "local"]);
+    /// ```
+    fn pop_back(&mut self) -> Option<Self::Ref>;
+
+    /// Iterate over the elements by shifting them off the front, or popping them off the back,
+    /// of the slice. Whatever is left when the iterator is dropped stays in the slice.
+    ///
+    /// ```rust
+    /// use shrink_slice::TakeElement;
+    ///
+    /// let mut tokens: &[&str] = &["let", "x", "=", "1", ";"];
+    /// let keyword = tokens.shrinking().next();
+    /// let mut binding = tokens.shrinking();
+    /// let name = binding.next();
+    /// assert_eq!(binding.next_back(), Some(&";"));
+    /// assert_eq!((keyword, name), (Some(&"let"), Some(&"x")));
+    /// assert_eq!(tokens, ["=", "1"]);
+    /// ```
+    fn shrinking(&mut self) -> Shrinking<'_, Self> {
+        Shrinking { slice: self }
+    }
+}
+
+/// An iterator that consumes a slice from either end, leaving the elements it has not yielded
+/// in the slice.
+///
+/// This is returned by [`TakeElement::shrinking`].
+#[derive(Debug)]
+pub struct Shrinking<'s, S> {
+    slice: &'s mut S,
+}
+
+impl<S> Shrinking<'_, S> {
+    /// The elements that have not been yielded yet.
+    pub fn remainder(&self) -> &S {
+        self.slice
+    }
+}
+
+impl<S: TakeElement> Iterator for Shrinking<'_, S> {
+    type Item = S::Ref;
+
+    fn next(&mut self) -> Option<S::Ref> {
+        self.slice.shift()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len(), Some(self.slice.len()))
+    }
+}
+
+impl<S: TakeElement> DoubleEndedIterator for Shrinking<'_, S> {
+    fn next_back(&mut self) -> Option<S::Ref> {
+        self.slice.pop_back()
+    }
+}
+
+impl<S: TakeElement> ExactSizeIterator for Shrinking<'_, S> { }
+
+impl<S: TakeElement> FusedIterator for Shrinking<'_, S> { }
+
+impl<'a, T> TakeElement for &'a [T] {
+    type Ref = &'a T;
+
+    fn shift(&mut self) -> Option<&'a T> {
+        let (first, rest) = self.split_first()?;
+        *self = rest;
+        Some(first)
+    }
+
+    fn pop_back(&mut self) -> Option<&'a T> {
+        let (last, rest) = self.split_last()?;
+        *self = rest;
+        Some(last)
+    }
+}
+
+impl<'a, T> TakeElement for &'a mut [T] {
+    type Ref = &'a mut T;
+
+    fn shift(&mut self) -> Option<&'a mut T> {
+        let (first, rest) = core::mem::take(self).split_first_mut()?;
+        *self = rest;
+        Some(first)
+    }
+
+    fn pop_back(&mut self) -> Option<&'a mut T> {
+        let (last, rest) = core::mem::take(self).split_last_mut()?;
+        *self = rest;
+        Some(last)
+    }
+}
+
+/// Extension trait for consuming fixed-size arrays from the front of a slice.
+pub trait TakeArray: Take {
+    /// A reference to an array of `N` elements of the slice.
+    type Array<const N: usize>;
+
+    /// Split off the first `N` elements as an array reference with the slice's original
+    /// lifetime, or return `None` without touching the slice if fewer than `N` remain.
+    ///
+    /// ```rust
+    /// use shrink_slice::TakeArray;
+    ///
+    /// let mut input: &[u8] = b"\x2a\x00\x00\x00rest";
+    /// let value = u32::from_le_bytes(*input.take_array().unwrap());
+    /// assert_eq!(value, 42);
+    /// assert_eq!(input, b"rest");
+    /// assert!(input.take_array::<5>().is_none());
+    /// ```
+    fn take_array<const N: usize>(&mut self) -> Option<Self::Array<N>>;
+
+    /// Convert the whole slice to an array reference with its original lifetime, leaving it
+    /// empty, or return an error without touching the slice if it is not exactly `N` elements
+    /// long.
+    ///
+    /// ```rust
+    /// use shrink_slice::{LengthMismatch, ShrinkExt, TakeArray};
+    ///
+    /// let mut packet: &[u8] = b"\x00\x04\xc0\xa8\x00\x01";
+    /// packet.shrink(2..);
+    /// let address: &[u8; 4] = packet.try_as_array().unwrap();
+    /// assert_eq!(address, &[192, 168, 0, 1]);
+    /// assert_eq!(packet.try_as_array::<4>(), Err(LengthMismatch { expected: 4, len: 0 }));
+    /// ```
+    fn try_as_array<const N: usize>(&mut self) -> Result<Self::Array<N>, LengthMismatch> {
+        let len = self.len();
+        if len != N {
+            return Err(LengthMismatch { expected: N, len });
+        }
+        Ok(self.take_array().expect("the slice is exactly N elements long"))
+    }
+}
+
+impl<'a, T> TakeArray for &'a [T] {
+    type Array<const N: usize> = &'a [T; N];
+
+    fn take_array<const N: usize>(&mut self) -> Option<&'a [T; N]> {
+        let (head, tail) = self.split_first_chunk()?;
+        *self = tail;
+        Some(head)
+    }
+}
+
+impl<'a, T> TakeArray for &'a mut [T] {
+    type Array<const N: usize> = &'a mut [T; N];
+
+    fn take_array<const N: usize>(&mut self) -> Option<&'a mut [T; N]> {
+        if self.len() < N {
+            return None;
+        }
+        let (head, tail) = core::mem::take(self).split_first_chunk_mut()?;
+        *self = tail;
+        Some(head)
+    }
+}
+
+/// Extension trait for consuming the prefix of a slice that matches a predicate, as a building
+/// block for small zero-copy lexers.
+///
+/// Like [`Iterator::take_while`], the predicate is passed a reference to each item: an element of
+/// a slice, or a `char` of a string slice. The prefix is returned with the slice's original
+/// lifetime.
+///
+/// ```rust
+/// use shrink_slice::TakeWhile;
+///
+/// let mut source = "count = 42;";
+/// let ident = source.take_while(|c| c.is_alphanumeric());
+/// source.take_while(|c| c.is_whitespace());
+/// let op = source.take_until(|c| c.is_whitespace());
+/// source.take_while(|c| c.is_whitespace());
+/// let value = source.take_while(char::is_ascii_digit);
+/// assert_eq!((ident, op, value, source), ("count", "=", "42", ";"));
+/// ```
+pub trait TakeWhile: Sized {
+    /// The type of item the predicate is passed a reference to.
+    type Item;
+
+    /// Split off the longest prefix whose items all satisfy `pred`, leaving the rest in `self`.
+    fn take_while<P: FnMut(&Self::Item) -> bool>(&mut self, pred: P) -> Self;
+
+    /// Split off the longest prefix whose items all fail `pred`, leaving the rest, starting with
+    /// the first item that satisfies it, in `self`.
+    fn take_until<P: FnMut(&Self::Item) -> bool>(&mut self, mut pred: P) -> Self {
+        self.take_while(|item| !pred(item))
+    }
+}
+
+impl<T> TakeWhile for &[T] {
+    type Item = T;
+
+    fn take_while<P: FnMut(&T) -> bool>(&mut self, mut pred: P) -> Self {
+        let n = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+        self.take_exact(n).expect("prefix is within the slice")
+    }
+}
+
+impl<T> TakeWhile for &mut [T] {
+    type Item = T;
+
+    fn take_while<P: FnMut(&T) -> bool>(&mut self, mut pred: P) -> Self {
+        let n = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+        self.take_exact(n).expect("prefix is within the slice")
+    }
+}
+
+impl TakeWhile for &str {
+    type Item = char;
+
+    fn take_while<P: FnMut(&char) -> bool>(&mut self, mut pred: P) -> Self {
+        let n = self.find(|c| !pred(&c)).unwrap_or(self.len());
+        let (head, tail) = self.split_at(n);
+        *self = tail;
+        head
+    }
+}
+
+impl TakeWhile for &mut str {
+    type Item = char;
+
+    fn take_while<P: FnMut(&char) -> bool>(&mut self, mut pred: P) -> Self {
+        let n = self.find(|c| !pred(&c)).unwrap_or(self.len());
+        let (head, tail) = core::mem::take(self).split_at_mut(n);
+        *self = tail;
+        head
+    }
+}
+
+/// This error signifies that the slice was too short, and holds the number of additional
+/// elements that would have been needed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NeedMore(pub usize);
+
+impl fmt::Display for NeedMore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "need {} more elements", self.0)
+    }
+}
+
+impl core::error::Error for NeedMore { }
+
+/// This error signifies that the slice did not have the exact length that was needed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LengthMismatch {
+    /// The length that was needed.
+    pub expected: usize,
+    /// The length of the slice.
+    pub len: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected exactly {} elements, found {}", self.expected, self.len)
+    }
+}
+
+impl core::error::Error for LengthMismatch { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn take_exact() {
+        let mut slice: &[u8] = b"hello, world!";
+        let hello = slice.take_exact(5).unwrap();
+        assert_eq!(hello, b"hello");
+        assert_eq!(slice.take_exact(10), Err(NeedMore(2)));
+        assert_eq!(slice, b", world!");
+        assert_eq!(slice.take_exact(8).unwrap(), b", world!");
+        assert_eq!(slice, b"");
+    }
+
+    #[test]
+    fn take_front_mut() {
+        let mut buffer = *b"abcd";
+        let mut slice: &mut [u8] = &mut buffer;
+        let head = slice.take_front(3).unwrap();
+        assert!(slice.take_front(2).is_none());
+        head[0] = b'A';
+        slice[0] = b'D';
+        assert_eq!(&buffer, b"AbcD");
+    }
+
+    #[test]
+    fn take_range() {
+        let mut buffer = *b"0123456789";
+        let mut slice: &mut [u8] = &mut buffer;
+        assert!(slice.take(8..11).is_none());
+        let (start, end) = (5, 4);
+        assert!(slice.take(start..end).is_none());
+        let token = slice.take(2..=3).unwrap();
+        token.copy_from_slice(b"ab");
+        assert_eq!(slice, b"456789");
+        assert_eq!(slice.take(..).unwrap(), b"456789");
+        assert!(slice.is_empty());
+        assert_eq!(&buffer, b"01ab456789");
+    }
+
+    #[test]
+    fn shift() {
+        let mut slice: &[u8] = b"ab";
+        let a = slice.shift();
+        let b = slice.shift();
+        assert_eq!((a, b, slice.shift()), (Some(&b'a'), Some(&b'b'), None));
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn pop_back_mut() {
+        let mut buffer = [1, 2, 3];
+        let mut slice: &mut [u8] = &mut buffer;
+        let last = slice.pop_back().unwrap();
+        let first = slice.shift().unwrap();
+        *last += *first;
+        assert_eq!(slice, [2]);
+        slice.pop_back();
+        assert_eq!(slice.pop_back(), None);
+        assert_eq!(buffer, [1, 2, 4]);
+    }
+
+    #[test]
+    fn take_array_mut() {
+        let mut buffer = [0u8; 5];
+        let mut slice: &mut [u8] = &mut buffer;
+        let pair: &mut [u8; 2] = slice.take_array().unwrap();
+        assert!(slice.take_array::<4>().is_none());
+        let triple: &mut [u8; 3] = slice.take_array().unwrap();
+        *pair = [1, 2];
+        triple[2] = 3;
+        assert!(slice.is_empty());
+        assert_eq!(slice.take_array::<0>(), Some(&mut []));
+        assert_eq!(buffer, [1, 2, 0, 0, 3]);
+    }
+
+    #[test]
+    fn take_back() {
+        let mut buffer = *b"abcd";
+        let mut slice: &mut [u8] = &mut buffer;
+        assert_eq!(slice.try_take_back(5), Err(NeedMore(1)));
+        let tail = slice.take_back(1).unwrap();
+        tail[0] = b'D';
+        let rest = slice.take_back(3).unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(rest, b"abc");
+        assert_eq!(&buffer, b"abcD");
+    }
+
+    #[test]
+    fn take_exact_mut() {
+        let mut buffer = *b"hello";
+        let mut slice: &mut [u8] = &mut buffer;
+        let head = slice.take_exact(2).unwrap();
+        head.copy_from_slice(b"HE");
+        slice[0] = b'L';
+        assert_eq!(slice.take_exact(4), Err(NeedMore(1)));
+        assert_eq!(&buffer, b"HELlo");
+    }
+
+    #[test]
+    fn shrink_exact_mut() {
+        let mut buffer = *b"len3abc";
+        let mut slice: &mut [u8] = &mut buffer;
+        assert_eq!(slice.ensure_remaining(7), Ok(()));
+        assert_eq!(slice.ensure_remaining(usize::MAX), Err(NeedMore(usize::MAX - 7)));
+        slice.try_shrink_exact(4).unwrap();
+        assert_eq!(slice.try_shrink_exact(4), Err(NeedMore(1)));
+        assert_eq!(slice.ensure_remaining(3), Ok(()));
+        slice.try_shrink_exact(3).unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(slice.try_shrink_exact(0), Ok(()));
+    }
+
+    #[test]
+    fn take_while_slices() {
+        let mut buffer = [1, 3, 4, 5, 6];
+        let mut slice: &mut [i32] = &mut buffer;
+        let odd = slice.take_while(|x| x % 2 == 1);
+        odd[0] = 0;
+        assert_eq!(slice.take_until(|&x| x > 5), [4, 5]);
+        assert_eq!(slice.take_while(|_| true), [6]);
+        assert!(slice.take_until(|_| true).is_empty());
+        assert_eq!(buffer, [0, 3, 4, 5, 6]);
+
+        let mut text = String::from("\u{e9}t\u{e9} ok");
+        let mut view: &mut str = &mut text;
+        view.take_until(|c| c.is_whitespace()).make_ascii_uppercase();
+        assert_eq!(view, " ok");
+        assert_eq!(text, "\u{e9}T\u{e9} ok");
+    }
+
+    #[test]
+    fn shrinking_stops_midway() {
+        let mut buffer = [1, 2, 3, 4, 5];
+        let mut slice: &mut [i32] = &mut buffer;
+        let mut iter = slice.shrinking();
+        for x in iter.by_ref().take_while(|x| **x < 2) {
+            *x = 0;
+        }
+        *iter.next_back().unwrap() = 50;
+        assert_eq!((iter.len(), iter.remainder().len()), (2, 2));
+        slice[0] = 30;
+        assert_eq!(buffer, [0, 2, 30, 4, 50]);
+    }
+
+    #[test]
+    fn mut_as_array() {
+        let mut buffer = [1, 2, 3];
+        let mut slice: &mut [i32] = &mut buffer;
+        assert_eq!(slice.try_as_array::<2>(), Err(LengthMismatch { expected: 2, len: 3 }));
+        slice.shrink(1..);
+        let array: &mut [i32; 2] = slice.try_as_array().unwrap();
+        array[1] = 30;
+        assert!(slice.is_empty());
+        assert_eq!(buffer, [1, 2, 30]);
+    }
+}
@@ -0,0 +1,235 @@
+//! Slices that report every shrink and take to a hook, for finding out what consumed what.
+//!
+//! [`Traced`] wraps any shrinkable slice and calls a [`ShrinkHook`] after each successful
+//! operation with a [`ShrinkEvent`]: the old length, the range that was kept and where in your
+//! code the operation was called from. Closures are hooks, and with the `tracing` feature,
+//! [`TracingHook`] emits each event through the `tracing` crate at trace level.
+//!
+//! ```rust
+//! use core::cell::RefCell;
+//! use shrink_slice::traced::{ShrinkEvent, Traced};
+//! use shrink_slice::{ShrinkExt, Take};
+//!
+//! let events = RefCell::new(Vec::new());
+//! let mut input = Traced::new(&b"\x02hi!"[..], |e: &ShrinkEvent| {
+//!     events.borrow_mut().push(e.to_string());
+//! });
+//! let len = input.take_exact(1).unwrap()[0] as usize;
+//! input.shrink(..len);
+//! assert_eq!(*input, b"hi");
+//!
+//! let line = line!() - 4;
+//! let events = events.borrow();
+//! assert_eq!(events[0], format!("take of 4 to 1..4 at {}:{}:17", file!(), line));
+//! assert_eq!(events[1], format!("shrink of 3 to 0..2 at {}:{}:7", file!(), line + 1));
+//! ```
+//!
+//! The location is that of the call into the crate, even through provided methods like
+//! [`shrink`](crate::ShrinkExt::shrink) and [`try_shrink_exact`](Take::try_shrink_exact), but
+//! through your own generic helpers it is the call inside the helper, unless they are
+//! `#[track_caller]` too.
+
+use crate::{range, NeedMore, Shrink, ShrinkError, Take, TakeWhile};
+use core::fmt;
+use core::ops::{Deref, Range, RangeBounds};
+use core::panic::Location;
+use core::slice::SliceIndex;
+
+/// What kind of operation a [`ShrinkEvent`] reports.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EventKind {
+    /// The slice was shrunk to a range, discarding the rest.
+    Shrink,
+    /// Elements were taken from the front and returned.
+    Take,
+    /// Elements were taken from the back and returned.
+    TakeBack,
+}
+
+/// A successful operation on a [`Traced`] slice.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ShrinkEvent {
+    /// What kind of operation it was.
+    pub kind: EventKind,
+    /// The length of the slice before the operation.
+    pub old_len: usize,
+    /// The range of the old slice that the slice was left with. The elements taken by a take
+    /// are those outside of it.
+    pub range: Range<usize>,
+    /// Where the operation was called from.
+    pub location: &'static Location<'static>,
+}
+
+impl ShrinkEvent {
+    /// The length of the slice after the operation.
+    pub fn new_len(&self) -> usize {
+        self.range.end - self.range.start
+    }
+}
+
+impl fmt::Display for ShrinkEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            EventKind::Shrink => "shrink",
+            EventKind::Take => "take",
+            EventKind::TakeBack => "take from the back",
+        };
+        let Range { start, end } = self.range;
+        write!(f, "{} of {} to {}..{} at {}", kind, self.old_len, start, end, self.location)
+    }
+}
+
+/// Something that is told about every operation on a [`Traced`] slice.
+///
+/// Any `FnMut(&ShrinkEvent)` closure is a hook. Only successful operations are reported, since
+/// failed ones leave the slice as it was.
+pub trait ShrinkHook {
+    /// Called after `event` has happened.
+    fn on_shrink(&mut self, event: &ShrinkEvent);
+}
+
+impl<F: FnMut(&ShrinkEvent)> ShrinkHook for F {
+    fn on_shrink(&mut self, event: &ShrinkEvent) {
+        self(event)
+    }
+}
+
+/// A hook that emits every event through the `tracing` crate, at trace level.
+///
+/// The event's fields are recorded as `kind`, `old_len`, `start`, `end`, `new_len` and
+/// `location`.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct TracingHook;
+
+#[cfg(feature = "tracing")]
+impl ShrinkHook for TracingHook {
+    fn on_shrink(&mut self, event: &ShrinkEvent) {
+        tracing::trace!(
+            kind = ?event.kind,
+            old_len = event.old_len,
+            start = event.range.start,
+            end = event.range.end,
+            new_len = event.new_len(),
+            location = %event.location,
+            "shrunk slice",
+        );
+    }
+}
+
+/// A shrinkable slice that reports every operation on it to a [`ShrinkHook`].
+///
+/// Parts taken from it are traced too, with a clone of the hook.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Traced<S, H> {
+    inner: S,
+    hook: H,
+}
+
+impl<S: Shrink, H: ShrinkHook> Traced<S, H> {
+    /// Report every operation on `inner` to `hook`.
+    pub fn new(inner: S, hook: H) -> Self {
+        Traced { inner, hook }
+    }
+
+    /// The hook that operations are reported to.
+    pub fn hook(&self) -> &H {
+        &self.hook
+    }
+
+    /// Stop tracing, returning the slice.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    #[track_caller]
+    fn report(&mut self, kind: EventKind, old_len: usize, range: Range<usize>) {
+        let location = Location::caller();
+        self.hook.on_shrink(&ShrinkEvent { kind, old_len, range, location });
+    }
+}
+
+impl<S, H> Deref for Traced<S, H> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: Shrink, H: ShrinkHook> Shrink for Traced<S, H> {
+    type Slice = S::Slice;
+
+    #[track_caller]
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<S::Slice, Output = S::Slice> + RangeBounds<usize>
+    {
+        let len = self.inner.len();
+        let (start, end) = range::endpoints(&range, len);
+        self.inner.try_shrink(range)?;
+        self.report(EventKind::Shrink, len, start..end);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<S: Take, H: ShrinkHook + Clone> Take for Traced<S, H> {
+    #[track_caller]
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let len = self.inner.len();
+        let taken = self.inner.take_exact(n)?;
+        self.report(EventKind::Take, len, n..len);
+        Ok(Traced::new(taken, self.hook.clone()))
+    }
+
+    #[track_caller]
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let len = self.inner.len();
+        let taken = self.inner.try_take_back(n)?;
+        self.report(EventKind::TakeBack, len, 0..len - n);
+        Ok(Traced::new(taken, self.hook.clone()))
+    }
+}
+
+impl<S: TakeWhile + Shrink, H: ShrinkHook + Clone> TakeWhile for Traced<S, H> {
+    type Item = S::Item;
+
+    #[track_caller]
+    fn take_while<P: FnMut(&S::Item) -> bool>(&mut self, pred: P) -> Self {
+        let len = self.inner.len();
+        let taken = self.inner.take_while(pred);
+        self.report(EventKind::Take, len, taken.len()..len);
+        Traced::new(taken, self.hook.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn reports_every_operation() {
+        let events = RefCell::new(Vec::new());
+        let mut buffer = *b"  key=value;";
+        let mut input = Traced::new(&mut buffer[..], |e: &ShrinkEvent| {
+            events.borrow_mut().push((e.kind, e.old_len, e.range.clone(), e.new_len()));
+        });
+        input.shrink_front(2);
+        let key = input.take_while(|&b| b != b'=');
+        key.into_inner()[0] = b'K';
+        assert!(input.try_shrink(..20).is_err());
+        assert!(input.take_back(1).is_some());
+        assert_eq!(**input, *b"=value");
+        assert_eq!(*events.borrow(), [
+            (EventKind::Shrink, 12, 2..12, 10),
+            (EventKind::Take, 10, 3..10, 7),
+            (EventKind::TakeBack, 7, 0..6, 6),
+        ]);
+        assert_eq!(&buffer, b"  Key=value;");
+    }
+}
@@ -0,0 +1,141 @@
+//! Slices that count how far they have been shrunk from the front.
+//!
+//! [`Tracked`] wraps any shrinkable slice, including mutable ones, and keeps a running offset
+//! from where it started, for reporting positions in error messages. Unlike
+//! [`Anchored`](crate::anchor::Anchored), it does not need to keep hold of the original buffer.
+//!
+//! ```rust
+//! use shrink_slice::tracked::TrackedStr;
+//! use shrink_slice::{ShrinkExt, TakeWhile};
+//!
+//! fn expect_digits(input: &mut TrackedStr) -> Result<u32, String> {
+//!     let digits = input.take_while(|c| c.is_ascii_digit());
+//!     digits.parse().map_err(|_| format!("expected a number at byte {}", digits.offset()))
+//! }
+//!
+//! let mut input = TrackedStr::new("12,x");
+//! assert_eq!(expect_digits(&mut input), Ok(12));
+//! input.shrink(1..);
+//! assert_eq!(expect_digits(&mut input).unwrap_err(), "expected a number at byte 3");
+//! ```
+
+use crate::anchor::Span;
+use crate::{range, NeedMore, Shrink, ShrinkError, Take, TakeWhile};
+use core::ops::{Deref, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A shrinkable slice together with the number of elements, or bytes for string slices, that
+/// have been shrunk off its front.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Tracked<S> {
+    pub(crate) inner: S,
+    pub(crate) offset: usize,
+}
+
+/// A tracked shared slice.
+pub type TrackedSlice<'a, T> = Tracked<&'a [T]>;
+
+/// A tracked mutable slice.
+pub type TrackedSliceMut<'a, T> = Tracked<&'a mut [T]>;
+
+/// A tracked string slice.
+pub type TrackedStr<'a> = Tracked<&'a str>;
+
+impl<S: Shrink> Tracked<S> {
+    /// Start tracking `inner` at offset zero.
+    pub fn new(inner: S) -> Self {
+        Tracked::with_offset(inner, 0)
+    }
+
+    /// Start tracking `inner`, which is already `offset` elements into something larger.
+    pub fn with_offset(inner: S, offset: usize) -> Self {
+        Tracked { inner, offset }
+    }
+
+    /// How far the front of the slice is from where tracking started.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The span covered by the slice, relative to where tracking started.
+    pub fn span(&self) -> Span {
+        Span { start: self.offset, end: self.offset + self.inner.len() }
+    }
+
+    /// Stop tracking, returning the slice.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> Deref for Tracked<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: Shrink> Shrink for Tracked<S> {
+    type Slice = S::Slice;
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<S::Slice, Output = S::Slice> + RangeBounds<usize>
+    {
+        let len = self.inner.len();
+        let (start, _) = range::endpoints(&range, len);
+        self.inner.try_shrink(range)?;
+        self.offset += start;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+/// Taken parts are tracked too, starting at the offset they were taken from.
+impl<S: Take> Take for Tracked<S> {
+    fn take_exact(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let taken = self.inner.take_exact(n)?;
+        let taken = Tracked::with_offset(taken, self.offset);
+        self.offset += n;
+        Ok(taken)
+    }
+
+    fn try_take_back(&mut self, n: usize) -> Result<Self, NeedMore> {
+        let taken = self.inner.try_take_back(n)?;
+        Ok(Tracked::with_offset(taken, self.offset + self.inner.len()))
+    }
+}
+
+impl<S: TakeWhile + Shrink> TakeWhile for Tracked<S> {
+    type Item = S::Item;
+
+    fn take_while<P: FnMut(&S::Item) -> bool>(&mut self, pred: P) -> Self {
+        let taken = Tracked::with_offset(self.inner.take_while(pred), self.offset);
+        self.offset += taken.len();
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn mut_slice_offsets() {
+        let mut buffer = [0u8; 8];
+        let mut input = TrackedSliceMut::with_offset(&mut buffer[..], 100);
+        input.shrink(1..7);
+        let head = input.take_exact(2).unwrap();
+        assert_eq!((head.offset(), input.offset()), (101, 103));
+        let tail = input.try_take_back(1).unwrap();
+        assert_eq!(tail.span(), Span { start: 106, end: 107 });
+        assert!(input.try_shrink(4..).is_err());
+        assert_eq!(input.span(), Span { start: 103, end: 106 });
+        input.into_inner()[0] = 1;
+        assert_eq!(buffer, [0, 0, 0, 1, 0, 0, 0, 0]);
+    }
+}
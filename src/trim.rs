@@ -0,0 +1,427 @@
+//! Trimming slices in place, by whitespace or by predicate.
+
+use crate::{Shrink, ShrinkExt};
+use core::convert::TryInto;
+
+/// The number of bytes compared at once when trimming byte slices.
+const WORD: usize = 16;
+
+/// One word of `bytes`, with its first byte in the least significant position.
+fn word(bytes: &[u8]) -> u128 {
+    u128::from_le_bytes(bytes.try_into().expect("chunk is a word long"))
+}
+
+/// The number of leading bytes of `bytes` that are equal to `byte`, comparing a word at a time.
+fn leading_bytes(bytes: &[u8], byte: u8) -> usize {
+    let splat = u128::from_le_bytes([byte; WORD]);
+    let mut words = bytes.chunks_exact(WORD);
+    let mut count = 0;
+    for chunk in &mut words {
+        let diff = word(chunk) ^ splat;
+        if diff != 0 {
+            return count + diff.trailing_zeros() as usize / 8;
+        }
+        count += WORD;
+    }
+    count + words.remainder().iter().take_while(|&&b| b == byte).count()
+}
+
+/// The number of trailing bytes of `bytes` that are equal to `byte`, comparing a word at a time.
+fn trailing_bytes(bytes: &[u8], byte: u8) -> usize {
+    let splat = u128::from_le_bytes([byte; WORD]);
+    let mut words = bytes.rchunks_exact(WORD);
+    let mut count = 0;
+    for chunk in &mut words {
+        let diff = word(chunk) ^ splat;
+        if diff != 0 {
+            return count + diff.leading_zeros() as usize / 8;
+        }
+        count += WORD;
+    }
+    count + words.remainder().iter().rev().take_while(|&&b| b == byte).count()
+}
+
+/// Whether every byte of `chunk` is ASCII whitespace, without branching on any of them.
+fn all_ascii_whitespace(chunk: &[u8]) -> bool {
+    chunk.iter().fold(true, |all, &b| {
+        // Tab, line feed, form feed and carriage return are 9 to 13, except 11.
+        all & ((b == b' ') | ((b.wrapping_sub(9) < 5) & (b != 11)))
+    })
+}
+
+/// The number of leading ASCII whitespace bytes of `bytes`.
+///
+/// Whole words are checked with a loop that has no early exit, which the compiler turns into
+/// SIMD instructions where available, so long runs of padding are skipped quickly.
+fn leading_ascii_whitespace(bytes: &[u8]) -> usize {
+    let mut words = bytes.chunks_exact(WORD);
+    let mut count = 0;
+    for chunk in &mut words {
+        if !all_ascii_whitespace(chunk) {
+            break;
+        }
+        count += WORD;
+    }
+    count + bytes[count..].iter().take_while(|b| b.is_ascii_whitespace()).count()
+}
+
+/// The number of trailing ASCII whitespace bytes of `bytes`, like [`leading_ascii_whitespace`].
+fn trailing_ascii_whitespace(bytes: &[u8]) -> usize {
+    let mut words = bytes.rchunks_exact(WORD);
+    let mut count = 0;
+    for chunk in &mut words {
+        if !all_ascii_whitespace(chunk) {
+            break;
+        }
+        count += WORD;
+    }
+    let rest = &bytes[..bytes.len() - count];
+    count + rest.iter().rev().take_while(|b| b.is_ascii_whitespace()).count()
+}
+
+/// Extension trait for trimming ASCII whitespace from a slice in place.
+///
+/// These mirror the standard library's `trim_ascii` methods, but shrink the slice instead of
+/// returning a new one. Whitespace is as defined by [`u8::is_ascii_whitespace`]. Byte slices
+/// and string slices are scanned a word at a time, which is considerably faster than the
+/// standard library's byte-at-a-time loop for long runs of whitespace.
+///
+/// ```rust
+/// use shrink_slice::trim::TrimAscii;
+///
+/// let mut field: &[u8] = b" \t value\r\n";
+/// field.trim_ascii_in_place();
+/// assert_eq!(field, b"value");
+/// ```
+pub trait TrimAscii {
+    /// Shrink past leading ASCII whitespace.
+    fn trim_ascii_start_in_place(&mut self);
+
+    /// Shrink away trailing ASCII whitespace.
+    fn trim_ascii_end_in_place(&mut self);
+
+    /// Shrink away leading and trailing ASCII whitespace.
+    fn trim_ascii_in_place(&mut self) {
+        self.trim_ascii_start_in_place();
+        self.trim_ascii_end_in_place();
+    }
+}
+
+impl TrimAscii for &[u8] {
+    fn trim_ascii_start_in_place(&mut self) {
+        let start = leading_ascii_whitespace(self);
+        self.shrink(start..);
+    }
+
+    fn trim_ascii_end_in_place(&mut self) {
+        let end = self.len() - trailing_ascii_whitespace(self);
+        self.shrink(..end);
+    }
+}
+
+impl TrimAscii for &mut [u8] {
+    fn trim_ascii_start_in_place(&mut self) {
+        let start = leading_ascii_whitespace(self);
+        self.shrink(start..);
+    }
+
+    fn trim_ascii_end_in_place(&mut self) {
+        let end = self.len() - trailing_ascii_whitespace(self);
+        self.shrink(..end);
+    }
+}
+
+impl TrimAscii for &str {
+    fn trim_ascii_start_in_place(&mut self) {
+        // Whitespace bytes are ASCII, so they never end within a multi-byte character.
+        let start = leading_ascii_whitespace(self.as_bytes());
+        self.shrink(start..);
+    }
+
+    fn trim_ascii_end_in_place(&mut self) {
+        let end = self.len() - trailing_ascii_whitespace(self.as_bytes());
+        self.shrink(..end);
+    }
+}
+
+impl TrimAscii for &mut str {
+    fn trim_ascii_start_in_place(&mut self) {
+        // Whitespace bytes are ASCII, so they never end within a multi-byte character.
+        let start = leading_ascii_whitespace(self.as_bytes());
+        self.shrink(start..);
+    }
+
+    fn trim_ascii_end_in_place(&mut self) {
+        let end = self.len() - trailing_ascii_whitespace(self.as_bytes());
+        self.shrink(..end);
+    }
+}
+
+/// Extension trait for trimming runs of one byte value from a byte slice in place, like the
+/// zero padding of fixed-size records.
+///
+/// The bytes are compared a word at a time.
+///
+/// ```rust
+/// use shrink_slice::trim::TrimByte;
+///
+/// let mut name: &[u8] = b"README.md\0\0\0\0\0\0\0";
+/// name.trim_byte_end(0);
+/// assert_eq!(name, b"README.md");
+/// ```
+pub trait TrimByte {
+    /// Shrink past leading bytes equal to `byte`.
+    fn trim_byte_start(&mut self, byte: u8);
+
+    /// Shrink away trailing bytes equal to `byte`.
+    fn trim_byte_end(&mut self, byte: u8);
+
+    /// Shrink away leading and trailing bytes equal to `byte`.
+    fn trim_byte(&mut self, byte: u8) {
+        self.trim_byte_start(byte);
+        self.trim_byte_end(byte);
+    }
+}
+
+impl TrimByte for &[u8] {
+    fn trim_byte_start(&mut self, byte: u8) {
+        let start = leading_bytes(self, byte);
+        self.shrink(start..);
+    }
+
+    fn trim_byte_end(&mut self, byte: u8) {
+        let end = self.len() - trailing_bytes(self, byte);
+        self.shrink(..end);
+    }
+}
+
+impl TrimByte for &mut [u8] {
+    fn trim_byte_start(&mut self, byte: u8) {
+        let start = leading_bytes(self, byte);
+        self.shrink(start..);
+    }
+
+    fn trim_byte_end(&mut self, byte: u8) {
+        let end = self.len() - trailing_bytes(self, byte);
+        self.shrink(..end);
+    }
+}
+
+/// Extension trait for trimming the items that match a predicate from the ends of a slice.
+///
+/// The predicate is passed a reference to each item: an element of a slice, or a `char` of a
+/// string slice.
+///
+/// ```rust
+/// use shrink_slice::trim::TrimWhile;
+///
+/// let mut samples: &[i16] = &[0, 0, 12, -3, 0, 7, 0];
+/// samples.shrink_while(|&s| s == 0);
+/// assert_eq!(samples, [12, -3, 0, 7]);
+///
+/// let mut number = "007.500";
+/// number.shrink_start_while(|&c| c == '0');
+/// number.shrink_end_while(|&c| c == '0');
+/// assert_eq!(number, "7.5");
+/// ```
+pub trait TrimWhile {
+    /// The type of item the predicate is passed a reference to.
+    type Item;
+
+    /// Shrink past the leading items that satisfy `pred`.
+    fn shrink_start_while<P: FnMut(&Self::Item) -> bool>(&mut self, pred: P);
+
+    /// Shrink away the trailing items that satisfy `pred`.
+    fn shrink_end_while<P: FnMut(&Self::Item) -> bool>(&mut self, pred: P);
+
+    /// Shrink away the leading and trailing items that satisfy `pred`.
+    fn shrink_while<P: FnMut(&Self::Item) -> bool>(&mut self, mut pred: P) {
+        self.shrink_start_while(&mut pred);
+        self.shrink_end_while(pred);
+    }
+}
+
+impl<T> TrimWhile for &[T] {
+    type Item = T;
+
+    fn shrink_start_while<P: FnMut(&T) -> bool>(&mut self, mut pred: P) {
+        let start = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+        self.shrink(start..);
+    }
+
+    fn shrink_end_while<P: FnMut(&T) -> bool>(&mut self, mut pred: P) {
+        let end = self.iter().rposition(|x| !pred(x)).map_or(0, |i| i + 1);
+        self.shrink(..end);
+    }
+}
+
+impl<T> TrimWhile for &mut [T] {
+    type Item = T;
+
+    fn shrink_start_while<P: FnMut(&T) -> bool>(&mut self, mut pred: P) {
+        let start = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+        self.shrink(start..);
+    }
+
+    fn shrink_end_while<P: FnMut(&T) -> bool>(&mut self, mut pred: P) {
+        let end = self.iter().rposition(|x| !pred(x)).map_or(0, |i| i + 1);
+        self.shrink(..end);
+    }
+}
+
+impl TrimWhile for &str {
+    type Item = char;
+
+    fn shrink_start_while<P: FnMut(&char) -> bool>(&mut self, mut pred: P) {
+        let start = self.len() - self.trim_start_matches(|c| pred(&c)).len();
+        self.shrink(start..);
+    }
+
+    fn shrink_end_while<P: FnMut(&char) -> bool>(&mut self, mut pred: P) {
+        let end = self.trim_end_matches(|c| pred(&c)).len();
+        self.shrink(..end);
+    }
+}
+
+impl TrimWhile for &mut str {
+    type Item = char;
+
+    fn shrink_start_while<P: FnMut(&char) -> bool>(&mut self, mut pred: P) {
+        let start = self.len() - self.trim_start_matches(|c| pred(&c)).len();
+        self.shrink(start..);
+    }
+
+    fn shrink_end_while<P: FnMut(&char) -> bool>(&mut self, mut pred: P) {
+        let end = self.trim_end_matches(|c| pred(&c)).len();
+        self.shrink(..end);
+    }
+}
+
+/// Extension trait for trimming Unicode whitespace from a string slice in place.
+///
+/// These mirror [`str::trim`] and friends, but shrink the slice instead of returning a new one.
+/// Whitespace is as defined by [`char::is_whitespace`]; see [`TrimAscii`] for ASCII whitespace.
+///
+/// ```rust
+/// use shrink_slice::trim::TrimWhitespace;
+///
+/// let mut text = "\u{3000}indented\u{a0}\n";
+/// text.trim_in_place();
+/// assert_eq!(text, "indented");
+/// ```
+pub trait TrimWhitespace: TrimWhile<Item = char> {
+    /// Shrink past leading whitespace.
+    fn trim_start_in_place(&mut self) {
+        self.shrink_start_while(|c| c.is_whitespace());
+    }
+
+    /// Shrink away trailing whitespace.
+    fn trim_end_in_place(&mut self) {
+        self.shrink_end_while(|c| c.is_whitespace());
+    }
+
+    /// Shrink away leading and trailing whitespace.
+    fn trim_in_place(&mut self) {
+        self.shrink_while(|c| c.is_whitespace());
+    }
+}
+
+impl<S: TrimWhile<Item = char>> TrimWhitespace for S { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mut_bytes() {
+        let mut buffer = *b"\x0c  ab c \n";
+        let mut slice: &mut [u8] = &mut buffer;
+        slice.trim_ascii_start_in_place();
+        assert_eq!(slice, b"ab c \n");
+        slice.trim_ascii_end_in_place();
+        slice[0] = b'A';
+        assert_eq!(&buffer, b"\x0c  Ab c \n");
+    }
+
+    #[test]
+    fn str_keeps_unicode_whitespace() {
+        let mut text = "\u{a0} x \t";
+        text.trim_ascii_in_place();
+        assert_eq!(text, "\u{a0} x");
+
+        let mut blank = " \r\n ";
+        blank.trim_ascii_in_place();
+        assert_eq!(blank, "");
+    }
+
+    #[test]
+    fn while_predicate() {
+        let mut buffer = [0, 1, 2, 0];
+        let mut slice: &mut [i32] = &mut buffer;
+        slice.shrink_end_while(|&x| x < 3);
+        assert!(slice.is_empty());
+        let mut slice: &mut [i32] = &mut buffer;
+        slice.shrink_while(|&x| x == 0);
+        slice[1] = 20;
+        assert_eq!(buffer, [0, 1, 20, 0]);
+
+        let mut owned = String::from(" \u{e9}t\u{e9}\u{2003}");
+        let mut text: &mut str = &mut owned;
+        text.trim_start_in_place();
+        text.shrink_end_while(|c| !c.is_ascii());
+        assert_eq!(text, "\u{e9}t");
+    }
+
+    #[test]
+    fn words_match_scalar() {
+        let mut buffer = [b' '; 40];
+        for (i, b) in buffer.iter_mut().enumerate() {
+            if i % 7 == 3 || i % 11 == 5 {
+                *b = b'x';
+            }
+        }
+        for start in 0..buffer.len() {
+            for end in start..=buffer.len() {
+                let bytes = &buffer[start..end];
+                let mut trimmed = bytes;
+                trimmed.trim_ascii_in_place();
+                assert_eq!(trimmed, bytes.trim_ascii());
+                let mut trimmed = bytes;
+                trimmed.trim_byte(b' ');
+                assert_eq!(trimmed, bytes.trim_ascii());
+            }
+        }
+        let mut buffer = [0u8; 19];
+        let mut slice: &mut [u8] = &mut buffer;
+        slice.trim_byte_end(0);
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn words_of_every_whitespace_byte() {
+        // Vertical tab, 11, sits between the whitespace bytes but is not one of them.
+        let whitespace = *b" \t\n\x0c\r";
+        let mut buffer = [0u8; 40];
+        for (i, b) in buffer.iter_mut().enumerate() {
+            *b = whitespace[i % whitespace.len()];
+        }
+        buffer[17] = 0x0b;
+        buffer[34] = b'x';
+        for start in 0..buffer.len() {
+            for end in start..=buffer.len() {
+                let bytes = &buffer[start..end];
+                let mut trimmed = bytes;
+                trimmed.trim_ascii_in_place();
+                assert_eq!(trimmed, bytes.trim_ascii());
+            }
+        }
+
+        for &b in &whitespace {
+            let mut buffer = [b; 37];
+            buffer[20] = b'y';
+            let mut slice: &[u8] = &buffer;
+            slice.trim_ascii_in_place();
+            assert_eq!(slice, b"y");
+        }
+    }
+}
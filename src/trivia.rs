@@ -0,0 +1,173 @@
+//! Skipping whitespace and comments between tokens.
+
+use crate::{Shrink, ShrinkExt};
+use core::fmt;
+
+/// Which comments [`SkipTrivia::skip_trivia`] recognizes.
+///
+/// Whitespace, as defined by [`char::is_whitespace`], is always skipped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Trivia<'c> {
+    /// Markers that start a comment running to the end of the line. Empty markers are ignored.
+    pub line_comments: &'c [&'c str],
+    /// Pairs of markers that open and close a block comment. Pairs with an empty marker are
+    /// ignored.
+    pub block_comments: &'c [(&'c str, &'c str)],
+    /// Whether block comments nest, so that `/* /* */ */` is a single comment.
+    pub nested: bool,
+}
+
+impl Trivia<'static> {
+    /// Only whitespace.
+    pub const WHITESPACE: Self = Trivia { line_comments: &[], block_comments: &[], nested: false };
+
+    /// `//` line comments and non-nesting `/* */` block comments, as in C.
+    pub const C: Self = Trivia {
+        line_comments: &["//"],
+        block_comments: &[("/*", "*/")],
+        nested: false,
+    };
+
+    /// `//` line comments and nesting `/* */` block comments, as in Rust.
+    pub const RUST: Self = Trivia { nested: true, ..Trivia::C };
+
+    /// `#` line comments, as in shell scripts, Python and TOML.
+    pub const HASH: Self = Trivia { line_comments: &["#"], block_comments: &[], nested: false };
+}
+
+/// Extension trait for skipping whitespace and comments at the front of a string slice.
+pub trait SkipTrivia {
+    /// Shrink past any whitespace and comments, and return the number of bytes skipped.
+    ///
+    /// If a block comment is not closed, the error reports where it was opened, relative to the
+    /// start of the string, and the string is left untouched.
+    ///
+    /// ```rust
+    /// use shrink_slice::trivia::{SkipTrivia, Trivia};
+    ///
+    /// let mut source = "  // comment\n /* more /* nested */ */ let x";
+    /// source.skip_trivia(&Trivia::RUST).unwrap();
+    /// assert_eq!(source, "let x");
+    ///
+    /// let mut broken = " /* oops";
+    /// assert_eq!(broken.skip_trivia(&Trivia::C).unwrap_err().offset, 1);
+    /// ```
+    fn skip_trivia(&mut self, trivia: &Trivia<'_>) -> Result<usize, UnclosedComment>;
+}
+
+impl SkipTrivia for &str {
+    fn skip_trivia(&mut self, trivia: &Trivia<'_>) -> Result<usize, UnclosedComment> {
+        let s: &str = self;
+        let mut i = 0;
+        loop {
+            let rest = &s[i..];
+            let trimmed = rest.trim_start();
+            if trimmed.len() != rest.len() {
+                i += rest.len() - trimmed.len();
+                continue;
+            }
+            if let Some(marker) = trivia.line_comments.iter()
+                .find(|m| !m.is_empty() && rest.starts_with(**m))
+            {
+                let line = &rest[marker.len()..];
+                i += marker.len() + line.find('\n').unwrap_or(line.len());
+                continue;
+            }
+            if let Some(&(open, close)) = trivia.block_comments.iter()
+                .filter(|(open, close)| !open.is_empty() && !close.is_empty())
+                .find(|(open, _)| rest.starts_with(open))
+            {
+                let len = block_len(rest, open, close, trivia.nested)
+                    .ok_or(UnclosedComment { offset: i })?;
+                i += len;
+                continue;
+            }
+            break;
+        }
+        self.shrink(i..);
+        Ok(i)
+    }
+}
+
+/// The length of the block comment at the front of `s`, including its markers.
+fn block_len(s: &str, open: &str, close: &str, nested: bool) -> Option<usize> {
+    let mut i = open.len();
+    let mut depth = 1;
+    while depth > 0 {
+        let rest = &s[i..];
+        if rest.starts_with(close) {
+            depth -= 1;
+            i += close.len();
+        } else if nested && rest.starts_with(open) {
+            depth += 1;
+            i += open.len();
+        } else {
+            i += rest.chars().next()?.len_utf8();
+        }
+    }
+    Some(i)
+}
+
+/// This error signifies that a block comment was never closed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UnclosedComment {
+    /// The byte offset where the comment was opened.
+    pub offset: usize,
+}
+
+impl fmt::Display for UnclosedComment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "block comment opened at byte {} is never closed", self.offset)
+    }
+}
+
+impl core::error::Error for UnclosedComment { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_nesting_blocks() {
+        let mut source = "/* a /* b */ c */";
+        assert_eq!(source.skip_trivia(&Trivia::C), Ok(13));
+        assert_eq!(source, "c */");
+
+        let mut source = "/* a /* b */ c";
+        assert_eq!(source.skip_trivia(&Trivia::RUST), Err(UnclosedComment { offset: 0 }));
+        assert_eq!(source, "/* a /* b */ c");
+    }
+
+    #[test]
+    fn custom_markers() {
+        let trivia = Trivia {
+            line_comments: &["--", ";"],
+            block_comments: &[("{-", "-}")],
+            nested: true,
+        };
+        let mut source = "-- sql\n; lisp\n{- {- haskell -} -}\tx -- trailing";
+        source.skip_trivia(&trivia).unwrap();
+        assert_eq!(source, "x -- trailing");
+        assert_eq!(source.skip_trivia(&trivia), Ok(0));
+
+        let mut comment_only = "# just a comment";
+        comment_only.skip_trivia(&Trivia::HASH).unwrap();
+        assert_eq!(comment_only, "");
+        let mut untouched = "# x";
+        assert_eq!(untouched.skip_trivia(&Trivia::WHITESPACE), Ok(0));
+    }
+
+    #[test]
+    fn empty_markers_are_ignored() {
+        let trivia = Trivia {
+            line_comments: &["", "#"],
+            block_comments: &[("", "*/"), ("(*", "")],
+            nested: false,
+        };
+        let mut source = " # comment\n(* x";
+        assert_eq!(source.skip_trivia(&trivia), Ok(11));
+        assert_eq!(source, "(* x");
+        let mut empty = "";
+        assert_eq!(empty.skip_trivia(&trivia), Ok(0));
+    }
+}
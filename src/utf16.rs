@@ -0,0 +1,116 @@
+//! Shrinking UTF-16 code unit slices without splitting surrogate pairs.
+//!
+//! Wide strings, like those passed to and from Windows APIs, are plain `[u16]` slices, so
+//! [`Shrink`] lets a range cut a surrogate pair in half. [`ShrinkUtf16`] checks both ends of the
+//! range first, the way string slices check for char boundaries. Unpaired surrogates are left
+//! alone: they are not part of a pair, so cutting next to one splits nothing.
+
+use crate::{range, Shrink, ShrinkError};
+use core::ops::{Range, RangeBounds};
+
+fn is_high_surrogate(unit: u16) -> bool {
+    (0xd800..0xdc00).contains(&unit)
+}
+
+fn is_low_surrogate(unit: u16) -> bool {
+    (0xdc00..0xe000).contains(&unit)
+}
+
+/// Whether cutting `units` at `index` keeps every surrogate pair whole.
+///
+/// Like [`str::is_char_boundary`], the start and end of the slice are boundaries, and indices
+/// past the end are not.
+///
+/// ```rust
+/// use shrink_slice::utf16::is_utf16_boundary;
+///
+/// let units: Vec<u16> = "a\u{1f980}".encode_utf16().collect();
+/// assert_eq!(units.len(), 3);
+/// assert!(is_utf16_boundary(&units, 1));
+/// assert!(!is_utf16_boundary(&units, 2));
+/// assert!(is_utf16_boundary(&units, 3));
+/// ```
+pub fn is_utf16_boundary(units: &[u16], index: usize) -> bool {
+    match (index.checked_sub(1).and_then(|i| units.get(i)), units.get(index)) {
+        (Some(&high), Some(&low)) => !(is_high_surrogate(high) && is_low_surrogate(low)),
+        _ => index <= units.len(),
+    }
+}
+
+/// Check that `range` fits `units` and splits no surrogate pair.
+fn check<R: RangeBounds<usize>>(units: &[u16], range: R) -> Result<Range<usize>, ShrinkError> {
+    let range = range::try_resolve(range, units.len())?;
+    for index in [range.start, range.end] {
+        if !is_utf16_boundary(units, index) {
+            let (start, end, len) = (range.start, range.end, units.len());
+            return Err(ShrinkError::NotCharBoundary { start, end, len, index });
+        }
+    }
+    Ok(range)
+}
+
+/// Extension trait for shrinking UTF-16 code unit slices on character boundaries.
+///
+/// ```rust
+/// use shrink_slice::utf16::{ShrinkUtf16, ShrinkUtf16Ext};
+///
+/// let wide: Vec<u16> = "\u{1f980}rs".encode_utf16().collect();
+/// let mut name: &[u16] = &wide;
+/// assert!(name.try_shrink_utf16(1..).is_err());
+/// name.shrink_utf16(2..);
+/// assert_eq!(String::from_utf16(name).unwrap(), "rs");
+/// ```
+pub trait ShrinkUtf16: Shrink<Slice = [u16]> {
+    /// Shrink the slice to `range`, failing if it is out of bounds or if either end would split
+    /// a surrogate pair, in which case the slice is left untouched.
+    fn try_shrink_utf16<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError>;
+}
+
+/// The panicking counterpart of [`try_shrink_utf16`](ShrinkUtf16::try_shrink_utf16),
+/// implemented for every [`ShrinkUtf16`] slice.
+pub trait ShrinkUtf16Ext: ShrinkUtf16 {
+    /// Shrink the slice to `range`, panicking if it is out of bounds or if either end would
+    /// split a surrogate pair.
+    #[track_caller]
+    fn shrink_utf16<R: RangeBounds<usize>>(&mut self, range: R) {
+        if let Err(e) = self.try_shrink_utf16(range) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<S: ShrinkUtf16 + ?Sized> ShrinkUtf16Ext for S { }
+
+impl ShrinkUtf16 for &[u16] {
+    fn try_shrink_utf16<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = check(self, range)?;
+        self.try_shrink(range)
+    }
+}
+
+impl ShrinkUtf16 for &mut [u16] {
+    fn try_shrink_utf16<R: RangeBounds<usize>>(&mut self, range: R) -> Result<(), ShrinkError> {
+        let range = check(self, range)?;
+        self.try_shrink(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpaired_and_mut() {
+        let mut units = [0xdc00, 0x61, 0xd83e, 0xdd80, 0xd800];
+        let mut slice: &mut [u16] = &mut units;
+        slice.shrink_utf16(1..);
+        assert_eq!(
+            slice.try_shrink_utf16(..2),
+            Err(ShrinkError::NotCharBoundary { start: 0, end: 2, len: 4, index: 2 })
+        );
+        slice.shrink_utf16(3..);
+        slice[0] = 0x62;
+        assert_eq!(units, [0xdc00, 0x61, 0xd83e, 0xdd80, 0x62]);
+        assert!(!is_utf16_boundary(&units, 6));
+    }
+}
@@ -0,0 +1,130 @@
+//! Decoding UTF-8 from the front of a byte slice one character at a time, for text that arrives
+//! in pieces, like network buffers, and cannot be validated all at once.
+
+use crate::{NeedMore, ShrinkExt};
+use core::{fmt, str};
+
+/// The length of the sequence that `lead` starts, if it is a valid leading byte.
+fn sequence_len(lead: u8) -> usize {
+    match lead {
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => 1,
+    }
+}
+
+/// Decode the character at the front of `bytes`, returning it and its length in bytes.
+fn decode(bytes: &[u8]) -> Result<Option<(char, usize)>, Utf8ShrinkError> {
+    let head = &bytes[..bytes.len().min(4)];
+    let valid = match str::from_utf8(head) {
+        Ok(s) => s,
+        Err(e) if e.valid_up_to() > 0 => {
+            str::from_utf8(&head[..e.valid_up_to()]).expect("the bytes before the error are valid")
+        }
+        Err(e) => {
+            return Err(match e.error_len() {
+                Some(len) => Utf8ShrinkError::Invalid { len },
+                None => Utf8ShrinkError::NeedMore(sequence_len(head[0]) - head.len()),
+            });
+        }
+    };
+    Ok(valid.chars().next().map(|c| (c, c.len_utf8())))
+}
+
+/// Extension trait for decoding UTF-8 characters from the front of a byte slice.
+///
+/// ```rust
+/// use shrink_slice::utf8::{NextChar, Utf8ShrinkError};
+///
+/// let packet = "h\u{e9}".as_bytes();
+/// let mut input = &packet[..2];
+/// assert_eq!(input.next_char(), Ok(Some('h')));
+/// assert_eq!(input.next_char(), Err(Utf8ShrinkError::NeedMore(1)));
+///
+/// // The rest of the character arrives.
+/// let mut input = &packet[1..];
+/// assert_eq!(input.next_char(), Ok(Some('\u{e9}')));
+/// assert_eq!(input.next_char(), Ok(None));
+/// ```
+pub trait NextChar {
+    /// Decode the first character and shrink past it, or return `None` if the slice is empty.
+    ///
+    /// If the slice does not start with a character, it is left untouched and the error tells
+    /// whether it starts with an invalid sequence, which a lossy decoder would skip and replace
+    /// with U+FFFD, or with the start of a character that is cut off.
+    fn next_char(&mut self) -> Result<Option<char>, Utf8ShrinkError>;
+}
+
+impl NextChar for &[u8] {
+    fn next_char(&mut self) -> Result<Option<char>, Utf8ShrinkError> {
+        let decoded = decode(self)?;
+        Ok(decoded.map(|(c, len)| {
+            self.shrink(len..);
+            c
+        }))
+    }
+}
+
+impl NextChar for &mut [u8] {
+    fn next_char(&mut self) -> Result<Option<char>, Utf8ShrinkError> {
+        let decoded = decode(self)?;
+        Ok(decoded.map(|(c, len)| {
+            self.shrink(len..);
+            c
+        }))
+    }
+}
+
+/// The error returned when a byte slice does not start with a UTF-8 character.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Utf8ShrinkError {
+    /// The slice starts with a sequence of `len` bytes that can never be part of a character.
+    Invalid {
+        /// The length of the invalid sequence, from one to three bytes.
+        len: usize,
+    },
+    /// The slice ends within a character, which needs the given number of bytes more.
+    NeedMore(usize),
+}
+
+impl From<NeedMore> for Utf8ShrinkError {
+    fn from(NeedMore(n): NeedMore) -> Self {
+        Utf8ShrinkError::NeedMore(n)
+    }
+}
+
+impl fmt::Display for Utf8ShrinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Utf8ShrinkError::Invalid { len } => {
+                write!(f, "invalid UTF-8 sequence of {} bytes", len)
+            }
+            Utf8ShrinkError::NeedMore(n) => NeedMore(*n).fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for Utf8ShrinkError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_and_incomplete() {
+        let mut input: &[u8] = b"\xf0\x9f\xa6";
+        assert_eq!(input.next_char(), Err(Utf8ShrinkError::NeedMore(1)));
+        let mut input: &[u8] = b"\xf0\x9f\x41";
+        assert_eq!(input.next_char(), Err(Utf8ShrinkError::Invalid { len: 2 }));
+        let mut input: &[u8] = b"\xff";
+        assert_eq!(input.next_char(), Err(Utf8ShrinkError::Invalid { len: 1 }));
+        assert_eq!(input.len(), 1);
+
+        let mut buffer = *b"\xf0\x9f\xa6\x80!\xe2";
+        let mut input: &mut [u8] = &mut buffer;
+        assert_eq!(input.next_char(), Ok(Some('\u{1f980}')));
+        assert_eq!(input.next_char(), Ok(Some('!')));
+        assert_eq!(input.next_char(), Err(Utf8ShrinkError::NeedMore(2)));
+    }
+}
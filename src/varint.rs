@@ -0,0 +1,174 @@
+//! Reading and writing LEB128 variable-length integers, as used by protobuf, WebAssembly and
+//! many other binary formats.
+//!
+//! Unsigned integers are written seven bits at a time, least significant first, with the high
+//! bit of each byte set if more follow. Signed integers are first mapped to unsigned ones by
+//! zigzag encoding, like protobuf's `sint64`, so that small negative numbers stay short.
+//!
+//! ```rust
+//! use shrink_slice::varint::{GetVarint, PutVarint};
+//!
+//! let mut buffer = [0; 8];
+//! let mut out: &mut [u8] = &mut buffer;
+//! out.put_varint_u64(300).unwrap();
+//! out.put_varint_i64(-2).unwrap();
+//! assert_eq!(out.len(), 5);
+//!
+//! let mut input: &[u8] = &buffer;
+//! assert_eq!(input.get_varint_u64(), Ok(300));
+//! assert_eq!(input.get_varint_i64(), Ok(-2));
+//! assert_eq!(&buffer[..3], [0xac, 0x02, 0x03]);
+//! ```
+
+use crate::write::PutBytes;
+use crate::{NeedMore, Take};
+use core::fmt;
+
+/// The longest encoding of a `u64`.
+const MAX_LEN: usize = 10;
+
+/// Decode the varint at the front of `bytes`, returning it and the length of its encoding.
+fn decode(bytes: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0;
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_LEN) {
+        if i == MAX_LEN - 1 && byte > 1 {
+            return Err(VarintError::Overflow);
+        }
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            if byte == 0 && i > 0 {
+                return Err(VarintError::Overlong);
+            }
+            return Ok((value, i + 1));
+        }
+    }
+    Err(VarintError::NeedMore(1))
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Extension trait for reading varints from the front of a byte slice.
+///
+/// Every method consumes the bytes it reads. If the varint is cut off, or encoded in more bytes
+/// than it needs, or too large for its type, the slice is left untouched.
+pub trait GetVarint {
+    /// Read an unsigned varint.
+    fn get_varint_u64(&mut self) -> Result<u64, VarintError>;
+
+    /// Read a zigzag-encoded signed varint.
+    fn get_varint_i64(&mut self) -> Result<i64, VarintError> {
+        self.get_varint_u64().map(unzigzag)
+    }
+}
+
+impl GetVarint for &[u8] {
+    fn get_varint_u64(&mut self) -> Result<u64, VarintError> {
+        let (value, len) = decode(self)?;
+        *self = &self[len..];
+        Ok(value)
+    }
+}
+
+impl GetVarint for &mut [u8] {
+    fn get_varint_u64(&mut self) -> Result<u64, VarintError> {
+        let (value, len) = decode(self)?;
+        self.take_exact(len).expect("the varint is within the slice");
+        Ok(value)
+    }
+}
+
+/// Extension trait for writing varints, implemented for everything that implements
+/// [`PutBytes`].
+///
+/// Varints are always written in as few bytes as possible. If they do not fit, nothing is
+/// written.
+pub trait PutVarint: PutBytes {
+    /// Write an unsigned varint.
+    fn put_varint_u64(&mut self, mut value: u64) -> Result<(), NeedMore> {
+        let mut bytes = [0; MAX_LEN];
+        let mut len = 0;
+        loop {
+            bytes[len] = value as u8 & 0x7f;
+            value >>= 7;
+            len += 1;
+            if value == 0 {
+                break;
+            }
+            bytes[len - 1] |= 0x80;
+        }
+        self.put_slice(&bytes[..len])
+    }
+
+    /// Write a zigzag-encoded signed varint.
+    fn put_varint_i64(&mut self, value: i64) -> Result<(), NeedMore> {
+        self.put_varint_u64(zigzag(value))
+    }
+}
+
+impl<W: PutBytes + ?Sized> PutVarint for W { }
+
+/// The error returned when a varint cannot be read.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VarintError {
+    /// The slice ended before the varint did. At least the given number of bytes are missing.
+    NeedMore(usize),
+    /// The varint had trailing zero bytes, which a canonical encoding leaves out.
+    Overlong,
+    /// The varint was too large for a `u64`.
+    Overflow,
+}
+
+impl From<NeedMore> for VarintError {
+    fn from(NeedMore(n): NeedMore) -> Self {
+        VarintError::NeedMore(n)
+    }
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VarintError::NeedMore(n) => NeedMore(*n).fmt(f),
+            VarintError::Overlong => f.write_str("varint is not minimally encoded"),
+            VarintError::Overflow => f.write_str("varint does not fit in 64 bits"),
+        }
+    }
+}
+
+impl core::error::Error for VarintError { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_rejects() {
+        let mut buffer = [0; 40];
+        let mut out: &mut [u8] = &mut buffer;
+        for value in [0, 127, 128, u64::MAX] {
+            out.put_varint_u64(value).unwrap();
+        }
+        out.put_varint_i64(i64::MIN).unwrap();
+        assert_eq!(out.len(), 40 - 1 - 1 - 2 - 10 - 10);
+        let mut input: &mut [u8] = &mut buffer;
+        for value in [0, 127, 128, u64::MAX] {
+            assert_eq!(input.get_varint_u64(), Ok(value));
+        }
+        assert_eq!(input.get_varint_i64(), Ok(i64::MIN));
+
+        let mut short: &mut [u8] = &mut [0; 1];
+        assert_eq!(short.put_varint_u64(128), Err(NeedMore(1)));
+        let mut input: &[u8] = &[0x80, 0x80];
+        assert_eq!(input.get_varint_u64(), Err(VarintError::NeedMore(1)));
+        let mut input: &[u8] = &[0x80, 0x00];
+        assert_eq!(input.get_varint_u64(), Err(VarintError::Overlong));
+        let mut input: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert_eq!(input.get_varint_u64(), Err(VarintError::Overflow));
+        assert_eq!(input.len(), 10);
+    }
+}
@@ -0,0 +1,192 @@
+//! A growable buffer that is filled at the back and consumed from the front.
+//!
+//! [`VecBuf`] owns a `Vec<T>` and a shrinkable view of its initialized elements. Consuming
+//! elements shrinks the view without moving anything, while new elements are written into the
+//! vector's spare capacity and extend the view at the back. Space taken up by consumed elements
+//! is reclaimed by [`compact`](VecBuf::compact), or automatically when the view becomes empty.
+//!
+//! ```rust
+//! use std::io::Read;
+//! use shrink_slice::vecbuf::VecBuf;
+//! use shrink_slice::ShrinkExt;
+//!
+//! let mut stream: &[u8] = b"GET / HTTP/1.1\r\n";
+//! let mut buf = VecBuf::with_capacity(8);
+//! buf.write_spare(8, |spare| stream.read(spare).unwrap());
+//! assert_eq!(&*buf, b"GET / HT");
+//!
+//! buf.shrink(4..);
+//! buf.compact();
+//! buf.write_spare(4, |spare| stream.read(spare).unwrap());
+//! assert_eq!(&*buf, b"/ HTTP/1");
+//! ```
+
+use crate::{Shrink, ShrinkError};
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A `Vec<T>` together with the range of its elements that has not been consumed yet.
+#[derive(Clone, Default, Debug)]
+pub struct VecBuf<T> {
+    vec: Vec<T>,
+    start: usize,
+}
+
+impl<T> VecBuf<T> {
+    /// A buffer whose view is all of `vec`.
+    pub fn new(vec: Vec<T>) -> Self {
+        VecBuf { vec, start: 0 }
+    }
+
+    /// An empty buffer with room for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        VecBuf::new(Vec::with_capacity(capacity))
+    }
+
+    /// The number of elements that can be written before the vector has to reallocate or be
+    /// compacted.
+    pub fn spare_capacity(&self) -> usize {
+        self.vec.capacity() - self.vec.len()
+    }
+
+    /// The number of consumed elements still occupying the front of the vector.
+    pub fn consumed(&self) -> usize {
+        self.start
+    }
+
+    /// Drop the consumed elements, moving the view to the front of the vector so that their
+    /// space becomes spare capacity.
+    pub fn compact(&mut self) {
+        self.vec.drain(..self.start);
+        self.start = 0;
+    }
+
+    /// Make room for at least `additional` new elements, dropping the consumed ones first, and
+    /// only reallocating if that is not enough.
+    pub fn reserve(&mut self, additional: usize) {
+        if self.spare_capacity() < additional {
+            self.compact();
+            self.vec.reserve(additional);
+        }
+    }
+
+    /// Append clones of `elements` to the view, reallocating if needed.
+    pub fn extend_from_slice(&mut self, elements: &[T])
+    where T: Clone,
+    {
+        self.vec.extend_from_slice(elements);
+    }
+
+    /// Let `write` fill up to `max` new elements at the back, and extend the view by the number
+    /// it returns.
+    ///
+    /// `write` is handed up to `max` elements of spare capacity, initialized with `T::default()`
+    /// so that no `unsafe` code is needed, and never causes a reallocation. Panics if `write`
+    /// returns more than it was handed.
+    pub fn write_spare<F>(&mut self, max: usize, write: F) -> usize
+    where T: Default + Clone,
+          F: FnOnce(&mut [T]) -> usize,
+    {
+        let len = self.vec.len();
+        self.vec.resize(len + max.min(self.spare_capacity()), T::default());
+        let written = write(&mut self.vec[len..]);
+        assert!(len + written <= self.vec.len(), "wrote more elements than there was room for");
+        self.vec.truncate(len + written);
+        written
+    }
+
+    /// Give up the buffer, returning the vector with the consumed elements dropped.
+    pub fn into_vec(mut self) -> Vec<T> {
+        self.compact();
+        self.vec
+    }
+}
+
+impl<T> Deref for VecBuf<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.vec[self.start..]
+    }
+}
+
+impl<T> DerefMut for VecBuf<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.vec[self.start..]
+    }
+}
+
+impl<T> From<Vec<T>> for VecBuf<T> {
+    fn from(vec: Vec<T>) -> Self {
+        VecBuf::new(vec)
+    }
+}
+
+/// Shrinking from the front only moves the view, while shrinking from the back drops elements.
+/// Once the view is empty, the whole vector is cleared, so that its capacity can be reused.
+impl<T> Shrink for VecBuf<T> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let view: &[T] = self;
+        let bounds = crate::range::bounds(&range);
+        let sub = view.get(range).ok_or_else(|| ShrinkError::out_of_bounds(bounds, view.len()))?;
+        let start = crate::range::offset_in(view, sub).expect("subslice is within the slice");
+        let end = start + sub.len();
+        self.vec.truncate(self.start + end);
+        self.start += start;
+        if self.start == self.vec.len() {
+            self.vec.clear();
+            self.start = 0;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.vec.len() - self.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn fill_and_consume() {
+        let mut buf = VecBuf::with_capacity(4);
+        assert_eq!(buf.write_spare(10, |spare| { spare[..3].copy_from_slice(b"abc"); 3 }), 3);
+        buf.shrink(1..2);
+        assert_eq!((&*buf, buf.consumed()), (&b"b"[..], 1));
+        assert!(buf.spare_capacity() >= 2);
+        buf.extend_from_slice(b"cd");
+        assert!(buf.try_shrink(4..).is_err());
+        buf.shrink(3..);
+        assert_eq!(buf.consumed(), 0);
+        assert!(buf.spare_capacity() >= 4);
+        assert_eq!(buf.into_vec(), b"");
+    }
+
+    #[test]
+    fn reserve_compacts_first() {
+        let mut buf = VecBuf::with_capacity(4);
+        buf.extend_from_slice(b"abcd");
+        buf.shrink(3..);
+        let capacity = buf.spare_capacity() + 4;
+        buf.reserve(3);
+        assert_eq!((&*buf, buf.consumed()), (&b"d"[..], 0));
+        assert_eq!(buf.spare_capacity() + 1, capacity);
+        buf.reserve(10);
+        assert!(buf.spare_capacity() >= 10);
+    }
+
+    #[test]
+    #[should_panic = "room"]
+    fn overlong_write() {
+        let mut buf = VecBuf::<u8>::with_capacity(2);
+        buf.write_spare(2, |spare| spare.len() + 1);
+    }
+}
@@ -0,0 +1,127 @@
+//! Consuming the bytes of JavaScript `Uint8Array`s, for parsers compiled to WebAssembly.
+//!
+//! The contents of a [`Uint8Array`] live in JavaScript memory, so they have to be copied into
+//! WebAssembly memory before Rust can borrow them as a slice, either into a new vector with
+//! [`parse_copy`] or into a buffer of your own with [`fill_from`]. Either way, the same
+//! shrink-based parsing code as in native builds can then run on them, and how many bytes it
+//! consumed is reported back as a JavaScript array length.
+//!
+//! Going the other way, bytes that are already in WebAssembly memory can be handed to
+//! JavaScript without a copy. With the `unsafe-fast` feature, `parse_view` parses them and
+//! returns a `Uint8Array` view of what was left, which is only valid for as long as the memory
+//! it views stays untouched.
+//!
+//! ```rust,no_run
+//! use js_sys::Uint8Array;
+//! use shrink_slice::wasm::parse_copy;
+//! use shrink_slice::Take;
+//!
+//! /// Called from JavaScript with the bytes received so far, returning how many it used.
+//! fn consume_records(array: &Uint8Array) -> u32 {
+//!     let (records, consumed) = parse_copy(array, |input| {
+//!         let mut records = 0;
+//!         while let Some(len) = input.first().copied() {
+//!             if input.take_exact(1 + len as usize).is_err() {
+//!                 break;
+//!             }
+//!             records += 1;
+//!         }
+//!         records
+//!     });
+//!     let _: u32 = records;
+//!     consumed
+//! }
+//! ```
+
+use crate::ShrinkExt;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use js_sys::Uint8Array;
+
+#[cfg(feature = "unsafe-fast")]
+pub use crate::fast::parse_view;
+
+/// Convert a number of bytes to the `u32` that JavaScript array lengths and offsets are stored
+/// in, panicking if it does not fit, which no count of bytes from a `Uint8Array` can fail to.
+pub fn js_len(n: usize) -> u32 {
+    u32::try_from(n).expect("byte count exceeds the length of any Uint8Array")
+}
+
+/// Copy `array` into a new vector, let `parse` consume from the front of it, and return what
+/// `parse` returned together with the number of bytes it consumed.
+pub fn parse_copy<F, T>(array: &Uint8Array, parse: F) -> (T, u32)
+where F: FnOnce(&mut &[u8]) -> T,
+{
+    let bytes: Vec<u8> = array.to_vec();
+    parse_bytes(&bytes, parse)
+}
+
+/// The part of [`parse_copy`] that runs once the bytes are in WebAssembly memory.
+fn parse_bytes<F, T>(bytes: &[u8], parse: F) -> (T, u32)
+where F: FnOnce(&mut &[u8]) -> T,
+{
+    let (result, consumed, _) = parse_rest(bytes, parse);
+    (result, consumed)
+}
+
+/// Like [`parse_bytes`], but also return the bytes that `parse` left.
+pub(crate) fn parse_rest<'a, F, T>(bytes: &'a [u8], parse: F) -> (T, u32, &'a [u8])
+where F: FnOnce(&mut &'a [u8]) -> T,
+{
+    let mut input = bytes;
+    let result = parse(&mut input);
+    (result, js_len(bytes.len() - input.len()), input)
+}
+
+/// Copy as much of the front of `array` as fits into the front of `buf`, which is shrunk past
+/// it, and return the number of bytes copied.
+///
+/// This avoids an allocation per call when the same buffer is filled over and over, and leaves
+/// `buf` as the space that is still free.
+pub fn fill_from(array: &Uint8Array, buf: &mut &mut [u8]) -> u32 {
+    fill_with(array.length() as usize, buf, |n, dest| {
+        array.subarray(0, js_len(n)).copy_to(dest);
+    })
+}
+
+/// Hand the first `min(available, buf.len())` bytes of `buf` to `copy`, then shrink past them.
+fn fill_with<F>(available: usize, buf: &mut &mut [u8], copy: F) -> u32
+where F: FnOnce(usize, &mut [u8]),
+{
+    let n = available.min(buf.len());
+    copy(n, &mut buf[..n]);
+    buf.shrink(n..);
+    js_len(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Take;
+
+    #[test]
+    fn consumed_counts() {
+        let (taken, consumed) = parse_bytes(b"\x02hi\x09trunc", |input| {
+            let len = input.take_exact(1).unwrap()[0] as usize;
+            let first = input.take_exact(len).unwrap().to_vec();
+            assert!(input.take_exact(1 + 9).is_err());
+            first
+        });
+        assert_eq!((taken, consumed), (b"hi".to_vec(), 3));
+
+        let mut buffer = [0; 5];
+        let mut free: &mut [u8] = &mut buffer;
+        assert_eq!(fill_with(3, &mut free, |n, dest| dest.copy_from_slice(&b"abc"[..n])), 3);
+        assert_eq!(fill_with(9, &mut free, |_, dest| dest.fill(b'x')), 2);
+        assert_eq!((free.len(), js_len(0)), (0, 0));
+        assert_eq!(&buffer, b"abcxx");
+    }
+
+    #[test]
+    fn rest_is_what_parse_left() {
+        let (first, consumed, rest) = parse_rest(b"\x01a\x02", |input| {
+            input.take_exact(2).unwrap()
+        });
+        assert_eq!((first, consumed, rest), (&b"\x01a"[..], 2, &b"\x02"[..]));
+    }
+}
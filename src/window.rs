@@ -0,0 +1,137 @@
+//! Windows into a borrowed slice that can grow back as well as shrink.
+//!
+//! ```rust
+//! use shrink_slice::window::Window;
+//! use shrink_slice::ShrinkExt;
+//!
+//! // A rolling sum over windows of three elements.
+//! let data = [1, 2, 3, 4, 5];
+//! let mut window = Window::new(&data[..]);
+//! window.shrink(..3);
+//! let mut sums = vec![window.iter().sum::<i32>()];
+//! while window.expand_back(1).is_ok() {
+//!     window.shrink(1..);
+//!     sums.push(window.iter().sum());
+//! }
+//! assert_eq!(sums, [6, 9, 12]);
+//! ```
+
+use crate::{range, NeedMore, Shrink, ShrinkError};
+use core::ops::{Deref, Range, RangeBounds};
+use core::slice::SliceIndex;
+
+/// A shrinkable view into a slice that remembers the whole slice, so that it can be expanded
+/// again up to its original extent.
+#[derive(Debug)]
+pub struct Window<'a, T> {
+    original: &'a [T],
+    start: usize,
+    end: usize,
+}
+
+impl<T> Clone for Window<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Window<'_, T> { }
+
+impl<'a, T> Window<'a, T> {
+    /// A window covering all of `original`.
+    pub fn new(original: &'a [T]) -> Self {
+        Window { original, start: 0, end: original.len() }
+    }
+
+    /// The slice this window was created from.
+    pub fn original(&self) -> &'a [T] {
+        self.original
+    }
+
+    /// The current view, with the original slice's lifetime.
+    pub fn view(&self) -> &'a [T] {
+        &self.original[self.start..self.end]
+    }
+
+    /// The range of the original slice that the window covers.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Move the start of the window `n` elements back.
+    ///
+    /// If there are fewer than `n` elements before the window, it is left untouched and the error
+    /// reports how many more would have been needed.
+    pub fn expand_front(&mut self, n: usize) -> Result<(), NeedMore> {
+        if n > self.start {
+            return Err(NeedMore(n - self.start));
+        }
+        self.start -= n;
+        Ok(())
+    }
+
+    /// Move the end of the window `n` elements forward.
+    ///
+    /// If there are fewer than `n` elements after the window, it is left untouched and the error
+    /// reports how many more would have been needed.
+    pub fn expand_back(&mut self, n: usize) -> Result<(), NeedMore> {
+        let after = self.original.len() - self.end;
+        if n > after {
+            return Err(NeedMore(n - after));
+        }
+        self.end += n;
+        Ok(())
+    }
+
+    /// Expand the window to cover all of the original slice again.
+    pub fn reset(&mut self) {
+        *self = Window::new(self.original);
+    }
+}
+
+impl<T> Deref for Window<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.view()
+    }
+}
+
+impl<T> Shrink for Window<'_, T> {
+    type Slice = [T];
+
+    fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+    where R: SliceIndex<[T], Output = [T]> + RangeBounds<usize>
+    {
+        let range = range::try_resolve(range, self.len())?;
+        self.end = self.start + range.end;
+        self.start += range.start;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShrinkExt;
+
+    #[test]
+    fn expand_within_original() {
+        let data = *b"abcdef";
+        let mut window = Window::new(&data[1..]);
+        window.shrink(1..3);
+        assert_eq!((window.view(), window.range()), (&b"cd"[..], 1..3));
+        assert_eq!(window.expand_front(2), Err(NeedMore(1)));
+        window.expand_front(1).unwrap();
+        assert_eq!(window.expand_back(3), Err(NeedMore(1)));
+        window.expand_back(2).unwrap();
+        assert_eq!(&*window, b"bcdef");
+        window.shrink(5..);
+        window.reset();
+        assert_eq!(window.range(), 0..5);
+    }
+}
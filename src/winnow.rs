@@ -0,0 +1,293 @@
+//! Parsing [`Tracked`] slices and [`LocatedStr`]s with `winnow` combinators.
+//!
+//! This module is only available with the `winnow` feature. It has no items of its own: it
+//! implements `winnow`'s stream traits for the crate's position-keeping slices, so that they can
+//! be passed to any parser written for `&str` or `&[T]`, and still know where they are once the
+//! parser returns. Tracked slices also implement [`Location`], for parsers that record spans.
+//!
+//! ```rust
+//! use shrink_slice::located::{LocatedStr, Location};
+//! use shrink_slice::tracked::TrackedStr;
+//! use winnow::ascii::{alpha1, digit1, line_ending};
+//! use winnow::combinator::separated_pair;
+//! use winnow::Parser;
+//!
+//! fn setting<'a, I>(input: &mut I) -> winnow::Result<(&'a str, &'a str)>
+//! where I: winnow::stream::Stream<Token = char, Slice = &'a str>
+//!     + winnow::stream::StreamIsPartial
+//!     + winnow::stream::Compare<char>,
+//! {
+//!     separated_pair(alpha1, '=', digit1).parse_next(input)
+//! }
+//!
+//! let mut input = TrackedStr::new("width=80;");
+//! assert_eq!(setting(&mut input), Ok(("width", "80")));
+//! assert_eq!((input.offset(), *input), (8, ";"));
+//!
+//! let mut input = LocatedStr::new("width=80\nheight=x\n");
+//! setting(&mut input).unwrap();
+//! line_ending::<_, winnow::error::ContextError>.parse_next(&mut input).unwrap();
+//! assert!(setting(&mut input).is_err());
+//! assert_eq!(input.location(), Location { line: 2, column: 8 });
+//! ```
+
+use crate::located::LocatedStr;
+use crate::tracked::Tracked;
+use core::ops::Range;
+use core::str::CharIndices;
+use winnow::error::Needed;
+use winnow::stream::{
+    AsBStr, AsBytes, Compare, CompareResult, FindSlice, Location, Offset, SliceLen, Stream,
+    StreamIsPartial,
+};
+
+impl<I: Stream + Clone> Tracked<I> {
+    /// Run `f` on the inner stream, and count whatever it consumed.
+    fn advance<R>(&mut self, f: impl FnOnce(&mut I) -> R) -> R {
+        let before = self.inner.eof_offset();
+        let result = f(&mut self.inner);
+        self.offset += before - self.inner.eof_offset();
+        result
+    }
+}
+
+/// Checkpoints are tracked slices too, so resetting to one restores its offset.
+impl<I: Stream + Clone> Stream for Tracked<I> {
+    type Token = I::Token;
+    type Slice = I::Slice;
+    type IterOffsets = I::IterOffsets;
+    type Checkpoint = Self;
+
+    fn iter_offsets(&self) -> I::IterOffsets {
+        self.inner.iter_offsets()
+    }
+
+    fn eof_offset(&self) -> usize {
+        self.inner.eof_offset()
+    }
+
+    fn next_token(&mut self) -> Option<I::Token> {
+        self.advance(I::next_token)
+    }
+
+    fn peek_token(&self) -> Option<I::Token> {
+        self.inner.peek_token()
+    }
+
+    fn offset_for<P: Fn(I::Token) -> bool>(&self, predicate: P) -> Option<usize> {
+        self.inner.offset_for(predicate)
+    }
+
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.inner.offset_at(tokens)
+    }
+
+    fn next_slice(&mut self, offset: usize) -> I::Slice {
+        self.advance(|inner| inner.next_slice(offset))
+    }
+
+    fn peek_slice(&self, offset: usize) -> I::Slice {
+        self.inner.peek_slice(offset)
+    }
+
+    fn checkpoint(&self) -> Self {
+        self.clone()
+    }
+
+    fn reset(&mut self, checkpoint: &Self) {
+        *self = checkpoint.clone();
+    }
+
+    fn raw(&self) -> &dyn core::fmt::Debug {
+        &self.inner
+    }
+}
+
+impl<I> Offset for Tracked<I> {
+    fn offset_from(&self, start: &Self) -> usize {
+        self.offset - start.offset
+    }
+}
+
+impl<I: Stream + Clone> Location for Tracked<I> {
+    fn previous_token_end(&self) -> usize {
+        self.offset
+    }
+
+    fn current_token_start(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<I: StreamIsPartial> StreamIsPartial for Tracked<I> {
+    type PartialState = I::PartialState;
+
+    fn complete(&mut self) -> I::PartialState {
+        self.inner.complete()
+    }
+
+    fn restore_partial(&mut self, state: I::PartialState) {
+        self.inner.restore_partial(state)
+    }
+
+    fn is_partial_supported() -> bool {
+        I::is_partial_supported()
+    }
+
+    fn is_partial(&self) -> bool {
+        self.inner.is_partial()
+    }
+}
+
+impl<I: SliceLen> SliceLen for Tracked<I> {
+    fn slice_len(&self) -> usize {
+        self.inner.slice_len()
+    }
+}
+
+impl<I: AsBytes> AsBytes for Tracked<I> {
+    fn as_bytes(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
+}
+
+impl<I: AsBStr> AsBStr for Tracked<I> {
+    fn as_bstr(&self) -> &[u8] {
+        self.inner.as_bstr()
+    }
+}
+
+impl<I: Compare<U>, U> Compare<U> for Tracked<I> {
+    fn compare(&self, other: U) -> CompareResult {
+        self.inner.compare(other)
+    }
+}
+
+impl<I: FindSlice<T>, T> FindSlice<T> for Tracked<I> {
+    fn find_slice(&self, substr: T) -> Option<Range<usize>> {
+        self.inner.find_slice(substr)
+    }
+}
+
+/// Checkpoints are located string slices too, so resetting to one restores its location.
+impl<'a> Stream for LocatedStr<'a> {
+    type Token = char;
+    type Slice = &'a str;
+    type IterOffsets = CharIndices<'a>;
+    type Checkpoint = Self;
+
+    fn iter_offsets(&self) -> CharIndices<'a> {
+        self.view.char_indices()
+    }
+
+    fn eof_offset(&self) -> usize {
+        self.view.len()
+    }
+
+    fn next_token(&mut self) -> Option<char> {
+        let c = self.view.peek_token()?;
+        self.next_slice(c.len_utf8());
+        Some(c)
+    }
+
+    fn peek_token(&self) -> Option<char> {
+        self.view.peek_token()
+    }
+
+    fn offset_for<P: Fn(char) -> bool>(&self, predicate: P) -> Option<usize> {
+        self.view.offset_for(predicate)
+    }
+
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.view.offset_at(tokens)
+    }
+
+    fn next_slice(&mut self, offset: usize) -> &'a str {
+        let slice = self.view.next_slice(offset);
+        self.location = self.location.advance(slice);
+        slice
+    }
+
+    fn peek_slice(&self, offset: usize) -> &'a str {
+        self.view.peek_slice(offset)
+    }
+
+    fn checkpoint(&self) -> Self {
+        *self
+    }
+
+    fn reset(&mut self, checkpoint: &Self) {
+        *self = *checkpoint;
+    }
+
+    fn raw(&self) -> &dyn core::fmt::Debug {
+        &self.view
+    }
+}
+
+impl Offset for LocatedStr<'_> {
+    fn offset_from(&self, start: &Self) -> usize {
+        self.view.offset_from(&start.view)
+    }
+}
+
+impl StreamIsPartial for LocatedStr<'_> {
+    type PartialState = ();
+
+    fn complete(&mut self) { }
+
+    fn restore_partial(&mut self, _state: ()) { }
+
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl SliceLen for LocatedStr<'_> {
+    fn slice_len(&self) -> usize {
+        self.view.len()
+    }
+}
+
+impl AsBStr for LocatedStr<'_> {
+    fn as_bstr(&self) -> &[u8] {
+        self.view.as_bytes()
+    }
+}
+
+impl<'a, U> Compare<U> for LocatedStr<'a> where &'a str: Compare<U> {
+    fn compare(&self, other: U) -> CompareResult {
+        self.view.compare(other)
+    }
+}
+
+impl<'a, T> FindSlice<T> for LocatedStr<'a> where &'a str: FindSlice<T> {
+    fn find_slice(&self, substr: T) -> Option<Range<usize>> {
+        self.view.find_slice(substr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracked::TrackedSlice;
+    use winnow::error::ContextError;
+    use winnow::token::{literal, take};
+    use winnow::Parser;
+
+    #[test]
+    fn reset_restores_position() {
+        let mut input = TrackedSlice::new(&b"\x01\x02abc"[..]);
+        let start = input.checkpoint();
+        assert_eq!(take::<_, _, ContextError>(2usize).parse_next(&mut input), Ok(&[1, 2][..]));
+        assert_eq!((input.offset(), input.offset_from(&start)), (2, 2));
+        assert!(literal::<_, _, ContextError>("abd").parse_next(&mut input).is_err());
+        input.reset(&start);
+        assert_eq!((input.offset(), input.current_token_start()), (0, 0));
+
+        let mut text = LocatedStr::new("a\n\u{e9}b");
+        let start = text.checkpoint();
+        assert_eq!((text.next_slice(2), text.next_token()), ("a\n", Some('\u{e9}')));
+        assert_eq!((text.offset_from(&start), text.location().column), (4, 2));
+    }
+}
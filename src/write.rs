@@ -0,0 +1,347 @@
+//! Writing binary data to the front of a mutable byte slice.
+//!
+//! [`PutBytes`] writes individual values, shrinking the slice past each one, so the slice always
+//! refers to the space that is still free. [`ShrinkWrite`] describes whole values, like headers,
+//! that know how to write themselves; with the `derive` feature it can be derived for structs.
+//! [`SliceWriter`] wraps a buffer for use with formatting macros and other writer APIs.
+//!
+//! ```rust
+//! use shrink_slice::write::PutBytes;
+//!
+//! let mut buffer = [0; 8];
+//! let mut out: &mut [u8] = &mut buffer;
+//! out.put_u16_be(0xcafe).unwrap();
+//! out.put_u32_le(1).unwrap();
+//! assert_eq!(out.len(), 2);
+//! assert!(out.put_u32_le(2).is_err());
+//! assert_eq!(buffer, [0xca, 0xfe, 1, 0, 0, 0, 0, 0]);
+//! ```
+
+use crate::endian::{ByteOrder, Endian};
+use crate::{NeedMore, Take};
+use core::fmt;
+
+#[cfg(feature = "derive")]
+pub use shrink_slice_derive::ShrinkWrite;
+
+macro_rules! put_methods {
+    ($($ty:ident: $le:ident, $be:ident, $ne:ident;)*) => {
+        $(
+            #[doc = concat!("Write a little endian `", stringify!($ty), "`.")]
+            fn $le(&mut self, value: $ty) -> Result<(), NeedMore> {
+                self.put_slice(&value.to_le_bytes())
+            }
+
+            #[doc = concat!("Write a big endian `", stringify!($ty), "`.")]
+            fn $be(&mut self, value: $ty) -> Result<(), NeedMore> {
+                self.put_slice(&value.to_be_bytes())
+            }
+
+            #[doc = concat!("Write a `", stringify!($ty), "` in the given byte order.")]
+            fn $ne(&mut self, value: $ty, endian: Endian) -> Result<(), NeedMore> {
+                match endian {
+                    Endian::Little => self.$le(value),
+                    Endian::Big => self.$be(value),
+                }
+            }
+        )*
+    };
+}
+
+/// Extension trait for writing binary values to the front of a mutable byte slice.
+///
+/// Every method shrinks the slice past what it wrote. If there is not enough room, nothing is
+/// written and the error reports how many more bytes would have been needed.
+pub trait PutBytes {
+    /// Copy `bytes` to the front of the slice.
+    fn put_slice(&mut self, bytes: &[u8]) -> Result<(), NeedMore>;
+
+    /// Write a single byte.
+    fn put_u8(&mut self, value: u8) -> Result<(), NeedMore> {
+        self.put_slice(&[value])
+    }
+
+    /// Write a single signed byte.
+    fn put_i8(&mut self, value: i8) -> Result<(), NeedMore> {
+        self.put_slice(&value.to_le_bytes())
+    }
+
+    put_methods! {
+        u16: put_u16_le, put_u16_be, put_u16;
+        u32: put_u32_le, put_u32_be, put_u32;
+        u64: put_u64_le, put_u64_be, put_u64;
+        u128: put_u128_le, put_u128_be, put_u128;
+        i16: put_i16_le, put_i16_be, put_i16;
+        i32: put_i32_le, put_i32_be, put_i32;
+        i64: put_i64_le, put_i64_be, put_i64;
+        i128: put_i128_le, put_i128_be, put_i128;
+        f32: put_f32_le, put_f32_be, put_f32;
+        f64: put_f64_le, put_f64_be, put_f64;
+    }
+}
+
+impl PutBytes for &mut [u8] {
+    fn put_slice(&mut self, bytes: &[u8]) -> Result<(), NeedMore> {
+        self.take_exact(bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// A writer that fills a borrowed byte buffer from the front, for formatting or serializing
+/// without allocating.
+///
+/// It implements [`fmt::Write`], [`PutBytes`], and with the `std` feature, `std::io::Write`.
+/// Writing a string with [`fmt::Write`] is all or nothing, like every [`PutBytes`] method, while
+/// `io::Write::write` writes as much as fits. A `write!` that fails may still have written the
+/// pieces of its output that came before the one that did not fit.
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use shrink_slice::write::SliceWriter;
+///
+/// let mut buffer = [0; 16];
+/// let mut out = SliceWriter::new(&mut buffer);
+/// write!(out, "{}x{}", 1920, 1080).unwrap();
+/// assert!(out.write_str(" pixels wide").is_err());
+/// assert_eq!(out.remaining(), 7);
+/// let written = out.written();
+/// assert_eq!(&buffer[..written], b"1920x1080");
+/// ```
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    rest: &'a mut [u8],
+    written: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Start writing at the front of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter { rest: buf, written: 0 }
+    }
+
+    /// The number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.rest.len()
+    }
+
+    /// The number of bytes written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Give up the writer, returning the part of the buffer that has not been written to.
+    pub fn into_remaining(self) -> &'a mut [u8] {
+        self.rest
+    }
+}
+
+impl PutBytes for SliceWriter<'_> {
+    fn put_slice(&mut self, bytes: &[u8]) -> Result<(), NeedMore> {
+        self.rest.put_slice(bytes)?;
+        self.written += bytes.len();
+        Ok(())
+    }
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.put_slice(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.remaining());
+        self.put_slice(&buf[..n]).expect("the bytes fit in the buffer");
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A value that can write itself to the front of a mutable byte slice.
+///
+/// Implementations must leave the slice untouched when they fail, though bytes past its front
+/// may have been overwritten.
+pub trait ShrinkWrite {
+    /// Write `self`, using `endian` for any multi-byte values that do not specify their own byte
+    /// order.
+    fn write_to_endian(&self, out: &mut &mut [u8], endian: Endian) -> Result<(), NeedMore>;
+
+    /// Write `self`, in little endian byte order unless specified otherwise.
+    fn write_to(&self, out: &mut &mut [u8]) -> Result<(), NeedMore> {
+        self.write_to_endian(out, Endian::Little)
+    }
+
+    /// Write `self`, in the byte order `E` unless specified otherwise.
+    fn write_ordered<E: ByteOrder>(&self, out: &mut &mut [u8]) -> Result<(), NeedMore> {
+        self.write_to_endian(out, E::ENDIAN)
+    }
+}
+
+macro_rules! shrink_write_primitive {
+    ($($ty:ident: $put:ident),*) => {
+        $(
+            impl ShrinkWrite for $ty {
+                fn write_to_endian(&self, out: &mut &mut [u8], endian: Endian)
+                    -> Result<(), NeedMore>
+                {
+                    out.$put(*self, endian)
+                }
+            }
+        )*
+    };
+}
+
+shrink_write_primitive! {
+    u16: put_u16, u32: put_u32, u64: put_u64, u128: put_u128,
+    i16: put_i16, i32: put_i32, i64: put_i64, i128: put_i128,
+    f32: put_f32, f64: put_f64
+}
+
+impl ShrinkWrite for u8 {
+    fn write_to_endian(&self, out: &mut &mut [u8], _: Endian) -> Result<(), NeedMore> {
+        out.put_u8(*self)
+    }
+}
+
+impl ShrinkWrite for i8 {
+    fn write_to_endian(&self, out: &mut &mut [u8], _: Endian) -> Result<(), NeedMore> {
+        out.put_i8(*self)
+    }
+}
+
+impl<T: ShrinkWrite> ShrinkWrite for [T] {
+    fn write_to_endian(&self, out: &mut &mut [u8], endian: Endian) -> Result<(), NeedMore> {
+        let mut view: &mut [u8] = out;
+        for item in self {
+            item.write_to_endian(&mut view, endian)?;
+        }
+        let remaining = view.len();
+        out.take_exact(out.len() - remaining)?;
+        Ok(())
+    }
+}
+
+impl<T: ShrinkWrite, const N: usize> ShrinkWrite for [T; N] {
+    fn write_to_endian(&self, out: &mut &mut [u8], endian: Endian) -> Result<(), NeedMore> {
+        self[..].write_to_endian(out, endian)
+    }
+}
+
+impl<T: ShrinkWrite + ?Sized> ShrinkWrite for &T {
+    fn write_to_endian(&self, out: &mut &mut [u8], endian: Endian) -> Result<(), NeedMore> {
+        (**self).write_to_endian(out, endian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_all_widths() {
+        let mut buffer = [0; 15];
+        let mut out: &mut [u8] = &mut buffer;
+        out.put_i8(-1).unwrap();
+        out.put_i16(-2, Endian::Big).unwrap();
+        out.put_f32_le(1.0).unwrap();
+        out.put_u64_be(0x0102_0304_0506_0708).unwrap();
+        assert!(out.is_empty());
+        assert_eq!(out.put_u8(0), Err(NeedMore(1)));
+        assert_eq!(buffer, [0xff, 0xff, 0xfe, 0, 0, 0x80, 0x3f, 1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn array_write_is_all_or_nothing() {
+        let mut buffer = [0; 5];
+        let mut out: &mut [u8] = &mut buffer;
+        assert_eq!([1u16, 2, 3].write_to(&mut out), Err(NeedMore(1)));
+        assert_eq!(out.len(), 5);
+        [1u16, 2].write_to_endian(&mut out, Endian::Big).unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(buffer[..4], [0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn ordered_by_type_parameter() {
+        use crate::endian::{BigEndian, LittleEndian, NativeEndian};
+
+        let mut buffer = [0; 7];
+        let mut out: &mut [u8] = &mut buffer;
+        0x0102u16.write_ordered::<BigEndian>(&mut out).unwrap();
+        0x0304u16.write_ordered::<LittleEndian>(&mut out).unwrap();
+        0x0506u16.write_ordered::<NativeEndian>(&mut out).unwrap();
+        assert_eq!(1u16.write_ordered::<BigEndian>(&mut out), Err(NeedMore(1)));
+        assert_eq!(out.len(), 1);
+        assert_eq!(buffer[..4], [1, 2, 4, 3]);
+        assert_eq!(buffer[4..6], 0x0506u16.to_ne_bytes());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn slice_writer_io() {
+        use std::io::Write;
+
+        let mut buffer = [0; 6];
+        let mut out = SliceWriter::new(&mut buffer);
+        out.put_u16_be(0x0102).unwrap();
+        assert_eq!(out.write(b"abcdef").unwrap(), 4);
+        assert_eq!(out.write(b"g").unwrap(), 0);
+        assert!(out.write_all(b"g").is_err());
+        assert_eq!((out.written(), out.remaining()), (6, 0));
+        assert!(out.into_remaining().is_empty());
+        assert_eq!(&buffer, b"\x01\x02abcd");
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived() {
+        #[derive(ShrinkWrite)]
+        struct Inner(u16, #[shrink(big_endian)] u16);
+
+        #[derive(ShrinkWrite)]
+        #[shrink(big_endian)]
+        struct Header<'a, const N: usize> {
+            magic: [u8; N],
+            #[shrink(little_endian)]
+            len: u32,
+            #[shrink(skip)]
+            #[allow(dead_code)]
+            note: &'a str,
+            inner: Inner,
+        }
+
+        let header = Header { magic: *b"SS", len: 7, note: "unused", inner: Inner(1, 2) };
+        let mut buffer = [0; 9];
+        let mut out: &mut [u8] = &mut buffer;
+        assert_eq!(header.write_to(&mut out), Err(NeedMore(1)));
+        assert_eq!(out.len(), 9);
+        let mut buffer = [0; 10];
+        let mut out: &mut [u8] = &mut buffer;
+        header.write_to(&mut out).unwrap();
+        assert!(out.is_empty());
+        assert_eq!(&buffer, b"SS\x07\0\0\0\0\x01\0\x02");
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_with_fn_bound() {
+        #[derive(ShrinkWrite)]
+        struct Mapped<F> where F: Fn(u8) -> u8 {
+            tag: u8,
+            #[shrink(skip)]
+            #[allow(dead_code)]
+            map: F,
+        }
+
+        let mapped = Mapped { tag: 3, map: |b| b + 1 };
+        let mut buffer = [0; 2];
+        let mut out: &mut [u8] = &mut buffer;
+        mapped.write_to(&mut out).unwrap();
+        assert_eq!((out.len(), buffer), (1, [3, 0]));
+    }
+}
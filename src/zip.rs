@@ -0,0 +1,156 @@
+//! Keeping parallel slices in step.
+
+use crate::{range, NeedMore, Shrink, ShrinkError, ShrinkExt, SliceLike, SliceLikeMut};
+use core::ops::{Range, RangeBounds};
+use core::slice::SliceIndex;
+
+/// Extension trait for matching a slice's length to that of another slice.
+///
+/// ```rust
+/// use shrink_slice::zip::MatchLen;
+///
+/// let gains = [0.5, 2.0];
+/// let mut samples: &[f32] = &[1.0, 1.0, 1.0];
+/// samples.shrink_to_match_len(&gains);
+/// assert_eq!(samples.len(), gains.len());
+/// ```
+pub trait MatchLen {
+    /// Shrink away elements from the back until `self` is no longer than `other`.
+    fn shrink_to_match_len<U>(&mut self, other: &[U]);
+
+    /// Shrink away elements from the back so that `self` is exactly as long as `other`.
+    ///
+    /// If `self` is shorter than `other`, it is left untouched and the error reports the
+    /// difference.
+    fn try_match_len<U>(&mut self, other: &[U]) -> Result<(), NeedMore>;
+}
+
+impl<T> MatchLen for &[T] {
+    fn shrink_to_match_len<U>(&mut self, other: &[U]) {
+        let len = self.len().min(other.len());
+        self.shrink(..len);
+    }
+
+    fn try_match_len<U>(&mut self, other: &[U]) -> Result<(), NeedMore> {
+        if self.len() < other.len() {
+            return Err(NeedMore(other.len() - self.len()));
+        }
+        self.shrink(..other.len());
+        Ok(())
+    }
+}
+
+impl<T> MatchLen for &mut [T] {
+    fn shrink_to_match_len<U>(&mut self, other: &[U]) {
+        let len = self.len().min(other.len());
+        self.shrink(..len);
+    }
+
+    fn try_match_len<U>(&mut self, other: &[U]) -> Result<(), NeedMore> {
+        if self.len() < other.len() {
+            return Err(NeedMore(other.len() - self.len()));
+        }
+        self.shrink(..other.len());
+        Ok(())
+    }
+}
+
+/// A reference to a slice that can be shrunk in lockstep with others, as part of a tuple.
+///
+/// It is implemented for shared and mutable references to every [`SliceLike`] type, which can
+/// check a range without shrinking.
+pub trait Lockstep: Shrink {
+    /// Check that shrinking to `range` would succeed, without shrinking.
+    fn check(&self, range: &Range<usize>) -> Result<(), ShrinkError>;
+}
+
+impl<S: SliceLike + ?Sized> Lockstep for &S {
+    fn check(&self, range: &Range<usize>) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range.clone(), S::len(self))?;
+        self.check_range(&range)
+    }
+}
+
+impl<S: SliceLikeMut + ?Sized> Lockstep for &mut S {
+    fn check(&self, range: &Range<usize>) -> Result<(), ShrinkError> {
+        let range = range::try_resolve(range.clone(), S::len(self))?;
+        self.check_range(&range)
+    }
+}
+
+macro_rules! lockstep_tuple {
+    ($(($($name:ident: $index:tt),*))*) => {
+        $(
+            /// Tuples of slices shrink in lockstep: every slice is shrunk to the same range, or,
+            /// if the range is invalid for any of them, none is and the first error is returned.
+            /// The length is that of the shortest slice.
+            ///
+            /// The slices may be of different types, so the tuple's [`Shrink::Slice`] is `[()]`,
+            /// whose indices are any ranges of `usize`.
+            impl<$($name),*> Shrink for ($($name,)*)
+            where
+                $($name: Lockstep,)*
+                Range<usize>: $(SliceIndex<$name::Slice, Output = $name::Slice> +)*,
+            {
+                type Slice = [()];
+
+                fn try_shrink<R>(&mut self, range: R) -> Result<(), ShrinkError>
+                where R: SliceIndex<[()], Output = [()]> + RangeBounds<usize>
+                {
+                    let range = range::try_resolve(range, self.len())?;
+                    $(self.$index.check(&range)?;)*
+                    $(self.$index.shrink(range.clone());)*
+                    Ok(())
+                }
+
+                fn len(&self) -> usize {
+                    let len = usize::MAX;
+                    $(let len = len.min(self.$index.len());)*
+                    len
+                }
+            }
+        )*
+    };
+}
+
+lockstep_tuple! {
+    (A: 0, B: 1)
+    (A: 0, B: 1, C: 2)
+    (A: 0, B: 1, C: 2, D: 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_match() {
+        let mut buffer = [0u8; 4];
+        let mut out: &mut [u8] = &mut buffer;
+        assert_eq!(out.try_match_len(&[(); 6]), Err(NeedMore(2)));
+        assert_eq!(out.len(), 4);
+        out.try_match_len(&['a', 'b', 'c']).unwrap();
+        assert_eq!(out.len(), 3);
+        out.shrink_to_match_len(&[1u64; 5]);
+        assert_eq!(out.len(), 3);
+    }
+
+    #[test]
+    fn tuples_shrink_atomically() {
+        let (mut xs, mut ys) = ([1.0, 2.0, 3.0], [10, 20, 30]);
+        let names = ["a", "b", "c"];
+        let mut columns = (&mut xs[..], &mut ys[..], &names[..]);
+        columns.shrink(1..);
+        columns.0[0] = 0.0;
+        let err = ShrinkError::OutOfBounds { start: 0, end: 3, len: 2 };
+        assert_eq!(columns.try_shrink(..3), Err(err));
+        assert_eq!(columns.2, ["b", "c"]);
+        assert_eq!((xs, ys), ([1.0, 0.0, 3.0], [10, 20, 30]));
+
+        let mut pair = (&b"ab\xc3"[..], "a\u{e9}");
+        assert!(matches!(pair.try_shrink(..2), Err(ShrinkError::NotCharBoundary { .. })));
+        assert_eq!(pair, (&b"ab\xc3"[..], "a\u{e9}"));
+        pair.shrink_back(2);
+        assert_eq!(pair, (&b"a"[..], "a"));
+    }
+}